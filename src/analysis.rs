@@ -0,0 +1,970 @@
+//! Read-only analysis of the `resymgen` YAML format. Implements the `version-delta`, `lookup`,
+//! `resolve`, `versions`, and `stats` commands.
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::AsRef;
+use std::error::Error;
+use std::fs::File;
+use std::iter;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use super::data_formats::symgen_yml::{
+    Block, Linkable, MaybeVersionDep, Subregion, SymGen, Symbol, Uint, Version,
+};
+use super::util::{self, ResymgenError, UsageError};
+
+/// The address offset of a symbol between two versions.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct VersionDelta<'a> {
+    pub name: &'a str,
+    pub from_address: Uint,
+    pub to_address: Uint,
+}
+
+impl<'a> VersionDelta<'a> {
+    /// Gets the signed offset from `from_address` to `to_address`.
+    pub fn delta(&self) -> i128 {
+        self.to_address as i128 - self.from_address as i128
+    }
+}
+
+/// Formats a signed offset as hexadecimal with an explicit sign.
+fn format_delta(delta: i128) -> String {
+    if delta < 0 {
+        format!("-{:#X}", -delta)
+    } else {
+        format!("+{:#X}", delta)
+    }
+}
+
+/// Computes the address offsets between versions `from` and `to` for every symbol in `symgen`
+/// that has an address for both versions.
+///
+/// Symbols are joined by name within each block. If a symbol has multiple addresses for a
+/// version, addresses are paired in order, and any unpaired addresses are ignored.
+///
+/// Fails if either version isn't a version of any block in `symgen`, since realizing symbols for
+/// an unknown version would otherwise silently fall back on their version-independent addresses.
+pub fn version_deltas<'a>(
+    symgen: &'a SymGen,
+    from: &str,
+    to: &str,
+) -> Result<Vec<VersionDelta<'a>>, UsageError> {
+    for version in [from, to] {
+        if !symgen.has_version(version) {
+            return Err(UsageError(format!("Unknown version '{}'", version)));
+        }
+    }
+    let mut deltas = Vec::new();
+    for block in symgen.blocks() {
+        let mut from_addrs: HashMap<&str, Vec<Uint>> = HashMap::new();
+        for s in block.iter_realized(from).filter(|s| !s.inlined) {
+            from_addrs.entry(s.name).or_default().push(s.address);
+        }
+        let mut to_addrs: Vec<(&str, Vec<Uint>)> = Vec::new();
+        for s in block.iter_realized(to).filter(|s| !s.inlined) {
+            match to_addrs.last_mut() {
+                Some((name, addrs)) if *name == s.name => addrs.push(s.address),
+                _ => to_addrs.push((s.name, vec![s.address])),
+            }
+        }
+        for (name, addrs) in to_addrs {
+            if let Some(from_addrs) = from_addrs.get(name) {
+                deltas.extend(
+                    from_addrs
+                        .iter()
+                        .zip(addrs)
+                        .map(|(&from_address, to_address)| VersionDelta {
+                            name,
+                            from_address,
+                            to_address,
+                        }),
+                );
+            }
+        }
+    }
+    Ok(deltas)
+}
+
+/// Finds the most common offset among `deltas`, along with the number of times it occurs.
+///
+/// Ties are broken in favor of the smaller offset. Returns [`None`] if `deltas` is empty.
+pub fn most_common_delta(deltas: &[VersionDelta]) -> Option<(i128, usize)> {
+    let mut counts: BTreeMap<i128, usize> = BTreeMap::new();
+    for d in deltas {
+        *counts.entry(d.delta()).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+}
+
+/// Reads a `resymgen` YAML file along with all its subregion files, and collapses them into a
+/// single [`SymGen`].
+pub(crate) fn read_collapsed(input_file: &Path) -> Result<SymGen, Box<dyn Error + Send + Sync>> {
+    let mut contents = {
+        let file = File::open(input_file)?;
+        SymGen::read(&file)?
+    };
+    contents.resolve_subregions(Subregion::subregion_dir(input_file), |p| File::open(p))?;
+    contents.collapse_subregions();
+    Ok(contents)
+}
+
+/// Prints the address offsets of symbols in `input_file` (and its subregion files) between
+/// versions `from` and `to`, followed by a summary of the most common offset.
+///
+/// # Examples
+/// ```ignore
+/// print_version_deltas("/path/to/symbols.yml", "NA", "EU")
+///     .expect("failed to compute version deltas");
+/// ```
+pub fn print_version_deltas<P: AsRef<Path>>(
+    input_file: P,
+    from: &str,
+    to: &str,
+) -> Result<(), ResymgenError> {
+    let contents = read_collapsed(input_file.as_ref())?;
+    let deltas = version_deltas(&contents, from, to)?;
+    for d in deltas.iter() {
+        println!(
+            "{}: {}={:#X} {}={:#X} delta={}",
+            d.name,
+            from,
+            d.from_address,
+            to,
+            d.to_address,
+            format_delta(d.delta())
+        );
+    }
+    match most_common_delta(&deltas) {
+        Some((delta, count)) => println!(
+            "\nMost common delta: {} ({} of {} symbols)",
+            format_delta(delta),
+            count,
+            deltas.len()
+        ),
+        None => println!("No symbols have addresses for both {} and {}", from, to),
+    }
+    Ok(())
+}
+
+/// Finds all symbols in `symgen` with a name or deprecated alias equal to `name`, along with the
+/// names of the blocks containing them.
+pub fn lookup_symbols<'a>(symgen: &'a SymGen, name: &str) -> Vec<(&'a str, &'a Symbol)> {
+    symgen
+        .iter()
+        .flat_map(|(bname, b)| b.iter().map(move |s| (bname.val.as_str(), s)))
+        .filter(|(_, s)| s.iter_names().any(|n| n == name))
+        .collect()
+}
+
+/// Computes the Levenshtein edit distance between `a` and `b`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // Distances from the current prefix of a to each prefix of b
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diag + usize::from(ca != cb);
+            diag = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diag + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Finds the (at most) `n` symbol names or deprecated aliases in `symgen` closest to `name` by
+/// edit distance, along with their distances.
+///
+/// Results are sorted by distance, then by name.
+pub fn fuzzy_lookup<'a>(symgen: &'a SymGen, name: &str, n: usize) -> Vec<(usize, &'a str)> {
+    let mut candidates: Vec<(usize, &str)> = symgen
+        .symbols()
+        .flat_map(|s| {
+            iter::once(s.name.as_str()).chain(
+                s.deprecated_aliases
+                    .as_deref()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|a| a.as_str()),
+            )
+        })
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+    candidates.truncate(n);
+    candidates
+}
+
+/// Formats a possibly version-dependent value using `fmt_val`.
+pub(crate) fn format_maybe_version_dep<T, F>(val: &MaybeVersionDep<T>, fmt_val: F) -> String
+where
+    F: Fn(&T) -> String,
+{
+    match val {
+        MaybeVersionDep::Common(v) => fmt_val(v),
+        MaybeVersionDep::ByVersion(vers) => vers
+            .iter()
+            .map(|(v, x)| format!("{}={}", v, fmt_val(x)))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+/// Formats an address or list of addresses as hexadecimal.
+pub(crate) fn format_linkable(addrs: &Linkable) -> String {
+    match addrs {
+        Linkable::Single(a) => format!("{:#X}", a),
+        Linkable::Expr(e) => match e.resolved {
+            Some(a) => format!("{:#X} ({})", a, e),
+            None => e.to_string(),
+        },
+        Linkable::Multiple(_) => format!(
+            "[{}]",
+            addrs
+                .iter()
+                .map(|a| format!("{:#X}", a))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Looks up symbols by name (or deprecated alias) in `input_file` (and its subregion files), and
+/// prints information about the matches.
+///
+/// If there are no exact matches and `fuzzy` is true, the closest symbol names are printed as
+/// suggestions instead. Returns whether or not an exact match was found.
+///
+/// # Examples
+/// ```ignore
+/// let found = print_lookup("/path/to/symbols.yml", "SomeFunction", true).expect("lookup failed");
+/// ```
+pub fn print_lookup<P: AsRef<Path>>(
+    input_file: P,
+    name: &str,
+    fuzzy: bool,
+) -> Result<bool, ResymgenError> {
+    /// Maximum number of suggestions to print in fuzzy mode
+    const MAX_SUGGESTIONS: usize = 5;
+
+    let contents = read_collapsed(input_file.as_ref())?;
+    let matches = lookup_symbols(&contents, name);
+    for (bname, s) in matches.iter() {
+        println!("{} (block \"{}\")", s.name, bname);
+        if let Some(aliases) = &s.deprecated_aliases {
+            println!("  deprecated aliases: {}", aliases.join(", "));
+        }
+        println!(
+            "  address: {}",
+            format_maybe_version_dep(&s.address, format_linkable)
+        );
+        if let Some(len) = &s.length {
+            println!(
+                "  length: {}",
+                format_maybe_version_dep(len, |&l| util::format_byte_size(l))
+            );
+        }
+        if let Some(desc) = &s.description {
+            match desc {
+                MaybeVersionDep::Common(d) => {
+                    println!("  description: {}", d.replace('\n', "\n    "))
+                }
+                MaybeVersionDep::ByVersion(descs) => {
+                    println!("  description:");
+                    for (v, d) in descs.iter() {
+                        println!("    {}: {}", v, d.replace('\n', "\n      "));
+                    }
+                }
+            }
+        }
+    }
+    if matches.is_empty() && fuzzy {
+        let suggestions = fuzzy_lookup(&contents, name, MAX_SUGGESTIONS);
+        if !suggestions.is_empty() {
+            println!("No symbol named \"{}\". Did you mean:", name);
+            for (_, candidate) in suggestions {
+                println!("  {}", candidate);
+            }
+        }
+    }
+    Ok(!matches.is_empty())
+}
+
+/// Finds all version names used in `symgen`, along with the number of symbols that have an
+/// address for each version.
+///
+/// Version names are gathered from block version lists, as well as from the version-dependent
+/// addresses and lengths of blocks and symbols. They are returned in the order they are first
+/// encountered. A symbol with a version-independent address counts towards every version of its
+/// block.
+pub fn version_symbol_counts(symgen: &SymGen) -> Vec<(&str, usize)> {
+    fn entry<'a, 'c>(counts: &'c mut Vec<(&'a str, usize)>, name: &'a str) -> &'c mut usize {
+        let i = match counts.iter().position(|(n, _)| *n == name) {
+            Some(i) => i,
+            None => {
+                counts.push((name, 0));
+                counts.len() - 1
+            }
+        };
+        &mut counts[i].1
+    }
+
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for block in symgen.blocks() {
+        // All versions mentioned anywhere in the block, in order of appearance
+        let mut block_versions: Vec<&str> = Vec::new();
+        let mentioned = block
+            .versions
+            .iter()
+            .flatten()
+            .chain(block.address.versions())
+            .chain(block.length.versions())
+            .chain(block.iter().flat_map(|s| {
+                s.address
+                    .versions()
+                    .chain(s.length.iter().flat_map(|l| l.versions()))
+            }));
+        for v in mentioned {
+            if !block_versions.contains(&v.name()) {
+                block_versions.push(v.name());
+            }
+        }
+        for &name in block_versions.iter() {
+            entry(&mut counts, name);
+        }
+        for s in block.iter() {
+            match &s.address {
+                MaybeVersionDep::Common(_) => {
+                    for &name in block_versions.iter() {
+                        *entry(&mut counts, name) += 1;
+                    }
+                }
+                MaybeVersionDep::ByVersion(addrs) => {
+                    for v in addrs.versions() {
+                        *entry(&mut counts, v.name()) += 1;
+                    }
+                }
+            }
+        }
+    }
+    counts
+}
+
+/// Prints all version names used in `input_file`, along with the number of symbols that have an
+/// address for each version.
+///
+/// In `recursive` mode, subregion files are also included.
+///
+/// # Examples
+/// ```ignore
+/// print_versions("/path/to/symbols.yml", true).expect("failed to list versions");
+/// ```
+pub fn print_versions<P: AsRef<Path>>(input_file: P, recursive: bool) -> Result<(), ResymgenError> {
+    let input_file = input_file.as_ref();
+    let contents = if recursive {
+        read_collapsed(input_file)?
+    } else {
+        let file = File::open(input_file)?;
+        SymGen::read(&file)?
+    };
+    let counts = version_symbol_counts(&contents);
+    for (name, count) in counts.iter() {
+        println!("{}: {} symbol(s)", name, count);
+    }
+    if counts.is_empty() {
+        println!("No versions found in \"{}\"", input_file.display());
+    }
+    Ok(())
+}
+
+/// How much of a memory extent is covered by symbols, for a particular version.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct Coverage {
+    /// The version name, or [`None`] for version-independent extents.
+    pub version: Option<String>,
+    /// The number of bytes covered by symbols with known lengths. Overlapping symbols are only
+    /// counted once.
+    pub covered_bytes: Uint,
+    pub total_bytes: Uint,
+    /// The percentage of the extent that's covered, or 0 for an empty extent.
+    pub percent: f64,
+}
+
+impl Coverage {
+    fn new(version: Option<String>, covered_bytes: Uint, total_bytes: Uint) -> Self {
+        let percent = if total_bytes > 0 {
+            100.0 * covered_bytes as f64 / total_bytes as f64
+        } else {
+            0.0
+        };
+        Self {
+            version,
+            covered_bytes,
+            total_bytes,
+            percent,
+        }
+    }
+}
+
+/// Statistics about the symbols in a block (or a combination of blocks).
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct BlockStats {
+    pub name: String,
+    pub functions: usize,
+    pub data: usize,
+    pub missing_length: usize,
+    pub missing_description: usize,
+    /// Coverage for each version of the block, in order.
+    pub coverage: Vec<Coverage>,
+}
+
+/// Statistics about the symbols in a [`SymGen`], by block and overall.
+#[derive(Debug, PartialEq, Clone, Serialize)]
+pub struct SymGenStats {
+    pub blocks: Vec<BlockStats>,
+    /// Totals over all blocks, with coverage combined across blocks by version name.
+    pub total: BlockStats,
+}
+
+/// Computes the number of bytes within `bound` covered by the union of the given `extents`.
+fn covered_bytes(bound: (Uint, Uint), mut extents: Vec<(Uint, Uint)>) -> Uint {
+    let (bound_start, bound_end) = bound;
+    extents.sort_unstable();
+    let mut covered = 0;
+    // Everything below this address has already been counted
+    let mut counted_to = bound_start;
+    for (start, end) in extents {
+        let start = start.max(counted_to);
+        let end = end.min(bound_end);
+        if start < end {
+            covered += end - start;
+            counted_to = end;
+        }
+    }
+    covered
+}
+
+/// Computes the symbol [`Coverage`] of `block` for each of its versions, based on
+/// [`Block::extent()`] and [`Symbol::extents()`] (including any additional address ranges).
+/// Symbols without a length don't count towards coverage, and neither do inlined symbols.
+fn block_coverage(block: &Block) -> Vec<Coverage> {
+    let coverage = |version: Option<&Version>, all_versions: Option<&[Version]>, bound| {
+        let (start, len): (Uint, Option<Uint>) = bound;
+        let end = start + len.unwrap_or(0);
+        let mut extents = Vec::new();
+        for s in block.iter().filter(|s| !s.is_inlined()) {
+            if let Some((addrs, Some(len))) = s.extents(all_versions).get(version) {
+                extents.extend(addrs.iter().map(|&a| (a, a + len)));
+            }
+            for r in s.range_extents(all_versions) {
+                if let Some(&(a, Some(len))) = r.get(version) {
+                    extents.push((a, a + len));
+                }
+            }
+        }
+        Coverage::new(
+            version.map(|v| v.name().to_string()),
+            covered_bytes((start, end), extents),
+            end - start,
+        )
+    };
+    match block.extent() {
+        MaybeVersionDep::Common(bound) => vec![coverage(None, None, bound)],
+        MaybeVersionDep::ByVersion(bounds) => {
+            let versions: Vec<Version> = bounds.versions().cloned().collect();
+            bounds
+                .iter()
+                .map(|(v, &bound)| coverage(Some(v), Some(&versions), bound))
+                .collect()
+        }
+    }
+}
+
+/// Computes statistics about the symbols in `symgen`, by block and overall.
+pub fn symgen_stats(symgen: &SymGen) -> SymGenStats {
+    let missing = |block: &Block, f: fn(&Symbol) -> bool| block.iter().filter(|s| f(s)).count();
+    let blocks: Vec<BlockStats> = symgen
+        .iter()
+        .map(|(name, b)| BlockStats {
+            name: name.val.clone(),
+            functions: b.functions.len(),
+            data: b.data.len(),
+            missing_length: missing(b, |s| s.length.is_none()),
+            missing_description: missing(b, |s| !s.is_documented()),
+            coverage: block_coverage(b),
+        })
+        .collect();
+
+    // Combine coverage across blocks by version name, in order of appearance
+    let mut combined: Vec<(Option<String>, Uint, Uint)> = Vec::new();
+    for c in blocks.iter().flat_map(|b| b.coverage.iter()) {
+        match combined.iter_mut().find(|(v, _, _)| *v == c.version) {
+            Some((_, covered, total)) => {
+                *covered += c.covered_bytes;
+                *total += c.total_bytes;
+            }
+            None => combined.push((c.version.clone(), c.covered_bytes, c.total_bytes)),
+        }
+    }
+    let total = BlockStats {
+        name: "total".to_string(),
+        functions: blocks.iter().map(|b| b.functions).sum(),
+        data: blocks.iter().map(|b| b.data).sum(),
+        missing_length: blocks.iter().map(|b| b.missing_length).sum(),
+        missing_description: blocks.iter().map(|b| b.missing_description).sum(),
+        coverage: combined
+            .into_iter()
+            .map(|(v, covered, total)| Coverage::new(v, covered, total))
+            .collect(),
+    };
+    SymGenStats { blocks, total }
+}
+
+/// Prints statistics about the symbols in `input_file` (and its subregion files), by block and
+/// overall: symbol counts, how many symbols are missing lengths and descriptions, and how much of
+/// each block is covered by symbols. With `json`, the statistics are printed as JSON instead.
+///
+/// # Examples
+/// ```ignore
+/// print_stats("/path/to/symbols.yml", false).expect("failed to compute stats");
+/// ```
+pub fn print_stats<P: AsRef<Path>>(input_file: P, json: bool) -> Result<(), ResymgenError> {
+    let contents = read_collapsed(input_file.as_ref())?;
+    let stats = symgen_stats(&contents);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+    for b in stats.blocks.iter().chain(iter::once(&stats.total)) {
+        println!("{}: {} function(s), {} data", b.name, b.functions, b.data);
+        println!(
+            "  missing length: {}, missing description: {}",
+            b.missing_length, b.missing_description
+        );
+        for c in b.coverage.iter() {
+            let version = c
+                .version
+                .as_ref()
+                .map(|v| format!(" ({})", v))
+                .unwrap_or_default();
+            println!(
+                "  coverage{}: {:#X} of {:#X} bytes ({:.2}%)",
+                version, c.covered_bytes, c.total_bytes, c.percent
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Lists the file paths of all resolved [`Subregion`]s within `symgen` (recursively), in
+/// depth-first order, where `dir_path` is the subregion directory of `symgen` itself.
+pub fn resolved_subregion_paths<P: AsRef<Path>>(symgen: &SymGen, dir_path: P) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for block in symgen.blocks() {
+        if let Some(subregions) = &block.subregions {
+            for s in subregions {
+                if let Some(contents) = &s.contents {
+                    let path = dir_path.as_ref().join(&s.name);
+                    paths.push(path.clone());
+                    paths.extend(resolved_subregion_paths(
+                        contents,
+                        Subregion::subregion_dir(&path),
+                    ));
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// Resolves the full subregion tree of `input_file` against the file system, without modifying
+/// anything, and prints the subregion files that were found.
+///
+/// Fails with the underlying error (e.g., a missing subregion file, a symlinked subregion
+/// directory, or an invalid subregion path) if any part of the tree fails to resolve.
+///
+/// # Examples
+/// ```ignore
+/// print_resolve("/path/to/symbols.yml").expect("subregion tree did not resolve");
+/// ```
+pub fn print_resolve<P: AsRef<Path>>(input_file: P) -> Result<(), ResymgenError> {
+    let input_file = input_file.as_ref();
+    let mut contents = {
+        let file = File::open(input_file)?;
+        SymGen::read(&file)?
+    };
+    let subregion_dir = Subregion::subregion_dir(input_file);
+    contents.resolve_subregions(&subregion_dir, |p| File::open(p))?;
+    let paths = resolved_subregion_paths(&contents, &subregion_dir);
+    for p in paths.iter() {
+        println!("  {}", p.display());
+    }
+    println!(
+        "Resolved {} subregion file(s) from \"{}\"",
+        paths.len(),
+        input_file.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::data_formats::symgen_yml::test_utils;
+    use super::*;
+
+    fn get_test_symgen() -> SymGen {
+        SymGen::read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2001000
+                    v2: 0x2001C00
+                - name: fn2
+                  address:
+                    v1:
+                      - 0x2002000
+                      - 0x2003000
+                    v2:
+                      - 0x2002C00
+                      - 0x2003C00
+                - name: fn3
+                  address:
+                    v1: 0x2004000
+              data:
+                - name: SOME_DATA
+                  address:
+                    v1: 0x2005000
+                    v2: 0x2004F00
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    #[test]
+    fn test_version_deltas() {
+        let symgen = get_test_symgen();
+        let deltas = version_deltas(&symgen, "v1", "v2").expect("version deltas failed");
+        assert_eq!(
+            deltas,
+            vec![
+                VersionDelta {
+                    name: "fn1",
+                    from_address: 0x2001000,
+                    to_address: 0x2001C00,
+                },
+                VersionDelta {
+                    name: "fn2",
+                    from_address: 0x2002000,
+                    to_address: 0x2002C00,
+                },
+                VersionDelta {
+                    name: "fn2",
+                    from_address: 0x2003000,
+                    to_address: 0x2003C00,
+                },
+                VersionDelta {
+                    name: "SOME_DATA",
+                    from_address: 0x2005000,
+                    to_address: 0x2004F00,
+                },
+            ]
+        );
+        assert_eq!(deltas[3].delta(), -0x100);
+        assert_eq!(most_common_delta(&deltas), Some((0xC00, 3)));
+    }
+
+    #[test]
+    fn test_version_deltas_unknown_version() {
+        let symgen = get_test_symgen();
+        let err = version_deltas(&symgen, "v1", "v3").expect_err("unknown version should fail");
+        assert!(err.to_string().contains("'v3'"));
+        assert!(version_deltas(&symgen, "V1", "v2").is_err());
+    }
+
+    #[test]
+    fn test_version_symbol_counts() {
+        let symgen = get_test_symgen();
+        assert_eq!(version_symbol_counts(&symgen), vec![("v1", 4), ("v2", 3)]);
+
+        let symgen = SymGen::read(
+            r"
+            main:
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address: 0x2001000
+              data: []
+            other:
+              address:
+                v3: 0x2100000
+              length: 0x1000
+              functions:
+                - name: fn2
+                  address: 0x2100000
+                - name: fn3
+                  address:
+                    v4: 0x2100100
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        assert_eq!(version_symbol_counts(&symgen), vec![("v3", 1), ("v4", 2)]);
+    }
+
+    #[test]
+    fn test_most_common_delta_empty() {
+        assert_eq!(most_common_delta(&[]), None);
+    }
+
+    #[test]
+    fn test_format_delta() {
+        assert_eq!(format_delta(0xC00), "+0xC00");
+        assert_eq!(format_delta(-0x10), "-0x10");
+        assert_eq!(format_delta(0), "+0x0");
+    }
+
+    #[test]
+    fn test_lookup_symbols() {
+        let mut symgen = get_test_symgen();
+        assert_eq!(
+            lookup_symbols(&symgen, "fn1")
+                .into_iter()
+                .map(|(bname, s)| (bname, s.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("main", "fn1")]
+        );
+        assert!(lookup_symbols(&symgen, "fn4").is_empty());
+
+        // Lookup should also work with deprecated aliases
+        let block = symgen.blocks_mut().next().unwrap();
+        block.functions.get_mut(0).unwrap().deprecated_aliases = Some(vec!["fn4".to_string()]);
+        assert_eq!(
+            lookup_symbols(&symgen, "fn4")
+                .into_iter()
+                .map(|(bname, s)| (bname, s.name.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("main", "fn1")]
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("flaw", "lawn"), 2);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_fuzzy_lookup() {
+        let symgen = get_test_symgen();
+        assert_eq!(
+            fuzzy_lookup(&symgen, "fn", 3),
+            vec![(1, "fn1"), (1, "fn2"), (1, "fn3")]
+        );
+        assert_eq!(fuzzy_lookup(&symgen, "SOME_DAT", 1), vec![(1, "SOME_DATA")]);
+    }
+
+    #[test]
+    fn test_covered_bytes() {
+        assert_eq!(covered_bytes((0x100, 0x200), vec![]), 0);
+        // Overlaps are only counted once
+        assert_eq!(
+            covered_bytes(
+                (0x100, 0x200),
+                vec![
+                    (0x140, 0x150),
+                    (0x100, 0x110),
+                    (0x108, 0x118),
+                    (0x140, 0x148)
+                ]
+            ),
+            0x28
+        );
+        // Extents are clipped to the bound
+        assert_eq!(
+            covered_bytes(
+                (0x100, 0x200),
+                vec![(0x0, 0x108), (0x1F0, 0x300), (0x300, 0x400)]
+            ),
+            0x18
+        );
+    }
+
+    #[test]
+    fn test_symgen_stats() {
+        let symgen = SymGen::read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address:
+                v1: 0x2000000
+                v2: 0x2000100
+              length:
+                v1: 0x1000
+                v2: 0x800
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2000000
+                    v2: 0x2000100
+                  length:
+                    v1: 0x100
+                    v2: 0x200
+                  description: foo
+                - name: fn2
+                  address:
+                    v1:
+                      - 0x2000200
+                      - 0x2000300
+                  length: 0x80
+                - name: fn3
+                  address: 0x2000400
+              data:
+                - name: OVERLAPPING_DATA
+                  address:
+                    v1: 0x2000080
+                    v2: 0x2000180
+                  length: 0x100
+                  description: bar
+            other:
+              address: 0x2100000
+              length: 0x100
+              functions: []
+              data:
+                - name: OTHER_DATA
+                  address: 0x2100000
+                  length: 0x40
+                  ranges:
+                    - address: 0x2100080
+                      length: 0x40
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        let stats = symgen_stats(&symgen);
+        fn coverage(b: &BlockStats) -> Vec<(Option<&str>, Uint, Uint, f64)> {
+            b.coverage
+                .iter()
+                .map(|c| {
+                    (
+                        c.version.as_deref(),
+                        c.covered_bytes,
+                        c.total_bytes,
+                        c.percent,
+                    )
+                })
+                .collect()
+        }
+
+        let main = &stats.blocks[0];
+        assert_eq!(main.name, "main");
+        assert_eq!((main.functions, main.data), (3, 1));
+        assert_eq!((main.missing_length, main.missing_description), (1, 2));
+        assert_eq!(
+            coverage(main),
+            vec![
+                // fn1 + the non-overlapping part of OVERLAPPING_DATA + both addresses of fn2
+                (Some("v1"), 0x280, 0x1000, 15.625),
+                // fn1 contains OVERLAPPING_DATA
+                (Some("v2"), 0x200, 0x800, 25.0),
+            ]
+        );
+
+        let other = &stats.blocks[1];
+        assert_eq!((other.functions, other.data), (0, 1));
+        assert_eq!(coverage(other), vec![(None, 0x80, 0x100, 50.0)]);
+
+        let total = &stats.total;
+        assert_eq!((total.functions, total.data), (3, 2));
+        assert_eq!((total.missing_length, total.missing_description), (1, 3));
+        assert_eq!(
+            coverage(total),
+            vec![
+                (Some("v1"), 0x280, 0x1000, 15.625),
+                (Some("v2"), 0x200, 0x800, 25.0),
+                (None, 0x80, 0x100, 50.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolved_subregion_paths() {
+        let symgen = test_utils::get_symgen_with_subregions(
+            r"
+            main:
+              address: 0x2000000
+              length: 0x100000
+              subregions:
+                - sub1.yml
+                - sub2.yml
+              functions: []
+              data: []
+            ",
+            &[
+                (
+                    "sub1.yml",
+                    r"
+                    sub1:
+                      address: 0x2000000
+                      length: 0x1000
+                      subregions:
+                        - nested.yml
+                      functions: []
+                      data: []
+                    ",
+                ),
+                (
+                    "sub1/nested.yml",
+                    r"
+                    nested:
+                      address: 0x2000000
+                      length: 0x100
+                      functions: []
+                      data: []
+                    ",
+                ),
+                (
+                    "sub2.yml",
+                    r"
+                    sub2:
+                      address: 0x2001000
+                      length: 0x1000
+                      functions: []
+                      data: []
+                    ",
+                ),
+            ],
+        );
+        let root_dir = Path::new("root");
+        assert_eq!(
+            resolved_subregion_paths(&symgen, root_dir),
+            vec![
+                root_dir.join("sub1.yml"),
+                root_dir.join("sub1").join("nested.yml"),
+                root_dir.join("sub2.yml"),
+            ]
+        );
+    }
+}
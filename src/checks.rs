@@ -2,29 +2,87 @@
 
 use std::borrow::Borrow;
 use std::cmp;
-use std::collections::{BTreeMap, HashMap, HashSet};
-use std::error::Error;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::{self, Display, Formatter};
 use std::fs::File;
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
 use std::iter;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
+use regex::Regex;
+use serde::Deserialize;
 use syn::{self, Ident};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 use super::data_formats::symgen_yml::bounds::{self, BoundViolation};
 use super::data_formats::symgen_yml::{
-    Block, MaybeVersionDep, OrdString, Subregion, SymGen, Symbol, Uint, Version, VersionDep,
+    self as symgen_yml, Block, IntFormat, Linkable, MaybeVersionDep, OrdString, Sort, Subregion,
+    SymGen, Symbol, SymbolList, Uint, Version, VersionDep,
 };
-use super::util::MultiFileError;
+use super::util::{self, MultiFileError, ResymgenError, UsageError};
+
+/// A regular expression that symbol names must match in their entirety.
+///
+/// Patterns are compared by their original pattern strings, so that [`NamingConvention`]s (and
+/// [`Check`]s) can be compared for equality. They're displayed as `regex:PATTERN`, matching how
+/// they're specified on the command line.
+#[derive(Clone)]
+pub struct NamePattern {
+    pattern: String,
+    regex: Regex,
+}
+
+impl NamePattern {
+    /// Compiles `pattern` into a [`NamePattern`]. The pattern is anchored at both ends, so it
+    /// doesn't need to start with `^` or end with `$` (though it can).
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        // Compile the raw pattern first, so that any error refers to what the user wrote
+        Regex::new(pattern)?;
+        Ok(Self {
+            pattern: pattern.to_string(),
+            regex: Regex::new(&format!("^(?:{})$", pattern))?,
+        })
+    }
+    /// Gets the original pattern string.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+    fn is_match(&self, name: &str) -> bool {
+        self.regex.is_match(name)
+    }
+}
+
+impl PartialEq for NamePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+impl Eq for NamePattern {}
+
+impl Display for NamePattern {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "regex:{}", self.pattern)
+    }
+}
+
+// The compiled regex is just noise, so Debug is the same as Display
+impl fmt::Debug for NamePattern {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
 
 /// Naming conventions for symbol names.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NamingConvention {
     /// Symbol names should be valid identifiers (in accordance with Rust syntax).
-    /// This condition implicitly applies to all other variants.
+    /// This condition implicitly applies to all other variants except [`KebabCase`] and
+    /// [`Regex`].
+    ///
+    /// [`KebabCase`]: NamingConvention::KebabCase
+    /// [`Regex`]: NamingConvention::Regex
     Identifier,
     /// snake_case
     SnakeCase,
@@ -34,12 +92,30 @@ pub enum NamingConvention {
     CamelCase,
     /// PascalCase
     PascalCase,
+    /// kebab-case (hyphens aren't allowed in identifiers, so names don't need to be identifiers)
+    KebabCase,
+    /// Symbol names must match a custom regular expression (which takes the place of all other
+    /// conventions, including [`Identifier`]).
+    ///
+    /// [`Identifier`]: NamingConvention::Identifier
+    Regex(NamePattern),
 }
 
 impl NamingConvention {
+    /// Creates a [`NamingConvention::Regex`] from a regular expression `pattern`, which must match
+    /// entire names.
+    pub fn from_regex(pattern: &str) -> Result<Self, UsageError> {
+        NamePattern::new(pattern).map(Self::Regex).map_err(|e| {
+            UsageError(format!(
+                "Invalid naming convention pattern '{}': {}",
+                pattern, e
+            ))
+        })
+    }
+
     /// Checks if a name is valid under a given naming convention
     fn check(&self, name: &str) -> bool {
-        if let Self::Identifier = self {
+        if let Self::Identifier | Self::KebabCase | Self::Regex(_) = self {
         } else {
             // All other conventions are contingent on valid identifiers everything goes through
             // the Identifier check first.
@@ -56,7 +132,7 @@ impl NamingConvention {
                         return false;
                     }
                 }
-                if let Some(c) = name.chars().rev().next() {
+                if let Some(c) = name.chars().next_back() {
                     if c.is_whitespace() {
                         return false;
                     }
@@ -95,6 +171,12 @@ impl NamingConvention {
                 }
                 NamingConvention::camel_family_check(name)
             }
+            // Nonempty words separated by single hyphens. This also rules out leading/trailing
+            // hyphens and whitespace.
+            Self::KebabCase => name.split('-').all(|word| {
+                !word.is_empty() && word.chars().all(|c| c.is_lowercase() || c.is_numeric())
+            }),
+            Self::Regex(pattern) => pattern.is_match(name),
         }
     }
 
@@ -121,8 +203,66 @@ impl NamingConvention {
     }
 }
 
+/// A named memory region, spanning the addresses from `start` (inclusive) to `end` (exclusive).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct AddressRegion {
+    #[serde(skip)]
+    pub name: String,
+    pub start: Uint,
+    pub end: Uint,
+}
+
+impl AddressRegion {
+    fn bound(&self) -> (Uint, Option<Uint>) {
+        (self.start, Some(self.end - self.start))
+    }
+}
+
+/// A named set of [`AddressRegion`]s that symbols are expected to fall within.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressRegionSet {
+    pub name: String,
+    pub regions: Vec<AddressRegion>,
+}
+
+impl AddressRegionSet {
+    /// Reads an [`AddressRegionSet`] with the given `name` from YAML data, which should be a map
+    /// from region names to `start` and `end` addresses. For example:
+    ///
+    /// ```yaml
+    /// main_ram:
+    ///   start: 0x2000000
+    ///   end: 0x2400000
+    /// itcm:
+    ///   start: 0x1FF8000
+    ///   end: 0x2000000
+    /// ```
+    pub fn read<R: Read>(name: &str, rdr: R) -> Result<Self, ResymgenError> {
+        let regions: BTreeMap<String, AddressRegion> =
+            serde_yaml::from_reader(rdr).map_err(symgen_yml::Error::Yaml)?;
+        let regions: Vec<AddressRegion> = regions
+            .into_iter()
+            .map(|(rname, region)| AddressRegion {
+                name: rname,
+                ..region
+            })
+            .collect();
+        if let Some(r) = regions.iter().find(|r| r.end <= r.start) {
+            return Err(format!(
+                "{}: address region \"{}\" is empty ({:#X}..{:#X})",
+                name, r.name, r.start, r.end
+            )
+            .into());
+        }
+        Ok(Self {
+            name: name.to_string(),
+            regions,
+        })
+    }
+}
+
 /// Checks that can be run on `resymgen` YAML symbol tables.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Check {
     /// All addresses and lengths (for both blocks and symbols) must be explicitly listed by version.
     ExplicitVersions,
@@ -135,17 +275,92 @@ pub enum Check {
     UniqueSymbols,
     /// Symbol names must be unique within a block and all its subregions.
     UniqueSymbolsAcrossSubregions,
+    /// Symbol names and deprecated aliases must be unique across all blocks in a file, including
+    /// all subregions (if they've been resolved).
+    GloballyUniqueNames,
     /// Symbols and subregions must fall within the address range of the parent block.
     InBoundsSymbols,
     /// For a given block and version, function symbols must not overlap with each other, and
     /// subregions must not overlap with other subregions, function symbols, or data symbols.
+    /// Subregions in different space groups (see [`Block::space_group`]) may overlap with each
+    /// other.
     NoOverlap,
+    /// For a given version, the extents of different blocks must not overlap with each other.
+    /// Blocks in different space groups (see [`Block::space_group`]) may overlap with each other.
+    NoBlockOverlap,
     /// Function symbol names must adhere to the specified [`NamingConvention`].
     FunctionNames(NamingConvention),
     /// Data symbol names must adhere to the specified [`NamingConvention`].
     DataNames(NamingConvention),
+    /// Deprecated aliases of function symbols must adhere to the specified [`NamingConvention`].
+    FunctionAliasNames(NamingConvention),
+    /// Deprecated aliases of data symbols must adhere to the specified [`NamingConvention`].
+    DataAliasNames(NamingConvention),
+    /// For data symbols with an element size, the length for every version must be a multiple
+    /// of the element size.
+    DataLengthMultiple,
+    /// For a given version, no two blocks may share the same base address.
+    UniqueBlockAddresses,
+    /// For a given version, a symbol with multiple addresses must not list the same address more
+    /// than once.
+    NoDuplicateSymbolAddresses,
+    /// For a given block and version, no two distinct symbols (whether functions or data) may
+    /// share an address. Unlike [`NoOverlap`], this also catches symbols without lengths.
+    ///
+    /// [`NoOverlap`]: Check::NoOverlap
+    NoSharedAddresses,
+    /// Blocks whose version lists contain the same set of versions must list them in the same
+    /// order as the first block with a version list.
+    ConsistentVersionOrder,
+    /// Block version lists must be sorted. Versions are sorted by name by default. If an order is
+    /// given, versions are sorted in that order instead, with any versions not in the order
+    /// sorted by name after the rest.
+    SortedVersions(Option<Vec<String>>),
+    /// The function and data symbols in each block must already be in the order that sorting
+    /// them (as with `resymgen fmt`) would produce, and each symbol's address list must be sorted.
+    /// Symbols that compare equal (e.g., symbols sharing the same addresses) can be in any order.
+    SortedSymbols,
+    /// Subregion blocks must fall within the address range of the parent block, and must not use
+    /// versions that the parent block doesn't have.
+    SubregionMetadataConsistency,
+    /// Single-line descriptions must not contain sequences that change meaning when written as
+    /// plain (unquoted) YAML scalars, such as `": "` or a leading indicator character.
+    SafeDescriptions,
+    /// Block and symbol descriptions must not have trailing whitespace on any line, or trailing
+    /// blank lines.
+    TrimmedDescriptions,
+    /// For every version, block base addresses must be a multiple of the given alignment (which
+    /// should be a power of two).
+    BlockAlignment(Uint),
+    /// Symbols must have either a length or a non-blank description. Symbols with neither are
+    /// essentially name-only placeholders.
+    IncompleteSymbols,
+    /// Formatting the file and reading the result back must produce identical contents. A failure
+    /// indicates a bug in the formatter or the parser that loses or mutates data.
+    RoundTripStable,
+    /// Explicit block and symbol lengths must be nonzero. If `within_block` is set, symbol lengths
+    /// must also not exceed the length of the parent block.
+    NonZeroLengths { within_block: bool },
+    /// Symbols must have a nonblank description (for every version, if the description is
+    /// version-dependent). This can be required for function symbols, data symbols, or both.
+    RequireDescriptions { functions: bool, data: bool },
+    /// Block and symbol descriptions must not be longer than the given number of bytes (not
+    /// characters) when encoded as UTF-8.
+    MaxDescriptionLength(usize),
+    /// Symbol names and deprecated aliases must not be longer than the given number of bytes (not
+    /// characters) when encoded as UTF-8.
+    MaxNameLength(usize),
+    /// Symbol names must not start with any of the given prefixes. Deprecated aliases are exempt,
+    /// since they often preserve old placeholder names.
+    ForbiddenPrefixes(Vec<String>),
+    /// Every symbol (and each of its additional ranges) must fall entirely within at least one
+    /// region of the given [`AddressRegionSet`], for every version.
+    AddressRegions(AddressRegionSet),
 }
 
+/// Names of the built-in check presets (see [`Check::preset()`]).
+pub const CHECK_PRESETS: [&str; 3] = ["basic", "ci", "strict"];
+
 /// The result of a [`Check`] run on `resymgen` YAML symbol tables.
 #[derive(Debug)]
 pub struct CheckResult {
@@ -155,6 +370,58 @@ pub struct CheckResult {
 }
 
 impl Check {
+    /// Returns the [`Check`]s in the built-in preset with the given `name`, if there is one.
+    ///
+    /// - `basic`: [`UniqueSymbols`], [`InBoundsSymbols`], [`NoOverlap`], and
+    ///   [`CompleteVersionList`].
+    /// - `ci`: the checks run by this project's CI. These are the `basic` checks plus
+    ///   [`ExplicitVersions`] and [`NonEmptyMaps`], along with [`FunctionNames`] in
+    ///   [`PascalCase`] and [`DataNames`] in [`ScreamingSnakeCase`].
+    /// - `strict`: the `ci` checks plus [`DataLengthMultiple`], [`UniqueBlockAddresses`],
+    ///   [`NoDuplicateSymbolAddresses`], [`ConsistentVersionOrder`], and [`SafeDescriptions`].
+    ///
+    /// [`UniqueSymbols`]: Check::UniqueSymbols
+    /// [`InBoundsSymbols`]: Check::InBoundsSymbols
+    /// [`NoOverlap`]: Check::NoOverlap
+    /// [`CompleteVersionList`]: Check::CompleteVersionList
+    /// [`ExplicitVersions`]: Check::ExplicitVersions
+    /// [`NonEmptyMaps`]: Check::NonEmptyMaps
+    /// [`FunctionNames`]: Check::FunctionNames
+    /// [`PascalCase`]: NamingConvention::PascalCase
+    /// [`DataNames`]: Check::DataNames
+    /// [`ScreamingSnakeCase`]: NamingConvention::ScreamingSnakeCase
+    /// [`DataLengthMultiple`]: Check::DataLengthMultiple
+    /// [`UniqueBlockAddresses`]: Check::UniqueBlockAddresses
+    /// [`NoDuplicateSymbolAddresses`]: Check::NoDuplicateSymbolAddresses
+    /// [`ConsistentVersionOrder`]: Check::ConsistentVersionOrder
+    /// [`SafeDescriptions`]: Check::SafeDescriptions
+    pub fn preset(name: &str) -> Option<Vec<Check>> {
+        let basic = [
+            Self::UniqueSymbols,
+            Self::InBoundsSymbols,
+            Self::NoOverlap,
+            Self::CompleteVersionList,
+        ];
+        let ci = [
+            Self::ExplicitVersions,
+            Self::NonEmptyMaps,
+            Self::FunctionNames(NamingConvention::PascalCase),
+            Self::DataNames(NamingConvention::ScreamingSnakeCase),
+        ];
+        let strict = [
+            Self::DataLengthMultiple,
+            Self::UniqueBlockAddresses,
+            Self::NoDuplicateSymbolAddresses,
+            Self::ConsistentVersionOrder,
+            Self::SafeDescriptions,
+        ];
+        match name {
+            "basic" => Some(basic.to_vec()),
+            "ci" => Some([&basic[..], &ci].concat()),
+            "strict" => Some([&basic[..], &ci, &strict].concat()),
+            _ => None,
+        }
+    }
     fn run(&self, symgen: &SymGen) -> CheckResult {
         match self {
             Self::ExplicitVersions => self.result(check_explicit_versions(symgen)),
@@ -164,15 +431,98 @@ impl Check {
             Self::UniqueSymbolsAcrossSubregions => {
                 self.result(check_unique_symbols_across_subregions(symgen))
             }
+            Self::GloballyUniqueNames => self.result(check_globally_unique_names(symgen)),
             Self::InBoundsSymbols => self.result(check_in_bounds_symbols(symgen)),
             Self::NoOverlap => self.result(check_no_overlap(symgen)),
-            Self::FunctionNames(conv) => self.result(check_function_names(symgen, *conv)),
-            Self::DataNames(conv) => self.result(check_data_names(symgen, *conv)),
+            Self::NoBlockOverlap => self.result(check_no_block_overlap(symgen)),
+            Self::FunctionNames(conv) => self.result(check_function_names(symgen, conv)),
+            Self::DataNames(conv) => self.result(check_data_names(symgen, conv)),
+            Self::FunctionAliasNames(conv) => self.result(check_function_alias_names(symgen, conv)),
+            Self::DataAliasNames(conv) => self.result(check_data_alias_names(symgen, conv)),
+            Self::DataLengthMultiple => self.result(check_data_length_multiple(symgen)),
+            Self::UniqueBlockAddresses => self.result(check_unique_block_addresses(symgen)),
+            Self::NoDuplicateSymbolAddresses => {
+                self.result(check_no_duplicate_symbol_addresses(symgen))
+            }
+            Self::NoSharedAddresses => self.result(check_no_shared_addresses(symgen)),
+            Self::ConsistentVersionOrder => self.result(check_consistent_version_order(symgen)),
+            Self::SortedSymbols => self.result(check_sorted_symbols(symgen)),
+            Self::SortedVersions(order) => {
+                self.result(check_sorted_versions(symgen, order.as_deref()))
+            }
+            Self::SubregionMetadataConsistency => {
+                self.result(check_subregion_metadata_consistency(symgen))
+            }
+            Self::SafeDescriptions => self.result(check_safe_descriptions(symgen)),
+            Self::TrimmedDescriptions => self.result(check_trimmed_descriptions(symgen)),
+            Self::BlockAlignment(align) => self.result(check_block_alignment(symgen, *align)),
+            Self::IncompleteSymbols => self.result(check_incomplete_symbols(symgen)),
+            Self::RoundTripStable => self.result(check_round_trip_stable(symgen)),
+            Self::NonZeroLengths { within_block } => {
+                self.result(check_nonzero_lengths(symgen, *within_block))
+            }
+            Self::RequireDescriptions { functions, data } => {
+                self.result(check_require_descriptions(symgen, *functions, *data))
+            }
+            Self::MaxDescriptionLength(max_len) => {
+                self.result(check_max_description_length(symgen, *max_len))
+            }
+            Self::MaxNameLength(max_len) => self.result(check_max_name_length(symgen, *max_len)),
+            Self::ForbiddenPrefixes(prefixes) => {
+                self.result(check_forbidden_prefixes(symgen, prefixes))
+            }
+            Self::AddressRegions(regions) => self.result(check_address_regions(symgen, regions)),
+        }
+    }
+    /// Whether the [`Check`] only compares addresses and lengths within each version, and so can
+    /// be restricted to a single version.
+    fn is_version_specific(&self) -> bool {
+        matches!(
+            self,
+            Self::InBoundsSymbols
+                | Self::NoOverlap
+                | Self::NoBlockOverlap
+                | Self::DataLengthMultiple
+                | Self::UniqueBlockAddresses
+                | Self::NoDuplicateSymbolAddresses
+                | Self::NoSharedAddresses
+                | Self::BlockAlignment(_)
+                | Self::NonZeroLengths { .. }
+                | Self::AddressRegions(_)
+        )
+    }
+    /// Whether the [`Check`] already covers all subregions when run on the root [`SymGen`], and so
+    /// shouldn't be run again on each subregion.
+    fn spans_subregions(&self) -> bool {
+        matches!(self, Self::GloballyUniqueNames)
+    }
+    /// Whether failures of the [`Check`] can be corrected automatically with [`Check::fix()`].
+    pub fn is_fixable(&self) -> bool {
+        matches!(
+            self,
+            Self::NoDuplicateSymbolAddresses
+                | Self::ConsistentVersionOrder
+                | Self::SortedSymbols
+                | Self::TrimmedDescriptions
+        )
+    }
+    /// Applies mechanical fixes for failures of the [`Check`] to `symgen` in place.
+    ///
+    /// Returns the number of fixes applied, or [`None`] if the [`Check`] doesn't support
+    /// automatic fixes (see [`Check::is_fixable()`]). Fixes only cover issues that don't need
+    /// human judgment, so the [`Check`] can still fail afterwards.
+    pub fn fix(&self, symgen: &mut SymGen) -> Option<usize> {
+        match self {
+            Self::NoDuplicateSymbolAddresses => Some(fix_duplicate_symbol_addresses(symgen)),
+            Self::ConsistentVersionOrder => Some(fix_version_order(symgen)),
+            Self::SortedSymbols => Some(fix_sorted_symbols(symgen)),
+            Self::TrimmedDescriptions => Some(fix_trimmed_descriptions(symgen)),
+            _ => None,
         }
     }
     fn result(&self, raw_result: Result<(), String>) -> CheckResult {
         CheckResult {
-            check: *self,
+            check: self.clone(),
             succeeded: raw_result.is_ok(),
             details: raw_result.err(),
         }
@@ -181,7 +531,17 @@ impl Check {
 
 impl Display for Check {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            // The full region list is too noisy for a check name
+            Self::AddressRegions(regions) => write!(f, "AddressRegions({})", regions.name),
+            Self::FunctionNames(NamingConvention::Regex(pattern)) => {
+                write!(f, "FunctionNames({})", pattern)
+            }
+            Self::DataNames(NamingConvention::Regex(pattern)) => {
+                write!(f, "DataNames({})", pattern)
+            }
+            _ => write!(f, "{:?}", self),
+        }
     }
 }
 
@@ -516,13 +876,54 @@ fn check_unique_symbols_across_subregions(symgen: &SymGen) -> Result<(), String>
     })
 }
 
-fn check_in_bounds_symbols(symgen: &SymGen) -> Result<(), String> {
-    fn range_str((addr, opt_len): (Uint, Option<Uint>)) -> String {
-        match opt_len {
-            Some(len) => format!("{:#X}..{:#X}", addr, addr + len),
-            None => format!("{:#X}", addr),
+fn check_globally_unique_names(symgen: &SymGen) -> Result<(), String> {
+    let mut locations: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    // Use paths relative to the root symgen
+    for cursor in symgen.cursor(Path::new("")).blocks() {
+        for block in cursor.dtraverse() {
+            let bname = if block.path().components().next().is_none() {
+                block.name().to_string()
+            } else {
+                format!("{}::{}", block.path().display(), block.name())
+            };
+            for s in block.block().iter() {
+                locations.entry(&s.name).or_default().push(bname.clone());
+                for alias in s.iter_aliases() {
+                    locations
+                        .entry(alias)
+                        .or_default()
+                        .push(format!("{} (alias of \"{}\")", bname, s.name));
+                }
+            }
         }
     }
+    let duplicates: Vec<String> = locations
+        .into_iter()
+        .filter(|(_, locs)| locs.len() > 1)
+        .map(|(name, locs)| format!("- \"{}\" used in: [{}]", name, locs.join(", ")))
+        .collect();
+    assert_check(duplicates.is_empty(), || {
+        format!(
+            "Found names used more than once across blocks:\n{}",
+            duplicates.join("\n")
+        )
+    })
+}
+
+/// Formats an extent or bound (as an offset and an optional length) for display.
+fn range_str((addr, opt_len): (Uint, Option<Uint>)) -> String {
+    match opt_len {
+        Some(len) => format!("{:#X}..{:#X}", addr, addr + len),
+        None => format!("{:#X}", addr),
+    }
+}
+
+/// Formats a version as a suffix for a violation message, or an empty string for no version.
+fn version_suffix<V: Display>(vers: Option<V>) -> String {
+    vers.map(|v| format!(" [{}]", v)).unwrap_or_default()
+}
+
+fn check_in_bounds_symbols(symgen: &SymGen) -> Result<(), String> {
     fn violation_str(violation: BoundViolation, bname: &OrdString, identifier: String) -> String {
         if let Some(vers) = &violation.version {
             format!(
@@ -546,7 +947,8 @@ fn check_in_bounds_symbols(symgen: &SymGen) -> Result<(), String> {
 
     for (bname, b) in symgen.iter() {
         let bounds = b.extent();
-        for s in b.iter() {
+        // Inlined symbols don't occupy any address space
+        for s in b.iter().filter(|s| !s.is_inlined()) {
             if let Some(violation) = bounds::symbol_in_bounds(&bounds, s, &b.versions) {
                 return Err(violation_str(
                     violation,
@@ -572,6 +974,48 @@ fn check_in_bounds_symbols(symgen: &SymGen) -> Result<(), String> {
     Ok(())
 }
 
+fn check_address_regions(symgen: &SymGen, regions: &AddressRegionSet) -> Result<(), String> {
+    let mut outside = Vec::new();
+    for (bname, b) in symgen.iter() {
+        // Inlined symbols don't occupy any address space
+        for s in b.iter().filter(|s| !s.is_inlined()) {
+            let mut check_extent = |vers: Option<&Version>, ext: (Uint, Option<Uint>)| {
+                if !regions
+                    .regions
+                    .iter()
+                    .any(|r| bounds::contains_extent(r.bound(), ext))
+                {
+                    outside.push(format!(
+                        "- block \"{}\", symbol \"{}\"{}: {}",
+                        bname,
+                        s.name,
+                        version_suffix(vers),
+                        range_str(ext)
+                    ));
+                }
+            };
+            let extents = s.extents(b.versions.as_deref());
+            for (vers, (addrs, len)) in extents.iter_versioned() {
+                for &addr in addrs.iter() {
+                    check_extent(vers, (addr, *len));
+                }
+            }
+            for range_exts in s.range_extents(b.versions.as_deref()) {
+                for (vers, &ext) in range_exts.iter_versioned() {
+                    check_extent(vers, ext);
+                }
+            }
+        }
+    }
+    assert_check(outside.is_empty(), || {
+        format!(
+            "Found symbols outside of the address regions in \"{}\":\n{}",
+            regions.name,
+            outside.join("\n")
+        )
+    })
+}
+
 fn check_no_overlap(symgen: &SymGen) -> Result<(), String> {
     type Extent = (Uint, Uint);
     struct ExtentsByVersion<'a> {
@@ -612,6 +1056,10 @@ fn check_no_overlap(symgen: &SymGen) -> Result<(), String> {
             }
         }
         fn append_symbol(&mut self, symbol: &'a Symbol, versions: Option<&[Version]>) {
+            if symbol.is_inlined() {
+                // Inlined symbols don't occupy any address space
+                return;
+            }
             match symbol.extents(versions) {
                 MaybeVersionDep::ByVersion(s_exts) => {
                     for (vers, (addrs, len)) in s_exts.iter() {
@@ -631,6 +1079,23 @@ fn check_no_overlap(symgen: &SymGen) -> Result<(), String> {
                     }
                 }
             }
+            for range_exts in symbol.range_extents(versions) {
+                match range_exts {
+                    MaybeVersionDep::ByVersion(r_exts) => {
+                        for (vers, &(addr, len)) in r_exts.iter() {
+                            self.append(
+                                Some(vers.clone()),
+                                Self::get_ext_endpoints(addr, len),
+                                &symbol.name,
+                            )
+                        }
+                    }
+                    MaybeVersionDep::Common((addr, len)) => {
+                        // Version expansion wasn't possible, assume unversioned
+                        self.append(None, Self::get_ext_endpoints(addr, len), &symbol.name)
+                    }
+                }
+            }
         }
         fn append_block(&mut self, bname: &'a str, block: &'a Block) {
             match block.extent() {
@@ -802,15 +1267,39 @@ fn check_no_overlap(symgen: &SymGen) -> Result<(), String> {
             }
 
             // Gather all subregion extents
+            let subblocks: Vec<_> = cursor.subblocks().collect();
             let mut subregion_extents_by_vers = ExtentsByVersion::new();
-            for subblock in cursor.subblocks() {
+            for subblock in subblocks.iter() {
                 // For subregions, each subblock has its own version list, so use that for Common
                 // expansion rather than the parent block's version list
                 subregion_extents_by_vers.append_block(subblock.name(), subblock.block());
             }
 
-            // Compare subregion extents among themselves for overlaps
-            subregion_extents_by_vers.check_for_self_overlap(&bname.val, "subregions")?;
+            // Compare subregion extents among themselves for overlaps. Subregions in different
+            // space groups are never resident at the same time, so only compare subregions that
+            // can be resident together: those in the same group, and those without a group.
+            let mut groups: Vec<_> = subblocks
+                .iter()
+                .map(|b| b.block().space_group.as_deref())
+                .collect();
+            groups.sort_unstable();
+            groups.dedup();
+            if groups.len() > 1 {
+                for group in groups.into_iter().flatten() {
+                    let mut group_extents_by_vers = ExtentsByVersion::new();
+                    for subblock in subblocks.iter().filter(|b| {
+                        b.block()
+                            .space_group
+                            .as_deref()
+                            .map_or(true, |g| g == group)
+                    }) {
+                        group_extents_by_vers.append_block(subblock.name(), subblock.block());
+                    }
+                    group_extents_by_vers.check_for_self_overlap(&bname.val, "subregions")?;
+                }
+            } else {
+                subregion_extents_by_vers.check_for_self_overlap(&bname.val, "subregions")?;
+            }
             // Compare subregion extents with function/data extents for overlaps
             subregion_extents_by_vers.check_for_overlap_with(
                 &mut extents_by_vers,
@@ -825,7 +1314,7 @@ fn check_no_overlap(symgen: &SymGen) -> Result<(), String> {
 
 fn symbols_name_check<'s, F, I>(
     symgen: &'s SymGen,
-    conv: NamingConvention,
+    conv: &NamingConvention,
     block_iter: F,
     symbol_type: &str,
 ) -> Result<(), String>
@@ -858,275 +1347,2894 @@ where
     })
 }
 
-fn check_function_names(symgen: &SymGen, conv: NamingConvention) -> Result<(), String> {
+fn check_function_names(symgen: &SymGen, conv: &NamingConvention) -> Result<(), String> {
     symbols_name_check(symgen, conv, |b: &Block| b.functions.iter(), "function")
 }
 
-fn check_data_names(symgen: &SymGen, conv: NamingConvention) -> Result<(), String> {
+fn check_data_names(symgen: &SymGen, conv: &NamingConvention) -> Result<(), String> {
     symbols_name_check(symgen, conv, |b: &Block| b.data.iter(), "data")
 }
 
-/// Validates a given `input_file` under the specified `checks`.
-///
-/// In `recursive` mode, subregion files are also validated.
-///
-/// Returns a `Vec<(PathBuf, CheckResult)>` with the results of all checks on all the files
-/// validated, if all checks were run without encountering any fatal errors.
-///
-/// # Examples
-/// ```ignore
-/// let results = run_checks(
-///     "/path/to/symbols.yml",
-///     &[
-///         Check::ExplicitVersions,
-///         Check::FunctionNames(NamingConvention::SnakeCase),
-///     ],
-///     true,
-/// )
-/// .expect("Fatal error occurred");
-/// ```
-pub fn run_checks<P: AsRef<Path>>(
-    input_file: P,
-    checks: &[Check],
-    recursive: bool,
-) -> Result<Vec<(PathBuf, CheckResult)>, Box<dyn Error>> {
-    /// For returning either a [`Once`] iterator or an [`Empty`] iterator, while still allowing
-    /// static dispatch.
-    enum OnceOrEmpty<T> {
-        Once(iter::Once<T>),
-        Empty(iter::Empty<T>),
+fn aliases_name_check<'s, F, I>(
+    symgen: &'s SymGen,
+    conv: &NamingConvention,
+    block_iter: F,
+    symbol_type: &str,
+) -> Result<(), String>
+where
+    F: Fn(&'s Block) -> I,
+    I: Iterator<Item = &'s Symbol>,
+{
+    let mut bad_aliases: BTreeMap<&OrdString, BTreeSet<(&str, &str)>> = BTreeMap::new();
+    for (bname, b) in symgen.iter() {
+        for s in block_iter(b) {
+            for alias in s.iter_aliases().filter(|a| !conv.check(a)) {
+                bad_aliases
+                    .entry(bname)
+                    .or_default()
+                    .insert((alias, &s.name));
+            }
+        }
     }
-    impl<T> Iterator for OnceOrEmpty<T> {
-        type Item = T;
+    assert_check(bad_aliases.is_empty(), || {
+        format!(
+            "Found invalid {} aliases:\n{}",
+            symbol_type,
+            bad_aliases
+                .into_iter()
+                .map(|(bname, aliases)| {
+                    let aliases: Vec<_> = aliases
+                        .into_iter()
+                        .map(|(alias, name)| format!("{} (alias of \"{}\")", alias, name))
+                        .collect();
+                    format!("- block \"{}\": [{}]", bname, aliases.join(", "))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    })
+}
 
-        fn next(&mut self) -> Option<Self::Item> {
-            match self {
-                Self::Once(o) => o.next(),
-                Self::Empty(e) => e.next(),
+fn check_function_alias_names(symgen: &SymGen, conv: &NamingConvention) -> Result<(), String> {
+    aliases_name_check(symgen, conv, |b: &Block| b.functions.iter(), "function")
+}
+
+fn check_data_alias_names(symgen: &SymGen, conv: &NamingConvention) -> Result<(), String> {
+    aliases_name_check(symgen, conv, |b: &Block| b.data.iter(), "data")
+}
+
+fn check_data_length_multiple(symgen: &SymGen) -> Result<(), String> {
+    let mut bad_lengths = Vec::new();
+    for (bname, b) in symgen.iter() {
+        for s in b.data.iter() {
+            if let (Some(size), Some(len)) = (s.element_size, &s.length) {
+                for (vers, &l) in len.iter_versioned() {
+                    if size == 0 || l % size != 0 {
+                        let vers_str = version_suffix(vers);
+                        bad_lengths.push(format!(
+                            "- block \"{}\", symbol \"{}\"{}: length {:#X}, element size {:#X}",
+                            bname, s.name, vers_str, l, size
+                        ));
+                    }
+                }
             }
         }
     }
+    assert_check(bad_lengths.is_empty(), || {
+        format!(
+            "Found data lengths that are not multiples of the element size:\n{}",
+            bad_lengths.join("\n")
+        )
+    })
+}
 
-    let input_file = input_file.as_ref();
-    let mut contents = {
-        let f = File::open(input_file)?;
-        SymGen::read(&f)?
-    };
-    if recursive {
-        contents.resolve_subregions(Subregion::subregion_dir(input_file), |p| File::open(p))?;
-    }
-    Ok(checks
+fn check_unique_block_addresses(symgen: &SymGen) -> Result<(), String> {
+    /// A base address, along with the version name it applies to. Unversioned addresses (None)
+    /// apply to every version.
+    type VersionedBase = (Option<String>, Uint);
+
+    let bases: Vec<(&OrdString, Vec<VersionedBase>)> = symgen
         .iter()
-        .flat_map(|chk| {
-            let check_results = contents
-                .cursor(input_file)
-                .dtraverse()
-                .map(move |cursor| (cursor.path().to_owned(), chk.run(cursor.symgen())));
-            if let (Check::UniqueSymbols, true) =
-                (chk, contents.cursor(input_file).has_subregions())
-            {
-                // Recursive UniqueSymbols is a special case.
-                // Add a cross-subregion uniqueness check that spans all subregions
-                check_results.chain(OnceOrEmpty::Once(iter::once((
-                    input_file.to_owned(),
-                    Check::UniqueSymbolsAcrossSubregions.run(&contents),
-                ))))
-            } else {
-                check_results.chain(OnceOrEmpty::Empty(iter::empty()))
-            }
+        .map(|(bname, b)| {
+            let addrs = b
+                .extent()
+                .iter_versioned()
+                .map(|(v, &(addr, _))| (v.map(|v| v.name().to_string()), addr))
+                .collect();
+            (bname, addrs)
         })
-        .collect())
-}
-
-/// Prints check results similar to `cargo test` output.
-fn print_report(results: &[(PathBuf, CheckResult)]) -> io::Result<()> {
-    let mut stdout = StandardStream::stdout(ColorChoice::Always);
-    let mut print_colored_report = || -> io::Result<()> {
-        let mut color = ColorSpec::new();
+        .collect();
 
-        // Results list
-        for (name, r) in results {
-            stdout.reset()?;
-            write!(&mut stdout, "check {}::{} ... ", name.display(), r.check)?;
-            if r.succeeded {
-                stdout.set_color(color.set_fg(Some(Color::Green)))?;
-                writeln!(&mut stdout, "ok")?;
-            } else {
-                stdout.set_color(color.set_fg(Some(Color::Red)))?;
-                writeln!(&mut stdout, "FAILED")?;
+    let mut shared = Vec::new();
+    for (i, (bname1, addrs1)) in bases.iter().enumerate() {
+        for (bname2, addrs2) in bases[i + 1..].iter() {
+            for (vers1, addr1) in addrs1.iter() {
+                for (vers2, addr2) in addrs2.iter() {
+                    let same_version = match (vers1, vers2) {
+                        (Some(v1), Some(v2)) => v1 == v2,
+                        _ => true,
+                    };
+                    if same_version && addr1 == addr2 {
+                        let vers_str = version_suffix(vers1.as_ref().or(vers2.as_ref()));
+                        shared.push(format!(
+                            "- blocks \"{}\" and \"{}\"{}: {:#X}",
+                            bname1, bname2, vers_str, addr1
+                        ));
+                    }
+                }
             }
         }
+    }
+    assert_check(shared.is_empty(), || {
+        format!(
+            "Found blocks with the same base address:\n{}",
+            shared.join("\n")
+        )
+    })
+}
 
-        stdout.reset()?;
-        writeln!(&mut stdout)?;
-        let n_failed = results.iter().filter(|(_, r)| !r.succeeded).count();
-        let n_passed = results.len() - n_failed;
+fn check_no_block_overlap(symgen: &SymGen) -> Result<(), String> {
+    /// An extent (as an offset and an optional length), along with the version name it applies
+    /// to. Unversioned extents (None) apply to every version.
+    type VersionedExtent = (Option<String>, (Uint, Option<Uint>));
+    /// Blocks without a length are unbounded above
+    fn end((addr, opt_len): (Uint, Option<Uint>)) -> Uint {
+        opt_len.map_or(Uint::MAX, |len| addr.saturating_add(len))
+    }
 
-        // Failure details
-        if n_failed > 0 {
-            writeln!(&mut stdout, "failures:")?;
-            writeln!(&mut stdout)?;
-            for (name, r) in results.iter().filter(|(_, r)| !r.succeeded) {
-                writeln!(&mut stdout, "---- [{}] {} ----", name.display(), r.check)?;
-                if let Some(msg) = &r.details {
-                    writeln!(&mut stdout, "{}", msg)?;
+    // Blocks can each have their own version ordinal space, so versions are matched by name
+    let extents: Vec<(&OrdString, &Block, Vec<VersionedExtent>)> = symgen
+        .iter()
+        .map(|(bname, b)| {
+            let exts = b
+                .extent()
+                .iter_versioned()
+                .map(|(v, &ext)| (v.map(|v| v.name().to_string()), ext))
+                .collect();
+            (bname, b, exts)
+        })
+        .collect();
+
+    let mut overlapping = Vec::new();
+    for (i, (bname1, b1, exts1)) in extents.iter().enumerate() {
+        for (bname2, b2, exts2) in extents[i + 1..].iter() {
+            if let (Some(g1), Some(g2)) = (&b1.space_group, &b2.space_group) {
+                if g1 != g2 {
+                    // Never resident at the same time
+                    continue;
+                }
+            }
+            for &(ref vers1, ext1) in exts1.iter() {
+                for &(ref vers2, ext2) in exts2.iter() {
+                    let same_version = match (vers1, vers2) {
+                        (Some(v1), Some(v2)) => v1 == v2,
+                        _ => true,
+                    };
+                    if same_version && ext1.0 < end(ext2) && ext2.0 < end(ext1) {
+                        let vers_str = version_suffix(vers1.as_ref().or(vers2.as_ref()));
+                        overlapping.push(format!(
+                            "- blocks \"{}\" ({}) and \"{}\" ({}){}",
+                            bname1,
+                            range_str(ext1),
+                            bname2,
+                            range_str(ext2),
+                            vers_str
+                        ));
+                    }
                 }
-                writeln!(&mut stdout)?;
             }
         }
+    }
+    assert_check(overlapping.is_empty(), || {
+        format!("Found overlapping blocks:\n{}", overlapping.join("\n"))
+    })
+}
 
-        // Results summary
-        write!(&mut stdout, "check result: ")?;
-        if n_failed == 0 {
-            stdout.set_color(color.set_fg(Some(Color::Green)))?;
-            write!(&mut stdout, "ok")?;
-        } else {
-            stdout.set_color(color.set_fg(Some(Color::Red)))?;
-            write!(&mut stdout, "FAILED")?;
+fn check_no_duplicate_symbol_addresses(symgen: &SymGen) -> Result<(), String> {
+    let mut duplicates = Vec::new();
+    for (bname, b) in symgen.iter() {
+        for s in b.iter() {
+            let exts = s.extents(None);
+            for (vers, (addrs, _)) in exts.iter_versioned() {
+                let mut seen = HashSet::new();
+                let mut repeated = BTreeSet::new();
+                for addr in addrs.iter() {
+                    if !seen.insert(addr) {
+                        repeated.insert(addr);
+                    }
+                }
+                for addr in repeated {
+                    let vers_str = version_suffix(vers);
+                    duplicates.push(format!(
+                        "- block \"{}\", symbol \"{}\"{}: {:#X}",
+                        bname, s.name, vers_str, addr
+                    ));
+                }
+            }
         }
-        stdout.reset()?;
-        writeln!(&mut stdout, ". {} passed; {} failed", n_passed, n_failed)?;
+    }
+    assert_check(duplicates.is_empty(), || {
+        format!(
+            "Found symbols with repeated addresses:\n{}",
+            duplicates.join("\n")
+        )
+    })
+}
 
-        Ok(())
+fn check_no_shared_addresses(symgen: &SymGen) -> Result<(), String> {
+    let mut shared = Vec::new();
+    for (bname, b) in symgen.iter() {
+        // Unversioned blocks can still realize symbols with common addresses
+        let versions: Vec<&str> = match &b.versions {
+            Some(vers) => vers.iter().map(|v| v.name()).collect(),
+            None => vec![""],
+        };
+        for vers in versions {
+            let mut names_by_addr: BTreeMap<Uint, Vec<&str>> = BTreeMap::new();
+            for s in b.iter_realized(vers).filter(|s| !s.inlined) {
+                let names = names_by_addr.entry(s.address).or_default();
+                // A symbol can list the same address more than once; that's a separate check
+                if !names.contains(&s.name) {
+                    names.push(s.name);
+                }
+            }
+            let vers_str = version_suffix(Some(vers).filter(|v| !v.is_empty()));
+            for (addr, names) in names_by_addr.into_iter().filter(|(_, n)| n.len() > 1) {
+                shared.push(format!(
+                    "- block \"{}\"{}: {:#X} ({})",
+                    bname,
+                    vers_str,
+                    addr,
+                    names.join(", ")
+                ));
+            }
+        }
+    }
+    assert_check(shared.is_empty(), || {
+        format!(
+            "Found distinct symbols with the same address:\n{}",
+            shared.join("\n")
+        )
+    })
+}
+
+fn check_block_alignment(symgen: &SymGen, alignment: Uint) -> Result<(), String> {
+    let mut misaligned = Vec::new();
+    for (bname, b) in symgen.iter() {
+        for (vers, &(addr, _)) in b.extent().iter_versioned() {
+            if alignment != 0 && addr % alignment != 0 {
+                misaligned.push(format!(
+                    "- block \"{}\"{}: {:#X}",
+                    bname,
+                    version_suffix(vers),
+                    addr
+                ));
+            }
+        }
+    }
+    assert_check(misaligned.is_empty(), || {
+        format!(
+            "Found blocks with base addresses not aligned to {:#X}:\n{}",
+            alignment,
+            misaligned.join("\n")
+        )
+    })
+}
+
+fn check_sorted_symbols(symgen: &SymGen) -> Result<(), String> {
+    let mut unsorted = Vec::new();
+    for (bname, b) in symgen.iter() {
+        for (stype, symbols) in [("function", &b.functions), ("data", &b.data)] {
+            // Sorting is stable, so symbols that compare equal never count as out of order
+            let mut sorted = symbols.clone();
+            sorted.sort();
+            if &sorted == symbols {
+                continue;
+            }
+            let pairs = || symbols.iter().zip(sorted.iter());
+            let reason = match pairs().find(|(s, sorted_s)| s.name != sorted_s.name) {
+                Some((s, sorted_s)) => format!(
+                    "{} \"{}\" is out of order (expected \"{}\")",
+                    stype, s.name, sorted_s.name
+                ),
+                None => {
+                    let (s, _) = pairs()
+                        .find(|(s, sorted_s)| s != sorted_s)
+                        .expect("sorted symbols differ");
+                    format!("{} \"{}\" has unsorted addresses", stype, s.name)
+                }
+            };
+            unsorted.push(format!("- block \"{}\": {}", bname, reason));
+        }
+    }
+    assert_check(unsorted.is_empty(), || {
+        format!(
+            "Found blocks with unsorted symbols:\n{}",
+            unsorted.join("\n")
+        )
+    })
+}
+
+fn check_incomplete_symbols(symgen: &SymGen) -> Result<(), String> {
+    let mut incomplete = Vec::new();
+    let mut total = 0;
+    for (bname, b) in symgen.iter() {
+        let names: Vec<&str> = b
+            .iter()
+            .filter(|s| s.length.is_none() && !s.is_documented())
+            .map(|s| s.name.as_str())
+            .collect();
+        if !names.is_empty() {
+            total += names.len();
+            incomplete.push(format!(
+                "- block \"{}\" ({}): [{}]",
+                bname,
+                names.len(),
+                names.join(", ")
+            ));
+        }
+    }
+    assert_check(incomplete.is_empty(), || {
+        format!(
+            "Found {} symbol(s) with neither a length nor a description:\n{}",
+            total,
+            incomplete.join("\n")
+        )
+    })
+}
+
+fn check_nonzero_lengths(symgen: &SymGen, within_block: bool) -> Result<(), String> {
+    let mut bad_lengths = Vec::new();
+    for (bname, b) in symgen.iter() {
+        for (vers, &l) in b.length.iter_versioned() {
+            if l == 0 {
+                let vers_str = version_suffix(vers);
+                bad_lengths.push(format!("- block \"{}\"{}: length 0x0", bname, vers_str));
+            }
+        }
+        for s in b.iter() {
+            let len = match &s.length {
+                Some(len) => len,
+                None => continue,
+            };
+            for (vers, &l) in len.iter_versioned() {
+                let vers_str = version_suffix(vers);
+                if l == 0 {
+                    bad_lengths.push(format!(
+                        "- block \"{}\", symbol \"{}\"{}: length 0x0",
+                        bname, s.name, vers_str
+                    ));
+                } else if within_block {
+                    // A common symbol length has to fit within the block for every version
+                    let block_len = match vers {
+                        Some(_) => b.length.get(vers).copied(),
+                        None => b.length.values().copied().min(),
+                    };
+                    if let Some(block_len) = block_len.filter(|&bl| l > bl) {
+                        bad_lengths.push(format!(
+                            "- block \"{}\", symbol \"{}\"{}: length {:#X} exceeds block length {:#X}",
+                            bname, s.name, vers_str, l, block_len
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    assert_check(bad_lengths.is_empty(), || {
+        format!(
+            "Found {}:\n{}",
+            if within_block {
+                "zero lengths or symbol lengths larger than the parent block"
+            } else {
+                "zero lengths"
+            },
+            bad_lengths.join("\n")
+        )
+    })
+}
+
+fn check_require_descriptions(symgen: &SymGen, functions: bool, data: bool) -> Result<(), String> {
+    fn undocumented(symbols: &SymbolList) -> impl Iterator<Item = &str> {
+        symbols
+            .iter()
+            .filter(|s| {
+                s.description.as_ref().map_or(true, |desc| {
+                    desc.is_empty() || desc.values().any(|d| d.trim().is_empty())
+                })
+            })
+            .map(|s| s.name.as_str())
+    }
+
+    let mut missing = Vec::new();
+    for (bname, b) in symgen.iter() {
+        let mut names = Vec::new();
+        if functions {
+            names.extend(undocumented(&b.functions));
+        }
+        if data {
+            names.extend(undocumented(&b.data));
+        }
+        if !names.is_empty() {
+            missing.push(format!("- block \"{}\": [{}]", bname, names.join(", ")));
+        }
+    }
+    assert_check(missing.is_empty(), || {
+        format!(
+            "Found symbols without descriptions:\n{}",
+            missing.join("\n")
+        )
+    })
+}
+
+/// Returns a possibly truncated prefix of `s` for previews in check results.
+fn truncate(s: &str) -> String {
+    util::truncate(s, 40)
+}
+
+fn check_max_description_length(symgen: &SymGen, max_len: usize) -> Result<(), String> {
+    let mut too_long = Vec::new();
+    // Quote the preview with Debug formatting so multi-line descriptions stay on one line
+    let violation_str = |identifier: String, desc: &str| {
+        format!(
+            "- {}: {} bytes ({:?})",
+            identifier,
+            desc.len(),
+            truncate(desc)
+        )
     };
-    let res = print_colored_report();
-    // Always try to clean up color settings before returning
-    if let Err(e) = stdout.reset() {
-        Err(e)
-    } else {
-        res
+    for (bname, b) in symgen.iter() {
+        if let Some(desc) = b.description.as_ref().filter(|d| d.len() > max_len) {
+            too_long.push(violation_str(format!("block \"{}\"", bname), desc));
+        }
+        for s in b.iter() {
+            let descs = match &s.description {
+                None => continue,
+                Some(descs) => descs,
+            };
+            for (vers, desc) in descs.iter_versioned().filter(|(_, d)| d.len() > max_len) {
+                let vers_str = version_suffix(vers);
+                too_long.push(violation_str(
+                    format!("block \"{}\", symbol \"{}\"{}", bname, s.name, vers_str),
+                    desc,
+                ));
+            }
+        }
     }
+    assert_check(too_long.is_empty(), || {
+        format!(
+            "Found descriptions longer than {} bytes:\n{}",
+            max_len,
+            too_long.join("\n")
+        )
+    })
 }
 
-/// Validates a given set of `input_files` under the specified `checks`, and prints a summary of
-/// the results.
-///
-/// In `recursive` mode, subregion files of the given input files are also validated.
-///
-/// If all checks were run without encountering a fatal error, returns `true` if all checks passed
-/// and `false` otherwise.
-///
-/// # Examples
-/// ```ignore
-/// let passed = run_and_print_checks(
-///     ["/path/to/symbols.yml", "/path/to/other_symbols.yml"],
-///     &[
-///         Check::ExplicitVersions,
-///         Check::FunctionNames(NamingConvention::SnakeCase),
-///     ],
-///     true,
-/// )
-/// .expect("Fatal error occurred");
-/// ```
-pub fn run_and_print_checks<I, P>(
-    input_files: I,
-    checks: &[Check],
-    recursive: bool,
-) -> Result<bool, Box<dyn Error>>
-where
-    P: AsRef<Path>,
-    I: AsRef<[P]>,
-{
-    let input_files = input_files.as_ref();
-    // At least this many check results, but there could be more in recursive mode
-    let mut results = Vec::with_capacity(input_files.len() * checks.len());
-    let mut errors = Vec::with_capacity(input_files.len());
-    for input_file in input_files {
-        match run_checks(input_file, checks, recursive) {
-            Ok(result) => results.extend(result.into_iter()),
-            Err(e) => errors.push((input_file.as_ref().to_string_lossy().into_owned(), e)),
+fn check_max_name_length(symgen: &SymGen, max_len: usize) -> Result<(), String> {
+    let mut too_long = Vec::new();
+    for s in symgen.symbols() {
+        // The first name is the primary name, and the rest are aliases
+        for (i, name) in s
+            .iter_names()
+            .enumerate()
+            .filter(|(_, n)| n.len() > max_len)
+        {
+            let alias_str = if i == 0 {
+                String::new()
+            } else {
+                format!(", alias of {:?}", truncate(&s.name))
+            };
+            too_long.push(format!(
+                "- {:?} ({} bytes{})",
+                truncate(name),
+                name.len(),
+                alias_str
+            ));
+        }
+    }
+    assert_check(too_long.is_empty(), || {
+        format!(
+            "Found names longer than {} bytes:\n{}",
+            max_len,
+            too_long.join("\n")
+        )
+    })
+}
+
+fn check_forbidden_prefixes(symgen: &SymGen, prefixes: &[String]) -> Result<(), String> {
+    let mut forbidden = Vec::new();
+    for (bname, b) in symgen.iter() {
+        for s in b.iter() {
+            if let Some(prefix) = prefixes.iter().find(|p| s.name.starts_with(p.as_str())) {
+                forbidden.push(format!(
+                    "- block \"{}\", symbol \"{}\": prefix \"{}\"",
+                    bname, s.name, prefix
+                ));
+            }
+        }
+    }
+    assert_check(forbidden.is_empty(), || {
+        format!(
+            "Found symbol names with forbidden prefixes:\n{}",
+            forbidden.join("\n")
+        )
+    })
+}
+
+fn check_consistent_version_order(symgen: &SymGen) -> Result<(), String> {
+    let mut reference: Option<(&OrdString, Vec<&str>)> = None;
+    let mut reordered = Vec::new();
+    for (bname, b) in symgen.iter() {
+        let names: Vec<&str> = match &b.versions {
+            Some(vers) => vers.iter().map(|v| v.name()).collect(),
+            None => continue,
+        };
+        match &reference {
+            None => reference = Some((bname, names)),
+            Some((_, ref_names)) => {
+                if &names != ref_names {
+                    let mut sorted_names = names.clone();
+                    sorted_names.sort_unstable();
+                    let mut sorted_ref_names = ref_names.clone();
+                    sorted_ref_names.sort_unstable();
+                    // Different version sets aren't a reordering, so they're left alone
+                    if sorted_names == sorted_ref_names {
+                        reordered.push(format!("- block \"{}\": {}", bname, names.join(", ")));
+                    }
+                }
+            }
+        }
+    }
+    assert_check(reordered.is_empty(), || {
+        let (ref_bname, ref_names) = reference.as_ref().unwrap();
+        format!(
+            "Found blocks with a different version order than block \"{}\" ({}):\n{}",
+            ref_bname,
+            ref_names.join(", "),
+            reordered.join("\n")
+        )
+    })
+}
+
+fn check_sorted_versions(symgen: &SymGen, order: Option<&[String]>) -> Result<(), String> {
+    // Versions in the declared order come first, followed by the rest sorted by name
+    let sort_key = |name: &str| {
+        let pos = order
+            .and_then(|o| o.iter().position(|v| v == name))
+            .unwrap_or(usize::MAX);
+        (pos, name.to_string())
+    };
+    let mut unsorted = Vec::new();
+    for (bname, b) in symgen.iter() {
+        let names: Vec<&str> = match &b.versions {
+            Some(vers) => vers.iter().map(|v| v.name()).collect(),
+            None => continue,
+        };
+        let mut sorted_names = names.clone();
+        sorted_names.sort_by_key(|&name| sort_key(name));
+        if names != sorted_names {
+            unsorted.push(format!(
+                "- block \"{}\":\n  - [{}]\n  + [{}]",
+                bname,
+                names.join(", "),
+                sorted_names.join(", ")
+            ));
+        }
+    }
+    assert_check(unsorted.is_empty(), || {
+        format!(
+            "Found blocks with unsorted version lists:\n{}",
+            unsorted.join("\n")
+        )
+    })
+}
+
+fn check_subregion_metadata_consistency(symgen: &SymGen) -> Result<(), String> {
+    fn unknown_versions<'v>(
+        versions: impl Iterator<Item = &'v Version>,
+        known: &BTreeSet<&str>,
+    ) -> BTreeSet<&'v str> {
+        versions
+            .map(|v| v.name())
+            .filter(|name| !known.contains(name))
+            .collect()
+    }
+
+    let mut inconsistencies = Vec::new();
+    for (bname, b) in symgen.iter() {
+        let bounds = b.extent();
+        // Versions are compared by name, since subregion blocks have their own ordinal spaces.
+        // A parent block without a version list still has versions if its extent is by-version.
+        let parent_versions: Option<BTreeSet<&str>> = match &b.versions {
+            Some(vers) => Some(vers.iter().map(|v| v.name()).collect()),
+            None => match &bounds {
+                MaybeVersionDep::ByVersion(exts) => {
+                    Some(exts.versions().map(|v| v.name()).collect())
+                }
+                MaybeVersionDep::Common(_) => None,
+            },
+        };
+        for subblock in b.cursor(&bname.val, Path::new("")).subblocks() {
+            let sb = subblock.block();
+            let mut issues = Vec::new();
+            if let Some(parent_versions) = &parent_versions {
+                if let Some(vers) = &sb.versions {
+                    let unknown = unknown_versions(vers.iter(), parent_versions);
+                    if !unknown.is_empty() {
+                        issues.push(format!(
+                            "version list contains versions not in the parent block: [{}]",
+                            unknown.into_iter().collect::<Vec<_>>().join(", ")
+                        ));
+                    }
+                }
+                let unknown = unknown_versions(
+                    sb.address.versions().chain(sb.length.versions()),
+                    parent_versions,
+                );
+                if !unknown.is_empty() {
+                    issues.push(format!(
+                        "address or length specified for versions not in the parent block: [{}]",
+                        unknown.into_iter().collect::<Vec<_>>().join(", ")
+                    ));
+                }
+            }
+            if let Some(violation) = bounds::block_in_bounds(&bounds, sb) {
+                let vers_str = version_suffix(violation.version);
+                issues.push(format!(
+                    "extent{} {} is outside of parent block bounds {}",
+                    vers_str,
+                    range_str(violation.extent),
+                    range_str(violation.bound),
+                ));
+            }
+            inconsistencies.extend(issues.into_iter().map(|issue| {
+                format!(
+                    "- block \"{}\", subregion block \"{}::{}\": {}",
+                    bname,
+                    subblock.path().display(),
+                    subblock.name(),
+                    issue
+                )
+            }));
+        }
+    }
+    assert_check(inconsistencies.is_empty(), || {
+        format!(
+            "Found inconsistent subregion block metadata:\n{}",
+            inconsistencies.join("\n")
+        )
+    })
+}
+
+/// Returns the reason a description would be ambiguous as a plain YAML scalar, if there is one.
+fn yaml_hazard(desc: &str) -> Option<&'static str> {
+    /// Indicators that are only special when followed by a space (or the end of the scalar).
+    const SPACED_INDICATORS: [char; 3] = ['-', '?', ':'];
+    /// Indicators that are always special at the start of a plain scalar.
+    const INDICATORS: [char; 16] = [
+        ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`',
+    ];
+
+    // Multi-line descriptions are written as block scalars, where none of this matters
+    if desc.contains('\n') {
+        return None;
+    }
+    let mut chars = desc.chars();
+    let first = chars.next()?;
+    if INDICATORS.contains(&first)
+        || (SPACED_INDICATORS.contains(&first) && chars.next().map_or(true, char::is_whitespace))
+    {
+        return Some("starts with a YAML indicator");
+    }
+    if desc.contains(": ") || desc.ends_with(':') {
+        return Some("contains a YAML mapping indicator (\": \")");
+    }
+    if desc.contains(" #") {
+        return Some("contains a YAML comment indicator (\" #\")");
+    }
+    None
+}
+
+fn check_safe_descriptions(symgen: &SymGen) -> Result<(), String> {
+    let mut risky = Vec::new();
+    for (bname, b) in symgen.iter() {
+        if let Some(hazard) = b.description.as_deref().and_then(yaml_hazard) {
+            risky.push(format!("- block \"{}\": description {}", bname, hazard));
+        }
+        for s in b.iter() {
+            for (vers, desc) in s.description.iter().flat_map(|d| d.iter_versioned()) {
+                if let Some(hazard) = yaml_hazard(desc) {
+                    let vers_str = version_suffix(vers);
+                    risky.push(format!(
+                        "- block \"{}\", symbol \"{}\"{}: description {}: {:?}",
+                        bname, s.name, vers_str, hazard, desc
+                    ));
+                }
+            }
+        }
+    }
+    assert_check(risky.is_empty(), || {
+        format!(
+            "Found descriptions that would be misparsed if hand-edited as plain YAML scalars. \
+            Consider writing them as block scalars (\"description: |-\") instead:\n{}",
+            risky.join("\n")
+        )
+    })
+}
+
+/// Strips trailing whitespace from each line of a description, along with any trailing blank
+/// lines.
+fn trim_description(desc: &str) -> String {
+    desc.lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+fn check_trimmed_descriptions(symgen: &SymGen) -> Result<(), String> {
+    let mut untrimmed = Vec::new();
+    for (bname, b) in symgen.iter() {
+        if matches!(&b.description, Some(desc) if trim_description(desc) != *desc) {
+            untrimmed.push(format!("- block \"{}\"", bname));
+        }
+        for s in b.iter() {
+            for (vers, desc) in s.description.iter().flat_map(|d| d.iter_versioned()) {
+                if trim_description(desc) != *desc {
+                    untrimmed.push(format!(
+                        "- block \"{}\", symbol \"{}\"{}",
+                        bname,
+                        s.name,
+                        version_suffix(vers)
+                    ));
+                }
+            }
+        }
+    }
+    assert_check(untrimmed.is_empty(), || {
+        format!(
+            "Found descriptions with trailing whitespace:\n{}",
+            untrimmed.join("\n")
+        )
+    })
+}
+
+/// Describes the first serialized field that differs between two [`Symbol`]s, falling back to
+/// the full symbols if the difference isn't visible in serialized form.
+fn symbol_difference(before: &Symbol, after: &Symbol) -> String {
+    if let (Ok(serde_json::Value::Object(b)), Ok(serde_json::Value::Object(a))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    {
+        for (key, val) in b.iter() {
+            match a.get(key) {
+                Some(after_val) if after_val == val => continue,
+                Some(after_val) => {
+                    return format!("{} changed from {} to {}", key, val, after_val);
+                }
+                None => return format!("{} was removed", key),
+            }
+        }
+        if let Some(key) = a.keys().find(|k| !b.contains_key(*k)) {
+            return format!("{} was added", key);
         }
     }
+    format!("changed from {:?} to {:?}", before, after)
+}
+
+/// Describes the first difference between `before` and `after`, if there is one.
+fn first_round_trip_difference(before: &SymGen, after: &SymGen) -> Option<String> {
+    for (bname, b) in before.iter() {
+        let after_b = match after.block_key(&bname.val).and_then(|k| after.get(k)) {
+            Some(after_b) => after_b,
+            None => return Some(format!("block \"{}\" is missing", bname)),
+        };
+        if b == after_b {
+            continue;
+        }
+        for (list_name, list, after_list) in [
+            ("functions", &b.functions, &after_b.functions),
+            ("data", &b.data, &after_b.data),
+        ] {
+            for (s, after_s) in list.iter().zip(after_list.iter()) {
+                if s != after_s {
+                    return Some(format!(
+                        "block \"{}\", symbol \"{}\": {}",
+                        bname,
+                        s.name,
+                        symbol_difference(s, after_s)
+                    ));
+                }
+            }
+            if list.len() != after_list.len() {
+                return Some(format!(
+                    "block \"{}\": {} list changed from {} to {} symbol(s)",
+                    bname,
+                    list_name,
+                    list.len(),
+                    after_list.len()
+                ));
+            }
+        }
+        return Some(format!("block \"{}\": block metadata changed", bname));
+    }
+    if let Some((bname, _)) = after
+        .iter()
+        .find(|(n, _)| before.block_key(&n.val).is_none())
+    {
+        return Some(format!("block \"{}\" was added", bname));
+    }
+    if before != after {
+        return Some("block order changed".to_string());
+    }
+    None
+}
+
+fn check_round_trip_stable(symgen: &SymGen) -> Result<(), String> {
+    // Subregion contents aren't written to the file, so they're checked separately
+    let mut before = symgen.clone();
+    for b in before.blocks_mut() {
+        for subregion in b.subregions.iter_mut().flatten() {
+            subregion.contents = None;
+        }
+    }
+    let text = before
+        .write_to_str(IntFormat::Hexadecimal)
+        .map_err(|e| format!("Could not format file: {}", e))?;
+    let after = SymGen::read(text.as_bytes())
+        .map_err(|e| format!("Could not read back formatted file: {}", e))?;
+    match first_round_trip_difference(&before, &after) {
+        Some(diff) => Err(format!(
+            "Contents changed after formatting and reading back the file: {}",
+            diff
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Removes repeated addresses from symbols with multiple addresses, keeping the first occurrence
+/// of each. Lists left with a single address are collapsed into a scalar. Returns the number of
+/// symbols changed.
+fn fix_duplicate_symbol_addresses(symgen: &mut SymGen) -> usize {
+    let mut n_fixed = 0;
+    for b in symgen.blocks_mut() {
+        for list in [&mut b.functions, &mut b.data] {
+            for i in 0..list.len() {
+                let s = list.get_mut(i).unwrap();
+                let mut changed = false;
+                for addrs in s.address.values_mut() {
+                    if let Linkable::Multiple(a) = addrs {
+                        let mut seen = HashSet::new();
+                        let n_addrs = a.len();
+                        a.retain(|addr| seen.insert(*addr));
+                        if a.len() < n_addrs {
+                            changed = true;
+                            if a.len() == 1 {
+                                *addrs = Linkable::Single(a[0]);
+                            }
+                        }
+                    }
+                }
+                if changed {
+                    n_fixed += 1;
+                }
+            }
+        }
+    }
+    n_fixed
+}
+
+/// Reorders block version lists to match the first block with a version list, for blocks with
+/// the same set of versions. Returns the number of blocks changed.
+fn fix_version_order(symgen: &mut SymGen) -> usize {
+    let mut reference: Option<Vec<Version>> = None;
+    let mut n_fixed = 0;
+    for b in symgen.blocks_mut() {
+        let versions = match &mut b.versions {
+            Some(vers) => vers,
+            None => continue,
+        };
+        match &reference {
+            None => reference = Some(versions.clone()),
+            Some(ref_versions) => {
+                let names = |vers: &[Version]| {
+                    let mut names: Vec<String> =
+                        vers.iter().map(|v| v.name().to_string()).collect();
+                    names.sort_unstable();
+                    names
+                };
+                let in_order = versions
+                    .iter()
+                    .map(|v| v.name())
+                    .eq(ref_versions.iter().map(|v| v.name()));
+                if !in_order && names(versions) == names(ref_versions) {
+                    *versions = ref_versions.clone();
+                    n_fixed += 1;
+                }
+            }
+        }
+    }
+    if n_fixed > 0 {
+        // Version ordinals are derived from the version lists, so they need to be recomputed
+        symgen.init();
+    }
+    n_fixed
+}
+
+/// Sorts the function and data symbols in each block (along with each symbol's address list), as
+/// `fmt` would. Returns the number of symbol lists changed.
+fn fix_sorted_symbols(symgen: &mut SymGen) -> usize {
+    let mut n_fixed = 0;
+    for b in symgen.blocks_mut() {
+        for list in [&mut b.functions, &mut b.data] {
+            let mut sorted = list.clone();
+            sorted.sort();
+            if sorted != *list {
+                *list = sorted;
+                n_fixed += 1;
+            }
+        }
+    }
+    n_fixed
+}
+
+/// Strips trailing whitespace from every line of block and symbol descriptions, along with any
+/// trailing blank lines. Returns the number of descriptions changed.
+fn fix_trimmed_descriptions(symgen: &mut SymGen) -> usize {
+    let mut n_fixed = 0;
+    let mut trim = |desc: &mut String| {
+        let trimmed = trim_description(desc);
+        if trimmed != *desc {
+            *desc = trimmed;
+            n_fixed += 1;
+        }
+    };
+    for b in symgen.blocks_mut() {
+        if let Some(desc) = &mut b.description {
+            trim(desc);
+        }
+        for list in [&mut b.functions, &mut b.data] {
+            for i in 0..list.len() {
+                let s = list.get_mut(i).unwrap();
+                for desc in s.description.iter_mut().flat_map(|d| d.values_mut()) {
+                    trim(desc);
+                }
+            }
+        }
+    }
+    n_fixed
+}
+
+/// Validates a given `input_file` under the specified `checks`.
+///
+/// In `recursive` mode, subregion files are also validated. If `only_version` is specified,
+/// checks that compare addresses and lengths within each version (like [`Check::NoOverlap`] and
+/// [`Check::InBoundsSymbols`]) only consider the version with that name. Other checks are
+/// unaffected. It's an error if no block has a version with that name.
+///
+/// If `input_file` is `-`, the file contents are read from standard input instead. Standard input
+/// can't be validated in `recursive` mode, since there's no directory to resolve subregions from.
+///
+/// Returns a `Vec<(PathBuf, CheckResult)>` with the results of all checks on all the files
+/// validated, if all checks were run without encountering any fatal errors.
+///
+/// # Examples
+/// ```ignore
+/// let results = run_checks(
+///     "/path/to/symbols.yml",
+///     &[
+///         Check::ExplicitVersions,
+///         Check::FunctionNames(NamingConvention::SnakeCase),
+///     ],
+///     true,
+///     None,
+/// )
+/// .expect("Fatal error occurred");
+/// ```
+pub fn run_checks<P: AsRef<Path>>(
+    input_file: P,
+    checks: &[Check],
+    recursive: bool,
+    only_version: Option<&str>,
+) -> Result<Vec<(PathBuf, CheckResult)>, ResymgenError> {
+    /// For returning either a [`Once`] iterator or an [`Empty`] iterator, while still allowing
+    /// static dispatch.
+    enum OnceOrEmpty<T> {
+        Once(iter::Once<T>),
+        Empty(iter::Empty<T>),
+    }
+    impl<T> Iterator for OnceOrEmpty<T> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            match self {
+                Self::Once(o) => o.next(),
+                Self::Empty(e) => e.next(),
+            }
+        }
+    }
+
+    let input_file = input_file.as_ref();
+    let read_timer = util::time_phase("read");
+    let mut contents = if input_file == Path::new("-") {
+        if recursive {
+            // There's no directory to look for subregion files in
+            return Err(UsageError(
+                "cannot check subregions of standard input; recursive mode requires a file"
+                    .to_string(),
+            )
+            .into());
+        }
+        SymGen::read(io::stdin())?
+    } else {
+        let f = File::open(input_file)?;
+        SymGen::read(&f)?
+    };
+    drop(read_timer);
+    if recursive {
+        let _timer = util::time_phase("resolve");
+        contents.resolve_subregions(Subregion::subregion_dir(input_file), |p| File::open(p))?;
+    }
+    if let Some(v) = only_version {
+        // Restricting to an unknown version would silently check nothing version-specific
+        if !contents
+            .cursor(input_file)
+            .dtraverse()
+            .any(|cursor| cursor.symgen().has_version(v))
+        {
+            return Err(UsageError(format!("Unknown version '{}'", v)).into());
+        }
+    }
+    let _timer = util::time_phase("check");
+    let restricted = only_version.map(|v| {
+        let mut restricted = contents.clone();
+        restricted.restrict_to_version(v);
+        restricted
+    });
+    let contents = &contents;
+    Ok(checks
+        .iter()
+        .flat_map(|chk| {
+            let target = match &restricted {
+                Some(r) if chk.is_version_specific() => r,
+                _ => contents,
+            };
+            let check_results = target
+                .cursor(input_file)
+                .dtraverse()
+                .take(if chk.spans_subregions() {
+                    1
+                } else {
+                    usize::MAX
+                })
+                .map(move |cursor| (cursor.path().to_owned(), chk.run(cursor.symgen())));
+            if let (Check::UniqueSymbols, true) =
+                (chk, contents.cursor(input_file).has_subregions())
+            {
+                // Recursive UniqueSymbols is a special case.
+                // Add a cross-subregion uniqueness check that spans all subregions
+                check_results.chain(OnceOrEmpty::Once(iter::once((
+                    input_file.to_owned(),
+                    Check::UniqueSymbolsAcrossSubregions.run(contents),
+                ))))
+            } else {
+                check_results.chain(OnceOrEmpty::Empty(iter::empty()))
+            }
+        })
+        .collect())
+}
+
+/// Prints check results similar to `cargo test` output.
+fn print_report(results: &[(PathBuf, CheckResult)]) -> io::Result<()> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut print_colored_report = || -> io::Result<()> {
+        let mut color = ColorSpec::new();
+
+        // Results list
+        for (name, r) in results {
+            stdout.reset()?;
+            write!(&mut stdout, "check {}::{} ... ", name.display(), r.check)?;
+            if r.succeeded {
+                stdout.set_color(color.set_fg(Some(Color::Green)))?;
+                writeln!(&mut stdout, "ok")?;
+            } else {
+                stdout.set_color(color.set_fg(Some(Color::Red)))?;
+                writeln!(&mut stdout, "FAILED")?;
+            }
+        }
+
+        stdout.reset()?;
+        writeln!(&mut stdout)?;
+        let n_failed = results.iter().filter(|(_, r)| !r.succeeded).count();
+        let n_passed = results.len() - n_failed;
+
+        // Failure details
+        if n_failed > 0 {
+            writeln!(&mut stdout, "failures:")?;
+            writeln!(&mut stdout)?;
+            for (name, r) in results.iter().filter(|(_, r)| !r.succeeded) {
+                writeln!(&mut stdout, "---- [{}] {} ----", name.display(), r.check)?;
+                if let Some(msg) = &r.details {
+                    writeln!(&mut stdout, "{}", msg)?;
+                }
+                writeln!(&mut stdout)?;
+            }
+        }
+
+        // Results summary
+        write!(&mut stdout, "check result: ")?;
+        if n_failed == 0 {
+            stdout.set_color(color.set_fg(Some(Color::Green)))?;
+            write!(&mut stdout, "ok")?;
+        } else {
+            stdout.set_color(color.set_fg(Some(Color::Red)))?;
+            write!(&mut stdout, "FAILED")?;
+        }
+        stdout.reset()?;
+        writeln!(&mut stdout, ". {} passed; {} failed", n_passed, n_failed)?;
+
+        Ok(())
+    };
+    let res = print_colored_report();
+    // Always try to clean up color settings before returning
+    if let Err(e) = stdout.reset() {
+        Err(e)
+    } else {
+        res
+    }
+}
+
+/// Escapes a string for use as the message of a GitHub Actions workflow command.
+fn escape_github_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a string for use as a property value of a GitHub Actions workflow command.
+fn escape_github_property(s: &str) -> String {
+    escape_github_data(s)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Formats a failed check result as a GitHub Actions `::error` workflow command.
+///
+/// Line numbers aren't tracked for symbols, so the annotation applies to the whole file.
+fn github_annotation(name: &Path, r: &CheckResult) -> String {
+    format!(
+        "::error file={},title={}::{}",
+        escape_github_property(&name.to_string_lossy()),
+        escape_github_property(&r.check.to_string()),
+        escape_github_data(r.details.as_deref().unwrap_or("check failed")),
+    )
+}
+
+/// Prints failed check results as GitHub Actions workflow commands, so that they show up as
+/// annotations in CI.
+fn print_github_report(results: &[(PathBuf, CheckResult)]) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    for (name, r) in results.iter().filter(|(_, r)| !r.succeeded) {
+        writeln!(&mut stdout, "{}", github_annotation(name, r))?;
+    }
+    Ok(())
+}
+
+/// Output formats for check results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// Human-readable output similar to `cargo test`.
+    Text,
+    /// GitHub Actions workflow commands (one `::error` annotation per failed check).
+    Github,
+}
+
+/// Validates a given set of `input_files` under the specified `checks`, and prints a summary of
+/// the results in the given `format`.
+///
+/// In `recursive` mode, subregion files of the given input files are also validated. If
+/// `only_version` is specified, version-specific checks are restricted to that version
+/// (see [`run_checks`]).
+///
+/// If all checks were run without encountering a fatal error, returns `true` if all checks passed
+/// and `false` otherwise.
+///
+/// # Examples
+/// ```ignore
+/// let passed = run_and_print_checks(
+///     ["/path/to/symbols.yml", "/path/to/other_symbols.yml"],
+///     &[
+///         Check::ExplicitVersions,
+///         Check::FunctionNames(NamingConvention::SnakeCase),
+///     ],
+///     true,
+///     None,
+///     ReportFormat::Text,
+/// )
+/// .expect("Fatal error occurred");
+/// ```
+pub fn run_and_print_checks<I, P>(
+    input_files: I,
+    checks: &[Check],
+    recursive: bool,
+    only_version: Option<&str>,
+    format: ReportFormat,
+) -> Result<bool, ResymgenError>
+where
+    P: AsRef<Path>,
+    I: AsRef<[P]>,
+{
+    let input_files = input_files.as_ref();
+    // At least this many check results, but there could be more in recursive mode
+    let mut results = Vec::with_capacity(input_files.len() * checks.len());
+    let mut errors = Vec::with_capacity(input_files.len());
+    for input_file in input_files {
+        match run_checks(input_file, checks, recursive, only_version) {
+            Ok(result) => results.extend(result),
+            Err(e) => errors.push((input_file.as_ref().to_string_lossy().into_owned(), e.into())),
+        }
+    }
+
+    // Best-effort: print what we have, even if some checks errored
+    match format {
+        ReportFormat::Text => print_report(&results)?,
+        ReportFormat::Github => print_github_report(&results)?,
+    }
+
+    if !errors.is_empty() {
+        return Err(MultiFileError {
+            base_msg: "Could not complete checks".to_string(),
+            errors,
+        }
+        .into());
+    }
+    Ok(results.iter().all(|(_, r)| r.succeeded))
+}
+
+/// A fix applied by [`fix_checks`]: the file that was changed, the [`Check`] whose failures were
+/// fixed, and the number of fixes applied.
+pub type AppliedFix = (PathBuf, Check, usize);
+
+/// Applies automatic fixes for the fixable `checks` (see [`Check::is_fixable()`]) to a given
+/// `input_file` in place. In `recursive` mode, subregion files are also fixed.
+///
+/// Only files that were actually changed are rewritten, in the same format as `fmt`, with
+/// integers written according to `int_format`. Returns a list of the fixes that were applied.
+///
+/// # Examples
+/// ```ignore
+/// let fixes = fix_checks(
+///     "/path/to/symbols.yml",
+///     &[Check::NoDuplicateSymbolAddresses],
+///     true,
+///     IntFormat::Hexadecimal,
+/// )
+/// .expect("Fatal error occurred");
+/// ```
+pub fn fix_checks<P: AsRef<Path>>(
+    input_file: P,
+    checks: &[Check],
+    recursive: bool,
+    int_format: IntFormat,
+) -> Result<Vec<AppliedFix>, ResymgenError> {
+    fn fix_tree(symgen: &mut SymGen, path: &Path, checks: &[Check], fixes: &mut Vec<AppliedFix>) {
+        for chk in checks {
+            if let Some(n) = chk.fix(symgen).filter(|&n| n > 0) {
+                fixes.push((path.to_owned(), chk.clone(), n));
+            }
+        }
+        let subregion_dir = Subregion::subregion_dir(path);
+        for b in symgen.blocks_mut() {
+            for subregion in b.subregions.iter_mut().flatten() {
+                if let Some(contents) = &mut subregion.contents {
+                    fix_tree(
+                        contents,
+                        &subregion_dir.join(&subregion.name),
+                        checks,
+                        fixes,
+                    );
+                }
+            }
+        }
+    }
+
+    let input_file = input_file.as_ref();
+    if input_file == Path::new("-") {
+        return Err(UsageError("cannot fix standard input in place".to_string()).into());
+    }
+    let mut contents = SymGen::read(&File::open(input_file)?)?;
+    if recursive {
+        contents.resolve_subregions(Subregion::subregion_dir(input_file), |p| File::open(p))?;
+    }
+    let mut fixes = Vec::new();
+    fix_tree(&mut contents, input_file, checks, &mut fixes);
+
+    let fixed_files: HashSet<&Path> = fixes.iter().map(|(p, _, _)| p.as_path()).collect();
+    for cursor in contents.cursor(input_file).btraverse() {
+        if fixed_files.contains(cursor.path()) {
+            let output_file = util::named_temp_file_for(cursor.path())?;
+            cursor.symgen().write(&output_file, int_format)?;
+            util::persist_named_temp_file_safe(output_file, cursor.path())?;
+        }
+    }
+    Ok(fixes)
+}
+
+/// Applies automatic fixes for the fixable `checks` to a given set of `input_files` with
+/// [`fix_checks`], and prints a summary of the fixes applied.
+///
+/// # Examples
+/// ```ignore
+/// run_and_print_fixes(
+///     ["/path/to/symbols.yml", "/path/to/other_symbols.yml"],
+///     &[Check::NoDuplicateSymbolAddresses, Check::ConsistentVersionOrder],
+///     true,
+///     IntFormat::Hexadecimal,
+/// )
+/// .expect("Fatal error occurred");
+/// ```
+pub fn run_and_print_fixes<I, P>(
+    input_files: I,
+    checks: &[Check],
+    recursive: bool,
+    int_format: IntFormat,
+) -> Result<(), ResymgenError>
+where
+    P: AsRef<Path>,
+    I: AsRef<[P]>,
+{
+    let input_files = input_files.as_ref();
+    let mut fixes = Vec::new();
+    let mut errors = Vec::with_capacity(input_files.len());
+    for input_file in input_files {
+        match fix_checks(input_file, checks, recursive, int_format) {
+            Ok(f) => fixes.extend(f),
+            Err(e) => errors.push((input_file.as_ref().to_string_lossy().into_owned(), e.into())),
+        }
+    }
+
+    let mut stdout = io::stdout();
+    for (path, chk, n) in fixes.iter() {
+        writeln!(
+            &mut stdout,
+            "fixed {}::{} ({} fix(es))",
+            path.display(),
+            chk,
+            n
+        )?;
+    }
+    let n_files = fixes
+        .iter()
+        .map(|(p, _, _)| p)
+        .collect::<HashSet<_>>()
+        .len();
+    writeln!(&mut stdout, "fix result: {} file(s) changed", n_files)?;
+    writeln!(&mut stdout)?;
+
+    if !errors.is_empty() {
+        return Err(MultiFileError {
+            base_msg: "Could not complete fixes".to_string(),
+            errors,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::data_formats::symgen_yml::test_utils;
+    use super::*;
+
+    #[cfg(test)]
+    mod naming_convention_tests {
+        use super::*;
+
+        fn run_name_checks<const M: usize, const N: usize>(
+            conv: NamingConvention,
+            good: [&str; M],
+            bad: [&str; N],
+        ) {
+            for name in good.iter() {
+                assert!(conv.check(name));
+            }
+            for name in bad.iter() {
+                assert!(!conv.check(name));
+            }
+        }
+
+        #[test]
+        fn test_identifier() {
+            run_name_checks(
+                NamingConvention::Identifier,
+                ["some_function", "àéïõç"],
+                [" f", "f ", "f f", "f-f", "1abc"],
+            )
+        }
+
+        #[test]
+        fn test_snake_case() {
+            run_name_checks(
+                NamingConvention::SnakeCase,
+                ["snake_case", "_case", "snake", "snake_", "with_number1"],
+                ["SCREAMING_SNAKE", "camelCase", "PascalCase", "Caps"],
+            )
+        }
+
+        #[test]
+        fn test_screaming_snake_case() {
+            run_name_checks(
+                NamingConvention::ScreamingSnakeCase,
+                [
+                    "SCREAMING_SNAKE",
+                    "_SNAKE",
+                    "SNAKE",
+                    "SCREAMING_",
+                    "WITH_NUMBER1",
+                ],
+                ["snake_case", "camelCase", "PascalCase", "Caps", "lower"],
+            )
+        }
+
+        #[test]
+        fn test_camel_case() {
+            run_name_checks(
+                NamingConvention::CamelCase,
+                ["camelCase", "camel", "withAWord", "withANumber1"],
+                ["snake_case", "SCREAMING_SNAKE", "PascalCase", "Caps"],
+            )
+        }
+
+        #[test]
+        fn test_pascal_case() {
+            run_name_checks(
+                NamingConvention::PascalCase,
+                ["PascalCase", "Pascal", "WithAWord", "WithANumber1"],
+                ["snake_case", "SCREAMING_SNAKE", "camelCase", "lower"],
+            )
+        }
+
+        #[test]
+        fn test_kebab_case() {
+            run_name_checks(
+                NamingConvention::KebabCase,
+                [
+                    "kebab-case",
+                    "kebab",
+                    "with-number1",
+                    "1-leading-digit",
+                    "àé-ïõç",
+                ],
+                [
+                    "snake_case",
+                    "Kebab-Case",
+                    "-leading",
+                    "trailing-",
+                    "double--hyphen",
+                    "-",
+                    "",
+                    " kebab",
+                    "kebab case",
+                ],
+            )
+        }
+
+        #[test]
+        fn test_regex() {
+            run_name_checks(
+                NamingConvention::from_regex("[A-Z][A-Za-z0-9]*_[a-z0-9]+")
+                    .expect("invalid pattern"),
+                ["Module_fn", "M_1", "Pascal2_lower"],
+                [
+                    "module_fn",
+                    "Module_Fn",
+                    "Module",
+                    "prefix Module_fn",
+                    "Module_fn suffix",
+                    "",
+                ],
+            );
+            // Explicit anchors are allowed too
+            run_name_checks(
+                NamingConvention::from_regex("^[a-z]+$").expect("invalid pattern"),
+                ["lower"],
+                ["Upper", "lower1", ""],
+            );
+            // Patterns don't need to match valid identifiers
+            run_name_checks(
+                NamingConvention::from_regex(r"\d+\.\d+|[a-z]+").expect("invalid pattern"),
+                ["1.5", "abc"],
+                ["1.5abc", "abc1"],
+            );
+        }
+
+        #[test]
+        fn test_regex_invalid() {
+            let err = NamingConvention::from_regex("(unclosed").expect_err("pattern should fail");
+            assert!(err.to_string().contains("'(unclosed'"));
+            assert!(NamingConvention::from_regex("[a-z").is_err());
+        }
+
+        #[test]
+        fn test_regex_eq() {
+            let conv = NamingConvention::from_regex("[a-z]+").expect("invalid pattern");
+            assert_eq!(
+                conv,
+                NamingConvention::from_regex("[a-z]+").expect("invalid pattern")
+            );
+            assert_ne!(
+                conv,
+                NamingConvention::from_regex("[a-z]+$").expect("invalid pattern")
+            );
+            assert_ne!(conv, NamingConvention::SnakeCase);
+        }
+
+        #[test]
+        fn test_regex_display() {
+            let conv = NamingConvention::from_regex("[a-z]+").expect("invalid pattern");
+            assert_eq!(
+                Check::FunctionNames(conv.clone()).to_string(),
+                "FunctionNames(regex:[a-z]+)"
+            );
+            assert_eq!(
+                Check::DataNames(conv).to_string(),
+                "DataNames(regex:[a-z]+)"
+            );
+            assert_eq!(
+                Check::FunctionNames(NamingConvention::SnakeCase).to_string(),
+                "FunctionNames(SnakeCase)"
+            );
+            let pattern = NamePattern::new("[A-Z_]+").expect("invalid pattern");
+            assert_eq!(format!("{:?}", pattern), "regex:[A-Z_]+");
+        }
+    }
+
+    fn get_test_symgen() -> SymGen {
+        SymGen::read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address:
+                v1: 0x2000000
+                v2: 0x2000000
+              length:
+                v1: 0x100000
+                v2: 0x100000
+              description: foo
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2001000
+                    v2: 0x2002000
+                  length:
+                    v1: 0x1000
+                    v2: 0x1000
+                  description: bar
+                - name: fn2
+                  address:
+                    v1:
+                      - 0x2000000
+                      - 0x2002000
+                    v2: 0x2004000
+                  description: baz
+              data:
+                - name: SOME_DATA
+                  address:
+                    v1: 0x2000000
+                    v2: 0x2000000
+                  length:
+                    v1: 0x1000
+                    v2: 0x2000
+                  description: foo bar baz
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    fn get_test_symgen_with_subregions() -> SymGen {
+        test_utils::get_symgen_with_subregions(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+                - v3
+              address:
+                v1: 0x2000000
+                v2: 0x2000000
+                v3: 0x2000000
+              length:
+                v1: 0x100000
+                v2: 0x100000
+                v3: 0x100000
+              subregions:
+                - sub1.yml
+                - sub2.yml
+              functions: []
+              data: []
+            ",
+            &[
+                (
+                    "sub1.yml",
+                    r"
+                    sub1:
+                      versions:
+                        - v3
+                        - v1
+                      address:
+                        v3: 0x2000000
+                        v1: 0x2000000
+                      length:
+                        v3: 0x100
+                        v1: 0x100
+                      functions:
+                        - name: sub1_fn
+                          address:
+                            v3: 0x2000000
+                            v1: 0x2000000
+                      data: []
+                    ",
+                ),
+                (
+                    "sub2.yml",
+                    r"
+                    sub2:
+                      versions:
+                        - v2
+                      address:
+                        v2: 0x20FFF00
+                      length:
+                        v2: 0x100
+                      functions: []
+                      data:
+                        - name: sub2_data
+                          address:
+                            v2: 0x20FFF80
+                          length:
+                            v2: 0x80
+                    ",
+                ),
+            ],
+        )
+    }
+
+    fn get_main_block(symgen: &mut SymGen) -> &mut Block {
+        symgen
+            .get_mut(&symgen.block_key("main").expect("No block \"main\"").clone())
+            .unwrap()
+    }
+
+    fn get_subregion_block(symgen: &mut SymGen, i: usize) -> &mut Block {
+        let subregion = get_main_block(symgen)
+            .subregions
+            .as_mut()
+            .expect("No subregions")
+            .get_mut(i)
+            .expect("Invalid index")
+            .contents
+            .as_mut()
+            .expect("Subregion has no contents");
+        subregion
+            .blocks_mut()
+            .next()
+            .expect("Subregion has no blocks")
+    }
+
+    #[test]
+    fn test_explicit_versions() {
+        let mut symgen = get_test_symgen();
+        assert!(check_explicit_versions(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // Change the block address to use an implicit version
+        block.address = MaybeVersionDep::Common(0x2000000);
+        assert!(check_explicit_versions(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_complete_version_list() {
+        let mut symgen = get_test_symgen();
+        assert!(check_complete_version_list(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // Delete the block version list
+        block.versions = None;
+        assert!(check_complete_version_list(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_complete_version_list_with_subregions() {
+        let mut symgen = get_test_symgen_with_subregions();
+        assert!(check_complete_version_list(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // Delete one of the versions
+        block.versions.as_mut().unwrap().pop();
+        assert!(check_complete_version_list(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_unique_symbols() {
+        let mut symgen = get_test_symgen();
+        assert!(check_unique_symbols(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // Copy the function symbols to the data symbols so they clash
+        block.data = block.functions.clone();
+        assert!(check_unique_symbols(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_unique_subregions() {
+        let mut symgen = get_test_symgen_with_subregions();
+        assert!(check_unique_symbols(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // Add a duplicate subregion
+        let subregion = Subregion {
+            name: block.subregions.as_ref().unwrap()[0].name.clone(),
+            contents: None,
+        };
+        block.subregions.as_mut().unwrap().push(subregion);
+        assert!(check_unique_symbols(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_unique_symbols_across_subregions() {
+        let mut symgen = get_test_symgen_with_subregions();
+        assert!(check_unique_symbols_across_subregions(&symgen).is_ok());
+
+        // Insert a copy of a subregion's symbol into the main block
+        let symbol = get_subregion_block(&mut symgen, 0)
+            .functions
+            .iter()
+            .next()
+            .expect("subregion block has no functions")
+            .clone();
+        let block = get_main_block(&mut symgen);
+        block.functions.push(symbol);
+        assert!(check_unique_symbols_across_subregions(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_globally_unique_names() {
+        let mut symgen = SymGen::read(
+            r"
+            main:
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  deprecated_aliases:
+                    - OldFn1
+                  address: 0x2000000
+              data: []
+            other:
+              address: 0x2100000
+              length: 0x100000
+              functions:
+                - name: fn2
+                  deprecated_aliases:
+                    - OldFn2
+                  address: 0x2100000
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        assert!(check_globally_unique_names(&symgen).is_ok());
+
+        // An alias in one block equal to a primary name in another
+        symgen
+            .get_mut(&symgen.block_key("other").unwrap().clone())
+            .unwrap()
+            .functions
+            .get_mut(0)
+            .unwrap()
+            .deprecated_aliases = Some(vec!["OldFn2".to_string(), "fn1".to_string()]);
+        let err = check_globally_unique_names(&symgen).unwrap_err();
+        assert_eq!(
+            err,
+            "Found names used more than once across blocks:
+- \"fn1\" used in: [main, other (alias of \"fn2\")]"
+        );
+    }
+
+    #[test]
+    fn test_globally_unique_names_with_subregions() {
+        let mut symgen = get_test_symgen_with_subregions();
+        assert!(check_globally_unique_names(&symgen).is_ok());
+
+        get_subregion_block(&mut symgen, 1)
+            .data
+            .get_mut(0)
+            .expect("subregion block has no data")
+            .deprecated_aliases = Some(vec!["sub1_fn".to_string()]);
+        let err = check_globally_unique_names(&symgen).unwrap_err();
+        assert!(err.contains("- \"sub1_fn\" used in: ["));
+        assert!(err.contains("::sub1, "));
+        assert!(err.contains("::sub2 (alias of \"sub2_data\")]"));
+    }
+
+    #[test]
+    fn test_in_bounds_symbols() {
+        let mut symgen = get_test_symgen();
+        assert!(check_in_bounds_symbols(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // Set the block length to 0 so the symbols end up out of bounds
+        for l in block.length.values_mut() {
+            *l = 0;
+        }
+        assert!(check_in_bounds_symbols(&symgen).is_err());
+
+        // reborrow
+        let block = get_main_block(&mut symgen);
+        // Inlined symbols don't occupy any address space, so they can't be out of bounds
+        for list in [&mut block.functions, &mut block.data] {
+            for i in 0..list.len() {
+                list.get_mut(i).unwrap().inlined = Some(true);
+            }
+        }
+        assert!(check_in_bounds_symbols(&symgen).is_ok());
+    }
+
+    #[test]
+    fn test_in_bounds_subregions() {
+        let mut symgen = get_test_symgen_with_subregions();
+        assert!(check_in_bounds_symbols(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // Shrink the main block so the sub2 subregion ends up out of bounds
+        *block.length.get_mut(Some(&"v2".into())).unwrap() -= 0x80;
+        assert!(check_in_bounds_symbols(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_subregion_metadata_consistency() {
+        let mut symgen = get_test_symgen_with_subregions();
+        assert!(check_subregion_metadata_consistency(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // Shrink the main block so the sub2 subregion ends up out of bounds
+        *block.length.get_mut(Some(&"v2".into())).unwrap() -= 0x80;
+        let sub1 = get_subregion_block(&mut symgen, 0);
+        // Give sub1 a version that the main block doesn't have
+        sub1.versions.as_mut().unwrap().push("v4".into());
+        if let MaybeVersionDep::ByVersion(addrs) = &mut sub1.address {
+            addrs.insert("v4".into(), 0x2000000);
+        }
+        let err = check_subregion_metadata_consistency(&symgen).expect_err("check should fail");
+        assert_eq!(
+            err,
+            "Found inconsistent subregion block metadata:\n- block \"main\", subregion block \"sub1.yml::sub1\": version list contains versions not in the parent block: [v4]\n- block \"main\", subregion block \"sub1.yml::sub1\": address or length specified for versions not in the parent block: [v4]\n- block \"main\", subregion block \"sub2.yml::sub2\": extent [v2] 0x20FFF00..0x2100000 is outside of parent block bounds 0x2000000..0x20FFF80"
+        );
+    }
+
+    #[test]
+    fn test_no_overlap() {
+        let mut symgen = get_test_symgen();
+        assert!(check_no_overlap(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // Swap the address of the second function to match the first, causing an overlap
+        let function = block
+            .functions
+            .iter()
+            .next()
+            .expect("symgen has no functions")
+            .clone();
+        let mut overlapping = block
+            .functions
+            .iter()
+            .next()
+            .expect("symgen does not have two functions")
+            .clone();
+        let addr = overlapping
+            .address
+            .get_mut_native(block.version("v1"))
+            .unwrap();
+        *addr = function
+            .address
+            .get_native(block.version("v1"))
+            .unwrap()
+            .clone();
+        block.functions = [function, overlapping].into();
+        assert!(check_no_overlap(&symgen).is_err());
+
+        // reborrow
+        let block = get_main_block(&mut symgen);
+        // Inlined symbols don't occupy any address space, so they can't overlap
+        block.functions.get_mut(1).unwrap().inlined = Some(true);
+        assert!(check_no_overlap(&symgen).is_ok());
+    }
+
+    #[test]
+    fn test_no_overlap_only_version() {
+        let symgen = SymGen::read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address: 0x2001000
+                  length: 0x1000
+                - name: fn2
+                  address:
+                    v1: 0x2002000
+                    v2: 0x2001800
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        assert!(check_no_overlap(&symgen).is_err());
+
+        let mut v1_only = symgen.clone();
+        v1_only.restrict_to_version("v1");
+        assert!(check_no_overlap(&v1_only).is_ok());
+
+        let mut v2_only = symgen.clone();
+        v2_only.restrict_to_version("v2");
+        assert!(check_no_overlap(&v2_only).is_err());
+    }
+
+    #[test]
+    fn test_no_overlap_common_with_version_list() {
+        let mut symgen = SymGen::read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address: 0x2001000
+                  length: 0x1000
+                - name: fn2
+                  address:
+                    v1:
+                      - 0x2000000
+                      - 0x2002000
+                    v2: 0x2004000
+              data: []
+        "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+
+        assert!(check_no_overlap(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // Swap the address of the first function to match one of the versions in the second,
+        // causing an overlap
+        let mut function = block
+            .functions
+            .iter()
+            .next()
+            .expect("symgen has no functions")
+            .clone();
+        let overlapping = block
+            .functions
+            .iter()
+            .next()
+            .expect("symgen does not have two functions")
+            .clone();
+        let addr = function.address.get_mut_native(None).unwrap();
+        *addr = overlapping
+            .address
+            .get_native(block.version("v1"))
+            .unwrap()
+            .clone();
+        block.functions = [function, overlapping].into();
+        assert!(check_no_overlap(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_no_overlap_common_no_version_list() {
+        let mut symgen = SymGen::read(
+            r"
+            main:
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address: 0x2001000
+                  length: 0x1000
+                - name: fn2
+                  address:
+                    - 0x2000000
+                    - 0x2002000
+              data: []
+        "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+
+        assert!(check_no_overlap(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // Swap the address of the second function to match the first, causing an overlap
+        let function = block
+            .functions
+            .iter()
+            .next()
+            .expect("symgen has no functions")
+            .clone();
+        let mut overlapping = block
+            .functions
+            .iter()
+            .next()
+            .expect("symgen does not have two functions")
+            .clone();
+        let addr = overlapping.address.get_mut_native(None).unwrap();
+        *addr = function.address.get_native(None).unwrap().clone();
+        block.functions = [function, overlapping].into();
+        assert!(check_no_overlap(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_ranges_overlap_and_bounds() {
+        let read_symgen = |range_addr: &str| {
+            SymGen::read(
+                format!(
+                    r"
+            main:
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address: 0x2001000
+                  length: 0x100
+                  ranges:
+                    - address: {}
+                      length: 0x100
+                - name: fn2
+                  address: 0x2002000
+                  length: 0x100
+              data: []
+        ",
+                    range_addr
+                )
+                .as_bytes(),
+            )
+            .expect("Read failed")
+        };
+
+        let symgen = read_symgen("0x2003000");
+        assert!(check_no_overlap(&symgen).is_ok());
+        assert!(check_in_bounds_symbols(&symgen).is_ok());
+
+        // The extra range overlaps fn2
+        let symgen = read_symgen("0x2002080");
+        assert!(check_no_overlap(&symgen).is_err());
+        assert!(check_in_bounds_symbols(&symgen).is_ok());
+
+        // The extra range falls outside the block
+        let symgen = read_symgen("0x2200000");
+        assert!(check_no_overlap(&symgen).is_ok());
+        assert!(check_in_bounds_symbols(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_no_overlap_with_subregions() {
+        let mut symgen = get_test_symgen_with_subregions();
+        assert!(check_no_overlap(&symgen).is_ok());
+
+        // Add a symbol to the main block that overlaps with a subregion symbol
+        let address = *get_subregion_block(&mut symgen, 0)
+            .address
+            .get(Some(&"v1".into()))
+            .unwrap();
+        let block = get_main_block(&mut symgen);
+        block.functions.push(Symbol {
+            name: String::from("main_fn"),
+            deprecated_aliases: None,
+            address: MaybeVersionDep::ByVersion([("v1".into(), [address].into())].into()),
+            length: None,
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        });
+        assert!(check_no_overlap(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_no_overlap_with_space_groups() {
+        let mut symgen = get_test_symgen_with_subregions();
+        // Move sub2 on top of sub1
+        let sub2 = get_subregion_block(&mut symgen, 1);
+        sub2.versions = Some(vec!["v1".into()]);
+        sub2.address = MaybeVersionDep::ByVersion([("v1".into(), 0x2000000)].into());
+        sub2.length = MaybeVersionDep::ByVersion([("v1".into(), 0x100)].into());
+        assert!(check_no_overlap(&symgen).is_err());
+
+        // Subregions in different groups may overlap
+        get_subregion_block(&mut symgen, 0).space_group = Some("group1".to_string());
+        assert!(check_no_overlap(&symgen).is_err());
+        get_subregion_block(&mut symgen, 1).space_group = Some("group2".to_string());
+        assert!(check_no_overlap(&symgen).is_ok());
+
+        // Subregions in the same group may not
+        get_subregion_block(&mut symgen, 1).space_group = Some("group1".to_string());
+        assert!(check_no_overlap(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_yaml_hazard() {
+        let cases = [
+            ("plain description", None),
+            ("-1 is returned on failure", None),
+            ("a ratio of 1:2", None),
+            ("see issue#12", None),
+            ("- not a list", Some("starts with a YAML indicator")),
+            ("*pointer to data", Some("starts with a YAML indicator")),
+            ("'quoted' name", Some("starts with a YAML indicator")),
+            (
+                "Note: this is important",
+                Some("contains a YAML mapping indicator (\": \")"),
+            ),
+            (
+                "returns:",
+                Some("contains a YAML mapping indicator (\": \")"),
+            ),
+            (
+                "r0: pointer",
+                Some("contains a YAML mapping indicator (\": \")"),
+            ),
+            (
+                "unknown # TODO",
+                Some("contains a YAML comment indicator (\" #\")"),
+            ),
+            ("multi\nline: description", None),
+        ];
+        for (desc, expected) in cases {
+            assert_eq!(yaml_hazard(desc), expected, "description: {:?}", desc);
+        }
+    }
+
+    #[test]
+    fn test_safe_descriptions() {
+        let mut symgen = get_test_symgen();
+        assert!(check_safe_descriptions(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        block.description = Some("[unknown] region".to_string());
+        block.functions.get_mut(0).unwrap().description =
+            Some(MaybeVersionDep::Common("Note: r0 is clobbered".to_string()));
+        let err = check_safe_descriptions(&symgen).expect_err("check should fail");
+        assert_eq!(
+            err,
+            "Found descriptions that would be misparsed if hand-edited as plain YAML scalars. Consider writing them as block scalars (\"description: |-\") instead:\n- block \"main\": description starts with a YAML indicator\n- block \"main\", symbol \"fn1\": description contains a YAML mapping indicator (\": \"): \"Note: r0 is clobbered\""
+        );
+    }
+
+    #[test]
+    fn test_trimmed_descriptions() {
+        let mut symgen = get_test_symgen();
+        assert!(check_trimmed_descriptions(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        block.description = Some("region \n".to_string());
+        block.functions.get_mut(0).unwrap().description = Some(MaybeVersionDep::Common(
+            "first line\t\nsecond line".to_string(),
+        ));
+        block.data.get_mut(0).unwrap().description =
+            Some(MaybeVersionDep::Common("trimmed\n  indented".to_string()));
+        let err = check_trimmed_descriptions(&symgen).expect_err("check should fail");
+        assert_eq!(
+            err,
+            "Found descriptions with trailing whitespace:\n- block \"main\"\n- block \"main\", symbol \"fn1\""
+        );
+    }
+
+    #[test]
+    fn test_data_length_multiple() {
+        let mut symgen = get_test_symgen();
+        assert!(check_data_length_multiple(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        // The data lengths are 0x1000 and 0x2000
+        block
+            .data
+            .get_mut(0)
+            .expect("symgen has no data")
+            .element_size = Some(0x400);
+        assert!(check_data_length_multiple(&symgen).is_ok());
+
+        // reborrow
+        let block = get_main_block(&mut symgen);
+        block
+            .data
+            .get_mut(0)
+            .expect("symgen has no data")
+            .element_size = Some(0x3);
+        assert!(check_data_length_multiple(&symgen).is_err());
+    }
+
+    #[test]
+    fn test_no_shared_addresses() {
+        let mut symgen = get_test_symgen();
+        // fn2 [v1] and SOME_DATA share 0x2000000
+        let err = check_no_shared_addresses(&symgen).unwrap_err();
+        assert!(err.contains("- block \"main\" [v1]: 0x2000000 (fn2, SOME_DATA)"));
+        assert!(!err.contains("[v2]"));
+
+        let block = get_main_block(&mut symgen);
+        block.data.get_mut(0).expect("symgen has no data").address = MaybeVersionDep::ByVersion(
+            [
+                (("v1", 0).into(), 0x2003000.into()),
+                (("v2", 1).into(), 0x2003000.into()),
+            ]
+            .into(),
+        );
+        block
+            .functions
+            .get_mut(1)
+            .expect("symgen has no functions")
+            .address = MaybeVersionDep::ByVersion(
+            [
+                (
+                    ("v1", 0).into(),
+                    Linkable::from([0x2000000, 0x2001000, 0x2001000]),
+                ),
+                (("v2", 1).into(), 0x2004000.into()),
+            ]
+            .into(),
+        );
+        // fn2 shares one of its addresses with fn1, and lists it twice itself
+        let err = check_no_shared_addresses(&symgen).unwrap_err();
+        assert_eq!(
+            err,
+            "Found distinct symbols with the same address:
+- block \"main\" [v1]: 0x2001000 (fn1, fn2)"
+        );
+
+        let block = get_main_block(&mut symgen);
+        block
+            .functions
+            .get_mut(1)
+            .expect("symgen has no functions")
+            .address = MaybeVersionDep::ByVersion(
+            [
+                (("v1", 0).into(), Linkable::from([0x2000000, 0x2002000])),
+                (("v2", 1).into(), 0x2004000.into()),
+            ]
+            .into(),
+        );
+        assert!(check_no_shared_addresses(&symgen).is_ok());
+    }
+
+    #[test]
+    fn test_no_shared_addresses_unversioned() {
+        let symgen = SymGen::read(
+            r"
+            main:
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address: 0x2000000
+              data:
+                - name: DATA
+                  address:
+                    - 0x2001000
+                    - 0x2000000
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        let err = check_no_shared_addresses(&symgen).unwrap_err();
+        assert!(err.contains("- block \"main\": 0x2000000 (fn1, DATA)"));
+    }
+
+    #[test]
+    fn test_no_block_overlap() {
+        let symgen = SymGen::read(
+            r"
+            arm9:
+              versions:
+                - NA
+                - EU
+              address:
+                NA: 0x2000000
+                EU: 0x2000000
+              length:
+                NA: 0x100000
+                EU: 0x100400
+              functions: []
+              data: []
+            overlay1:
+              versions:
+                - EU
+                - NA
+              address:
+                EU: 0x2100400
+                NA: 0x2100000
+              length: 0x1000
+              functions: []
+              data: []
+            overlay2:
+              address: 0x2101400
+              length: 0x1000
+              functions: []
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        // Adjacent blocks don't overlap, even with different version orders
+        assert!(check_no_block_overlap(&symgen).is_ok());
+
+        let symgen = SymGen::read(
+            r"
+            arm9:
+              versions:
+                - NA
+                - EU
+              address:
+                NA: 0x2000000
+                EU: 0x2000000
+              length:
+                NA: 0x100000
+                EU: 0x100800
+              functions: []
+              data: []
+            overlay1:
+              versions:
+                - EU
+                - NA
+              address:
+                EU: 0x2100400
+                NA: 0x2100000
+              length: 0x1000
+              functions: []
+              data: []
+            overlay2:
+              address: 0x2101000
+              length: 0x1000
+              functions: []
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        let err = check_no_block_overlap(&symgen).unwrap_err();
+        assert_eq!(
+            err,
+            "Found overlapping blocks:
+- blocks \"arm9\" (0x2000000..0x2100800) and \"overlay1\" (0x2100400..0x2101400) [EU]
+- blocks \"overlay1\" (0x2100400..0x2101400) and \"overlay2\" (0x2101000..0x2102000) [EU]"
+        );
+    }
+
+    #[test]
+    fn test_no_block_overlap_space_groups() {
+        let mut symgen = SymGen::read(
+            r"
+            overlay1:
+              address: 0x2100000
+              length: 0x1000
+              functions: []
+              data: []
+            overlay2:
+              address: 0x2100000
+              length: 0x2000
+              functions: []
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        let err = check_no_block_overlap(&symgen).unwrap_err();
+        assert!(err.contains("- blocks \"overlay1\" (0x2100000..0x2101000) and \"overlay2\""));
+
+        for (i, b) in symgen.blocks_mut().enumerate() {
+            b.space_group = Some(format!("group{}", i));
+        }
+        assert!(check_no_block_overlap(&symgen).is_ok());
+    }
+
+    #[test]
+    fn test_no_duplicate_symbol_addresses() {
+        let mut symgen = SymGen::read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address:
+                    v1:
+                      - 0x2001000
+                      - 0x2002000
+                    v2: 0x2001000
+                - name: fn2
+                  address:
+                    - 0x2003000
+                    - 0x2004000
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        assert!(check_no_duplicate_symbol_addresses(&symgen).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        let (v1, v2) = (
+            block.version("v1").unwrap().clone(),
+            block.version("v2").unwrap().clone(),
+        );
+        block.functions.get_mut(0).unwrap().address = MaybeVersionDep::ByVersion(
+            [
+                (v1, Linkable::from([0x2001000, 0x2001000])),
+                (v2, Linkable::from(0x2001000)),
+            ]
+            .into(),
+        );
+        block.functions.get_mut(1).unwrap().address =
+            MaybeVersionDep::Common(Linkable::from([0x2003000, 0x2004000, 0x2003000]));
+        let err = check_no_duplicate_symbol_addresses(&symgen).expect_err("check should fail");
+        assert_eq!(
+            err,
+            "Found symbols with repeated addresses:\n- block \"main\", symbol \"fn1\" [v1]: 0x2001000\n- block \"main\", symbol \"fn2\": 0x2003000"
+        );
+    }
+
+    fn get_multi_version_symgen(functions: &str) -> SymGen {
+        SymGen::read(
+            format!(
+                r"
+                main:
+                  versions:
+                    - v1
+                    - v2
+                  address: 0x2000000
+                  length: 0x100000
+                  functions:
+{}
+                  data: []
+                ",
+                functions
+            )
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    #[test]
+    fn test_sorted_symbols() {
+        let mut symgen = get_test_symgen();
+        // fn1 [v1] is at 0x2001000, but fn2 [v1] starts at 0x2000000
+        let err = check_sorted_symbols(&symgen).unwrap_err();
+        assert_eq!(
+            err,
+            "Found blocks with unsorted symbols:
+- block \"main\": function \"fn1\" is out of order (expected \"fn2\")"
+        );
+
+        symgen.sort();
+        assert!(check_sorted_symbols(&symgen).is_ok());
+    }
+
+    #[test]
+    fn test_sorted_symbols_multi_version() {
+        // Symbols are compared version by version, with common addresses applying to all versions
+        let symgen = get_multi_version_symgen(
+            r"
+                    - name: fn1
+                      address: 0x2000000
+                    - name: fn2
+                      address:
+                        v1: 0x2001000
+                        v2: 0x2003000
+                    - name: fn3
+                      address:
+                        v1: 0x2001000
+                        v2: 0x2004000
+                    - name: fn4
+                      address:
+                        v2: 0x2002000",
+        );
+        assert!(check_sorted_symbols(&symgen).is_ok());
+
+        let symgen = get_multi_version_symgen(
+            r"
+                    - name: fn1
+                      address: 0x2000000
+                    - name: fn3
+                      address:
+                        v1: 0x2001000
+                        v2: 0x2004000
+                    - name: fn2
+                      address:
+                        v1: 0x2001000
+                        v2: 0x2003000",
+        );
+        let err = check_sorted_symbols(&symgen).unwrap_err();
+        assert!(
+            err.contains("- block \"main\": function \"fn3\" is out of order (expected \"fn2\")")
+        );
+    }
+
+    #[test]
+    fn test_sorted_symbols_ties_and_addresses() {
+        // Symbols with identical addresses can't be ordered, so either order is fine
+        let symgen = get_multi_version_symgen(
+            r"
+                    - name: fn2
+                      address:
+                        v1: 0x2001000
+                        v2: 0x2002000
+                    - name: fn1
+                      address:
+                        v1: 0x2001000
+                        v2: 0x2002000",
+        );
+        assert!(check_sorted_symbols(&symgen).is_ok());
+
+        let symgen = get_multi_version_symgen(
+            r"
+                    - name: fn1
+                      address:
+                        - 0x2002000
+                        - 0x2001000",
+        );
+        let err = check_sorted_symbols(&symgen).unwrap_err();
+        assert!(err.contains("- block \"main\": function \"fn1\" has unsorted addresses"));
+    }
+
+    #[test]
+    fn test_sorted_versions() {
+        let symgen = SymGen::read(
+            r"
+            arm9:
+              versions:
+                - EU
+                - JP
+                - NA
+              address: 0x2000000
+              length: 0x100000
+              functions: []
+              data: []
+            overlay1:
+              address: 0x2100000
+              length: 0x1000
+              functions: []
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        assert!(check_sorted_versions(&symgen, None).is_ok());
+
+        let symgen = SymGen::read(
+            r"
+            arm9:
+              versions:
+                - NA
+                - EU
+                - JP
+              address: 0x2000000
+              length: 0x100000
+              functions: []
+              data: []
+            overlay1:
+              versions:
+                - EU
+                - NA
+              address: 0x2100000
+              length: 0x1000
+              functions: []
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        let err = check_sorted_versions(&symgen, None).unwrap_err();
+        assert_eq!(
+            err,
+            "Found blocks with unsorted version lists:
+- block \"arm9\":
+  - [NA, EU, JP]
+  + [EU, JP, NA]"
+        );
+    }
+
+    #[test]
+    fn test_sorted_versions_declared_order() {
+        let symgen = SymGen::read(
+            r"
+            arm9:
+              versions:
+                - NA
+                - EU
+                - JP
+                - beta
+                - alpha
+              address: 0x2000000
+              length: 0x100000
+              functions: []
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        let order = ["NA", "EU", "JP"].map(String::from);
+        let err = check_sorted_versions(&symgen, Some(&order)).unwrap_err();
+        assert!(err.contains("+ [NA, EU, JP, alpha, beta]"));
+
+        let order = ["NA", "EU", "JP", "beta", "alpha"].map(String::from);
+        assert!(check_sorted_versions(&symgen, Some(&order)).is_ok());
+    }
+
+    #[test]
+    fn test_consistent_version_order() {
+        let symgen = SymGen::read(
+            r"
+            arm9:
+              versions:
+                - NA
+                - EU
+                - JP
+              address: 0x2000000
+              length: 0x100000
+              functions: []
+              data: []
+            overlay1:
+              address: 0x2100000
+              length: 0x1000
+              functions: []
+              data: []
+            overlay2:
+              versions:
+                - NA
+                - EU
+              address: 0x2200000
+              length: 0x1000
+              functions: []
+              data: []
+            overlay3:
+              versions:
+                - NA
+                - EU
+                - JP
+              address: 0x2300000
+              length: 0x1000
+              functions: []
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        assert!(check_consistent_version_order(&symgen).is_ok());
+
+        let symgen = SymGen::read(
+            r"
+            arm9:
+              versions:
+                - NA
+                - EU
+                - JP
+              address: 0x2000000
+              length: 0x100000
+              functions: []
+              data: []
+            overlay1:
+              versions:
+                - EU
+                - NA
+                - JP
+              address: 0x2100000
+              length: 0x1000
+              functions: []
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        let err = check_consistent_version_order(&symgen).expect_err("check should fail");
+        assert_eq!(
+            err,
+            "Found blocks with a different version order than block \"arm9\" (NA, EU, JP):\n- block \"overlay1\": EU, NA, JP"
+        );
+    }
 
-    // Best-effort: print what we have, even if some checks errored
-    print_report(&results)?;
+    #[test]
+    fn test_fix() {
+        let mut symgen = SymGen::read(
+            r"
+            arm9:
+              versions:
+                - NA
+                - EU
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address:
+                    NA:
+                      - 0x2001000
+                      - 0x2001000
+                    EU:
+                      - 0x2001800
+                      - 0x2002800
+                      - 0x2001800
+              data: []
+            overlay1:
+              versions:
+                - EU
+                - NA
+              address:
+                EU: 0x2100000
+                NA: 0x2110000
+              length: 0x1000
+              functions: []
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        assert!(check_no_duplicate_symbol_addresses(&symgen).is_err());
+        assert!(check_consistent_version_order(&symgen).is_err());
 
-    if !errors.is_empty() {
-        return Err(MultiFileError {
-            base_msg: "Could not complete checks".to_string(),
-            errors,
-        }
-        .into());
+        assert_eq!(Check::NoDuplicateSymbolAddresses.fix(&mut symgen), Some(1));
+        assert_eq!(Check::ConsistentVersionOrder.fix(&mut symgen), Some(1));
+        assert_eq!(Check::NoOverlap.fix(&mut symgen), None);
+        assert!(check_no_duplicate_symbol_addresses(&symgen).is_ok());
+        assert!(check_consistent_version_order(&symgen).is_ok());
+        // Fixing again is a no-op
+        assert_eq!(Check::NoDuplicateSymbolAddresses.fix(&mut symgen), Some(0));
+        assert_eq!(Check::ConsistentVersionOrder.fix(&mut symgen), Some(0));
+
+        assert_eq!(
+            symgen
+                .write_to_str(IntFormat::Hexadecimal)
+                .expect("Write failed"),
+            r"arm9:
+  versions:
+    - NA
+    - EU
+  address: 0x2000000
+  length: 0x100000
+  functions:
+    - name: fn1
+      address:
+        NA: 0x2001000
+        EU:
+          - 0x2001800
+          - 0x2002800
+  data: []
+overlay1:
+  versions:
+    - NA
+    - EU
+  address:
+    NA: 0x2110000
+    EU: 0x2100000
+  length: 0x1000
+  functions: []
+  data: []
+"
+        );
     }
-    Ok(results.iter().all(|(_, r)| r.succeeded))
-}
 
-#[cfg(test)]
-mod tests {
-    use super::super::data_formats::symgen_yml::test_utils;
-    use super::*;
+    #[test]
+    fn test_fix_sorted_and_trimmed() {
+        let mut symgen = get_test_symgen();
+        let block = get_main_block(&mut symgen);
+        block.description = Some("region\n\n".to_string());
+        block.functions.get_mut(0).unwrap().description = Some(MaybeVersionDep::Common(
+            "first line \nsecond line\t".to_string(),
+        ));
+        assert!(check_sorted_symbols(&symgen).is_err());
+        assert!(check_trimmed_descriptions(&symgen).is_err());
 
-    #[cfg(test)]
-    mod naming_convention_tests {
-        use super::*;
+        // Only the function list is out of order
+        assert_eq!(Check::SortedSymbols.fix(&mut symgen), Some(1));
+        assert_eq!(Check::TrimmedDescriptions.fix(&mut symgen), Some(2));
+        assert!(check_sorted_symbols(&symgen).is_ok());
+        assert!(check_trimmed_descriptions(&symgen).is_ok());
+        let block = get_main_block(&mut symgen);
+        assert_eq!(block.description.as_deref(), Some("region"));
+        assert_eq!(
+            block.functions.get(1).unwrap().description,
+            Some(MaybeVersionDep::Common(
+                "first line\nsecond line".to_string()
+            ))
+        );
+        // Fixing again is a no-op
+        assert_eq!(Check::SortedSymbols.fix(&mut symgen), Some(0));
+        assert_eq!(Check::TrimmedDescriptions.fix(&mut symgen), Some(0));
+    }
 
-        fn run_name_checks<const M: usize, const N: usize>(
-            conv: NamingConvention,
-            good: [&str; M],
-            bad: [&str; N],
-        ) {
-            for name in good.iter() {
-                assert!(conv.check(name));
-            }
-            for name in bad.iter() {
-                assert!(!conv.check(name));
-            }
-        }
+    #[test]
+    fn test_fix_checks() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let path = dir.path().join("main.yml");
+        let contents = "main:\n  address: 0x2000000\n  length: 0x1000\n  functions:\n    - name: fn1\n      address:\n        - 0x2000000\n        - 0x2000000\n  data: []\n";
+        std::fs::write(&path, contents).expect("Failed to write file");
 
-        #[test]
-        fn test_identifier() {
-            run_name_checks(
-                NamingConvention::Identifier,
-                ["some_function", "àéïõç"],
-                [" f", "f ", "f f", "f-f", "1abc"],
-            )
-        }
+        let fixes = fix_checks(
+            &path,
+            &[Check::UniqueSymbols],
+            false,
+            IntFormat::Hexadecimal,
+        )
+        .expect("fix failed");
+        assert!(fixes.is_empty());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), contents);
 
-        #[test]
-        fn test_snake_case() {
-            run_name_checks(
-                NamingConvention::SnakeCase,
-                ["snake_case", "_case", "snake", "snake_", "with_number1"],
-                ["SCREAMING_SNAKE", "camelCase", "PascalCase", "Caps"],
-            )
-        }
+        let fixes = fix_checks(
+            &path,
+            &[Check::NoDuplicateSymbolAddresses],
+            false,
+            IntFormat::Hexadecimal,
+        )
+        .expect("fix failed");
+        assert_eq!(
+            fixes,
+            vec![(path.clone(), Check::NoDuplicateSymbolAddresses, 1)]
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "main:\n  address: 0x2000000\n  length: 0x1000\n  functions:\n    - name: fn1\n      address: 0x2000000\n  data: []\n"
+        );
+        assert!(fix_checks(
+            "-",
+            &[Check::NoDuplicateSymbolAddresses],
+            false,
+            IntFormat::Hexadecimal
+        )
+        .is_err());
 
-        #[test]
-        fn test_screaming_snake_case() {
-            run_name_checks(
-                NamingConvention::ScreamingSnakeCase,
-                [
-                    "SCREAMING_SNAKE",
-                    "_SNAKE",
-                    "SNAKE",
-                    "SCREAMING_",
-                    "WITH_NUMBER1",
-                ],
-                ["snake_case", "camelCase", "PascalCase", "Caps", "lower"],
-            )
-        }
+        std::fs::write(&path, contents).expect("Failed to write file");
+        fix_checks(
+            &path,
+            &[Check::NoDuplicateSymbolAddresses],
+            false,
+            IntFormat::GroupedDecimal,
+        )
+        .expect("fix failed");
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "main:\n  address: 33_554_432\n  length: 4_096\n  functions:\n    - name: fn1\n      address: 33_554_432\n  data: []\n"
+        );
+    }
 
-        #[test]
-        fn test_camel_case() {
-            run_name_checks(
-                NamingConvention::CamelCase,
-                ["camelCase", "camel", "withAWord", "withANumber1"],
-                ["snake_case", "SCREAMING_SNAKE", "PascalCase", "Caps"],
-            )
-        }
+    #[test]
+    fn test_run_checks_unknown_version() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let path = dir.path().join("main.yml");
+        std::fs::write(
+            &path,
+            "main:\n  versions:\n    - v1\n  address: 0x2000000\n  length: 0x1000\n  functions: []\n  data: []\n",
+        )
+        .expect("Failed to write file");
 
-        #[test]
-        fn test_pascal_case() {
-            run_name_checks(
-                NamingConvention::PascalCase,
-                ["PascalCase", "Pascal", "WithAWord", "WithANumber1"],
-                ["snake_case", "SCREAMING_SNAKE", "camelCase", "lower"],
-            )
-        }
+        assert!(run_checks(&path, &[Check::NoOverlap], false, Some("v1")).is_ok());
+        let err = run_checks(&path, &[Check::NoOverlap], false, Some("v2")).unwrap_err();
+        assert!(matches!(err, ResymgenError::Usage(_)));
     }
 
-    fn get_test_symgen() -> SymGen {
-        SymGen::read(
+    #[test]
+    fn test_unique_block_addresses() {
+        let symgen = SymGen::read(
             r"
             main:
               versions:
@@ -1135,318 +4243,388 @@ mod tests {
               address:
                 v1: 0x2000000
                 v2: 0x2000000
-              length:
-                v1: 0x100000
-                v2: 0x100000
-              description: foo
-              functions:
-                - name: fn1
-                  address:
-                    v1: 0x2001000
-                    v2: 0x2002000
-                  length:
-                    v1: 0x1000
-                    v2: 0x1000
-                  description: bar
-                - name: fn2
-                  address:
-                    v1:
-                      - 0x2000000
-                      - 0x2002000
-                    v2: 0x2004000
-                  description: baz
-              data:
-                - name: SOME_DATA
-                  address:
-                    v1: 0x2000000
-                    v2: 0x2000000
-                  length:
-                    v1: 0x1000
-                    v2: 0x2000
-                  description: foo bar baz
+              length: 0x100000
+              functions: []
+              data: []
+            overlay1:
+              versions:
+                - v1
+                - v2
+              address:
+                v1: 0x2100000
+                v2: 0x2000000
+              length: 0x1000
+              functions: []
+              data: []
+            overlay2:
+              address: 0x2200000
+              length: 0x1000
+              functions: []
+              data: []
             "
             .as_bytes(),
         )
-        .expect("Read failed")
-    }
+        .expect("Read failed");
+        let err = check_unique_block_addresses(&symgen).expect_err("check should fail");
+        assert_eq!(
+            err,
+            "Found blocks with the same base address:\n- blocks \"main\" and \"overlay1\" [v2]: 0x2000000"
+        );
 
-    fn get_test_symgen_with_subregions() -> SymGen {
-        test_utils::get_symgen_with_subregions(
+        let symgen = SymGen::read(
             r"
             main:
+              address: 0x2000000
+              length: 0x100000
+              functions: []
+              data: []
+            overlay1:
               versions:
                 - v1
-                - v2
-                - v3
               address:
-                v1: 0x2000000
-                v2: 0x2000000
-                v3: 0x2000000
-              length:
-                v1: 0x100000
-                v2: 0x100000
-                v3: 0x100000
-              subregions:
-                - sub1.yml
-                - sub2.yml
+                v1: 0x2100000
+              length: 0x1000
               functions: []
               data: []
-            ",
-            &[
-                (
-                    "sub1.yml",
-                    r"
-                    sub1:
-                      versions:
-                        - v3
-                        - v1
-                      address:
-                        v3: 0x2000000
-                        v1: 0x2000000
-                      length:
-                        v3: 0x100
-                        v1: 0x100
-                      functions:
-                        - name: sub1_fn
-                          address:
-                            v3: 0x2000000
-                            v1: 0x2000000
-                      data: []
-                    ",
-                ),
-                (
-                    "sub2.yml",
-                    r"
-                    sub2:
-                      versions:
-                        - v2
-                      address:
-                        v2: 0x20FFF00
-                      length:
-                        v2: 0x100
-                      functions: []
-                      data:
-                        - name: sub2_data
-                          address:
-                            v2: 0x20FFF80
-                          length:
-                            v2: 0x80
-                    ",
-                ),
-            ],
+            "
+            .as_bytes(),
         )
+        .expect("Read failed");
+        assert!(check_unique_block_addresses(&symgen).is_ok());
     }
 
-    fn get_main_block(symgen: &mut SymGen) -> &mut Block {
-        symgen
-            .get_mut(&symgen.block_key("main").expect("No block \"main\"").clone())
-            .unwrap()
-    }
+    #[test]
+    fn test_symbols_name_check() {
+        let mut symgen = get_test_symgen();
+        assert!(check_function_names(&symgen, &NamingConvention::SnakeCase).is_ok());
+        assert!(check_data_names(&symgen, &NamingConvention::ScreamingSnakeCase).is_ok());
 
-    fn get_subregion_block(symgen: &mut SymGen, i: usize) -> &mut Block {
-        let subregion = get_main_block(symgen)
-            .subregions
-            .as_mut()
-            .expect("No subregions")
-            .get_mut(i)
-            .expect("Invalid index")
-            .contents
-            .as_mut()
-            .expect("Subregion has no contents");
-        subregion
-            .blocks_mut()
-            .next()
-            .expect("Subregion has no blocks")
+        let block = get_main_block(&mut symgen);
+        // Set the function to have the wrong case
+        block
+            .functions
+            .get_mut(0)
+            .expect("symgen has no functions")
+            .name = "PascalCase".to_string();
+        assert!(check_function_names(&symgen, &NamingConvention::SnakeCase).is_err());
+
+        // reborrow
+        let block = get_main_block(&mut symgen);
+        // Set the data to have the wrong case
+        block.data.get_mut(0).expect("symgen has no data").name = "snake_case".to_string();
+        assert!(check_data_names(&symgen, &NamingConvention::ScreamingSnakeCase).is_err());
     }
 
     #[test]
-    fn test_explicit_versions() {
+    fn test_alias_names_check() {
         let mut symgen = get_test_symgen();
-        assert!(check_explicit_versions(&symgen).is_ok());
+        assert!(check_function_alias_names(&symgen, &NamingConvention::SnakeCase).is_ok());
 
         let block = get_main_block(&mut symgen);
-        // Change the block address to use an implicit version
-        block.address = MaybeVersionDep::Common(0x2000000);
-        assert!(check_explicit_versions(&symgen).is_err());
+        // The primary names are valid, but some aliases aren't
+        block.functions.get_mut(0).unwrap().deprecated_aliases =
+            Some(vec!["old_fn1".to_string(), "OldFn1".to_string()]);
+        block.functions.get_mut(1).unwrap().deprecated_aliases = Some(vec!["fn_2".to_string()]);
+        block.data.get_mut(0).unwrap().deprecated_aliases = Some(vec!["old_data".to_string()]);
+        assert!(check_function_names(&symgen, &NamingConvention::SnakeCase).is_ok());
+        assert!(check_data_names(&symgen, &NamingConvention::ScreamingSnakeCase).is_ok());
+
+        let err = check_function_alias_names(&symgen, &NamingConvention::SnakeCase).unwrap_err();
+        assert_eq!(
+            err,
+            "Found invalid function aliases:
+- block \"main\": [OldFn1 (alias of \"fn1\")]"
+        );
+        let err =
+            check_data_alias_names(&symgen, &NamingConvention::ScreamingSnakeCase).unwrap_err();
+        assert_eq!(
+            err,
+            "Found invalid data aliases:
+- block \"main\": [old_data (alias of \"SOME_DATA\")]"
+        );
     }
 
     #[test]
-    fn test_complete_version_list() {
+    fn test_symbols_name_check_kebab_case() {
         let mut symgen = get_test_symgen();
-        assert!(check_complete_version_list(&symgen).is_ok());
+        let block = get_main_block(&mut symgen);
+        for i in 0..block.functions.len() {
+            block.functions.get_mut(i).unwrap().name = format!("kebab-fn-{}", i);
+        }
+        // Kebab-case names aren't identifiers, but still pass the kebab-case check
+        assert!(check_function_names(&symgen, &NamingConvention::Identifier).is_err());
+        assert!(check_function_names(&symgen, &NamingConvention::KebabCase).is_ok());
 
         let block = get_main_block(&mut symgen);
-        // Delete the block version list
-        block.versions = None;
-        assert!(check_complete_version_list(&symgen).is_err());
+        block
+            .functions
+            .get_mut(0)
+            .expect("symgen has no functions")
+            .name = "kebab--fn".to_string();
+        let err = check_function_names(&symgen, &NamingConvention::KebabCase)
+            .expect_err("double hyphens should fail");
+        assert!(err.contains("kebab--fn"));
     }
 
     #[test]
-    fn test_complete_version_list_with_subregions() {
-        let mut symgen = get_test_symgen_with_subregions();
-        assert!(check_complete_version_list(&symgen).is_ok());
+    fn test_block_alignment() {
+        let mut symgen = get_test_symgen();
+        assert!(check_block_alignment(&symgen, 0x1000).is_ok());
 
         let block = get_main_block(&mut symgen);
-        // Delete one of the versions
-        block.versions.as_mut().unwrap().pop();
-        assert!(check_complete_version_list(&symgen).is_err());
+        block.address = MaybeVersionDep::ByVersion(
+            [(("v1", 0).into(), 0x2000000), (("v2", 1).into(), 0x2000400)].into(),
+        );
+        assert!(check_block_alignment(&symgen, 0x400).is_ok());
+        let err = check_block_alignment(&symgen, 0x1000).unwrap_err();
+        assert!(err.contains("block \"main\" [v2]: 0x2000400"));
+        assert!(!err.contains("[v1]"));
     }
 
     #[test]
-    fn test_unique_symbols() {
+    fn test_incomplete_symbols() {
         let mut symgen = get_test_symgen();
-        assert!(check_unique_symbols(&symgen).is_ok());
+        assert!(check_incomplete_symbols(&symgen).is_ok());
 
         let block = get_main_block(&mut symgen);
-        // Copy the function symbols to the data symbols so they clash
-        block.data = block.functions.clone();
-        assert!(check_unique_symbols(&symgen).is_err());
-    }
+        let function = block.functions.get_mut(0).expect("symgen has no functions");
+        function.description = None;
+        assert!(check_incomplete_symbols(&symgen).is_ok());
 
-    #[test]
-    fn test_unique_subregions() {
-        let mut symgen = get_test_symgen_with_subregions();
-        assert!(check_unique_symbols(&symgen).is_ok());
+        let block = get_main_block(&mut symgen);
+        let function = block.functions.get_mut(0).expect("symgen has no functions");
+        function.length = None;
+        let err = check_incomplete_symbols(&symgen).unwrap_err();
+        assert!(err.contains("Found 1 symbol(s)"));
+        assert!(err.contains("- block \"main\" (1): [fn1]"));
 
+        // Blank descriptions don't count
         let block = get_main_block(&mut symgen);
-        // Add a duplicate subregion
-        let subregion = Subregion {
-            name: block.subregions.as_ref().unwrap()[0].name.clone(),
-            contents: None,
-        };
-        block.subregions.as_mut().unwrap().push(subregion);
-        assert!(check_unique_symbols(&symgen).is_err());
+        let function = block.functions.get_mut(0).expect("symgen has no functions");
+        function.description = Some(MaybeVersionDep::Common("  ".to_string()));
+        let err = check_incomplete_symbols(&symgen).unwrap_err();
+        assert!(err.contains("- block \"main\" (1): [fn1]"));
     }
 
     #[test]
-    fn test_unique_symbols_across_subregions() {
-        let mut symgen = get_test_symgen_with_subregions();
-        assert!(check_unique_symbols_across_subregions(&symgen).is_ok());
+    fn test_nonzero_lengths() {
+        let mut symgen = get_test_symgen();
+        assert!(check_nonzero_lengths(&symgen, false).is_ok());
+        assert!(check_nonzero_lengths(&symgen, true).is_ok());
 
-        // Insert a copy of a subregion's symbol into the main block
-        let symbol = get_subregion_block(&mut symgen, 0)
-            .functions
-            .iter()
-            .next()
-            .expect("subregion block has no functions")
-            .clone();
         let block = get_main_block(&mut symgen);
-        block.functions.push(symbol);
-        assert!(check_unique_symbols_across_subregions(&symgen).is_err());
+        let function = block.functions.get_mut(0).expect("symgen has no functions");
+        function.length = Some(MaybeVersionDep::ByVersion(
+            [(("v1", 0).into(), 0x1000), (("v2", 1).into(), 0)].into(),
+        ));
+        let err = check_nonzero_lengths(&symgen, false).unwrap_err();
+        assert!(err.contains("- block \"main\", symbol \"fn1\" [v2]: length 0x0"));
+        assert!(!err.contains("[v1]"));
+
+        let block = get_main_block(&mut symgen);
+        let function = block.functions.get_mut(0).expect("symgen has no functions");
+        function.length = Some(MaybeVersionDep::Common(0x1000));
+        block.length = MaybeVersionDep::Common(0);
+        let err = check_nonzero_lengths(&symgen, false).unwrap_err();
+        assert!(err.contains("- block \"main\": length 0x0"));
+        assert!(!err.contains("symbol"));
     }
 
     #[test]
-    fn test_in_bounds_symbols() {
+    fn test_nonzero_lengths_within_block() {
         let mut symgen = get_test_symgen();
-        assert!(check_in_bounds_symbols(&symgen).is_ok());
+        let block = get_main_block(&mut symgen);
+        block.length = MaybeVersionDep::ByVersion(
+            [(("v1", 0).into(), 0x100000), (("v2", 1).into(), 0x1800)].into(),
+        );
+        // SOME_DATA has length 0x2000 in v2
+        assert!(check_nonzero_lengths(&symgen, false).is_ok());
+        let err = check_nonzero_lengths(&symgen, true).unwrap_err();
+        assert!(err.contains(
+            "- block \"main\", symbol \"SOME_DATA\" [v2]: length 0x2000 exceeds block length 0x1800"
+        ));
+        assert!(!err.contains("fn1"));
 
+        // Common symbol lengths are checked against every version of the block
         let block = get_main_block(&mut symgen);
-        // Set the block length to 0 so the symbols end up out of bounds
-        for l in block.length.values_mut() {
-            *l = 0;
-        }
-        assert!(check_in_bounds_symbols(&symgen).is_err());
+        let function = block.functions.get_mut(0).expect("symgen has no functions");
+        function.length = Some(MaybeVersionDep::Common(0x1900));
+        let err = check_nonzero_lengths(&symgen, true).unwrap_err();
+        assert!(err.contains(
+            "- block \"main\", symbol \"fn1\": length 0x1900 exceeds block length 0x1800"
+        ));
     }
 
     #[test]
-    fn test_in_bounds_subregions() {
-        let mut symgen = get_test_symgen_with_subregions();
-        assert!(check_in_bounds_symbols(&symgen).is_ok());
+    fn test_require_descriptions() {
+        let mut symgen = get_test_symgen();
+        assert!(check_require_descriptions(&symgen, true, true).is_ok());
 
         let block = get_main_block(&mut symgen);
-        // Shrink the main block so the sub2 subregion ends up out of bounds
-        *block.length.get_mut(Some(&"v2".into())).unwrap() -= 0x80;
-        assert!(check_in_bounds_symbols(&symgen).is_err());
+        block
+            .functions
+            .get_mut(0)
+            .expect("symgen has no functions")
+            .description = None;
+        block
+            .functions
+            .get_mut(1)
+            .expect("symgen has no functions")
+            .description = Some(MaybeVersionDep::Common(" \n\t".to_string()));
+        let err = check_require_descriptions(&symgen, true, false).unwrap_err();
+        assert!(err.contains("- block \"main\": [fn1, fn2]"));
+        assert!(check_require_descriptions(&symgen, false, true).is_ok());
+
+        let block = get_main_block(&mut symgen);
+        block
+            .data
+            .get_mut(0)
+            .expect("symgen has no data")
+            .description = Some(MaybeVersionDep::ByVersion(
+            [
+                (("v1", 0).into(), "foo".to_string()),
+                (("v2", 1).into(), String::new()),
+            ]
+            .into(),
+        ));
+        let err = check_require_descriptions(&symgen, false, true).unwrap_err();
+        assert!(err.contains("- block \"main\": [SOME_DATA]"));
+        let err = check_require_descriptions(&symgen, true, true).unwrap_err();
+        assert!(err.contains("- block \"main\": [fn1, fn2, SOME_DATA]"));
     }
 
     #[test]
-    fn test_no_overlap() {
+    fn test_max_description_length() {
         let mut symgen = get_test_symgen();
-        assert!(check_no_overlap(&symgen).is_ok());
+        // The longest description is "foo bar baz"
+        assert!(check_max_description_length(&symgen, 11).is_ok());
+        let err = check_max_description_length(&symgen, 10).unwrap_err();
+        assert!(err.contains("Found descriptions longer than 10 bytes"));
+        assert!(err.contains("- block \"main\", symbol \"SOME_DATA\": 11 bytes (\"foo bar baz\")"));
+        assert!(!err.contains("fn1"));
 
+        // Lengths are measured in bytes: "ÀÉÎÕÜ" has 5 characters but 10 bytes
         let block = get_main_block(&mut symgen);
-        // Swap the address of the second function to match the first, causing an overlap
-        let function = block
+        block.description = Some("ÀÉÎÕÜ".to_string());
+        block
             .functions
-            .iter()
-            .next()
+            .get_mut(0)
             .expect("symgen has no functions")
-            .clone();
-        let mut overlapping = block
-            .functions
-            .iter()
-            .next()
-            .expect("symgen does not have two functions")
-            .clone();
-        let addr = overlapping
-            .address
-            .get_mut_native(block.version("v1"))
-            .unwrap();
-        *addr = function
-            .address
-            .get_native(block.version("v1"))
-            .unwrap()
-            .clone();
-        block.functions = [function, overlapping].into();
-        assert!(check_no_overlap(&symgen).is_err());
+            .description = Some(MaybeVersionDep::ByVersion(
+            [
+                (("v1", 0).into(), "ok".to_string()),
+                (("v2", 1).into(), "日本語".to_string()),
+            ]
+            .into(),
+        ));
+        block
+            .data
+            .get_mut(0)
+            .expect("symgen has no data")
+            .description = None;
+        assert!(check_max_description_length(&symgen, 10).is_ok());
+        let err = check_max_description_length(&symgen, 9).unwrap_err();
+        assert!(err.contains("- block \"main\": 10 bytes (\"ÀÉÎÕÜ\")"));
+        assert!(!err.contains("fn1"));
+        let err = check_max_description_length(&symgen, 8).unwrap_err();
+        assert!(err.contains("- block \"main\", symbol \"fn1\" [v2]: 9 bytes (\"日本語\")"));
+        assert!(!err.contains("[v1]"));
+    }
+
+    fn read_address_regions(yaml: &str) -> AddressRegionSet {
+        AddressRegionSet::read("regions.yml", yaml.as_bytes()).expect("Read failed")
     }
 
     #[test]
-    fn test_no_overlap_common_with_version_list() {
-        let mut symgen = SymGen::read(
+    fn test_read_address_regions() {
+        let regions = read_address_regions(
             r"
-            main:
-              versions:
-                - v1
-                - v2
-              address: 0x2000000
-              length: 0x100000
-              functions:
-                - name: fn1
-                  address: 0x2001000
-                  length: 0x1000
-                - name: fn2
-                  address:
-                    v1:
-                      - 0x2000000
-                      - 0x2002000
-                    v2: 0x2004000
-              data: []
-        "
-            .as_bytes(),
+            main_ram:
+              start: 0x2000000
+              end: 0x2400000
+            itcm:
+              start: 0x1FF8000
+              end: 0x2000000
+            ",
+        );
+        assert_eq!(regions.name, "regions.yml");
+        assert_eq!(
+            regions.regions,
+            vec![
+                AddressRegion {
+                    name: "itcm".to_string(),
+                    start: 0x1FF8000,
+                    end: 0x2000000,
+                },
+                AddressRegion {
+                    name: "main_ram".to_string(),
+                    start: 0x2000000,
+                    end: 0x2400000,
+                },
+            ]
+        );
+
+        let err = AddressRegionSet::read(
+            "regions.yml",
+            "empty:\n  start: 0x2000000\n  end: 0x2000000\n".as_bytes(),
         )
-        .expect("Read failed");
+        .unwrap_err();
+        assert!(err.to_string().contains("\"empty\""));
+        assert!(AddressRegionSet::read("regions.yml", "ram: 0x2000000".as_bytes()).is_err());
+    }
 
-        assert!(check_no_overlap(&symgen).is_ok());
+    #[test]
+    fn test_address_regions() {
+        let symgen = get_test_symgen();
+        let regions = read_address_regions(
+            r"
+            ram:
+              start: 0x2000000
+              end: 0x2003000
+            ",
+        );
+        // fn1 [v2] ends exactly at the end of the region
+        let err = check_address_regions(&symgen, &regions).unwrap_err();
+        assert!(err.contains("Found symbols outside of the address regions in \"regions.yml\""));
+        assert!(err.contains("- block \"main\", symbol \"fn2\" [v2]: 0x2004000"));
+        assert!(!err.contains("fn1"));
+        assert!(!err.contains("SOME_DATA"));
 
-        let block = get_main_block(&mut symgen);
-        // Swap the address of the first function to match one of the versions in the second,
-        // causing an overlap
-        let mut function = block
-            .functions
-            .iter()
-            .next()
-            .expect("symgen has no functions")
-            .clone();
-        let overlapping = block
-            .functions
-            .iter()
-            .next()
-            .expect("symgen does not have two functions")
-            .clone();
-        let addr = function.address.get_mut_native(None).unwrap();
-        *addr = overlapping
-            .address
-            .get_native(block.version("v1"))
-            .unwrap()
-            .clone();
-        block.functions = [function, overlapping].into();
-        assert!(check_no_overlap(&symgen).is_err());
+        let regions = read_address_regions(
+            r"
+            ram:
+              start: 0x2000000
+              end: 0x2010000
+            ",
+        );
+        assert!(check_address_regions(&symgen, &regions).is_ok());
     }
 
     #[test]
-    fn test_no_overlap_common_no_version_list() {
-        let mut symgen = SymGen::read(
+    fn test_address_regions_straddling() {
+        let symgen = get_test_symgen();
+        let regions = read_address_regions(
+            r"
+            low:
+              start: 0x2000000
+              end: 0x2001800
+            high:
+              start: 0x2001800
+              end: 0x2010000
+            ",
+        );
+        // Symbols must fall entirely within a single region
+        let err = check_address_regions(&symgen, &regions).unwrap_err();
+        assert!(err.contains("- block \"main\", symbol \"fn1\" [v1]: 0x2001000..0x2002000"));
+        assert!(err.contains("- block \"main\", symbol \"SOME_DATA\" [v2]: 0x2000000..0x2002000"));
+        assert!(!err.contains("fn1\" [v2]"));
+        assert!(!err.contains("SOME_DATA\" [v1]"));
+        assert!(!err.contains("fn2"));
+    }
+
+    #[test]
+    fn test_address_regions_ranges() {
+        let symgen = SymGen::read(
             r"
             main:
               address: 0x2000000
@@ -1454,78 +4632,140 @@ mod tests {
               functions:
                 - name: fn1
                   address: 0x2001000
-                  length: 0x1000
-                - name: fn2
-                  address:
-                    - 0x2000000
-                    - 0x2002000
+                  length: 0x100
+                  ranges:
+                    - address: 0x2080000
+                      length: 0x100
               data: []
-        "
+            "
             .as_bytes(),
         )
         .expect("Read failed");
-
-        assert!(check_no_overlap(&symgen).is_ok());
-
-        let block = get_main_block(&mut symgen);
-        // Swap the address of the second function to match the first, causing an overlap
-        let function = block
-            .functions
-            .iter()
-            .next()
-            .expect("symgen has no functions")
-            .clone();
-        let mut overlapping = block
-            .functions
-            .iter()
-            .next()
-            .expect("symgen does not have two functions")
-            .clone();
-        let addr = overlapping.address.get_mut_native(None).unwrap();
-        *addr = function.address.get_native(None).unwrap().clone();
-        block.functions = [function, overlapping].into();
-        assert!(check_no_overlap(&symgen).is_err());
+        let regions = read_address_regions(
+            r"
+            ram:
+              start: 0x2000000
+              end: 0x2010000
+            ",
+        );
+        let err = check_address_regions(&symgen, &regions).unwrap_err();
+        assert!(err.contains("- block \"main\", symbol \"fn1\": 0x2080000..0x2080100"));
+        assert!(!err.contains("0x2001000"));
     }
 
     #[test]
-    fn test_no_overlap_with_subregions() {
-        let mut symgen = get_test_symgen_with_subregions();
-        assert!(check_no_overlap(&symgen).is_ok());
+    fn test_max_name_length() {
+        let mut symgen = get_test_symgen();
+        // The longest name is "SOME_DATA"
+        assert!(check_max_name_length(&symgen, 9).is_ok());
+        let err = check_max_name_length(&symgen, 8).unwrap_err();
+        assert_eq!(
+            err,
+            "Found names longer than 8 bytes:
+- \"SOME_DATA\" (9 bytes)"
+        );
 
-        // Add a symbol to the main block that overlaps with a subregion symbol
-        let address = *get_subregion_block(&mut symgen, 0)
-            .address
-            .get(Some(&"v1".into()))
-            .unwrap();
+        // Lengths are measured in bytes: "fn_日本" has 5 characters but 9 bytes
         let block = get_main_block(&mut symgen);
-        block.functions.push(Symbol {
-            name: String::from("main_fn"),
-            address: MaybeVersionDep::ByVersion([("v1".into(), [address].into())].into()),
-            length: None,
-            description: None,
-        });
-        assert!(check_no_overlap(&symgen).is_err());
+        block.functions.get_mut(0).unwrap().deprecated_aliases = Some(vec!["fn_日本".to_string()]);
+        block.data.get_mut(0).unwrap().name = "DATA".to_string();
+        assert!(check_max_name_length(&symgen, 9).is_ok());
+        let err = check_max_name_length(&symgen, 8).unwrap_err();
+        assert_eq!(
+            err,
+            "Found names longer than 8 bytes:
+- \"fn_日本\" (9 bytes, alias of \"fn1\")"
+        );
     }
 
     #[test]
-    fn test_symbols_name_check() {
+    fn test_forbidden_prefixes() {
         let mut symgen = get_test_symgen();
-        assert!(check_function_names(&symgen, NamingConvention::SnakeCase).is_ok());
-        assert!(check_data_names(&symgen, NamingConvention::ScreamingSnakeCase).is_ok());
+        let prefixes = ["Unknown".to_string(), "Sub_".to_string()];
+        assert!(check_forbidden_prefixes(&symgen, &prefixes).is_ok());
 
+        // Placeholder names are fine as aliases
         let block = get_main_block(&mut symgen);
-        // Set the function to have the wrong case
-        block
-            .functions
-            .get_mut(0)
-            .expect("symgen has no functions")
-            .name = "PascalCase".to_string();
-        assert!(check_function_names(&symgen, NamingConvention::SnakeCase).is_err());
+        block.functions.get_mut(0).unwrap().deprecated_aliases =
+            Some(vec!["Sub_2001000".to_string()]);
+        block.data.get_mut(0).unwrap().deprecated_aliases = Some(vec!["UnknownData".to_string()]);
+        assert!(check_forbidden_prefixes(&symgen, &prefixes).is_ok());
 
-        // reborrow
+        // But not as primary names
         let block = get_main_block(&mut symgen);
-        // Set the data to have the wrong case
-        block.data.get_mut(0).expect("symgen has no data").name = "snake_case".to_string();
-        assert!(check_data_names(&symgen, NamingConvention::ScreamingSnakeCase).is_err());
+        block.functions.get_mut(1).unwrap().name = "Sub_2000000".to_string();
+        block.data.get_mut(0).unwrap().name = "UnknownData".to_string();
+        let err = check_forbidden_prefixes(&symgen, &prefixes).unwrap_err();
+        assert_eq!(
+            err,
+            "Found symbol names with forbidden prefixes:
+- block \"main\", symbol \"Sub_2000000\": prefix \"Sub_\"
+- block \"main\", symbol \"UnknownData\": prefix \"Unknown\""
+        );
+        // Prefixes are case-sensitive
+        assert!(check_forbidden_prefixes(&symgen, &["sub_".to_string()]).is_ok());
+    }
+
+    #[test]
+    fn test_round_trip_stable() {
+        let symgen = get_test_symgen();
+        assert!(check_round_trip_stable(&symgen).is_ok());
+        assert_eq!(first_round_trip_difference(&symgen, &symgen), None);
+
+        let mut changed = symgen.clone();
+        let block = get_main_block(&mut changed);
+        let function = block.functions.get_mut(0).expect("symgen has no functions");
+        function.description = Some(MaybeVersionDep::Common("changed".to_string()));
+        assert_eq!(
+            first_round_trip_difference(&symgen, &changed),
+            Some(
+                "block \"main\", symbol \"fn1\": description changed from \"bar\" to \"changed\""
+                    .to_string()
+            )
+        );
+
+        let mut changed = symgen.clone();
+        get_main_block(&mut changed).description = None;
+        assert_eq!(
+            first_round_trip_difference(&symgen, &changed),
+            Some("block \"main\": block metadata changed".to_string())
+        );
+        assert_eq!(
+            first_round_trip_difference(&symgen, &SymGen::from([])),
+            Some("block \"main\" is missing".to_string())
+        );
+    }
+
+    #[test]
+    fn test_presets() {
+        for name in CHECK_PRESETS {
+            let checks = Check::preset(name).expect("missing preset");
+            assert!(checks.contains(&Check::UniqueSymbols));
+        }
+        let ci = Check::preset("ci").unwrap();
+        let strict = Check::preset("strict").unwrap();
+        assert!(ci.iter().all(|c| strict.contains(c)));
+        assert!(strict.contains(&Check::SafeDescriptions));
+        assert!(!ci.contains(&Check::SafeDescriptions));
+        assert!(Check::preset("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_stdin_recursive() {
+        let err = run_checks("-", &[Check::UniqueSymbols], true, None).unwrap_err();
+        assert!(matches!(err, ResymgenError::Usage(_)));
+    }
+
+    #[test]
+    fn test_github_annotation() {
+        let result = CheckResult {
+            check: Check::NoOverlap,
+            succeeded: false,
+            details: Some("100% overlap:\nfn1, fn2".to_string()),
+        };
+        assert_eq!(
+            github_annotation(Path::new("a:b,c.yml"), &result),
+            "::error file=a%3Ab%2Cc.yml,title=NoOverlap::100%25 overlap:%0Afn1, fn2"
+        );
     }
 }
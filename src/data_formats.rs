@@ -4,23 +4,46 @@
 //! The code for each data format is separated into its own module, including the `resymgen` YAML
 //! format itself (the [`symgen_yml`] module).
 
+pub mod binja;
+pub mod c_header;
+pub mod csv_table;
+pub mod flat;
 pub mod ghidra;
 pub mod ghidra_csv;
+pub mod ghidra_xml;
+pub mod idc;
 pub mod json;
+pub mod ld;
+pub mod nocash_sym;
+pub mod objdump;
+pub mod r2;
 pub mod sym;
 pub mod symgen_yml;
 
+use std::collections::HashSet;
 use std::error::Error;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::Path;
 
+use binja::BinaryNinjaFormatter;
+use c_header::CHeaderFormatter;
+use csv_table::CsvTableFormatter;
+use flat::FlatLoader;
 use ghidra::GhidraFormatter;
 use ghidra_csv::CsvLoader;
+use ghidra_xml::{GhidraXmlFormatter, GhidraXmlLoader};
+use idc::IdcFormatter;
 use json::JsonFormatter;
+use ld::LdFormatter;
+use nocash_sym::NoCashSymFormatter;
+use objdump::ObjdumpLoader;
+use r2::RadareFormatter;
 use sym::SymFormatter;
 pub use symgen_yml::Generate;
-use symgen_yml::{Load, LoadParams, Subregion, SymGen, Symbol};
+use symgen_yml::{
+    AddSymbol, AddressNotation, IntFormat, Load, LoadParams, MergeSummary, Subregion, SymGen,
+};
 
 // `OutFormat` is like a poor man's version of trait objects for Generate. Real trait objects don't
 // work because `Generate` isn't object-safe (generate() is generic), so we can't use dynamic
@@ -39,6 +62,47 @@ pub enum OutFormat {
     Sym,
     /// [`json`] format
     Json,
+    /// [`ghidra_xml`] format
+    GhidraXml,
+    /// [`idc`] format
+    Idc,
+    /// [`binja`] format
+    BinaryNinja,
+    /// [`r2`] format
+    Radare,
+    /// [`ld`] format
+    Ld,
+    /// [`c_header`] format
+    CHeader,
+    /// [`csv_table`] format
+    Csv,
+    /// [`nocash_sym`] format
+    NoCashSym,
+}
+
+/// Alternate names accepted by [`OutFormat::from()`], along with the [`OutFormat`]s they refer to.
+const OUT_FORMAT_ALIASES: [(&str, OutFormat); 1] = [
+    // DeSmuME reads the plain sym format
+    ("desmume", OutFormat::Sym),
+];
+
+/// Options for [`OutFormat`]s that support them. Formats ignore options that don't apply to them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FormatOptions {
+    /// How to write integers, for formats that allow a choice.
+    pub int_format: IntFormat,
+    /// How to write addresses, for formats that write them as plain hexadecimal numbers
+    /// ([`OutFormat::Ld`], [`OutFormat::Radare`], [`OutFormat::Idc`], and
+    /// [`OutFormat::NoCashSym`]). If `None`, each format uses its usual notation.
+    pub address_notation: Option<AddressNotation>,
+    /// Whether to include descriptions as comments, for formats where they're optional.
+    pub comment_descriptions: bool,
+    /// Whether to keep only the first address of symbols with multiple addresses, for formats
+    /// that only allow one address per symbol (like [`OutFormat::Ld`]). Otherwise, such symbols
+    /// are an error in those formats.
+    pub first_address_only: bool,
+    /// Whether to suppress warnings printed while generating.
+    pub quiet: bool,
 }
 
 // Technically this makes it redundant to impl Generate for the individual formatters, but I think
@@ -49,11 +113,50 @@ impl Generate for OutFormat {
         writer: W,
         symgen: &SymGen,
         version: &str,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        self.generate_with(writer, symgen, version, &FormatOptions::default())
+    }
+}
+
+impl OutFormat {
+    /// Like [`Generate::generate()`], but with `options` for formats that support them.
+    pub fn generate_with<W: Write>(
+        &self,
+        writer: W,
+        symgen: &SymGen,
+        version: &str,
+        options: &FormatOptions,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
         match self {
             Self::Ghidra => GhidraFormatter {}.generate(writer, symgen, version),
             Self::Sym => SymFormatter {}.generate(writer, symgen, version),
             Self::Json => JsonFormatter {}.generate(writer, symgen, version),
+            Self::GhidraXml => GhidraXmlFormatter {}.generate(writer, symgen, version),
+            Self::Idc => IdcFormatter {
+                address_notation: options.address_notation.unwrap_or_default(),
+            }
+            .generate(writer, symgen, version),
+            Self::BinaryNinja => BinaryNinjaFormatter {}.generate(writer, symgen, version),
+            Self::Radare => RadareFormatter {
+                address_notation: options.address_notation.unwrap_or_default(),
+            }
+            .generate(writer, symgen, version),
+            Self::Ld => LdFormatter {
+                first_address_only: options.first_address_only,
+                quiet: options.quiet,
+                address_notation: options.address_notation.unwrap_or_default(),
+            }
+            .generate(writer, symgen, version),
+            Self::CHeader => CHeaderFormatter {}.generate(writer, symgen, version),
+            Self::Csv => CsvTableFormatter {
+                int_format: options.int_format,
+            }
+            .generate(writer, symgen, version),
+            Self::NoCashSym => NoCashSymFormatter {
+                comment_descriptions: options.comment_descriptions,
+                address_notation: options.address_notation.unwrap_or(AddressNotation::Bare),
+            }
+            .generate(writer, symgen, version),
         }
     }
 }
@@ -65,7 +168,34 @@ impl OutFormat {
             "ghidra" => Some(Self::Ghidra),
             "sym" => Some(Self::Sym),
             "json" => Some(Self::Json),
-            _ => None,
+            "ghidraxml" => Some(Self::GhidraXml),
+            "idc" => Some(Self::Idc),
+            "binja" => Some(Self::BinaryNinja),
+            "r2" => Some(Self::Radare),
+            "ld" => Some(Self::Ld),
+            "cheader" => Some(Self::CHeader),
+            "csv" => Some(Self::Csv),
+            "nocashsym" => Some(Self::NoCashSym),
+            _ => OUT_FORMAT_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == name)
+                .map(|&(_, f)| f),
+        }
+    }
+    /// Returns an [`Iterator`] over the alternate names accepted by [`OutFormat::from()`], in
+    /// addition to the [`OutFormat::name()`] of each variant.
+    pub fn aliases() -> impl Iterator<Item = &'static str> {
+        OUT_FORMAT_ALIASES.iter().map(|&(alias, _)| alias)
+    }
+    /// Returns the name of the [`OutFormat`], as accepted by [`OutFormat::from()`]. This is the
+    /// same as the file extension, except for formats whose extension is ambiguous.
+    pub fn name(&self) -> String {
+        match self {
+            Self::GhidraXml => String::from("ghidraxml"),
+            Self::BinaryNinja => String::from("binja"),
+            Self::CHeader => String::from("cheader"),
+            Self::NoCashSym => String::from("nocashsym"),
+            _ => self.extension(),
         }
     }
     /// Returns the file extension associated with the [`OutFormat`].
@@ -74,11 +204,34 @@ impl OutFormat {
             Self::Ghidra => String::from("ghidra"),
             Self::Sym => String::from("sym"),
             Self::Json => String::from("json"),
+            Self::GhidraXml => String::from("xml"),
+            Self::Idc => String::from("idc"),
+            Self::BinaryNinja => String::from("py"),
+            Self::Radare => String::from("r2"),
+            Self::Ld => String::from("ld"),
+            Self::CHeader => String::from("h"),
+            Self::Csv => String::from("csv"),
+            // Distinct from the plain sym format, so both can be generated into one directory
+            Self::NoCashSym => String::from("nocash.sym"),
         }
     }
     /// Returns an [`Iterator`] over all [`OutFormat`] variants.
     pub fn all() -> impl Iterator<Item = OutFormat> {
-        [Self::Ghidra, Self::Sym, Self::Json].iter().copied()
+        [
+            Self::Ghidra,
+            Self::Sym,
+            Self::Json,
+            Self::GhidraXml,
+            Self::Idc,
+            Self::BinaryNinja,
+            Self::Radare,
+            Self::Ld,
+            Self::CHeader,
+            Self::Csv,
+            Self::NoCashSym,
+        ]
+        .iter()
+        .copied()
     }
 }
 
@@ -96,6 +249,18 @@ pub enum InFormat {
     ///
     /// [CSV]: ghidra_csv
     Csv,
+    /// The symbol table printed by [`objdump -t`].
+    ///
+    /// [`objdump -t`]: objdump
+    Objdump,
+    /// The [XML] format exported from Ghidra projects.
+    ///
+    /// [XML]: ghidra_xml
+    GhidraXml,
+    /// A legacy [flat] `name,address` symbol table, with blocks inferred by address.
+    ///
+    /// [flat]: flat
+    Flat,
 }
 
 impl InFormat {
@@ -104,6 +269,9 @@ impl InFormat {
         match name {
             "yml" => Some(Self::Yaml),
             "csv" => Some(Self::Csv),
+            "objdump" => Some(Self::Objdump),
+            "xml" => Some(Self::GhidraXml),
+            "flat" => Some(Self::Flat),
             _ => None,
         }
     }
@@ -112,11 +280,22 @@ impl InFormat {
         match self {
             Self::Yaml => String::from("yml"),
             Self::Csv => String::from("csv"),
+            Self::Objdump => String::from("objdump"),
+            Self::GhidraXml => String::from("xml"),
+            Self::Flat => String::from("flat"),
         }
     }
     /// Returns an [`Iterator`] over all [`InFormat`] variants.
     pub fn all() -> impl Iterator<Item = InFormat> {
-        [Self::Yaml, Self::Csv].iter().copied()
+        [
+            Self::Yaml,
+            Self::Csv,
+            Self::Objdump,
+            Self::GhidraXml,
+            Self::Flat,
+        ]
+        .iter()
+        .copied()
     }
 
     /// Reads data from `rdr` in the format specified by the [`InFormat`], and merges it into
@@ -124,18 +303,25 @@ impl InFormat {
     ///
     /// If a `file_name` is provided, it may be used for subregion resolution, depending on the
     /// [`InFormat`].
+    ///
+    /// If an error is returned, `symgen` is left unchanged.
     pub fn merge<R, P>(
         &self,
         symgen: &mut SymGen,
         rdr: R,
         file_name: Option<P>,
         params: &LoadParams,
-    ) -> Result<Vec<Symbol>, Box<dyn Error>>
+    ) -> Result<MergeSummary, Box<dyn Error + Send + Sync>>
     where
         R: Read,
         P: AsRef<Path>,
     {
-        let unmerged = match self {
+        // Version renames are applied to the incoming data, before it's merged
+        let rename = |mut s: AddSymbol| {
+            s.symbol.rename_versions(&params.version_renames);
+            s
+        };
+        let summary = match self {
             Self::Yaml => {
                 let mut other = SymGen::read_no_init(rdr)?;
                 if let Some(file_name) = file_name {
@@ -144,11 +330,43 @@ impl InFormat {
                             File::open(p)
                         })?;
                 }
-                symgen.merge_symgen(&other)?;
-                Vec::new()
+                other.rename_versions(&params.version_renames);
+                symgen.merge_symgen(&other)?
+            }
+            Self::Csv => symgen.merge_symbols(CsvLoader::load(rdr, params)?.map(rename))?,
+            Self::Objdump => symgen.merge_symbols(ObjdumpLoader::load(rdr, params)?.map(rename))?,
+            Self::Flat => symgen.merge_symbols(FlatLoader::load(rdr, params)?.map(rename))?,
+            Self::GhidraXml => {
+                // Blocks inferred from memory section names are only a hint. If a section doesn't
+                // correspond to a block, fall back to inferring the block from the symbol address.
+                let block_names: HashSet<String> =
+                    symgen.iter().map(|(name, _)| name.val.clone()).collect();
+                symgen.merge_symbols(GhidraXmlLoader::load(rdr, params)?.map(|mut s| {
+                    if params.default_block_name.is_none()
+                        && !s.block_name.iter().any(|b| block_names.contains(b))
+                    {
+                        s.block_name = None;
+                    }
+                    rename(s)
+                }))?
             }
-            Self::Csv => symgen.merge_symbols(CsvLoader::load(rdr, params)?)?,
         };
-        Ok(unmerged)
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_format_from() {
+        for f in OutFormat::all() {
+            assert_eq!(OutFormat::from(&f.name()).map(|f| f.name()), Some(f.name()));
+        }
+        // DeSmuME reads the plain sym format
+        assert!(matches!(OutFormat::from("desmume"), Some(OutFormat::Sym)));
+        assert!(OutFormat::aliases().all(|a| OutFormat::from(a).is_some()));
+        assert!(OutFormat::from("unknown").is_none());
     }
 }
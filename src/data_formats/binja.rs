@@ -0,0 +1,192 @@
+//! A Binary Ninja Python script format (.py).
+//!
+//! The script is meant to be run from Binary Ninja's Python console (or as a snippet), with the
+//! target `BinaryView` in scope as `bv`. Each symbol is defined with `bv.define_user_symbol()`,
+//! as a `FunctionSymbol` for function symbols and a `DataSymbol` for data symbols. Data symbols
+//! with a known length also get a data variable of that size, and descriptions become comments.
+//! Strings are written as JSON string literals, which are also valid Python string literals.
+//!
+//! # Example
+//! ```python
+//! from binaryninja import Symbol, SymbolType, Type
+//!
+//! bv.define_user_symbol(Symbol(SymbolType.FunctionSymbol, 0x2000000, "main"))
+//! bv.set_comment_at(0x2000000, "the main function")
+//! bv.define_user_symbol(Symbol(SymbolType.DataSymbol, 0x2FFFFFC, "SOME_DATA"))
+//! bv.define_user_data_var(0x2FFFFFC, Type.int(4, False))
+//! ```
+
+use std::error::Error;
+use std::io::Write;
+
+use super::symgen_yml::{Generate, RealizedSymbol, SymGen, Uint};
+
+/// Generator for the Binary Ninja .py format.
+pub struct BinaryNinjaFormatter {}
+
+impl BinaryNinjaFormatter {
+    /// Gets the Python expression for the type of a data symbol with the given length.
+    fn data_type(length: Uint) -> String {
+        match length {
+            1 | 2 | 4 | 8 => format!("Type.int({}, False)", length),
+            _ => format!("Type.array(Type.int(1, False), {:#X})", length),
+        }
+    }
+
+    fn write_symbol<W: Write>(
+        writer: &mut W,
+        symbol_type: &str,
+        s: &RealizedSymbol,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        writeln!(
+            writer,
+            "bv.define_user_symbol(Symbol(SymbolType.{}, {:#X}, {}))",
+            symbol_type,
+            s.address,
+            serde_json::to_string(s.name)?
+        )?;
+        if let Some(desc) = s.description {
+            writeln!(
+                writer,
+                "bv.set_comment_at({:#X}, {})",
+                s.address,
+                serde_json::to_string(desc)?
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Generate for BinaryNinjaFormatter {
+    fn generate<W: Write>(
+        &self,
+        mut writer: W,
+        symgen: &SymGen,
+        version: &str,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut count = 0;
+        writeln!(writer, "from binaryninja import Symbol, SymbolType, Type")?;
+        writeln!(writer)?;
+        for f in symgen.functions_realized(version).filter(|s| !s.inlined) {
+            Self::write_symbol(&mut writer, "FunctionSymbol", &f)?;
+            count += 1;
+        }
+        for d in symgen.data_realized(version).filter(|s| !s.inlined) {
+            Self::write_symbol(&mut writer, "DataSymbol", &d)?;
+            if let Some(len) = d.length.filter(|&len| len > 0) {
+                writeln!(
+                    writer,
+                    "bv.define_user_data_var({:#X}, {})",
+                    d.address,
+                    Self::data_type(len)
+                )?;
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::{Command, Stdio};
+
+    fn get_test_symgen() -> SymGen {
+        SymGen::read(
+            r#"
+            main:
+              versions:
+                - v1
+                - v2
+              address:
+                v1: 0x2000000
+                v2: 0x2000000
+              length:
+                v1: 0x100000
+                v2: 0x100000
+              description: foo
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2000000
+                    v2: 0x2002000
+                  description: |-
+                    Says "hello"
+                    on two lines
+                - name: fn2
+                  address:
+                    v1: 0x2001000
+              data:
+                - name: SOME_DATA
+                  address:
+                    v1: 0x2003000
+                    v2: 0x2004000
+                  length:
+                    v1: 0x4
+                    v2: 0x10
+                - name: OTHER_DATA
+                  address: 0x2005000
+        "#
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    #[test]
+    fn test_generate() {
+        let symgen = get_test_symgen();
+        let f = BinaryNinjaFormatter {};
+        assert_eq!(
+            f.generate_str(&symgen, "v1").expect("generate failed"),
+            r#"from binaryninja import Symbol, SymbolType, Type
+
+bv.define_user_symbol(Symbol(SymbolType.FunctionSymbol, 0x2000000, "fn1"))
+bv.set_comment_at(0x2000000, "Says \"hello\"\non two lines")
+bv.define_user_symbol(Symbol(SymbolType.FunctionSymbol, 0x2001000, "fn2"))
+bv.define_user_symbol(Symbol(SymbolType.DataSymbol, 0x2003000, "SOME_DATA"))
+bv.define_user_data_var(0x2003000, Type.int(4, False))
+bv.define_user_symbol(Symbol(SymbolType.DataSymbol, 0x2005000, "OTHER_DATA"))
+"#
+        );
+        assert_eq!(
+            f.generate_str(&symgen, "v2").expect("generate failed"),
+            r#"from binaryninja import Symbol, SymbolType, Type
+
+bv.define_user_symbol(Symbol(SymbolType.FunctionSymbol, 0x2002000, "fn1"))
+bv.set_comment_at(0x2002000, "Says \"hello\"\non two lines")
+bv.define_user_symbol(Symbol(SymbolType.DataSymbol, 0x2004000, "SOME_DATA"))
+bv.define_user_data_var(0x2004000, Type.array(Type.int(1, False), 0x10))
+bv.define_user_symbol(Symbol(SymbolType.DataSymbol, 0x2005000, "OTHER_DATA"))
+"#
+        );
+    }
+
+    #[test]
+    #[ignore = "requires python3; run with `cargo test -- --ignored`"]
+    fn test_generate_valid_python() {
+        let symgen = get_test_symgen();
+        let script = BinaryNinjaFormatter {}
+            .generate_str(&symgen, "v1")
+            .expect("generate failed");
+        let mut child = Command::new("python3")
+            .args(["-c", "import ast, sys; ast.parse(sys.stdin.read())"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to run python3");
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(script.as_bytes())
+            .expect("Failed to write script");
+        let output = child.wait_with_output().expect("Failed to run python3");
+        assert!(
+            output.status.success(),
+            "Invalid Python: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! A C header format (.h) with a declaration for every symbol.
+//!
+//! Symbols are grouped by block, with a banner comment for each block. Functions are declared as
+//! `void name(void);`, since the `resymgen` YAML format doesn't record function signatures. Data
+//! symbols are declared with their data type if they have one. Otherwise, data symbols with a
+//! known length are declared as `u8` arrays of that length, and other data symbols are declared
+//! as `void*`. The `u8` type isn't defined by the header itself, since most projects already
+//! define it. Descriptions become comments above each declaration.
+//!
+//! Symbols with multiple addresses are only declared once per block. Since the header doesn't
+//! contain addresses, inlined symbols are declared too.
+//!
+//! # Example
+//! ```c
+//! #pragma once
+//!
+//! /* ===== main ===== */
+//!
+//! /* the main function */
+//! void main(void);
+//! extern u8 SOME_DATA[0x4];
+//! ```
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::{self, Write};
+
+use super::symgen_yml::{Generate, RealizedSymbol, SymGen};
+
+/// Generator for the C header format.
+pub struct CHeaderFormatter {}
+
+impl CHeaderFormatter {
+    /// Writes a description as a comment.
+    fn write_comment<W: Write>(writer: &mut W, desc: &str) -> io::Result<()> {
+        // Make sure the description can't terminate the comment early
+        let desc = desc.trim().replace("*/", "* /");
+        let mut lines = desc.lines();
+        match (lines.next(), lines.next()) {
+            (Some(line), None) => writeln!(writer, "/* {} */", line),
+            (Some(_), Some(_)) => {
+                writeln!(writer, "/*")?;
+                for line in desc.lines() {
+                    if line.is_empty() {
+                        writeln!(writer, " *")?;
+                    } else {
+                        writeln!(writer, " * {}", line)?;
+                    }
+                }
+                writeln!(writer, " */")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Gets the declaration for a data symbol.
+    fn data_declaration(s: &RealizedSymbol) -> String {
+        match (s.data_type, s.length) {
+            (Some(dtype), _) => format!("extern {} {};", dtype.trim(), s.name),
+            (None, Some(len)) if len > 0 => format!("extern u8 {}[{:#X}];", s.name, len),
+            _ => format!("extern void* {};", s.name),
+        }
+    }
+
+    fn write_declarations<'a, W, I>(
+        writer: &mut W,
+        symbols: I,
+        declaration: fn(&RealizedSymbol) -> String,
+    ) -> io::Result<usize>
+    where
+        W: Write,
+        I: Iterator<Item = RealizedSymbol<'a>>,
+    {
+        let mut count = 0;
+        let mut declared = HashSet::new();
+        for s in symbols {
+            count += 1;
+            if !declared.insert(s.name) {
+                continue;
+            }
+            if let Some(desc) = s.description {
+                Self::write_comment(writer, desc)?;
+            }
+            writeln!(writer, "{}", declaration(&s))?;
+        }
+        Ok(count)
+    }
+}
+
+impl Generate for CHeaderFormatter {
+    fn generate<W: Write>(
+        &self,
+        mut writer: W,
+        symgen: &SymGen,
+        version: &str,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut count = 0;
+        writeln!(writer, "#pragma once")?;
+        for (name, block) in symgen.iter() {
+            writeln!(writer)?;
+            writeln!(writer, "/* ===== {} ===== */", name.val)?;
+            writeln!(writer)?;
+            count +=
+                Self::write_declarations(&mut writer, block.functions_realized(version), |s| {
+                    format!("void {}(void);", s.name)
+                })?;
+            count += Self::write_declarations(
+                &mut writer,
+                block.data_realized(version),
+                Self::data_declaration,
+            )?;
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_symgen() -> SymGen {
+        SymGen::read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2000000
+                    v2: 0x2002000
+                  description: |-
+                    Does a thing.
+
+                    Returns */ nothing.
+                - name: fn2
+                  address:
+                    v1:
+                      - 0x2001FFF
+                      - 0x2002000
+                  description: Another thing
+              data:
+                - name: SOME_DATA
+                  address: 0x2003000
+                  length:
+                    v1: 0x4
+                    v2: 0x100
+                - name: TYPED_DATA
+                  address: 0x2004000
+                  length: 0x20
+                  data_type: struct thing
+                - name: UNSIZED_DATA
+                  address: 0x2005000
+            other:
+              address: 0x2100000
+              length: 0x1000
+              functions: []
+              data: []
+        "
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    #[test]
+    fn test_generate() {
+        let symgen = get_test_symgen();
+        let f = CHeaderFormatter {};
+        assert_eq!(
+            f.generate_str(&symgen, "v1").expect("generate failed"),
+            r"#pragma once
+
+/* ===== main ===== */
+
+/*
+ * Does a thing.
+ *
+ * Returns * / nothing.
+ */
+void fn1(void);
+/* Another thing */
+void fn2(void);
+extern u8 SOME_DATA[0x4];
+extern struct thing TYPED_DATA;
+extern void* UNSIZED_DATA;
+
+/* ===== other ===== */
+
+"
+        );
+    }
+
+    #[test]
+    fn test_generate_array_lengths() {
+        let symgen = get_test_symgen();
+        let f = CHeaderFormatter {};
+        let header = f.generate_str(&symgen, "v2").expect("generate failed");
+        assert!(header.contains("\nextern u8 SOME_DATA[0x100];\n"));
+        // Data types take precedence over lengths
+        assert!(header.contains("\nextern struct thing TYPED_DATA;\n"));
+        assert!(!header.contains("fn2"));
+    }
+
+    #[test]
+    fn test_generate_inlined() {
+        let mut symgen = get_test_symgen();
+        for block in symgen.blocks_mut() {
+            if let Some(f) = block.functions.get_mut(0) {
+                f.inlined = Some(true);
+            }
+        }
+        let f = CHeaderFormatter {};
+        let header = f.generate_str(&symgen, "v1").expect("generate failed");
+        assert!(header.contains("\n */\nvoid fn1(void);\n"));
+    }
+}
@@ -0,0 +1,167 @@
+//! A CSV symbol table format (.csv), meant for spreadsheets.
+//!
+//! The table has a header row, followed by one row for each address of each symbol, with the
+//! columns `block`, `type` (`function` or `data`), `name`, `address`, `length`, and
+//! `description`. The `length` and `description` columns are empty if the symbol doesn't have
+//! them. Integers are written in the configured [`IntFormat`] (hexadecimal by default), and fields
+//! are quoted as specified by [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180) when needed.
+//!
+//! # Example
+//! ```csv
+//! block,type,name,address,length,description
+//! arm9,function,main,0x2000000,0x40,"the main function, which runs first"
+//! arm9,data,SOME_DATA,0x2FFFFFC,0x4,
+//! ```
+
+use std::error::Error;
+use std::io::Write;
+
+use csv::Writer;
+
+use super::symgen_yml::{Generate, IntFormat, SymGen};
+
+/// Generator for the .csv format.
+#[derive(Default)]
+pub struct CsvTableFormatter {
+    /// How to write addresses and lengths.
+    pub int_format: IntFormat,
+}
+
+impl Generate for CsvTableFormatter {
+    fn generate<W: Write>(
+        &self,
+        writer: W,
+        symgen: &SymGen,
+        version: &str,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut count = 0;
+        let mut wtr = Writer::from_writer(writer);
+        wtr.write_record(["block", "type", "name", "address", "length", "description"])?;
+        for (bname, block) in symgen.iter() {
+            let symbols = block
+                .functions_realized(version)
+                .filter(|s| !s.inlined)
+                .map(|s| ("function", s))
+                .chain(
+                    block
+                        .data_realized(version)
+                        .filter(|s| !s.inlined)
+                        .map(|s| ("data", s)),
+                );
+            for (stype, s) in symbols {
+                wtr.write_record([
+                    bname.val.as_str(),
+                    stype,
+                    s.name,
+                    &self.int_format.format(s.address),
+                    &s.length
+                        .map(|len| self.int_format.format(len))
+                        .unwrap_or_default(),
+                    s.description.unwrap_or_default(),
+                ])?;
+                count += 1;
+            }
+        }
+        wtr.flush()?;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_symgen() -> SymGen {
+        SymGen::read(
+            r#"
+            main:
+              versions:
+                - v1
+                - v2
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2000000
+                    v2: 0x2002000
+                  length: 0x1000
+                  description: does this, that, and "the other"
+                - name: fn2
+                  address:
+                    v1:
+                      - 0x2001000
+                      - 0x2002000
+                  description: |-
+                    first line
+                    second line
+              data:
+                - name: SOME_DATA
+                  address: 0x2003000
+            other:
+              address: 0x2100000
+              length: 0x1000
+              functions: []
+              data:
+                - name: OTHER_DATA
+                  address: 0x2100000
+                  length: 0x4
+        "#
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    #[test]
+    fn test_generate() {
+        let symgen = get_test_symgen();
+        let f = CsvTableFormatter::default();
+        assert_eq!(
+            f.generate_str(&symgen, "v1").expect("generate failed"),
+            r#"block,type,name,address,length,description
+main,function,fn1,0x2000000,0x1000,"does this, that, and ""the other"""
+main,function,fn2,0x2001000,,"first line
+second line"
+main,function,fn2,0x2002000,,"first line
+second line"
+main,data,SOME_DATA,0x2003000,,
+other,data,OTHER_DATA,0x2100000,0x4,
+"#
+        );
+    }
+
+    #[test]
+    fn test_generate_int_format() {
+        let symgen = get_test_symgen();
+        let f = CsvTableFormatter {
+            int_format: IntFormat::Decimal,
+        };
+        let table = f.generate_str(&symgen, "v2").expect("generate failed");
+        assert_eq!(
+            table.lines().nth(1),
+            Some(r#"main,function,fn1,33562624,4096,"does this, that, and ""the other""""#)
+        );
+    }
+
+    #[test]
+    fn test_generate_round_trip() {
+        let symgen = get_test_symgen();
+        let table = CsvTableFormatter::default()
+            .generate_str(&symgen, "v1")
+            .expect("generate failed");
+        let descriptions: Vec<String> = csv::Reader::from_reader(table.as_bytes())
+            .records()
+            .map(|r| r.expect("invalid CSV")[5].to_string())
+            .collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "does this, that, and \"the other\"",
+                "first line\nsecond line",
+                "first line\nsecond line",
+                "",
+                ""
+            ]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! A legacy flat symbol table format, with one `name,address` pair per line and no block or
+//! symbol type information.
+//!
+//! Addresses are hexadecimal, with or without a `0x` prefix. Blank lines and lines starting with
+//! `#` are ignored, as is an optional `name,address` header row. Whitespace around each field is
+//! trimmed.
+//!
+//! Since the format has no block information, symbols are assigned to blocks by address when
+//! merged. Since it also has no symbol type information, a default symbol type must be specified
+//! when loading.
+//!
+//! # Example
+//! ```text
+//! # Dumped from an old project
+//! name,address
+//! main,02000800
+//! SOME_DATA,0x2001000
+//! ```
+
+use std::error::Error;
+use std::io::{BufRead, BufReader, Read};
+use std::vec::IntoIter;
+
+use super::symgen_yml::{AddSymbol, Load, LoadParams, MaybeVersionDep, Symbol, SymbolType, Uint};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Entry {
+    name: String,
+    address: Uint,
+}
+
+impl Entry {
+    /// Parses a flat symbol table line. Returns [`None`] if the line is blank, a comment, or the
+    /// header row, and an error if the line is malformed.
+    fn parse(line: &str) -> Result<Option<Self>, String> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+        let (name, address) = line
+            .split_once(',')
+            .map(|(n, a)| (n.trim(), a.trim()))
+            .ok_or_else(|| format!("expected 'name,address', found '{}'", line))?;
+        if name.eq_ignore_ascii_case("name") && address.eq_ignore_ascii_case("address") {
+            return Ok(None);
+        }
+        if name.is_empty() {
+            return Err(format!("missing symbol name in '{}'", line));
+        }
+        let digits = address
+            .strip_prefix("0x")
+            .or_else(|| address.strip_prefix("0X"))
+            .unwrap_or(address);
+        let address = Uint::from_str_radix(digits, 16)
+            .map_err(|e| format!("invalid address '{}' for \"{}\": {}", address, name, e))?;
+        Ok(Some(Self {
+            name: name.to_string(),
+            address,
+        }))
+    }
+}
+
+/// Loader for legacy flat `name,address` symbol tables.
+pub struct FlatLoader {
+    entries: IntoIter<Entry>,
+    stype: SymbolType,
+    params: LoadParams,
+}
+
+impl FlatLoader {
+    fn read<R: Read>(rdr: R) -> Result<Vec<Entry>, Box<dyn Error + Send + Sync>> {
+        let mut entries = Vec::new();
+        for (i, line) in BufReader::new(rdr).lines().enumerate() {
+            if let Some(entry) =
+                Entry::parse(&line?).map_err(|e| format!("line {}: {}", i + 1, e))?
+            {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+}
+
+impl Iterator for FlatLoader {
+    type Item = AddSymbol;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|entry| AddSymbol {
+            symbol: Symbol {
+                name: entry.name,
+                deprecated_aliases: None,
+                address: match &self.params.default_version_name {
+                    Some(vers) => MaybeVersionDep::ByVersion(
+                        [(vers.as_str().into(), entry.address.into())].into(),
+                    ),
+                    None => MaybeVersionDep::Common(entry.address.into()),
+                },
+                length: None,
+                description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
+            },
+            stype: self.stype,
+            block_name: self.params.default_block_name.clone(),
+        })
+    }
+}
+
+impl Load for FlatLoader {
+    type Source = Self;
+
+    fn load<R: Read>(
+        rdr: R,
+        params: &LoadParams,
+    ) -> Result<Self::Source, Box<dyn Error + Send + Sync>> {
+        let stype = params.default_symbol_type.ok_or(
+            "flat symbol tables don't specify symbol types, so a default symbol type is required",
+        )?;
+        Ok(Self {
+            entries: Self::read(rdr)?.into_iter(),
+            stype,
+            params: params.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn get_test_flat() -> String {
+        String::from(
+            "# legacy dump
+name,address
+main,02000800
+
+ SOME_DATA , 0x2001000
+",
+        )
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            Entry::parse("main,02000800"),
+            Ok(Some(Entry {
+                name: "main".to_string(),
+                address: 0x2000800,
+            }))
+        );
+        assert_eq!(Entry::parse("Name,Address"), Ok(None));
+        assert_eq!(Entry::parse("  # comment"), Ok(None));
+        assert!(Entry::parse("main").is_err());
+        assert!(Entry::parse(",02000800").is_err());
+        assert!(Entry::parse("main,0xZZ").is_err());
+    }
+
+    #[test]
+    fn test_load() {
+        let contents = get_test_flat();
+        let params = LoadParams {
+            default_block_name: None,
+            default_symbol_type: Some(SymbolType::Function),
+            default_version_name: Some("v1".to_string()),
+            version_renames: HashMap::new(),
+        };
+        let symbols: Vec<AddSymbol> = FlatLoader::load(contents.as_bytes(), &params)
+            .expect("Load failed")
+            .collect();
+        assert_eq!(
+            symbols
+                .iter()
+                .map(|s| (s.symbol.name.as_str(), &s.symbol.address, s.stype))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "main",
+                    &MaybeVersionDep::ByVersion([(("v1", 0).into(), 0x2000800.into())].into()),
+                    SymbolType::Function
+                ),
+                (
+                    "SOME_DATA",
+                    &MaybeVersionDep::ByVersion([(("v1", 0).into(), 0x2001000.into())].into()),
+                    SymbolType::Function
+                ),
+            ]
+        );
+        assert!(symbols.iter().all(|s| s.block_name.is_none()));
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let mut params = LoadParams {
+            default_block_name: None,
+            default_symbol_type: None,
+            default_version_name: None,
+            version_renames: HashMap::new(),
+        };
+        // No symbol type
+        assert!(FlatLoader::load(get_test_flat().as_bytes(), &params).is_err());
+        params.default_symbol_type = Some(SymbolType::Data);
+        let err = FlatLoader::load("main,02000800\nbad line\n".as_bytes(), &params)
+            .err()
+            .expect("Load succeeded");
+        assert_eq!(
+            err.to_string(),
+            "line 2: expected 'name,address', found 'bad line'"
+        );
+    }
+}
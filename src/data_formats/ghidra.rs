@@ -61,26 +61,29 @@ impl Generate for GhidraFormatter {
         writer: W,
         symgen: &SymGen,
         version: &str,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut count = 0;
         let mut wtr = WriterBuilder::new()
             .delimiter(b' ')
             .has_headers(false)
             .from_writer(writer);
-        for f in symgen.functions_realized(version) {
+        for f in symgen.functions_realized(version).filter(|s| !s.inlined) {
             wtr.serialize(Entry {
                 name: f.name,
                 address: f.address,
                 stype: SymbolType::Function,
             })?;
+            count += 1;
         }
-        for d in symgen.data_realized(version) {
+        for d in symgen.data_realized(version).filter(|s| !s.inlined) {
             wtr.serialize(Entry {
                 name: d.name,
                 address: d.address,
                 stype: SymbolType::Label,
             })?;
+            count += 1;
         }
-        Ok(())
+        Ok(count)
     }
 }
 
@@ -146,4 +149,26 @@ mod tests {
             "fn1 2002000 f\nfn2 2003000 f\nSOME_DATA 2004000 l\n"
         );
     }
+
+    #[test]
+    fn test_generate_count() {
+        let symgen = get_test_symgen();
+        let f = GhidraFormatter {};
+        // Counts are per realized symbol, so multiple addresses count separately
+        assert_eq!(
+            f.generate(Vec::new(), &symgen, "v1")
+                .expect("generate failed"),
+            4
+        );
+        assert_eq!(
+            f.generate(Vec::new(), &symgen, "v2")
+                .expect("generate failed"),
+            3
+        );
+        assert_eq!(
+            f.generate(Vec::new(), &symgen, "v3")
+                .expect("generate failed"),
+            0
+        );
+    }
 }
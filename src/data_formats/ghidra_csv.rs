@@ -14,12 +14,15 @@
 //! "function1","02400000","Function"
 //! "SOME_DATA","02ffffff","Data Label"
 //! ```
+//!
+//! Symbols can also be written back out in this format with [`write_symbols()`], which is useful
+//! for saving symbols that need to be fixed up manually and merged again later.
 
 use std::error::Error;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::vec::IntoIter;
 
-use csv::{self, ReaderBuilder};
+use csv::{self, QuoteStyle, ReaderBuilder, WriterBuilder};
 use serde::{de, Deserialize, Deserializer};
 
 use super::symgen_yml::{AddSymbol, Load, LoadParams, MaybeVersionDep, Symbol, SymbolType, Uint};
@@ -97,6 +100,7 @@ impl Iterator for CsvLoader {
         self.entries.next().map(|entry| AddSymbol {
             symbol: Symbol {
                 name: entry.name,
+                deprecated_aliases: None,
                 address: match &self.params.default_version_name {
                     Some(vers) => MaybeVersionDep::ByVersion(
                         [(vers.as_str().into(), entry.location.into())].into(),
@@ -105,6 +109,12 @@ impl Iterator for CsvLoader {
                 },
                 length: None,
                 description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             },
             stype: entry.stype,
             block_name: self.params.default_block_name.clone(),
@@ -115,7 +125,10 @@ impl Iterator for CsvLoader {
 impl Load for CsvLoader {
     type Source = Self;
 
-    fn load<R: Read>(rdr: R, params: &LoadParams) -> Result<Self::Source, Box<dyn Error>> {
+    fn load<R: Read>(
+        rdr: R,
+        params: &LoadParams,
+    ) -> Result<Self::Source, Box<dyn Error + Send + Sync>> {
         Ok(Self {
             entries: Self::read(rdr)?.into_iter(),
             params: params.clone(),
@@ -123,9 +136,44 @@ impl Load for CsvLoader {
     }
 }
 
+/// Writes `symbols` to `writer` in the Ghidra CSV format, along with their types.
+///
+/// Since the format has no notion of versions or multiple addresses, a separate row is written
+/// for each distinct address of each symbol, across all versions.
+pub fn write_symbols<'a, W, I>(writer: W, symbols: I) -> Result<(), csv::Error>
+where
+    W: Write,
+    I: IntoIterator<Item = (&'a Symbol, SymbolType)>,
+{
+    let mut csv_wtr = WriterBuilder::new()
+        .quote_style(QuoteStyle::Always)
+        .double_quote(false)
+        .escape(b'\\')
+        .from_writer(writer);
+    csv_wtr.write_record(["Name", "Location", "Type"])?;
+    for (symbol, stype) in symbols {
+        let stype = match stype {
+            SymbolType::Function => "Function",
+            SymbolType::Data => "Data Label",
+        };
+        let mut addrs: Vec<Uint> = Vec::new();
+        for addr in symbol.address.values().flat_map(|a| a.iter()) {
+            if !addrs.contains(addr) {
+                addrs.push(*addr);
+            }
+        }
+        for addr in addrs {
+            csv_wtr.write_record([symbol.name.as_str(), &format!("{:X}", addr), stype])?;
+        }
+    }
+    csv_wtr.flush()?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     fn get_test_csv() -> String {
         String::from(
@@ -144,6 +192,7 @@ mod tests {
                 default_block_name: None,
                 default_symbol_type: None,
                 default_version_name: None,
+                version_renames: HashMap::new(),
             },
         );
         assert!(result.is_ok());
@@ -153,9 +202,16 @@ mod tests {
             Some(AddSymbol {
                 symbol: Symbol {
                     name: "fn1".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(0x2000000.into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 stype: SymbolType::Function,
                 block_name: None,
@@ -166,9 +222,16 @@ mod tests {
             Some(AddSymbol {
                 symbol: Symbol {
                     name: "SOME_DATA".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(0x2010000.into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 stype: SymbolType::Data,
                 block_name: None,
@@ -186,6 +249,7 @@ mod tests {
                 default_block_name: Some("main".to_string()),
                 default_symbol_type: None,
                 default_version_name: Some("v1".to_string()),
+                version_renames: HashMap::new(),
             },
         );
         assert!(result.is_ok());
@@ -195,11 +259,18 @@ mod tests {
             Some(AddSymbol {
                 symbol: Symbol {
                     name: "fn1".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::ByVersion(
                         [(("v1", 0).into(), 0x2000000.into())].into()
                     ),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 stype: SymbolType::Function,
                 block_name: Some("main".to_string()),
@@ -210,11 +281,18 @@ mod tests {
             Some(AddSymbol {
                 symbol: Symbol {
                     name: "SOME_DATA".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::ByVersion(
                         [(("v1", 0).into(), 0x2010000.into())].into()
                     ),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 stype: SymbolType::Data,
                 block_name: Some("main".to_string()),
@@ -222,4 +300,69 @@ mod tests {
         );
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_write_symbols() {
+        let symbol = |name: &str, address| Symbol {
+            name: name.to_string(),
+            deprecated_aliases: None,
+            address,
+            length: None,
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        };
+        let fn1 = symbol(
+            "fn1",
+            MaybeVersionDep::ByVersion(
+                [
+                    (("v1", 0).into(), 0x2000000.into()),
+                    (("v2", 1).into(), [0x2000000, 0x2000100].into()),
+                ]
+                .into(),
+            ),
+        );
+        let data = symbol("SOME_DATA", MaybeVersionDep::Common(0x2010000.into()));
+
+        let mut written = Vec::new();
+        write_symbols(
+            &mut written,
+            [(&fn1, SymbolType::Function), (&data, SymbolType::Data)],
+        )
+        .expect("Write failed");
+        assert_eq!(
+            String::from_utf8(written.clone()).expect("Invalid UTF-8"),
+            r#""Name","Location","Type"
+"fn1","2000000","Function"
+"fn1","2000100","Function"
+"SOME_DATA","2010000","Data Label"
+"#
+        );
+
+        // The output should be loadable again
+        let reloaded: Vec<_> = CsvLoader::load(
+            written.as_slice(),
+            &LoadParams {
+                default_block_name: None,
+                default_symbol_type: None,
+                default_version_name: None,
+                version_renames: HashMap::new(),
+            },
+        )
+        .expect("Load failed")
+        .map(|s| (s.symbol.name, s.stype))
+        .collect();
+        assert_eq!(
+            reloaded,
+            vec![
+                ("fn1".to_string(), SymbolType::Function),
+                ("fn1".to_string(), SymbolType::Function),
+                ("SOME_DATA".to_string(), SymbolType::Data),
+            ]
+        );
+    }
 }
@@ -0,0 +1,708 @@
+//! The XML format used by Ghidra's "XML" program exporter and importer.
+//!
+//! A full program export contains much more than symbols, but only three kinds of elements are
+//! relevant here:
+//! - `<MEMORY_SECTION>` elements in the `<MEMORY_MAP>`, each with a name, a start address, and a
+//!   length. These are used to infer the block each symbol belongs to.
+//! - `<FUNCTION>` elements in `<FUNCTIONS>`, each with an entry point and a name. The length of a
+//!   function is taken from its `<ADDRESS_RANGE>` starting at the entry point, if there is one.
+//! - `<SYMBOL>` elements in the `<SYMBOL_TABLE>`, each with an address and a name. Symbols that
+//!   don't name a function are treated as data labels.
+//!
+//! Addresses are hexadecimal, and may be qualified with an address space, like
+//! `overlay_11::021C0000` for an address within an overlay. Address spaces are only used to match
+//! symbols to memory sections.
+//!
+//! When generating this format, each block becomes a `<MEMORY_SECTION>`, and blocks named like
+//! `overlay11` are placed in the matching overlay address space (`overlay_11`). Every symbol gets
+//! a `<SYMBOL>` in the global namespace, with deprecated aliases as additional non-primary symbols
+//! at the same address. Function symbols are also listed as `<FUNCTION>`s, which is how Ghidra
+//! distinguishes code from data; functions with a known length get an `<ADDRESS_RANGE>`. Data
+//! lengths have no equivalent in the symbol table, so they're skipped.
+//!
+//! # Example
+//! ```xml
+//! <?xml version="1.0" standalone="yes"?>
+//! <PROGRAM NAME="arm9.bin" IMAGE_BASE="02000000">
+//!     <MEMORY_MAP>
+//!         <MEMORY_SECTION NAME="arm9" START_ADDR="02000000" LENGTH="0x100000" PERMISSIONS="rwx" />
+//!     </MEMORY_MAP>
+//!     <FUNCTIONS>
+//!         <FUNCTION ENTRY_POINT="02000800" NAME="main">
+//!             <ADDRESS_RANGE START="02000800" END="0200083f" />
+//!         </FUNCTION>
+//!     </FUNCTIONS>
+//!     <SYMBOL_TABLE>
+//!         <SYMBOL ADDRESS="02000800" NAME="main" TYPE="global" SOURCE_TYPE="USER_DEFINED" />
+//!         <SYMBOL ADDRESS="02001000" NAME="SOME_DATA" TYPE="global" SOURCE_TYPE="USER_DEFINED" />
+//!     </SYMBOL_TABLE>
+//! </PROGRAM>
+//! ```
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::{Read, Write};
+use std::vec::IntoIter;
+
+use xml::attribute::OwnedAttribute;
+use xml::reader::{EventReader, XmlEvent};
+use xml::writer::{self, EmitterConfig, EventWriter};
+
+use super::symgen_yml::{
+    AddSymbol, Block, Generate, Load, LoadParams, MaybeVersionDep, RealizedSymbol, SymGen, Symbol,
+    SymbolType, Uint,
+};
+
+/// An address, possibly qualified with the name of an address space.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+struct Address {
+    space: Option<String>,
+    offset: Uint,
+}
+
+impl Address {
+    /// Parses an address string like `02000800`, `ram:02000800`, or `overlay_11::021c0000`.
+    fn parse(s: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let (space, offset) = match s.rsplit_once(':') {
+            Some((space, offset)) => (Some(space.trim_end_matches(':').to_string()), offset),
+            None => (None, s),
+        };
+        Ok(Self {
+            space,
+            offset: parse_hex(offset)?,
+        })
+    }
+}
+
+/// Parses a hexadecimal integer, with or without a `0x` prefix.
+fn parse_hex(s: &str) -> Result<Uint, Box<dyn Error + Send + Sync>> {
+    let digits = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    Uint::from_str_radix(digits, 16).map_err(|e| format!("invalid address '{}': {}", s, e).into())
+}
+
+#[derive(Debug)]
+struct MemorySection {
+    name: String,
+    start: Address,
+    length: Uint,
+}
+
+impl MemorySection {
+    fn contains(&self, addr: &Address) -> bool {
+        self.start.space == addr.space
+            && addr.offset >= self.start.offset
+            && addr.offset - self.start.offset < self.length
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Entry {
+    name: String,
+    address: Address,
+    length: Option<Uint>,
+    stype: SymbolType,
+}
+
+/// Gets the value of the attribute named `name`, or returns an error naming the `element`.
+fn get_attr<'a>(
+    attributes: &'a [OwnedAttribute],
+    element: &str,
+    name: &str,
+) -> Result<&'a str, Box<dyn Error + Send + Sync>> {
+    attributes
+        .iter()
+        .find(|a| a.name.local_name == name)
+        .map(|a| a.value.as_str())
+        .ok_or_else(|| format!("<{}> element is missing the {} attribute", element, name).into())
+}
+
+/// Loader for the XML format produced by Ghidra's program exporter.
+pub struct GhidraXmlLoader {
+    entries: IntoIter<Entry>,
+    sections: Vec<MemorySection>,
+    params: LoadParams,
+}
+
+impl GhidraXmlLoader {
+    fn read<R: Read>(
+        rdr: R,
+    ) -> Result<(Vec<Entry>, Vec<MemorySection>), Box<dyn Error + Send + Sync>> {
+        let mut sections = Vec::new();
+        let mut functions: Vec<Entry> = Vec::new();
+        let mut labels = Vec::new();
+        let mut in_function = false;
+        for event in EventReader::new(rdr) {
+            match event? {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => {
+                    let attr = |attr_name| get_attr(&attributes, &name.local_name, attr_name);
+                    match name.local_name.as_str() {
+                        "MEMORY_SECTION" => sections.push(MemorySection {
+                            name: attr("NAME")?.to_string(),
+                            start: Address::parse(attr("START_ADDR")?)?,
+                            length: parse_hex(attr("LENGTH")?)?,
+                        }),
+                        "FUNCTION" => {
+                            in_function = true;
+                            functions.push(Entry {
+                                name: attr("NAME")?.to_string(),
+                                address: Address::parse(attr("ENTRY_POINT")?)?,
+                                length: None,
+                                stype: SymbolType::Function,
+                            });
+                        }
+                        "ADDRESS_RANGE" if in_function => {
+                            let start = Address::parse(attr("START")?)?;
+                            let end = Address::parse(attr("END")?)?;
+                            // The function body can be split into multiple ranges; the length
+                            // comes from the one starting at the entry point.
+                            let function = functions.last_mut().unwrap();
+                            if start == function.address && end.offset >= start.offset {
+                                function.length = Some(end.offset - start.offset + 1);
+                            }
+                        }
+                        "SYMBOL" => {
+                            // Default names (like DAT_02000800) are generated by Ghidra and carry
+                            // no information.
+                            if attr("SOURCE_TYPE").ok() == Some("DEFAULT") {
+                                continue;
+                            }
+                            labels.push(Entry {
+                                name: attr("NAME")?.to_string(),
+                                address: Address::parse(attr("ADDRESS")?)?,
+                                length: None,
+                                stype: SymbolType::Data,
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                XmlEvent::EndElement { name } if name.local_name == "FUNCTION" => {
+                    in_function = false;
+                }
+                _ => {}
+            }
+        }
+
+        // Symbols that name a function are already accounted for; the rest are data labels.
+        let function_symbols: HashSet<_> = functions
+            .iter()
+            .map(|f| (f.name.as_str(), &f.address))
+            .collect();
+        labels.retain(|l| !function_symbols.contains(&(l.name.as_str(), &l.address)));
+        functions.append(&mut labels);
+        Ok((functions, sections))
+    }
+}
+
+impl Iterator for GhidraXmlLoader {
+    type Item = AddSymbol;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|entry| {
+            let block_name = match &self.params.default_block_name {
+                Some(name) => Some(name.clone()),
+                None => self
+                    .sections
+                    .iter()
+                    .find(|s| s.contains(&entry.address))
+                    .map(|s| s.name.clone()),
+            };
+            AddSymbol {
+                symbol: Symbol {
+                    name: entry.name,
+                    deprecated_aliases: None,
+                    address: match &self.params.default_version_name {
+                        Some(vers) => MaybeVersionDep::ByVersion(
+                            [(vers.as_str().into(), entry.address.offset.into())].into(),
+                        ),
+                        None => MaybeVersionDep::Common(entry.address.offset.into()),
+                    },
+                    length: entry
+                        .length
+                        .map(|len| match &self.params.default_version_name {
+                            Some(vers) => {
+                                MaybeVersionDep::ByVersion([(vers.as_str().into(), len)].into())
+                            }
+                            None => MaybeVersionDep::Common(len),
+                        }),
+                    description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
+                },
+                stype: entry.stype,
+                block_name,
+            }
+        })
+    }
+}
+
+impl Load for GhidraXmlLoader {
+    type Source = Self;
+
+    fn load<R: Read>(
+        rdr: R,
+        params: &LoadParams,
+    ) -> Result<Self::Source, Box<dyn Error + Send + Sync>> {
+        let (entries, sections) = Self::read(rdr)?;
+        Ok(Self {
+            entries: entries.into_iter(),
+            sections,
+            params: params.clone(),
+        })
+    }
+}
+
+/// Generator for the Ghidra XML format.
+pub struct GhidraXmlFormatter {}
+
+impl GhidraXmlFormatter {
+    /// Gets the address space for a block, if it's not in the default space.
+    fn address_space(block_name: &str) -> Option<String> {
+        let n = block_name.strip_prefix("overlay")?;
+        if !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) {
+            Some(format!("overlay_{}", n))
+        } else {
+            None
+        }
+    }
+
+    /// Formats an address, qualified with the address space if there is one.
+    fn address(space: Option<&str>, offset: Uint) -> String {
+        match space {
+            Some(space) => format!("{}::{:08x}", space, offset),
+            None => format!("{:08x}", offset),
+        }
+    }
+
+    fn write_section<W: Write>(
+        wtr: &mut EventWriter<W>,
+        name: &str,
+        block: &Block,
+        version: &str,
+    ) -> Result<(), writer::Error> {
+        let version = block.version(version);
+        let (start, length) = match (block.address.get(version), block.length.get(version)) {
+            (Some(start), Some(length)) => (*start, *length),
+            _ => return Ok(()), // the block doesn't exist in this version
+        };
+        let space = Self::address_space(name);
+        let start = Self::address(space.as_deref(), start);
+        let length = format!("{:#x}", length);
+        wtr.write(
+            writer::XmlEvent::start_element("MEMORY_SECTION")
+                .attr("NAME", name)
+                .attr("START_ADDR", &start)
+                .attr("LENGTH", &length)
+                .attr("PERMISSIONS", "rwx"),
+        )?;
+        wtr.write(writer::XmlEvent::end_element())
+    }
+
+    fn write_function<W: Write>(
+        wtr: &mut EventWriter<W>,
+        space: Option<&str>,
+        function: &RealizedSymbol,
+    ) -> Result<(), writer::Error> {
+        let entry_point = Self::address(space, function.address);
+        wtr.write(
+            writer::XmlEvent::start_element("FUNCTION")
+                .attr("ENTRY_POINT", &entry_point)
+                .attr("NAME", function.name),
+        )?;
+        if let Some(length) = function.length.filter(|&len| len > 0) {
+            let end = Self::address(space, function.address + length - 1);
+            wtr.write(
+                writer::XmlEvent::start_element("ADDRESS_RANGE")
+                    .attr("START", &entry_point)
+                    .attr("END", &end),
+            )?;
+            wtr.write(writer::XmlEvent::end_element())?;
+        }
+        wtr.write(writer::XmlEvent::end_element())
+    }
+
+    fn write_symbols<W: Write>(
+        wtr: &mut EventWriter<W>,
+        space: Option<&str>,
+        symbol: &RealizedSymbol,
+    ) -> Result<(), writer::Error> {
+        let address = Self::address(space, symbol.address);
+        let aliases = symbol.deprecated_aliases.unwrap_or_default();
+        let names = std::iter::once(symbol.name).chain(aliases.iter().map(|a| a.as_str()));
+        for (i, name) in names.enumerate() {
+            wtr.write(
+                writer::XmlEvent::start_element("SYMBOL")
+                    .attr("ADDRESS", &address)
+                    .attr("NAME", name)
+                    .attr("TYPE", "global")
+                    .attr("SOURCE_TYPE", "USER_DEFINED")
+                    .attr("PRIMARY", if i == 0 { "y" } else { "n" }),
+            )?;
+            wtr.write(writer::XmlEvent::end_element())?;
+        }
+        Ok(())
+    }
+}
+
+impl Generate for GhidraXmlFormatter {
+    fn generate<W: Write>(
+        &self,
+        writer: W,
+        symgen: &SymGen,
+        version: &str,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut wtr = EmitterConfig::new()
+            .perform_indent(true)
+            .create_writer(writer);
+        wtr.write(writer::XmlEvent::StartDocument {
+            version: xml::common::XmlVersion::Version10,
+            encoding: None,
+            standalone: Some(true),
+        })?;
+        wtr.write(writer::XmlEvent::start_element("PROGRAM").attr("NAME", version))?;
+
+        wtr.write(writer::XmlEvent::start_element("MEMORY_MAP"))?;
+        for (name, block) in symgen.iter() {
+            Self::write_section(&mut wtr, &name.val, block, version)?;
+        }
+        wtr.write(writer::XmlEvent::end_element())?;
+
+        wtr.write(writer::XmlEvent::start_element("FUNCTIONS"))?;
+        for (name, block) in symgen.iter() {
+            let space = Self::address_space(&name.val);
+            for f in block.functions_realized(version).filter(|s| !s.inlined) {
+                Self::write_function(&mut wtr, space.as_deref(), &f)?;
+            }
+        }
+        wtr.write(writer::XmlEvent::end_element())?;
+
+        let mut count = 0;
+        wtr.write(writer::XmlEvent::start_element("SYMBOL_TABLE"))?;
+        for (name, block) in symgen.iter() {
+            let space = Self::address_space(&name.val);
+            for s in block.iter_realized(version).filter(|s| !s.inlined) {
+                Self::write_symbols(&mut wtr, space.as_deref(), &s)?;
+                count += 1;
+            }
+        }
+        wtr.write(writer::XmlEvent::end_element())?;
+
+        wtr.write(writer::XmlEvent::end_element())?;
+        writeln!(wtr.inner_mut())?;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn get_test_xml() -> String {
+        String::from(
+            r#"<?xml version="1.0" standalone="yes"?>
+<PROGRAM NAME="arm9.bin" IMAGE_BASE="02000000">
+    <MEMORY_MAP>
+        <MEMORY_SECTION NAME="arm9" START_ADDR="02000000" LENGTH="0x100000" PERMISSIONS="rwx" />
+        <MEMORY_SECTION NAME="overlay11" START_ADDR="overlay_11::022dc240" LENGTH="0x1000" PERMISSIONS="rwx" />
+    </MEMORY_MAP>
+    <FUNCTIONS>
+        <FUNCTION ENTRY_POINT="02000800" NAME="main" LIBRARY_FUNCTION="n">
+            <ADDRESS_RANGE START="02000800" END="0200083f" />
+        </FUNCTION>
+        <FUNCTION ENTRY_POINT="overlay_11::022dc300" NAME="OvFunction" LIBRARY_FUNCTION="n">
+        </FUNCTION>
+    </FUNCTIONS>
+    <SYMBOL_TABLE>
+        <SYMBOL ADDRESS="02000800" NAME="main" TYPE="global" SOURCE_TYPE="USER_DEFINED" PRIMARY="y" />
+        <SYMBOL ADDRESS="02001000" NAME="SOME_DATA" TYPE="global" SOURCE_TYPE="USER_DEFINED" PRIMARY="y" />
+        <SYMBOL ADDRESS="02002000" NAME="DAT_02002000" TYPE="global" SOURCE_TYPE="DEFAULT" PRIMARY="y" />
+        <SYMBOL ADDRESS="03000000" NAME="OUTSIDE" TYPE="global" SOURCE_TYPE="IMPORTED" PRIMARY="y" />
+    </SYMBOL_TABLE>
+</PROGRAM>
+"#,
+        )
+    }
+
+    fn symbol(name: &str, address: Uint, length: Option<Uint>) -> Symbol {
+        Symbol {
+            name: name.to_string(),
+            deprecated_aliases: None,
+            address: MaybeVersionDep::Common(address.into()),
+            length: length.map(MaybeVersionDep::Common),
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_address() {
+        assert_eq!(
+            Address::parse("02000800").unwrap(),
+            Address {
+                space: None,
+                offset: 0x2000800
+            }
+        );
+        assert_eq!(
+            Address::parse("ram:0x02000800").unwrap(),
+            Address {
+                space: Some("ram".to_string()),
+                offset: 0x2000800
+            }
+        );
+        assert_eq!(
+            Address::parse("overlay_11::022DC240").unwrap(),
+            Address {
+                space: Some("overlay_11".to_string()),
+                offset: 0x22DC240
+            }
+        );
+        assert!(Address::parse("not_an_address").is_err());
+    }
+
+    #[test]
+    fn test_load_no_params() {
+        let contents = get_test_xml();
+        let params = LoadParams {
+            default_block_name: None,
+            default_symbol_type: None,
+            default_version_name: None,
+            version_renames: HashMap::new(),
+        };
+        let symbols: Vec<AddSymbol> = GhidraXmlLoader::load(contents.as_bytes(), &params)
+            .expect("Load failed")
+            .collect();
+        assert_eq!(
+            symbols,
+            vec![
+                AddSymbol {
+                    symbol: symbol("main", 0x2000800, Some(0x40)),
+                    stype: SymbolType::Function,
+                    block_name: Some("arm9".to_string()),
+                },
+                AddSymbol {
+                    symbol: symbol("OvFunction", 0x22DC300, None),
+                    stype: SymbolType::Function,
+                    block_name: Some("overlay11".to_string()),
+                },
+                AddSymbol {
+                    symbol: symbol("SOME_DATA", 0x2001000, None),
+                    stype: SymbolType::Data,
+                    block_name: Some("arm9".to_string()),
+                },
+                AddSymbol {
+                    symbol: symbol("OUTSIDE", 0x3000000, None),
+                    stype: SymbolType::Data,
+                    block_name: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_with_params() {
+        let contents = get_test_xml();
+        let params = LoadParams {
+            default_block_name: Some("main".to_string()),
+            default_symbol_type: None,
+            default_version_name: Some("v1".to_string()),
+            version_renames: HashMap::new(),
+        };
+        let symbols: Vec<AddSymbol> = GhidraXmlLoader::load(contents.as_bytes(), &params)
+            .expect("Load failed")
+            .collect();
+        assert_eq!(symbols.len(), 4);
+        assert_eq!(
+            symbols[0],
+            AddSymbol {
+                symbol: Symbol {
+                    name: "main".to_string(),
+                    deprecated_aliases: None,
+                    address: MaybeVersionDep::ByVersion(
+                        [(("v1", 0).into(), 0x2000800.into())].into()
+                    ),
+                    length: Some(MaybeVersionDep::ByVersion(
+                        [(("v1", 0).into(), 0x40)].into()
+                    )),
+                    description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
+                },
+                stype: SymbolType::Function,
+                block_name: Some("main".to_string()),
+            }
+        );
+        assert!(symbols
+            .iter()
+            .all(|s| s.block_name == Some("main".to_string())));
+    }
+
+    #[test]
+    fn test_load_invalid() {
+        let params = LoadParams {
+            default_block_name: None,
+            default_symbol_type: None,
+            default_version_name: None,
+            version_renames: HashMap::new(),
+        };
+        assert!(GhidraXmlLoader::load("<PROGRAM>".as_bytes(), &params).is_err());
+        assert!(GhidraXmlLoader::load(
+            r#"<PROGRAM><SYMBOL_TABLE><SYMBOL NAME="x" /></SYMBOL_TABLE></PROGRAM>"#.as_bytes(),
+            &params
+        )
+        .is_err());
+    }
+
+    fn get_test_symgen() -> SymGen {
+        SymGen::read(
+            r"
+            arm9:
+              versions:
+                - v1
+                - v2
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: main
+                  address: 0x2000800
+                  length: 0x40
+                - name: fn2
+                  deprecated_aliases:
+                    - OldFn2
+                  address:
+                    v1:
+                      - 0x2001000
+                      - 0x2001100
+                    v2: 0x2002000
+              data:
+                - name: SOME_DATA
+                  address:
+                    v1: 0x2003000
+                  length: 0x4
+            overlay11:
+              versions:
+                - v1
+                - v2
+              address:
+                v1: 0x22DC240
+                v2: 0x22DD8E0
+              length: 0x1000
+              functions:
+                - name: OvFunction
+                  address:
+                    v1: 0x22DC300
+                    v2: 0x22DD9A0
+              data:
+                - name: OV_DATA
+                  address:
+                    v2: 0x22DDA00
+        "
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    /// Counts the symbols of each type in each block.
+    fn count_by_block<I>(symbols: I) -> BTreeMap<(Option<String>, String), usize>
+    where
+        I: IntoIterator<Item = AddSymbol>,
+    {
+        let mut counts = BTreeMap::new();
+        for s in symbols {
+            *counts
+                .entry((s.block_name, format!("{:?}", s.stype)))
+                .or_insert(0) += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn test_address_space() {
+        assert_eq!(
+            GhidraXmlFormatter::address_space("overlay11"),
+            Some("overlay_11".to_string())
+        );
+        assert_eq!(GhidraXmlFormatter::address_space("arm9"), None);
+        assert_eq!(GhidraXmlFormatter::address_space("overlay"), None);
+        assert_eq!(GhidraXmlFormatter::address_space("overlay_x"), None);
+    }
+
+    #[test]
+    fn test_generate() {
+        let symgen = get_test_symgen();
+        let xml = GhidraXmlFormatter {}
+            .generate_str(&symgen, "v1")
+            .expect("generate failed");
+        assert!(xml.contains(
+            r#"<MEMORY_SECTION NAME="overlay11" START_ADDR="overlay_11::022dc240" LENGTH="0x1000" PERMISSIONS="rwx" />"#
+        ));
+        assert!(xml.contains(r#"<ADDRESS_RANGE START="02000800" END="0200083f" />"#));
+        assert!(xml.contains(
+            r#"<SYMBOL ADDRESS="02001100" NAME="OldFn2" TYPE="global" SOURCE_TYPE="USER_DEFINED" PRIMARY="n" />"#
+        ));
+    }
+
+    #[test]
+    fn test_generate_round_trip() {
+        let symgen = get_test_symgen();
+        let params = LoadParams {
+            default_block_name: None,
+            default_symbol_type: None,
+            default_version_name: None,
+            version_renames: HashMap::new(),
+        };
+        let block = |name: &str| Some(name.to_string());
+        let expected = [
+            (
+                "v1",
+                vec![
+                    // main, fn2 x2
+                    ((block("arm9"), "Function".to_string()), 3),
+                    // SOME_DATA, plus the fn2 aliases (which aren't listed as functions)
+                    ((block("arm9"), "Data".to_string()), 3),
+                    ((block("overlay11"), "Function".to_string()), 1),
+                ],
+            ),
+            (
+                "v2",
+                vec![
+                    ((block("arm9"), "Function".to_string()), 2),
+                    ((block("arm9"), "Data".to_string()), 1),
+                    ((block("overlay11"), "Function".to_string()), 1),
+                    ((block("overlay11"), "Data".to_string()), 1),
+                ],
+            ),
+        ];
+        for (version, counts) in expected {
+            let xml = GhidraXmlFormatter {}
+                .generate_str(&symgen, version)
+                .expect("generate failed");
+            let symbols = GhidraXmlLoader::load(xml.as_bytes(), &params).expect("Load failed");
+            assert_eq!(
+                count_by_block(symbols),
+                counts.into_iter().collect(),
+                "version {}",
+                version
+            );
+        }
+    }
+}
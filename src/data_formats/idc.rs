@@ -0,0 +1,248 @@
+//! An IDA Pro IDC script format (.idc).
+//!
+//! The script consists of a single `main()` function that names each symbol with `MakeName`.
+//! Function symbols are also defined as functions with `MakeFunction` (bounded by the symbol
+//! length, if there is one). Data symbols with a known length are defined as data items of that
+//! size. Descriptions become regular comments, and deprecated aliases become repeatable comments.
+//!
+//! # Example
+//! ```c
+//! #include <idc.idc>
+//!
+//! static main() {
+//!     MakeFunction(0x2000000, 0x2000040);
+//!     MakeName(0x2000000, "main");
+//!     MakeComm(0x2000000, "the main function");
+//!     MakeDword(0x2FFFFFC);
+//!     MakeName(0x2FFFFFC, "SOME_DATA");
+//! }
+//! ```
+
+use std::error::Error;
+use std::io::Write;
+
+use super::symgen_yml::{AddressNotation, Generate, RealizedSymbol, SymGen, Uint};
+
+/// Generator for the .idc format.
+#[derive(Default)]
+pub struct IdcFormatter {
+    /// How to write addresses and lengths.
+    pub address_notation: AddressNotation,
+}
+
+/// Escapes a string for use within a C-style string literal.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\x{:02X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl IdcFormatter {
+    /// Formats an address or length in the configured notation.
+    fn hex(&self, value: Uint) -> String {
+        self.address_notation.format(value)
+    }
+    /// Writes the name and comments for a symbol.
+    fn write_annotations<W: Write>(
+        &self,
+        writer: &mut W,
+        s: &RealizedSymbol,
+    ) -> std::io::Result<()> {
+        let address = self.hex(s.address);
+        writeln!(writer, "    MakeName({}, \"{}\");", address, escape(s.name))?;
+        if let Some(desc) = s.description {
+            writeln!(writer, "    MakeComm({}, \"{}\");", address, escape(desc))?;
+        }
+        if let Some(aliases) = s.deprecated_aliases.filter(|a| !a.is_empty()) {
+            writeln!(
+                writer,
+                "    MakeRptCmt({}, \"{}\");",
+                address,
+                escape(&format!("Deprecated aliases: {}", aliases.join(", ")))
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Generate for IdcFormatter {
+    fn generate<W: Write>(
+        &self,
+        mut writer: W,
+        symgen: &SymGen,
+        version: &str,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut count = 0;
+        writeln!(writer, "#include <idc.idc>")?;
+        writeln!(writer)?;
+        writeln!(writer, "static main() {{")?;
+        for f in symgen.functions_realized(version).filter(|s| !s.inlined) {
+            match f.length.filter(|&len| len > 0) {
+                Some(len) => writeln!(
+                    writer,
+                    "    MakeFunction({}, {});",
+                    self.hex(f.address),
+                    self.hex(f.address + len)
+                )?,
+                None => writeln!(
+                    writer,
+                    "    MakeFunction({}, BADADDR);",
+                    self.hex(f.address)
+                )?,
+            }
+            self.write_annotations(&mut writer, &f)?;
+            count += 1;
+        }
+        for d in symgen.data_realized(version).filter(|s| !s.inlined) {
+            match d.length {
+                Some(1) => writeln!(writer, "    MakeByte({});", self.hex(d.address))?,
+                Some(2) => writeln!(writer, "    MakeWord({});", self.hex(d.address))?,
+                Some(4) => writeln!(writer, "    MakeDword({});", self.hex(d.address))?,
+                Some(len) if len > 0 => writeln!(
+                    writer,
+                    "    MakeData({}, FF_BYTE, {}, BADADDR);",
+                    self.hex(d.address),
+                    self.hex(len)
+                )?,
+                _ => {}
+            }
+            self.write_annotations(&mut writer, &d)?;
+            count += 1;
+        }
+        writeln!(writer, "}}")?;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_symgen() -> SymGen {
+        SymGen::read(
+            r#"
+            main:
+              versions:
+                - v1
+                - v2
+              address:
+                v1: 0x2000000
+                v2: 0x2000000
+              length:
+                v1: 0x100000
+                v2: 0x100000
+              description: foo
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2000000
+                    v2: 0x2002000
+                  length:
+                    v1: 0x1000
+                    v2: 0x1000
+                  description: |-
+                    Says "hello"
+                    on two lines
+                - name: fn2
+                  deprecated_aliases:
+                    - OldFn2
+                  address:
+                    v1: 0x2001000
+              data:
+                - name: SOME_DATA
+                  address:
+                    v1: 0x2003000
+                    v2: 0x2004000
+                  length:
+                    v1: 0x4
+                    v2: 0x10
+                - name: OTHER_DATA
+                  address: 0x2005000
+                  description: C:\path
+        "#
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape("a \"quote\""), "a \\\"quote\\\"");
+        assert_eq!(escape("back\\slash"), "back\\\\slash");
+        assert_eq!(escape("line1\nline2\r\n"), "line1\\nline2\\r\\n");
+        assert_eq!(escape("tab\tbell\u{7}"), "tab\\tbell\\x07");
+    }
+
+    #[test]
+    fn test_generate() {
+        let symgen = get_test_symgen();
+        let f = IdcFormatter::default();
+        assert_eq!(
+            f.generate_str(&symgen, "v1").expect("generate failed"),
+            r#"#include <idc.idc>
+
+static main() {
+    MakeFunction(0x2000000, 0x2001000);
+    MakeName(0x2000000, "fn1");
+    MakeComm(0x2000000, "Says \"hello\"\non two lines");
+    MakeFunction(0x2001000, BADADDR);
+    MakeName(0x2001000, "fn2");
+    MakeRptCmt(0x2001000, "Deprecated aliases: OldFn2");
+    MakeDword(0x2003000);
+    MakeName(0x2003000, "SOME_DATA");
+    MakeName(0x2005000, "OTHER_DATA");
+    MakeComm(0x2005000, "C:\\path");
+}
+"#
+        );
+        assert_eq!(
+            f.generate_str(&symgen, "v2").expect("generate failed"),
+            r#"#include <idc.idc>
+
+static main() {
+    MakeFunction(0x2002000, 0x2003000);
+    MakeName(0x2002000, "fn1");
+    MakeComm(0x2002000, "Says \"hello\"\non two lines");
+    MakeData(0x2004000, FF_BYTE, 0x10, BADADDR);
+    MakeName(0x2004000, "SOME_DATA");
+    MakeName(0x2005000, "OTHER_DATA");
+    MakeComm(0x2005000, "C:\\path");
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_generate_address_notation() {
+        let symgen = get_test_symgen();
+        let f = IdcFormatter {
+            address_notation: AddressNotation::PrefixDollar,
+        };
+        assert_eq!(
+            f.generate_str(&symgen, "v2").expect("generate failed"),
+            r#"#include <idc.idc>
+
+static main() {
+    MakeFunction($2002000, $2003000);
+    MakeName($2002000, "fn1");
+    MakeComm($2002000, "Says \"hello\"\non two lines");
+    MakeData($2004000, FF_BYTE, $10, BADADDR);
+    MakeName($2004000, "SOME_DATA");
+    MakeName($2005000, "OTHER_DATA");
+    MakeComm($2005000, "C:\\path");
+}
+"#
+        );
+    }
+}
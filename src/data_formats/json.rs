@@ -2,7 +2,8 @@
 //!
 //! The JSON file contains a single array. Each element is a symbol, and contains the properties
 //! "type" (which can be "function" or "data"), "name", "address" (integer),
-//! optional "length" (integer), and optional "description".
+//! optional "length" (integer), optional "description", optional "deprecated_aliases"
+//! (array of strings), and "block" (the name of the block containing the symbol).
 //!
 //! # Example
 //! Note: The following example shows pretty-printed JSON for readability. However, the actual
@@ -13,18 +14,21 @@
 //!         "type": "function",
 //!         "name": "main",
 //!         "address": 33554432,
-//!         "description": "the main function"
+//!         "description": "the main function",
+//!         "block": "arm9"
 //!     },
 //!     {
 //!         "type": "function",
 //!         "name": "function1",
-//!         "address": 37748736
+//!         "address": 37748736,
+//!         "block": "arm9"
 //!     },
 //!     {
 //!         "type": "data",
 //!         "name": "SOME_DATA",
 //!         "address": 50331647,
-//!         "length": 4
+//!         "length": 4,
+//!         "block": "arm9"
 //!     }
 //! ]
 //! ```
@@ -51,11 +55,14 @@ struct Entry<'a> {
     #[serde(rename(serialize = "type"))]
     stype: SymbolType,
     name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deprecated_aliases: Option<&'a [String]>,
     address: Uint,
     #[serde(skip_serializing_if = "Option::is_none")]
     length: Option<Uint>,
     #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<&'a str>,
+    block: &'a str,
 }
 
 impl Generate for JsonFormatter {
@@ -64,11 +71,16 @@ impl Generate for JsonFormatter {
         mut writer: W,
         symgen: &SymGen,
         version: &str,
-    ) -> Result<(), Box<dyn Error>> {
-        let mut needs_comma = false;
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut count = 0;
         writer.write_all(b"[")?;
-        for f in symgen.functions_realized(version) {
-            if needs_comma {
+        let functions = symgen.iter().flat_map(|(bname, b)| {
+            b.functions_realized(version)
+                .filter(|s| !s.inlined)
+                .map(move |s| (bname, s))
+        });
+        for (bname, f) in functions {
+            if count > 0 {
                 writer.write_all(b",")?;
             }
             serde_json::to_writer(
@@ -76,15 +88,22 @@ impl Generate for JsonFormatter {
                 &Entry {
                     stype: SymbolType::Function,
                     name: f.name,
+                    deprecated_aliases: f.deprecated_aliases,
                     address: f.address,
                     length: f.length,
                     description: f.description,
+                    block: &bname.val,
                 },
             )?;
-            needs_comma = true;
+            count += 1;
         }
-        for d in symgen.data_realized(version) {
-            if needs_comma {
+        let data = symgen.iter().flat_map(|(bname, b)| {
+            b.data_realized(version)
+                .filter(|s| !s.inlined)
+                .map(move |s| (bname, s))
+        });
+        for (bname, d) in data {
+            if count > 0 {
                 writer.write_all(b",")?;
             }
             serde_json::to_writer(
@@ -92,15 +111,17 @@ impl Generate for JsonFormatter {
                 &Entry {
                     stype: SymbolType::Data,
                     name: d.name,
+                    deprecated_aliases: d.deprecated_aliases,
                     address: d.address,
                     length: d.length,
                     description: d.description,
+                    block: &bname.val,
                 },
             )?;
-            needs_comma = true;
+            count += 1;
         }
         writer.write_all(b"]")?;
-        Ok(())
+        Ok(count)
     }
 }
 
@@ -132,6 +153,8 @@ mod tests {
                     v2: 0x1000
                   description: bar
                 - name: fn2
+                  deprecated_aliases:
+                    - old_fn2
                   address:
                     v1:
                       - 0x2001000
@@ -164,24 +187,30 @@ mod tests {
                     "name": "fn1",
                     "address": 33554432,
                     "length": 4096,
-                    "description": "bar"
+                    "description": "bar",
+                    "block": "main"
                 },
                 {
                     "type": "function",
                     "name": "fn2",
-                    "address": 33558528
+                    "deprecated_aliases": ["old_fn2"],
+                    "address": 33558528,
+                    "block": "main"
                 },
                 {
                     "type": "function",
                     "name": "fn2",
-                    "address": 33562624
+                    "deprecated_aliases": ["old_fn2"],
+                    "address": 33562624,
+                    "block": "main"
                 },
                 {
                     "type": "data",
                     "name": "SOME_DATA",
                     "address": 33566720,
                     "length": 4096,
-                    "description": "baz"
+                    "description": "baz",
+                    "block": "main"
                 }
             ]"#
             .replace(" ", "")
@@ -195,23 +224,67 @@ mod tests {
                     "name": "fn1",
                     "address": 33562624,
                     "length": 4096,
-                    "description": "bar"
+                    "description": "bar",
+                    "block": "main"
                 },
                 {
                     "type": "function",
                     "name": "fn2",
-                    "address": 33566720
+                    "deprecated_aliases": ["old_fn2"],
+                    "address": 33566720,
+                    "block": "main"
                 },
                 {
                     "type": "data",
                     "name": "SOME_DATA",
                     "address": 33570816,
                     "length": 8192,
-                    "description": "baz"
+                    "description": "baz",
+                    "block": "main"
                 }
             ]"#
             .replace(" ", "")
             .replace("\n", "")
         );
     }
+
+    #[test]
+    fn test_generate_blocks() {
+        let symgen = SymGen::read(
+            r"
+            main:
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address: 0x2000000
+              data:
+                - name: SOME_DATA
+                  address: 0x2003000
+            other:
+              address: 0x2100000
+              length: 0x100000
+              functions:
+                - name: fn2
+                  address: 0x2100000
+              data: []
+        "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        let f = JsonFormatter {};
+        let entries: serde_json::Value =
+            serde_json::from_str(&f.generate_str(&symgen, "").expect("generate failed"))
+                .expect("invalid JSON");
+        let blocks: Vec<(&str, &str)> = entries
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| (e["name"].as_str().unwrap(), e["block"].as_str().unwrap()))
+            .collect();
+        assert_eq!(
+            blocks,
+            vec![("fn1", "main"), ("fn2", "other"), ("SOME_DATA", "main")]
+        );
+    }
 }
@@ -0,0 +1,199 @@
+//! A GNU ld linker script fragment format (.ld).
+//!
+//! Each symbol is defined as an absolute symbol with a `PROVIDE` statement, regardless of whether
+//! it's a function or data. Each deprecated alias is defined as another symbol with the same
+//! value. Names that aren't plain identifiers are quoted.
+//!
+//! A linker symbol can only have one address, so symbols with multiple addresses are an error,
+//! unless [`LdFormatter::first_address_only`] is set, in which case only the first address of each
+//! symbol is kept (with a warning for each address skipped).
+//!
+//! # Example
+//! ```text
+//! PROVIDE(main = 0x2000000);
+//! PROVIDE(function1 = 0x2400000);
+//! PROVIDE(OldFunction1 = function1);
+//! PROVIDE(SOME_DATA = 0x2FFFFFF);
+//! ```
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Write;
+
+use super::symgen_yml::{AddressNotation, Generate, SymGen, Uint};
+
+/// Generator for the .ld format.
+#[derive(Default)]
+pub struct LdFormatter {
+    /// Whether to keep only the first address of symbols with multiple addresses, rather than
+    /// failing.
+    pub first_address_only: bool,
+    /// Whether to suppress the warnings printed for skipped addresses.
+    pub quiet: bool,
+    /// How to write addresses.
+    pub address_notation: AddressNotation,
+}
+
+/// Quotes a symbol name if it isn't a plain identifier.
+fn quote_name(name: &str) -> Cow<'_, str> {
+    let plain = name.chars().enumerate().all(|(i, c)| {
+        c.is_ascii_alphabetic() || "_.$".contains(c) || (i > 0 && c.is_ascii_digit())
+    });
+    if plain && !name.is_empty() {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(format!("\"{}\"", name.replace('"', "\\\"")))
+    }
+}
+
+impl Generate for LdFormatter {
+    fn generate<W: Write>(
+        &self,
+        mut writer: W,
+        symgen: &SymGen,
+        version: &str,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut count = 0;
+        let mut defined: HashMap<&str, Uint> = HashMap::new();
+        for s in symgen.symbols_realized(version).filter(|s| !s.inlined) {
+            if let Some(&prev) = defined.get(s.name) {
+                if !self.first_address_only {
+                    return Err(format!(
+                        "symbol \"{}\" has multiple addresses ({:#X}, {:#X}), but a linker symbol can only have one address",
+                        s.name, prev, s.address
+                    )
+                    .into());
+                }
+                if !self.quiet {
+                    eprintln!(
+                        "Warning: skipped address {:#X} of symbol \"{}\" (only the first address, {:#X}, was kept).",
+                        s.address, s.name, prev
+                    );
+                }
+                continue;
+            }
+            defined.insert(s.name, s.address);
+            let name = quote_name(s.name);
+            writeln!(
+                writer,
+                "PROVIDE({} = {});",
+                name,
+                self.address_notation.format(s.address)
+            )?;
+            for alias in s.deprecated_aliases.unwrap_or_default() {
+                writeln!(writer, "PROVIDE({} = {});", quote_name(alias), name)?;
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_symgen() -> SymGen {
+        SymGen::read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address:
+                v1: 0x2000000
+                v2: 0x2000000
+              length:
+                v1: 0x100000
+                v2: 0x100000
+              description: foo
+              functions:
+                - name: fn1
+                  deprecated_aliases:
+                    - OldFn1
+                    - Ns::Fn1
+                  address:
+                    v1: 0x2000000
+                    v2: 0x2002000
+                - name: fn2
+                  address:
+                    v1:
+                      - 0x2001FFF
+                      - 0x2002000
+                    v2: 0x2003000
+              data:
+                - name: SOME_DATA
+                  address:
+                    v1: 0x2003000
+                    v2: 0x2004000
+        "
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    #[test]
+    fn test_quote_name() {
+        assert_eq!(quote_name("fn_1.part$"), Cow::Borrowed("fn_1.part$"));
+        assert_eq!(quote_name("1fn"), "\"1fn\"");
+        assert_eq!(quote_name("Ns::Fn1"), "\"Ns::Fn1\"");
+    }
+
+    #[test]
+    fn test_generate() {
+        let symgen = get_test_symgen();
+        let f = LdFormatter::default();
+        assert_eq!(
+            f.generate_str(&symgen, "v2").expect("generate failed"),
+            r#"PROVIDE(fn1 = 0x2002000);
+PROVIDE(OldFn1 = fn1);
+PROVIDE("Ns::Fn1" = fn1);
+PROVIDE(fn2 = 0x2003000);
+PROVIDE(SOME_DATA = 0x2004000);
+"#
+        );
+    }
+
+    #[test]
+    fn test_generate_multiple_addresses() {
+        let symgen = get_test_symgen();
+        let err = LdFormatter::default()
+            .generate_str(&symgen, "v1")
+            .expect_err("generate should fail with multiple addresses");
+        assert!(err.to_string().contains("\"fn2\" has multiple addresses"));
+
+        let f = LdFormatter {
+            first_address_only: true,
+            quiet: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            f.generate_str(&symgen, "v1").expect("generate failed"),
+            r#"PROVIDE(fn1 = 0x2000000);
+PROVIDE(OldFn1 = fn1);
+PROVIDE("Ns::Fn1" = fn1);
+PROVIDE(fn2 = 0x2001FFF);
+PROVIDE(SOME_DATA = 0x2003000);
+"#
+        );
+    }
+
+    #[test]
+    fn test_generate_address_notation() {
+        let symgen = get_test_symgen();
+        let f = LdFormatter {
+            address_notation: AddressNotation::SuffixH,
+            ..Default::default()
+        };
+        assert_eq!(
+            f.generate_str(&symgen, "v2").expect("generate failed"),
+            r#"PROVIDE(fn1 = 2002000h);
+PROVIDE(OldFn1 = fn1);
+PROVIDE("Ns::Fn1" = fn1);
+PROVIDE(fn2 = 2003000h);
+PROVIDE(SOME_DATA = 2004000h);
+"#
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! The No$gba symbol file format (.sym), as read by No$gba and many other DS emulators.
+//!
+//! Each line consists of a memory address (as a raw, 8-character hexadecimal number) and a name
+//! separated by a space, and lines are sorted by address. Unlike the plain [`sym`] format, each
+//! deprecated alias gets its own line at the same address as the symbol. No$gba uses a flat
+//! address space, so overlay symbols are listed at their raw addresses. Optionally, each
+//! description can be appended to its line as a `;` comment.
+//!
+//! # Example
+//! ```text
+//! 02000000 main ; the main function
+//! 02400000 function1
+//! 02400000 OldFunction1
+//! 02FFFFFF SOME_DATA
+//! ```
+//!
+//! [`sym`]: super::sym
+
+use std::error::Error;
+use std::io::Write;
+
+use super::symgen_yml::{AddressNotation, Generate, SymGen, Uint};
+
+/// Generator for the No$gba .sym format.
+pub struct NoCashSymFormatter {
+    /// Whether to append descriptions to lines as comments.
+    pub comment_descriptions: bool,
+    /// How to write addresses. No$gba itself expects [`AddressNotation::Bare`].
+    pub address_notation: AddressNotation,
+}
+
+impl Default for NoCashSymFormatter {
+    fn default() -> Self {
+        Self {
+            comment_descriptions: false,
+            address_notation: AddressNotation::Bare,
+        }
+    }
+}
+
+/// A single line in the symbol file.
+struct Line<'a> {
+    address: Uint,
+    name: &'a str,
+    description: Option<&'a str>,
+}
+
+/// Collapses a (possibly multi-line) description onto a single line.
+fn one_line(desc: &str) -> String {
+    desc.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl Generate for NoCashSymFormatter {
+    fn generate<W: Write>(
+        &self,
+        mut writer: W,
+        symgen: &SymGen,
+        version: &str,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut count = 0;
+        let mut lines = Vec::new();
+        for s in symgen.symbols_realized(version).filter(|s| !s.inlined) {
+            let aliases = s.deprecated_aliases.unwrap_or_default();
+            for name in Some(s.name)
+                .into_iter()
+                .chain(aliases.iter().map(|a| a.as_str()))
+            {
+                lines.push(Line {
+                    address: s.address,
+                    name,
+                    description: s.description,
+                });
+            }
+            count += 1;
+        }
+        // Stable sort, so lines with the same address keep their original order
+        lines.sort_by_key(|l| l.address);
+        for l in lines {
+            write!(
+                writer,
+                "{} {}",
+                self.address_notation.format_padded(l.address, 8),
+                l.name
+            )?;
+            match l.description.map(one_line) {
+                Some(desc) if self.comment_descriptions && !desc.is_empty() => {
+                    writeln!(writer, " ; {}", desc)?
+                }
+                _ => writeln!(writer)?,
+            }
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_symgen() -> SymGen {
+        SymGen::read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  deprecated_aliases:
+                    - OldFn1
+                  address:
+                    v1: 0x2002000
+                    v2: 0x2000000
+                  description: |-
+                    Does a thing.
+                    Then another.
+                - name: fn2
+                  address:
+                    v1:
+                      - 0x2001FFF
+                      - 0x2003000
+                  description: Second function
+              data:
+                - name: SOME_DATA
+                  address: 0x2000100
+            overlay11:
+              address: 0x22DC240
+              length: 0x1000
+              functions:
+                - name: OverlayFn
+                  address: 0x22DC240
+              data: []
+        "
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    #[test]
+    fn test_one_line() {
+        assert_eq!(
+            one_line("  first line\n  second\tline  "),
+            "first line second line"
+        );
+        assert_eq!(one_line("\n"), "");
+    }
+
+    #[test]
+    fn test_generate() {
+        let symgen = get_test_symgen();
+        let f = NoCashSymFormatter::default();
+        assert_eq!(
+            f.generate_str(&symgen, "v1").expect("generate failed"),
+            "02000100 SOME_DATA
+02001FFF fn2
+02002000 fn1
+02002000 OldFn1
+02003000 fn2
+022DC240 OverlayFn
+"
+        );
+        assert_eq!(
+            f.generate_str(&symgen, "v2").expect("generate failed"),
+            "02000000 fn1
+02000000 OldFn1
+02000100 SOME_DATA
+022DC240 OverlayFn
+"
+        );
+    }
+
+    #[test]
+    fn test_generate_multiple_addresses() {
+        let symgen = get_test_symgen();
+        let f = NoCashSymFormatter::default();
+        let mut contents = Vec::new();
+        let count = f
+            .generate(&mut contents, &symgen, "v1")
+            .expect("generate failed");
+        // fn1, fn2 (x2), SOME_DATA, OverlayFn
+        assert_eq!(count, 5);
+        let fn2_lines: Vec<&str> = std::str::from_utf8(&contents)
+            .unwrap()
+            .lines()
+            .filter(|l| l.ends_with(" fn2"))
+            .collect();
+        assert_eq!(fn2_lines, vec!["02001FFF fn2", "02003000 fn2"]);
+    }
+
+    #[test]
+    fn test_generate_comment_descriptions() {
+        let symgen = get_test_symgen();
+        let f = NoCashSymFormatter {
+            comment_descriptions: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            f.generate_str(&symgen, "v1").expect("generate failed"),
+            "02000100 SOME_DATA
+02001FFF fn2 ; Second function
+02002000 fn1 ; Does a thing. Then another.
+02002000 OldFn1 ; Does a thing. Then another.
+02003000 fn2 ; Second function
+022DC240 OverlayFn
+"
+        );
+    }
+
+    #[test]
+    fn test_generate_address_notation() {
+        let symgen = get_test_symgen();
+        let f = NoCashSymFormatter {
+            address_notation: AddressNotation::Prefix0x,
+            ..Default::default()
+        };
+        assert_eq!(
+            f.generate_str(&symgen, "v2").expect("generate failed"),
+            "0x02000000 fn1
+0x02000000 OldFn1
+0x02000100 SOME_DATA
+0x022DC240 OverlayFn
+"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! The symbol table format printed by `objdump -t` (e.g., `arm-none-eabi-objdump -t arm9.elf`).
+//!
+//! The symbol table is preceded by a header, which ends with a `SYMBOL TABLE:` line. Each symbol
+//! is listed on a separate line, and consists of a value (as hexadecimal), a 7-character group of
+//! flags, a section name, a size (as hexadecimal), and a name. The section name and the size are
+//! separated by a tab (shown as spaces below); the other fields are separated by spaces. Among
+//! the flags, `F` marks a function, `O` marks a data object, `d` marks a debugging symbol, and
+//! `f` marks a file name.
+//!
+//! Debugging symbols, file names, and symbols in the absolute (`*ABS*`) or undefined (`*UND*`)
+//! sections are skipped, as are ARM mapping symbols (`$a`, `$d`, `$t`). Symbols that are neither
+//! functions nor data objects are only loaded if a default symbol type is specified. The Thumb bit
+//! (the low bit of the value) is cleared for functions.
+//!
+//! # Example
+//! ```text
+//! arm9.elf:     file format elf32-littlearm
+//!
+//! SYMBOL TABLE:
+//! 02000000 l    d  .text    00000000 .text
+//! 00000000 l    df *ABS*    00000000 main.c
+//! 02000800 g     F .text    00000040 main
+//! 02001000 g     O .data    00000004 SOME_DATA
+//! ```
+
+use std::error::Error;
+use std::io::{BufRead, BufReader, Read};
+use std::vec::IntoIter;
+
+use super::symgen_yml::{AddSymbol, Load, LoadParams, MaybeVersionDep, Symbol, SymbolType, Uint};
+
+/// Sections that don't correspond to a memory location within the binary.
+const NON_MEMORY_SECTIONS: [&str; 2] = ["*ABS*", "*UND*"];
+/// Symbol visibility markers that can precede the name of an ELF symbol.
+const VISIBILITY_MARKERS: [&str; 3] = [".hidden ", ".internal ", ".protected "];
+
+#[derive(Debug, PartialEq, Eq)]
+struct Entry {
+    name: String,
+    value: Uint,
+    size: Uint,
+    stype: Option<SymbolType>, // None means an untyped symbol
+}
+
+impl Entry {
+    /// Parses a symbol table line. Returns [`None`] if the line isn't a symbol table entry.
+    fn parse(line: &str) -> Option<Self> {
+        let (value, rest) = line.split_once(' ')?;
+        let value = Uint::from_str_radix(value, 16).ok()?;
+        let flags = rest.get(..7)?.as_bytes();
+        let (section, rest) = rest.get(7..)?.trim_start().split_once('\t')?;
+        let (size, name) = rest.split_once(' ')?;
+        let size = Uint::from_str_radix(size, 16).ok()?;
+        let name = name.trim();
+        let name = VISIBILITY_MARKERS
+            .iter()
+            .find_map(|m| name.strip_prefix(m))
+            .unwrap_or(name);
+
+        if flags[5] == b'd' || flags[6] == b'f' || NON_MEMORY_SECTIONS.contains(&section) {
+            return None;
+        }
+        if name.is_empty() || name.starts_with('$') {
+            return None;
+        }
+        let stype = match flags[6] {
+            b'F' => Some(SymbolType::Function),
+            b'O' => Some(SymbolType::Data),
+            _ => None,
+        };
+        Some(Self {
+            name: name.to_string(),
+            // Thumb function values have the low bit set, which isn't part of the address
+            value: match stype {
+                Some(SymbolType::Function) => value & !1,
+                _ => value,
+            },
+            size,
+            stype,
+        })
+    }
+}
+
+/// Loader for the symbol table format printed by `objdump -t`.
+pub struct ObjdumpLoader {
+    entries: IntoIter<(Entry, SymbolType)>,
+    params: LoadParams,
+}
+
+impl ObjdumpLoader {
+    fn read<R: Read>(
+        rdr: R,
+        params: &LoadParams,
+    ) -> Result<Vec<(Entry, SymbolType)>, Box<dyn Error + Send + Sync>> {
+        let mut symbols = Vec::new();
+        let mut in_table = false;
+        for line in BufReader::new(rdr).lines() {
+            let line = line?;
+            if !in_table {
+                in_table = line.trim() == "SYMBOL TABLE:";
+                continue;
+            }
+            if let Some(mut entry) = Entry::parse(&line) {
+                if let Some(stype) = entry.stype.take().or(params.default_symbol_type) {
+                    symbols.push((entry, stype));
+                }
+            }
+        }
+        if !in_table {
+            return Err("no symbol table found in objdump output".into());
+        }
+        Ok(symbols)
+    }
+}
+
+impl Iterator for ObjdumpLoader {
+    type Item = AddSymbol;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(entry, stype)| AddSymbol {
+            symbol: Symbol {
+                name: entry.name,
+                deprecated_aliases: None,
+                address: match &self.params.default_version_name {
+                    Some(vers) => MaybeVersionDep::ByVersion(
+                        [(vers.as_str().into(), entry.value.into())].into(),
+                    ),
+                    None => MaybeVersionDep::Common(entry.value.into()),
+                },
+                length: if entry.size > 0 {
+                    Some(match &self.params.default_version_name {
+                        Some(vers) => {
+                            MaybeVersionDep::ByVersion([(vers.as_str().into(), entry.size)].into())
+                        }
+                        None => MaybeVersionDep::Common(entry.size),
+                    })
+                } else {
+                    None
+                },
+                description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
+            },
+            stype,
+            block_name: self.params.default_block_name.clone(),
+        })
+    }
+}
+
+impl Load for ObjdumpLoader {
+    type Source = Self;
+
+    fn load<R: Read>(
+        rdr: R,
+        params: &LoadParams,
+    ) -> Result<Self::Source, Box<dyn Error + Send + Sync>> {
+        Ok(Self {
+            entries: Self::read(rdr, params)?.into_iter(),
+            params: params.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn get_test_objdump() -> String {
+        String::from(
+            "
+arm9.elf:     file format elf32-littlearm
+
+SYMBOL TABLE:
+02000000 l    d  .text\t00000000 .text
+00000000 l    df *ABS*\t00000000 main.c
+02000000 l       .text\t00000000 $a
+02000400 l       .text\t00000000 local_label
+02000800 g     F .text\t00000040 main
+02000840 l     F .text\t00000010 .hidden helper
+02001000 g     O .data\t00000004 SOME_DATA
+00000010 g       *ABS*\t00000000 ABS_VALUE
+00000000         *UND*\t00000000 undefined_fn
+",
+        )
+    }
+
+    fn symbol(name: &str, address: Uint, length: Option<Uint>) -> Symbol {
+        Symbol {
+            name: name.to_string(),
+            deprecated_aliases: None,
+            address: MaybeVersionDep::Common(address.into()),
+            length: length.map(MaybeVersionDep::Common),
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            Entry::parse("02000800 g     F .text\t00000040 main"),
+            Some(Entry {
+                name: "main".to_string(),
+                value: 0x2000800,
+                size: 0x40,
+                stype: Some(SymbolType::Function),
+            })
+        );
+        assert_eq!(
+            Entry::parse("02000901 g     F .text\t00000020 thumb_fn"),
+            Some(Entry {
+                name: "thumb_fn".to_string(),
+                value: 0x2000900,
+                size: 0x20,
+                stype: Some(SymbolType::Function),
+            })
+        );
+        assert_eq!(
+            Entry::parse("02001001 g     O .data\t00000001 ODD_DATA"),
+            Some(Entry {
+                name: "ODD_DATA".to_string(),
+                value: 0x2001001,
+                size: 0x1,
+                stype: Some(SymbolType::Data),
+            })
+        );
+        assert_eq!(Entry::parse("02000000 l    d  .text\t00000000 .text"), None);
+        assert_eq!(Entry::parse("SYMBOL TABLE:"), None);
+        assert_eq!(Entry::parse(""), None);
+    }
+
+    #[test]
+    fn test_load_no_params() {
+        let contents = get_test_objdump();
+        let params = LoadParams {
+            default_block_name: None,
+            default_symbol_type: None,
+            default_version_name: None,
+            version_renames: HashMap::new(),
+        };
+        let symbols: Vec<AddSymbol> = ObjdumpLoader::load(contents.as_bytes(), &params)
+            .expect("Load failed")
+            .collect();
+        assert_eq!(
+            symbols,
+            vec![
+                AddSymbol {
+                    symbol: symbol("main", 0x2000800, Some(0x40)),
+                    stype: SymbolType::Function,
+                    block_name: None,
+                },
+                AddSymbol {
+                    symbol: symbol("helper", 0x2000840, Some(0x10)),
+                    stype: SymbolType::Function,
+                    block_name: None,
+                },
+                AddSymbol {
+                    symbol: symbol("SOME_DATA", 0x2001000, Some(0x4)),
+                    stype: SymbolType::Data,
+                    block_name: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_with_params() {
+        let contents = get_test_objdump();
+        let params = LoadParams {
+            default_block_name: Some("main".to_string()),
+            default_symbol_type: Some(SymbolType::Data),
+            default_version_name: Some("v1".to_string()),
+            version_renames: HashMap::new(),
+        };
+        let symbols: Vec<AddSymbol> = ObjdumpLoader::load(contents.as_bytes(), &params)
+            .expect("Load failed")
+            .collect();
+        assert_eq!(symbols.len(), 4);
+        assert_eq!(
+            symbols[0],
+            AddSymbol {
+                symbol: Symbol {
+                    name: "local_label".to_string(),
+                    deprecated_aliases: None,
+                    address: MaybeVersionDep::ByVersion(
+                        [(("v1", 0).into(), 0x2000400.into())].into()
+                    ),
+                    length: None,
+                    description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
+                },
+                stype: SymbolType::Data,
+                block_name: Some("main".to_string()),
+            }
+        );
+        assert_eq!(
+            symbols[1].symbol.length,
+            Some(MaybeVersionDep::ByVersion(
+                [(("v1", 0).into(), 0x40)].into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_load_no_symbol_table() {
+        let params = LoadParams {
+            default_block_name: None,
+            default_symbol_type: None,
+            default_version_name: None,
+            version_renames: HashMap::new(),
+        };
+        assert!(ObjdumpLoader::load("not objdump output".as_bytes(), &params).is_err());
+    }
+}
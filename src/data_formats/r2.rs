@@ -0,0 +1,214 @@
+//! A radare2/rizin command script format (.r2).
+//!
+//! The script can be loaded with `. script.r2` (or `r2 -i script.r2`). Each symbol gets a flag
+//! named `sym.<name>` (sized by the symbol length, if there is one). Function symbols are also
+//! analyzed as functions with `af`, and data symbols with a known length are marked as data with
+//! `Cd`. Descriptions become comments.
+//!
+//! Characters that aren't allowed in flag names are replaced with underscores, and a warning is
+//! printed for each name that gets changed.
+//!
+//! # Example
+//! ```text
+//! f sym.main 0x40 @ 0x2000000
+//! CCu "the main function" @ 0x2000000
+//! af main 0x2000000
+//! f sym.SOME_DATA 0x4 @ 0x2FFFFFC
+//! Cd 0x4 @ 0x2FFFFFC
+//! ```
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::io::Write;
+
+use super::symgen_yml::{AddressNotation, Generate, RealizedSymbol, SymGen, Uint};
+
+/// Generator for the .r2 format.
+#[derive(Default)]
+pub struct RadareFormatter {
+    /// How to write addresses and lengths.
+    pub address_notation: AddressNotation,
+}
+
+/// Replaces characters that radare2 doesn't allow in flag names with underscores.
+fn sanitize_name(name: &str) -> Cow<'_, str> {
+    let allowed = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '.';
+    if name.chars().all(allowed) {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(
+            name.chars()
+                .map(|c| if allowed(c) { c } else { '_' })
+                .collect(),
+        )
+    }
+}
+
+/// Escapes a string for use within a double-quoted radare2 command argument.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\r', "")
+        .replace('\n', "\\n")
+}
+
+impl RadareFormatter {
+    /// Formats an address or length in the configured notation.
+    fn hex(&self, value: Uint) -> String {
+        self.address_notation.format(value)
+    }
+    /// Writes the flag and comment for a symbol, and returns the sanitized name.
+    fn write_flag<'a, W: Write>(
+        &self,
+        writer: &mut W,
+        s: &RealizedSymbol<'a>,
+    ) -> Result<Cow<'a, str>, Box<dyn Error + Send + Sync>> {
+        let name = sanitize_name(s.name);
+        if let Cow::Owned(sanitized) = &name {
+            eprintln!(
+                "Warning: symbol name \"{}\" is not a valid radare2 flag name, using \"{}\" instead.",
+                s.name, sanitized
+            );
+        }
+        match s.length.filter(|&len| len > 0) {
+            Some(len) => writeln!(
+                writer,
+                "f sym.{} {} @ {}",
+                name,
+                self.hex(len),
+                self.hex(s.address)
+            )?,
+            None => writeln!(writer, "f sym.{} @ {}", name, self.hex(s.address))?,
+        }
+        if let Some(desc) = s.description {
+            writeln!(writer, "CCu \"{}\" @ {}", escape(desc), self.hex(s.address))?;
+        }
+        Ok(name)
+    }
+}
+
+impl Generate for RadareFormatter {
+    fn generate<W: Write>(
+        &self,
+        mut writer: W,
+        symgen: &SymGen,
+        version: &str,
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut count = 0;
+        for f in symgen.functions_realized(version).filter(|s| !s.inlined) {
+            let name = self.write_flag(&mut writer, &f)?;
+            writeln!(writer, "af {} {}", name, self.hex(f.address))?;
+            count += 1;
+        }
+        for d in symgen.data_realized(version).filter(|s| !s.inlined) {
+            self.write_flag(&mut writer, &d)?;
+            if let Some(len) = d.length.filter(|&len| len > 0) {
+                writeln!(writer, "Cd {} @ {}", self.hex(len), self.hex(d.address))?;
+            }
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_symgen() -> SymGen {
+        SymGen::read(
+            r#"
+            main:
+              versions:
+                - v1
+                - v2
+              address:
+                v1: 0x2000000
+                v2: 0x2000000
+              length:
+                v1: 0x100000
+                v2: 0x100000
+              description: foo
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2000000
+                    v2: 0x2002000
+                  length:
+                    v1: 0x1000
+                    v2: 0x1000
+                  description: |-
+                    Says "hello"
+                    on two lines
+                - name: Ns::fn2
+                  address:
+                    v1: 0x2001000
+              data:
+                - name: SOME_DATA
+                  address:
+                    v1: 0x2003000
+                    v2: 0x2004000
+                  length:
+                    v1: 0x4
+                    v2: 0x10
+                - name: OTHER_DATA
+                  address: 0x2005000
+        "#
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(sanitize_name("fn_1.part"), Cow::Borrowed("fn_1.part"));
+        assert_eq!(sanitize_name("Ns::fn 2"), "Ns__fn_2");
+        assert_eq!(sanitize_name("fn$@"), "fn__");
+    }
+
+    #[test]
+    fn test_generate() {
+        let symgen = get_test_symgen();
+        let f = RadareFormatter::default();
+        assert_eq!(
+            f.generate_str(&symgen, "v1").expect("generate failed"),
+            r#"f sym.fn1 0x1000 @ 0x2000000
+CCu "Says \"hello\"\non two lines" @ 0x2000000
+af fn1 0x2000000
+f sym.Ns__fn2 @ 0x2001000
+af Ns__fn2 0x2001000
+f sym.SOME_DATA 0x4 @ 0x2003000
+Cd 0x4 @ 0x2003000
+f sym.OTHER_DATA @ 0x2005000
+"#
+        );
+        assert_eq!(
+            f.generate_str(&symgen, "v2").expect("generate failed"),
+            r#"f sym.fn1 0x1000 @ 0x2002000
+CCu "Says \"hello\"\non two lines" @ 0x2002000
+af fn1 0x2002000
+f sym.SOME_DATA 0x10 @ 0x2004000
+Cd 0x10 @ 0x2004000
+f sym.OTHER_DATA @ 0x2005000
+"#
+        );
+    }
+
+    #[test]
+    fn test_generate_address_notation() {
+        let symgen = get_test_symgen();
+        let f = RadareFormatter {
+            address_notation: AddressNotation::Bare,
+        };
+        assert_eq!(
+            f.generate_str(&symgen, "v2").expect("generate failed"),
+            r#"f sym.fn1 1000 @ 2002000
+CCu "Says \"hello\"\non two lines" @ 2002000
+af fn1 2002000
+f sym.SOME_DATA 10 @ 2004000
+Cd 10 @ 2004000
+f sym.OTHER_DATA @ 2005000
+"#
+        );
+    }
+}
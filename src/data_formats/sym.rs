@@ -45,18 +45,20 @@ impl Generate for SymFormatter {
         writer: W,
         symgen: &SymGen,
         version: &str,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let mut count = 0;
         let mut wtr = WriterBuilder::new()
             .delimiter(b' ')
             .has_headers(false)
             .from_writer(writer);
-        for s in symgen.symbols_realized(version) {
+        for s in symgen.symbols_realized(version).filter(|s| !s.inlined) {
             wtr.serialize(Entry {
                 address: s.address,
                 name: s.name,
             })?;
+            count += 1;
         }
-        Ok(())
+        Ok(count)
     }
 }
 
@@ -123,6 +125,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generate_zero_padding() {
+        let symgen = SymGen::read(
+            r"
+            main:
+              address: 0x0
+              length: 0x1000
+              functions:
+                - name: LowFn
+                  address: 0x100
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed");
+
+        let f = SymFormatter {};
+        assert_eq!(
+            f.generate_str(&symgen, "").expect("generate failed"),
+            "00000100 LowFn\n"
+        );
+    }
+
     #[test]
     fn test_generate_64bit() {
         let symgen = SymGen::read(
@@ -151,4 +176,17 @@ mod tests {
             "100000000 fn1\n02000000 fn2\nFFFFFFFFFF fn3\n"
         );
     }
+
+    #[test]
+    fn test_generate_skips_inlined() {
+        let mut symgen = get_test_symgen();
+        for block in symgen.blocks_mut() {
+            block.functions.get_mut(0).unwrap().inlined = Some(true);
+        }
+        let f = SymFormatter {};
+        assert_eq!(
+            f.generate_str(&symgen, "v2").expect("generate failed"),
+            "02003000 fn2\n02004000 SOME_DATA\n"
+        );
+    }
 }
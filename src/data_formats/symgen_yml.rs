@@ -75,5 +75,8 @@ pub mod bounds;
 
 pub use adapter::*;
 pub use error::*;
+pub use merge::{MergeConflict, MergeSummary};
 pub use symgen::*;
-pub use types::{Linkable, MaybeVersionDep, OrdString, OrderMap, Sort, Uint, Version, VersionDep};
+pub use types::{
+    AddressExpr, Linkable, MaybeVersionDep, OrdString, OrderMap, Sort, Uint, Version, VersionDep,
+};
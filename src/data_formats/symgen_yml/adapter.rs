@@ -1,31 +1,111 @@
 //! Adapter APIs to integrate the `resymgen` YAML format with other data formats
 //! (through the `gen` and `merge` commands).
 
+use std::collections::HashMap;
 use std::error::Error;
 use std::io::{Read, Write};
 
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
 use super::symgen::{SymGen, Symbol};
+use super::types::Uint;
 
 /// `Generate` implementers can convert a [`SymGen`] into a different data format.
 pub trait Generate {
     /// Write the contents of `symgen` for `version` to `writer` in the desired format.
+    ///
+    /// Returns the number of symbols written.
     fn generate<W: Write>(
         &self,
         writer: W,
         symgen: &SymGen,
         version: &str,
-    ) -> Result<(), Box<dyn Error>>;
+    ) -> Result<usize, Box<dyn Error + Send + Sync>>;
 
     /// Write the contents of `symgen` for `version` to a [`String`].
-    fn generate_str(&self, symgen: &SymGen, version: &str) -> Result<String, Box<dyn Error>> {
+    fn generate_str(
+        &self,
+        symgen: &SymGen,
+        version: &str,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
         let mut bytes = Vec::<u8>::new();
         self.generate(&mut bytes, symgen, version)?;
         Ok(String::from_utf8(bytes)?)
     }
 }
 
+/// Notations for writing hexadecimal addresses in generated output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressNotation {
+    /// `0x` prefix (e.g., `0x2012345`).
+    Prefix0x,
+    /// `$` prefix (e.g., `$2012345`).
+    PrefixDollar,
+    /// `h` suffix (e.g., `2012345h`).
+    SuffixH,
+    /// No prefix or suffix (e.g., `2012345`).
+    Bare,
+}
+
+impl Default for AddressNotation {
+    fn default() -> Self {
+        Self::Prefix0x
+    }
+}
+
+impl AddressNotation {
+    /// Returns the [`AddressNotation`] corresponding to the given `name`, if there is one.
+    pub fn from(name: &str) -> Option<Self> {
+        match name {
+            "0x" => Some(Self::Prefix0x),
+            "$" => Some(Self::PrefixDollar),
+            "h" => Some(Self::SuffixH),
+            "bare" => Some(Self::Bare),
+            _ => None,
+        }
+    }
+    /// Returns the name associated with the [`AddressNotation`].
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Prefix0x => "0x",
+            Self::PrefixDollar => "$",
+            Self::SuffixH => "h",
+            Self::Bare => "bare",
+        }
+    }
+    /// Returns an [`Iterator`] over all [`AddressNotation`] variants.
+    pub fn all() -> impl Iterator<Item = AddressNotation> {
+        [
+            Self::Prefix0x,
+            Self::PrefixDollar,
+            Self::SuffixH,
+            Self::Bare,
+        ]
+        .iter()
+        .copied()
+    }
+    /// Formats `value` as uppercase hexadecimal in this notation.
+    pub fn format(&self, value: Uint) -> String {
+        self.format_padded(value, 0)
+    }
+    /// Like [`AddressNotation::format()`], but zero-pads the digits to at least `width`
+    /// characters (not counting the prefix or suffix).
+    pub fn format_padded(&self, value: Uint, width: usize) -> String {
+        match self {
+            Self::Prefix0x => format!("0x{:0width$X}", value, width = width),
+            Self::PrefixDollar => format!("${:0width$X}", value, width = width),
+            Self::SuffixH => format!("{:0width$X}h", value, width = width),
+            Self::Bare => format!("{:0width$X}", value, width = width),
+        }
+    }
+}
+
 /// Types of symbols within a [`SymGen`].
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "lowercase")]
 pub enum SymbolType {
     Function,
     Data,
@@ -48,6 +128,9 @@ pub struct LoadParams {
     pub default_symbol_type: Option<SymbolType>,
     /// Default version name to assign to a symbol if none is present.
     pub default_version_name: Option<String>,
+    /// Version names to rename before merging, mapping names in the foreign data to names in the
+    /// [`SymGen`]. Renaming happens after the default version name is assigned.
+    pub version_renames: HashMap<String, String>,
 }
 
 /// `Load` implementers can read the contents of a [`Read`] type and produce a stream of
@@ -57,5 +140,31 @@ pub trait Load {
 
     /// Load data in some format from `rdr` and return an iterator of [`AddSymbol`]s based on
     /// the options specified in `params`.
-    fn load<R: Read>(rdr: R, params: &LoadParams) -> Result<Self::Source, Box<dyn Error>>;
+    fn load<R: Read>(
+        rdr: R,
+        params: &LoadParams,
+    ) -> Result<Self::Source, Box<dyn Error + Send + Sync>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_notation() {
+        let cases = [
+            (AddressNotation::Prefix0x, "0x2012345", "0x02012345"),
+            (AddressNotation::PrefixDollar, "$2012345", "$02012345"),
+            (AddressNotation::SuffixH, "2012345h", "02012345h"),
+            (AddressNotation::Bare, "2012345", "02012345"),
+        ];
+        for (notation, exp, exp_padded) in cases {
+            assert_eq!(notation.format(0x2012345), exp);
+            assert_eq!(notation.format_padded(0x2012345, 8), exp_padded);
+            assert_eq!(AddressNotation::from(notation.name()), Some(notation));
+        }
+        assert_eq!(AddressNotation::Prefix0x.format(0), "0x0");
+        assert_eq!(AddressNotation::default(), AddressNotation::Prefix0x);
+        assert_eq!(AddressNotation::all().count(), cases.len());
+    }
 }
@@ -33,6 +33,18 @@ fn bounds_check(
     true
 }
 
+/// Checks whether a given address lies within a given extent (an offset and an optional length).
+/// Extents without a length are unbounded above.
+pub fn contains_address(extent: (Uint, Option<Uint>), addr: Uint) -> bool {
+    bounds_check((addr, None), extent)
+}
+
+/// Checks whether a given extent lies entirely within a given bound (each an offset and an
+/// optional length). Bounds without a length are unbounded above.
+pub fn contains_extent(bound: (Uint, Option<Uint>), extent: (Uint, Option<Uint>)) -> bool {
+    bounds_check(extent, bound)
+}
+
 /// An type that can be checked against a range bound (addr, Option<len>) and potentially returns
 /// a [`BoundViolation`].
 trait Bounded {
@@ -127,7 +139,8 @@ fn extents_in_bounds<B: Bounded>(
 /// for the matching versions.
 ///
 /// If `symbol` is not explicitly version-dependent, and `all_versions` is provided, the check
-/// will be done for every given version.
+/// will be done for every given version. Any additional ranges occupied by `symbol` are also
+/// checked.
 ///
 /// Returns [`None`] on success, or a [`BoundViolation`] if the bound is found to be violated.
 pub fn symbol_in_bounds(
@@ -135,7 +148,12 @@ pub fn symbol_in_bounds(
     symbol: &Symbol,
     all_versions: &Option<Vec<Version>>,
 ) -> Option<BoundViolation> {
-    extents_in_bounds(bounds, &symbol.extents(all_versions.as_deref()))
+    extents_in_bounds(bounds, &symbol.extents(all_versions.as_deref())).or_else(|| {
+        symbol
+            .range_extents(all_versions.as_deref())
+            .iter()
+            .find_map(|ext| extents_in_bounds(bounds, ext))
+    })
 }
 
 /// Checks that a `block` falls within the given `bounds` (as an offset and an optional length)
@@ -218,8 +236,8 @@ mod tests {
         for case in cases {
             assert_eq!(
                 extents_in_bounds(
-                    &MaybeVersionDep::ByVersion(case.0.into()),
-                    &MaybeVersionDep::ByVersion(case.1.into())
+                    &MaybeVersionDep::ByVersion(case.0),
+                    &MaybeVersionDep::ByVersion(case.1)
                 )
                 .is_none(),
                 case.2
@@ -269,8 +287,8 @@ mod tests {
         for case in cases {
             assert_eq!(
                 extents_in_bounds(
-                    &MaybeVersionDep::ByVersion(case.0.into()),
-                    &MaybeVersionDep::ByVersion(case.1.into())
+                    &MaybeVersionDep::ByVersion(case.0),
+                    &MaybeVersionDep::ByVersion(case.1)
                 )
                 .is_none(),
                 case.2
@@ -300,7 +318,7 @@ mod tests {
         for case in cases {
             assert_eq!(
                 extents_in_bounds(
-                    &MaybeVersionDep::ByVersion(case.0.into()),
+                    &MaybeVersionDep::ByVersion(case.0),
                     &MaybeVersionDep::Common(case.1)
                 )
                 .is_none(),
@@ -332,7 +350,7 @@ mod tests {
             assert_eq!(
                 extents_in_bounds(
                     &MaybeVersionDep::Common(case.0),
-                    &MaybeVersionDep::ByVersion(case.1.into())
+                    &MaybeVersionDep::ByVersion(case.1)
                 )
                 .is_none(),
                 case.2
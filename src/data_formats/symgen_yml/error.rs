@@ -16,6 +16,8 @@ pub enum SubregionError {
     InvalidPath(PathBuf),
     Symlink(PathBuf),
     SymGen((PathBuf, Box<Error>)),
+    Missing(PathBuf),
+    Unresolved(PathBuf),
 }
 
 impl error::Error for SubregionError {}
@@ -30,6 +32,8 @@ impl Display for SubregionError {
                 p.display()
             ),
             Self::SymGen((p, e)) => write!(f, "{}: {}", p.display(), e),
+            Self::Missing(p) => write!(f, "no subregion with name '{}'", p.display()),
+            Self::Unresolved(p) => write!(f, "subregion '{}' is not resolved", p.display()),
         }
     }
 }
@@ -43,6 +47,7 @@ pub enum Error {
     Io(io::Error),
     FromUtf8(string::FromUtf8Error),
     Subregion(SubregionError),
+    AddressExpr(String),
 }
 
 impl error::Error for Error {}
@@ -56,6 +61,7 @@ impl Display for Error {
             Self::Io(e) => write!(f, "{}", e),
             Self::FromUtf8(e) => write!(f, "{}", e),
             Self::Subregion(e) => write!(f, "{}", e),
+            Self::AddressExpr(e) => write!(f, "{}", e),
         }
     }
 }
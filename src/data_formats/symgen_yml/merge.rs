@@ -5,16 +5,19 @@
 //! through reinitialization. However, the publicly exported utilities are safe.
 
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::error::Error;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::path::{Path, PathBuf};
 
+use serde_yaml::{Mapping, Value};
+
 use super::adapter::{AddSymbol, SymbolType};
 use super::bounds;
 use super::error::MergeError;
 use super::symgen::*;
 use super::types::*;
+use crate::util;
 
 /// A conflict encountered while merging.
 #[derive(Debug)]
@@ -36,16 +39,12 @@ impl MergeConflict {
         }
     }
 
-    /// Wraps a [`Result<T, MergeConflict>`] in another [`MergeConflict`] with a description
-    /// `desc`, if an [`Err`].
-    fn wrap<T, S: ToString>(res: Result<T, MergeConflict>, desc: S) -> Result<T, MergeConflict> {
-        match res {
-            Ok(x) => Ok(x),
-            Err(inner) => Err(MergeConflict {
-                parent_desc: desc.to_string(),
-                other_desc: desc.to_string(),
-                inner: Some(Box::new(inner)),
-            }),
+    /// Wraps `self` in another [`MergeConflict`] with a description `desc`.
+    fn nest<S: ToString>(self, desc: S) -> Self {
+        MergeConflict {
+            parent_desc: desc.to_string(),
+            other_desc: desc.to_string(),
+            inner: Some(Box::new(self)),
         }
     }
 }
@@ -71,6 +70,86 @@ impl Display for MergeConflict {
     }
 }
 
+/// A summary of the actions taken when merging symbols into a [`SymGen`].
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct MergeSummary {
+    /// Number of symbols that were added as new entries.
+    pub added: usize,
+    /// Number of symbols that were merged into existing entries.
+    pub merged: usize,
+    /// Symbols that could not be merged, with their intended [`SymbolType`] recorded in
+    /// [`Symbol::stype`].
+    pub unmerged: Vec<Symbol>,
+    /// Number of symbols added or merged into each block, by block name. Blocks within
+    /// subregions are named `<subregion path>::<block name>`. Only tracked when merging a stream of
+    /// symbols, since whole-[`SymGen`] merges match blocks by name rather than assigning them.
+    pub blocks: BTreeMap<String, usize>,
+}
+
+/// Counts all the symbols in a [`SymGen`], including those within resolved subregions.
+fn count_symbols(symgen: &SymGen) -> usize {
+    symgen
+        .blocks()
+        .map(|b| {
+            b.iter().count()
+                + b.subregions
+                    .iter()
+                    .flatten()
+                    .filter_map(|s| s.contents.as_deref())
+                    .map(count_symbols)
+                    .sum::<usize>()
+        })
+        .sum()
+}
+
+/// Counts the symbols in `other` that would be merged into an existing entry when merging `other`
+/// into `symgen`, rather than added as a new entry. Symbols within resolved subregions are
+/// included.
+///
+/// A symbol is merged if there's already an entry with the same name and symbol type in the same
+/// block, either from `symgen` or from an earlier symbol in `other`. Blocks and subregions that
+/// `symgen` doesn't have yet are copied over as is, so none of their symbols are merged.
+fn count_merged_symbols(symgen: &SymGen, other: &SymGen) -> usize {
+    other
+        .iter()
+        .filter_map(|(bname, other_block)| {
+            let block = symgen.get(symgen.block_key(&bname.val)?)?;
+            Some((block, other_block))
+        })
+        .map(|(block, other_block)| {
+            let merged = |list: &SymbolList, other_list: &SymbolList| {
+                let mut seen = HashSet::new();
+                other_list
+                    .iter()
+                    .filter(|s| {
+                        let exists = list.find_by_name(&s.name).is_some();
+                        !seen.insert(s.name.as_str()) || exists
+                    })
+                    .count()
+            };
+            let in_subregions: usize = other_block
+                .subregions
+                .iter()
+                .flatten()
+                .filter_map(|other_sub| {
+                    let sub = block
+                        .subregions
+                        .iter()
+                        .flatten()
+                        .find(|s| s.name == other_sub.name)?;
+                    Some(count_merged_symbols(
+                        sub.contents.as_deref()?,
+                        other_sub.contents.as_deref()?,
+                    ))
+                })
+                .sum();
+            merged(&block.functions, &other_block.functions)
+                + merged(&block.data, &other_block.data)
+                + in_subregions
+        })
+        .sum()
+}
+
 /// An error encountered when attempting to merge into a [`Block`] that doesn't exist in the
 /// parent `SymGen`.
 #[derive(Debug)]
@@ -116,18 +195,71 @@ impl Display for BlockInferenceError {
     }
 }
 
+/// Policy for handling [`MergeConflict`]s encountered while merging.
+enum Conflicts {
+    /// Abort the merge at the first conflict.
+    Abort,
+    /// Record every conflict and keep merging. Conflicting values are left unchanged.
+    Collect(Vec<MergeConflict>),
+}
+
+impl Conflicts {
+    /// Reports a conflict. Returns an [`Err`] if the merge should be aborted.
+    fn report(&mut self, conflict: MergeConflict) -> Result<(), MergeConflict> {
+        match self {
+            Self::Abort => Err(conflict),
+            Self::Collect(conflicts) => {
+                conflicts.push(conflict);
+                Ok(())
+            }
+        }
+    }
+
+    /// Runs a nested merge `f`, wrapping any conflicts it encounters with a description `desc`.
+    fn nested<S, F>(&mut self, desc: S, f: F) -> Result<(), MergeConflict>
+    where
+        S: ToString,
+        F: FnOnce(&mut Self) -> Result<(), MergeConflict>,
+    {
+        if let Self::Collect(conflicts) = self {
+            let mut inner = Self::Collect(Vec::new());
+            f(&mut inner)?;
+            if let Self::Collect(inner) = inner {
+                let desc = desc.to_string();
+                conflicts.extend(inner.into_iter().map(|c| c.nest(&desc)));
+            }
+            Ok(())
+        } else {
+            f(self).map_err(|c| c.nest(desc))
+        }
+    }
+
+    /// The number of conflicts collected so far.
+    fn len(&self) -> usize {
+        match self {
+            Self::Abort => 0,
+            Self::Collect(conflicts) => conflicts.len(),
+        }
+    }
+}
+
 /// A type that can be merged with another instance of the same type.
 trait Merge {
-    /// Merge `other` into `self`.
-    fn merge(&mut self, other: &Self) -> Result<(), MergeConflict>;
+    /// Merge `other` into `self`, handling conflicts according to `conflicts`.
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict>;
+
+    /// Merge `other` into `self`, aborting at the first conflict.
+    fn merge(&mut self, other: &Self) -> Result<(), MergeConflict> {
+        self.merge_with(other, &mut Conflicts::Abort)
+    }
 }
 
 impl Merge for Uint {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeConflict> {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
         if self == other {
             Ok(())
         } else {
-            Err(MergeConflict::new(
+            conflicts.report(MergeConflict::new(
                 format!("{:#X}", self),
                 format!("{:#X}", other),
             ))
@@ -136,7 +268,23 @@ impl Merge for Uint {
 }
 
 impl Merge for Linkable {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeConflict> {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
+        if let (Self::Expr(_), _) | (_, Self::Expr(_)) = (&*self, other) {
+            // Expressions can't be combined with other addresses, but merging in the same
+            // address is fine
+            let addrs: Vec<_> = self.iter().collect();
+            let other_addrs: Vec<_> = other.iter().collect();
+            return if self == other || (!addrs.is_empty() && addrs == other_addrs) {
+                Ok(())
+            } else {
+                let desc = |l: &Self| match l {
+                    Self::Single(x) => format!("{:#X}", x),
+                    Self::Multiple(v) => format!("{:#X?}", v),
+                    Self::Expr(e) => e.to_string(),
+                };
+                conflicts.report(MergeConflict::new(desc(self), desc(other)))
+            };
+        }
         if let Self::Single(x) = self {
             if let Self::Single(y) = other {
                 if x != y {
@@ -166,7 +314,7 @@ impl<T> Merge for VersionDep<T>
 where
     T: Merge + Debug + Clone + 'static,
 {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeConflict> {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
         // Map version names to the actual Version keys
         let mut versions_by_name: HashMap<_, _> = self
             .versions()
@@ -175,9 +323,9 @@ where
         for (v, x) in other.iter() {
             match versions_by_name.get(v.name()) {
                 // This version key already exists (by name), so try to merge the inner value
-                Some(vers) => {
-                    MergeConflict::wrap(self.get_mut_native(vers).unwrap().merge(x), v.name())?
-                }
+                Some(vers) => conflicts.nested(v.name(), |c| {
+                    self.get_mut_native(vers).unwrap().merge_with(x, c)
+                })?,
                 None => {
                     // This version key is new, so insert it
                     self.insert_native(v.clone(), x.clone());
@@ -193,13 +341,13 @@ impl<T> Merge for MaybeVersionDep<T>
 where
     T: Merge + PartialEq + Debug + Clone + 'static,
 {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeConflict> {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
         match self {
             Self::Common(x) => {
                 match other {
                     Self::Common(y) => {
                         // Directly pass up the error without wrapping since this is Common
-                        x.merge(y)?;
+                        x.merge_with(y, conflicts)?;
                     }
                     Self::ByVersion(other_vals) => {
                         let mut values = other_vals.values();
@@ -211,13 +359,17 @@ where
                                 // we can just keep self as Common to preserve generality.
                                 // All we need to do here is merge the shared value from other,
                                 // as if it were Common.
-                                x.merge(y)?;
+                                x.merge_with(y, conflicts)?;
                             } else {
                                 // If the versions are actually different, swap self/other
-                                // and call into the ByVersion <- Common code path.
+                                // and call into the ByVersion <- Common code path. If any
+                                // conflicts were collected, keep self as is.
+                                let n_conflicts = conflicts.len();
                                 let mut new_self = other.clone();
-                                new_self.merge(self)?;
-                                *self = new_self;
+                                new_self.merge_with(self, conflicts)?;
+                                if conflicts.len() == n_conflicts {
+                                    *self = new_self;
+                                }
                             }
                         }
                     }
@@ -231,7 +383,7 @@ where
                         // Here (ByVersion <- Common), since self is already versioned, never
                         // convert back to Common, and always just realize other before merging.
                         for (vers, x) in vals.iter_mut() {
-                            MergeConflict::wrap(x.merge(y), vers.name())?;
+                            conflicts.nested(vers.name(), |c| x.merge_with(y, c))?;
                         }
                         // The merge was successful, but since version inference was involved,
                         // print a warning. This case shouldn't be common for merges anyway.
@@ -244,7 +396,7 @@ where
                     Self::ByVersion(other_vals) => {
                         // Directly pass up the error without wrapping since VersionDep already
                         // handles version keys on merge failure.
-                        vals.merge(other_vals)?;
+                        vals.merge_with(other_vals, conflicts)?;
                     }
                 }
             }
@@ -253,74 +405,138 @@ where
     }
 }
 
-/// Returns a possibly truncated substring of `s`. If truncated, the "..." suffix will be appended
-/// to indicate continuation.
+impl Merge for SymbolType {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
+        if self != other {
+            return conflicts.report(MergeConflict::new(
+                format!("{:?}", self),
+                format!("{:?}", other),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Returns a possibly truncated substring of `s` for previews in merge conflicts.
 fn truncate(s: &str) -> String {
-    const MAX_LENGTH: usize = 100;
-    if s.chars().count() <= MAX_LENGTH {
-        s.to_string()
-    } else {
-        format!(
-            "{}...",
-            s.chars()
-                .take(MAX_LENGTH)
-                .fold(String::with_capacity(MAX_LENGTH), |mut acc, c| {
-                    acc.push(c);
-                    acc
-                })
-        )
+    util::truncate(s, 100)
+}
+
+impl Merge for String {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
+        if self != other {
+            return conflicts.report(MergeConflict::new(truncate(self), truncate(other)));
+        }
+        Ok(())
+    }
+}
+
+/// Formats a YAML value for display in a [`MergeConflict`].
+fn yaml_desc(val: &Value) -> String {
+    match serde_yaml::to_string(val) {
+        Ok(s) => truncate(s.trim_start_matches("---").trim()),
+        Err(_) => format!("{:?}", val),
+    }
+}
+
+impl Merge for Mapping {
+    // Unknown fields are opaque, so any values present in both self and other must be identical.
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
+        for (key, other_val) in other.iter() {
+            match self.get(key) {
+                Some(val) => {
+                    if val != other_val {
+                        conflicts.report(
+                            MergeConflict::new(yaml_desc(val), yaml_desc(other_val))
+                                .nest(yaml_desc(key)),
+                        )?;
+                    }
+                }
+                None => {
+                    self.insert(key.clone(), other_val.clone());
+                }
+            }
+        }
+        Ok(())
     }
 }
 
 impl Merge for Symbol {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeConflict> {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
         if self.name != other.name {
-            return Err(MergeConflict::new(&self.name, &other.name));
+            return conflicts.report(MergeConflict::new(&self.name, &other.name));
         }
         if let Some(other_desc) = &other.description {
             match &mut self.description {
                 Some(self_desc) => {
-                    if self_desc != other_desc {
-                        return MergeConflict::wrap(
-                            Err(MergeConflict::new(
-                                truncate(self_desc),
-                                truncate(other_desc),
-                            )),
-                            "description",
-                        );
-                    }
+                    conflicts.nested("description", |c| self_desc.merge_with(other_desc, c))?;
                 }
                 None => self.description = Some(other_desc.clone()),
             };
         }
-        MergeConflict::wrap(self.address.merge(&other.address), "address")?;
+        if let Some(other_aliases) = &other.deprecated_aliases {
+            let aliases = self.deprecated_aliases.get_or_insert_with(Vec::new);
+            for alias in other_aliases {
+                if !aliases.contains(alias) {
+                    aliases.push(alias.clone());
+                }
+            }
+        }
+        conflicts.nested("address", |c| self.address.merge_with(&other.address, c))?;
         if let Some(other_len) = &other.length {
             match &mut self.length {
                 None => self.length = Some(other_len.clone()),
-                Some(len) => MergeConflict::wrap(len.merge(other_len), "length")?,
+                Some(len) => conflicts.nested("length", |c| len.merge_with(other_len, c))?,
+            };
+        }
+        if let Some(other_ranges) = &other.ranges {
+            let ranges = self.ranges.get_or_insert_with(Vec::new);
+            for range in other_ranges {
+                if !ranges.contains(range) {
+                    ranges.push(range.clone());
+                }
+            }
+        }
+        if let Some(other_size) = &other.element_size {
+            match &mut self.element_size {
+                None => self.element_size = Some(*other_size),
+                Some(size) => {
+                    conflicts.nested("element_size", |c| size.merge_with(other_size, c))?
+                }
             };
         }
+        if let Some(other_type) = &other.data_type {
+            match &mut self.data_type {
+                None => self.data_type = Some(other_type.clone()),
+                Some(dtype) => {
+                    conflicts.nested("data_type", |c| dtype.merge_with(other_type, c))?
+                }
+            };
+        }
+        if let Some(other_inlined) = other.inlined {
+            self.inlined = Some(self.inlined.unwrap_or(false) || other_inlined);
+        }
+        if let Some(other_type) = &other.stype {
+            match &mut self.stype {
+                None => self.stype = Some(*other_type),
+                Some(stype) => conflicts.nested("type", |c| stype.merge_with(other_type, c))?,
+            };
+        }
+        if let Some(other_fields) = &other.extra_fields {
+            self.extra_fields
+                .get_or_insert_with(Mapping::new)
+                .merge_with(other_fields, conflicts)?;
+        }
         Ok(())
     }
 }
 
 impl Merge for SymbolList {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeConflict> {
-        let mut name_to_idx: HashMap<_, _> = self
-            .iter()
-            .enumerate()
-            .map(|(i, s)| (s.name.clone(), i))
-            .collect();
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
         for symbol in other.iter() {
-            match name_to_idx.get(&symbol.name) {
-                Some(&i) => unsafe {
-                    // Never out of bounds since it comes from the list, which never shrinks
-                    MergeConflict::wrap(self.get_unchecked_mut(i).merge(symbol), &symbol.name)?;
-                },
-                None => {
-                    self.push(symbol.clone());
-                    name_to_idx.insert(symbol.name.clone(), self.len() - 1);
-                }
+            match self.find_by_name_mut(&symbol.name) {
+                Some(s) => conflicts.nested(&symbol.name, |c| s.merge_with(symbol, c))?,
+                None => self.push(symbol.clone()),
             }
         }
         Ok(())
@@ -328,23 +544,28 @@ impl Merge for SymbolList {
 }
 
 impl Merge for Block {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeConflict> {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
         if let Some(other_desc) = &other.description {
             match &mut self.description {
                 Some(self_desc) => {
                     if self_desc != other_desc {
-                        return MergeConflict::wrap(
-                            Err(MergeConflict::new(
-                                truncate(self_desc),
-                                truncate(other_desc),
-                            )),
-                            "description",
-                        );
+                        conflicts.report(
+                            MergeConflict::new(truncate(self_desc), truncate(other_desc))
+                                .nest("description"),
+                        )?;
                     }
                 }
                 None => self.description = Some(other_desc.clone()),
             };
         }
+        if let Some(other_group) = &other.space_group {
+            match &mut self.space_group {
+                Some(group) => {
+                    conflicts.nested("space_group", |c| group.merge_with(other_group, c))?
+                }
+                None => self.space_group = Some(other_group.clone()),
+            };
+        }
 
         if let Some(versions) = &self.versions {
             // If other has fields that might have different versions than self, make sure to
@@ -381,7 +602,7 @@ impl Merge for Block {
                 for other_sub in other_subregions {
                     if let Some(sub) = subregions.iter_mut().find(|s| s.name == other_sub.name) {
                         // Found matching subregions; merge them
-                        sub.merge(other_sub)?;
+                        sub.merge_with(other_sub, conflicts)?;
                     } else {
                         // No matching subregions; just append to the subregion list
                         subregions.push(other_sub.clone());
@@ -423,27 +644,34 @@ impl Merge for Block {
             other_address = Cow::Borrowed(&other.address);
             other_length = Cow::Borrowed(&other.length);
         }
-        MergeConflict::wrap(self.address.merge(&other_address), "address")?;
-        MergeConflict::wrap(self.length.merge(&other_length), "length")?;
-
-        MergeConflict::wrap(self.functions.merge(&other_functions), "functions")?;
-        MergeConflict::wrap(self.data.merge(&other_data), "data")?;
+        conflicts.nested("address", |c| self.address.merge_with(&other_address, c))?;
+        conflicts.nested("length", |c| self.length.merge_with(&other_length, c))?;
+
+        conflicts.nested("functions", |c| {
+            self.functions.merge_with(&other_functions, c)
+        })?;
+        conflicts.nested("data", |c| self.data.merge_with(&other_data, c))?;
+
+        if let Some(other_fields) = &other.extra_fields {
+            self.extra_fields
+                .get_or_insert_with(Mapping::new)
+                .merge_with(other_fields, conflicts)?;
+        }
         Ok(())
     }
 }
 
 impl Merge for SymGen {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeConflict> {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
         for (bname, block) in other.iter() {
             // Ensure the block exists
             match self.block_key(&bname.val).cloned() {
                 Some(bkey) => {
-                    MergeConflict::wrap(
+                    conflicts.nested(bname, |c| {
                         self.get_mut(&bkey)
-                            .map(|b| b.merge(block))
-                            .unwrap_or(Ok(())),
-                        bname,
-                    )?;
+                            .map(|b| b.merge_with(block, c))
+                            .unwrap_or(Ok(()))
+                    })?;
                 }
                 None => {
                     self.insert(bname.clone(), block.clone());
@@ -456,82 +684,6 @@ impl Merge for SymGen {
     }
 }
 
-type BySymbolListId<T> = HashMap<Option<PathBuf>, HashMap<String, HashMap<SymbolType, T>>>;
-
-/// For [`SymbolList`] lookup and insertion, managed by a cache that stores
-/// index by subregion path, by block name, by symbol type, by symbol name.
-struct SymbolManager(BySymbolListId<HashMap<String, usize>>);
-
-impl SymbolManager {
-    fn new() -> Self {
-        Self(HashMap::new())
-    }
-
-    /// Gets a mutable reference to a [`Symbol`] from within `slist`, with the given
-    /// cache key (`block_name`, `symbol_type`, `symbol_name`), if present in `slist`.
-    /// If the symbol is present but not within the [`SymbolManager`] cache, it will be
-    /// added the cache for future lookups.
-    fn get<'s>(
-        &mut self,
-        slist: &'s mut SymbolList,
-        subregion_path: &Option<PathBuf>,
-        block_name: &str,
-        symbol_type: &SymbolType,
-        symbol_name: &str,
-    ) -> Option<&'s mut Symbol> {
-        let idx = if let Some(imap) = self
-            .0
-            .get(subregion_path)
-            .and_then(|m| m.get(block_name))
-            .and_then(|m| m.get(symbol_type))
-        {
-            imap.get(symbol_name).copied()
-        } else {
-            let imap = slist.iter().enumerate().fold(
-                HashMap::with_capacity(slist.len()),
-                |mut m, (i, x)| {
-                    // Give the first appearance of a symbol name precedence
-                    if !m.contains_key(&x.name) {
-                        m.insert(x.name.clone(), i);
-                    }
-                    m
-                },
-            );
-            let i = imap.get(symbol_name).copied();
-            self.0
-                .entry(subregion_path.clone())
-                .or_insert_with(HashMap::new)
-                .entry(block_name.to_owned())
-                .or_insert_with(HashMap::new)
-                .entry(*symbol_type)
-                .or_insert(imap);
-            i
-        };
-        idx.map(move |i| unsafe { slist.get_unchecked_mut(i) })
-    }
-
-    /// Appends `symbol` onto `slist`, and update the [`SymbolManager`] cache with a given
-    /// `block_name` and `symbol_type`.
-    fn insert(
-        &mut self,
-        slist: &mut SymbolList,
-        subregion_path: &Option<PathBuf>,
-        block_name: &str,
-        symbol_type: &SymbolType,
-        symbol: Symbol,
-    ) {
-        if let Some(imap) = self
-            .0
-            .get_mut(subregion_path)
-            .and_then(|m| m.get_mut(block_name))
-            .and_then(|m| m.get_mut(symbol_type))
-        {
-            imap.insert(symbol.name.clone(), slist.len());
-        }
-        slist.push(symbol);
-    }
-}
-
 /// A type that can be intrinsically associated with a single block name.
 trait BlockMatch {
     type Raw;
@@ -599,33 +751,281 @@ where
     }
 }
 
+/// Match result from block inference using a [`BlockIndex`], identifying a block by its position
+/// in the index.
+struct IndexedBlockMatch<'n, P>((Option<P>, &'n String, usize));
+
+impl<'n, P> BlockMatch for IndexedBlockMatch<'n, P>
+where
+    P: AsRef<Path>,
+{
+    type Raw = (Option<P>, &'n String, usize);
+
+    fn new(raw: Self::Raw) -> Self {
+        Self(raw)
+    }
+    fn raw(self) -> Self::Raw {
+        self.0
+    }
+    fn block_name(&self) -> String {
+        match &self.0 .0 {
+            Some(p) => format!("{}::{}", p.as_ref().display(), self.0 .1),
+            None => self.0 .1.clone(),
+        }
+    }
+}
+
+/// An index of the blocks in a [`SymGen`] (and recursively, in its resolved subregions), used to
+/// speed up block inference.
+///
+/// Rather than checking a symbol against every block, the index narrows the search down to the
+/// blocks with an extent containing one of the symbol's addresses (found by binary search over
+/// the extents sorted by start address), along with any blocks that would contain the symbol
+/// vacuously because none of its versions are checked against the block. Candidates are then
+/// checked with [`bounds::block_contains_symbol`] as usual, so the results are the same as
+/// checking every block.
+///
+/// The index only depends on block extents and subregions, so it stays valid while symbols are
+/// being merged.
+struct BlockIndex {
+    /// Block keys, in the same order as in the [`SymGen`].
+    keys: Vec<OrdString>,
+    /// Bounded block extents as (start, end, block position), sorted by start. The end is
+    /// exclusive.
+    extents: Vec<(Uint, Uint, usize)>,
+    /// The running maximum of the ends in `extents`, to know when to stop searching backwards.
+    max_ends: Vec<Uint>,
+    /// Block extents with an end that doesn't fit in a [`Uint`], as (start, block position).
+    unbounded_extents: Vec<(Uint, usize)>,
+    /// Positions of blocks with version-dependent extents, grouped by the version names they have
+    /// extents for.
+    version_groups: BTreeMap<BTreeSet<String>, Vec<usize>>,
+    /// Positions of blocks with an empty version list.
+    unversioned: Vec<usize>,
+    /// Indexes of the resolved subregions of each block, by block position.
+    subregions: Vec<Vec<Option<BlockIndex>>>,
+}
+
+impl BlockIndex {
+    fn new(symgen: &SymGen) -> Self {
+        let mut index = Self {
+            keys: Vec::new(),
+            extents: Vec::new(),
+            max_ends: Vec::new(),
+            unbounded_extents: Vec::new(),
+            version_groups: BTreeMap::new(),
+            unversioned: Vec::new(),
+            subregions: Vec::new(),
+        };
+        for (i, (bname, block)) in symgen.iter().enumerate() {
+            let extent = block.extent();
+            for &(start, len) in extent.values() {
+                match len.and_then(|l| start.checked_add(l)) {
+                    Some(end) => index.extents.push((start, end, i)),
+                    None => index.unbounded_extents.push((start, i)),
+                }
+            }
+            if let MaybeVersionDep::ByVersion(by_vers) = &extent {
+                index
+                    .version_groups
+                    .entry(by_vers.iter().map(|(v, _)| v.name().to_owned()).collect())
+                    .or_default()
+                    .push(i);
+            }
+            if matches!(&block.versions, Some(v) if v.is_empty()) {
+                index.unversioned.push(i);
+            }
+            index.keys.push(bname.clone());
+            index.subregions.push(
+                block
+                    .subregions
+                    .iter()
+                    .flatten()
+                    .map(|s| s.contents.as_deref().map(Self::new))
+                    .collect(),
+            );
+        }
+        index.extents.sort_unstable();
+        index.max_ends = index
+            .extents
+            .iter()
+            .scan(0, |max_end, &(_, end, _)| {
+                *max_end = end.max(*max_end);
+                Some(*max_end)
+            })
+            .collect();
+        index
+    }
+
+    /// Gets the position of the block with the given `key`.
+    fn position(&self, key: &OrdString) -> Option<usize> {
+        self.keys.binary_search(key).ok()
+    }
+
+    /// Appends the positions of all blocks with an extent containing `addr` to `positions`.
+    fn find_address(&self, addr: Uint, positions: &mut Vec<usize>) {
+        let n_before = self.extents.partition_point(|&(start, _, _)| start <= addr);
+        for j in (0..n_before).rev() {
+            if self.max_ends[j] <= addr {
+                // No earlier extent can reach addr
+                break;
+            }
+            let (_, end, i) = self.extents[j];
+            if addr < end {
+                positions.push(i);
+            }
+        }
+        positions.extend(
+            self.unbounded_extents
+                .iter()
+                .filter(|&&(start, _)| start <= addr)
+                .map(|&(_, i)| i),
+        );
+    }
+
+    /// Finds the positions of all blocks within `symgen` that contain `symbol`, in order.
+    ///
+    /// `symgen` must be the [`SymGen`] the index was built from.
+    fn blocks_containing(&self, symgen: &SymGen, symbol: &Symbol) -> Vec<usize> {
+        let mut candidates = Vec::new();
+        // A block containing the symbol must contain the first address for some version, unless
+        // the containment is vacuous
+        let mut any_addr = false;
+        for first_addr in symbol.address.values().filter_map(|l| l.iter().next()) {
+            any_addr = true;
+            self.find_address(*first_addr, &mut candidates);
+        }
+        match &symbol.address {
+            _ if !any_addr => candidates.extend(0..self.keys.len()),
+            MaybeVersionDep::Common(_) => {
+                // Common addresses get realized with the block's version list
+                candidates.extend(self.unversioned.iter().copied())
+            }
+            MaybeVersionDep::ByVersion(by_vers) => {
+                let symbol_versions: BTreeSet<&str> = by_vers
+                    .iter()
+                    .filter(|(_, l)| l.iter().next().is_some())
+                    .map(|(v, _)| v.name())
+                    .collect();
+                for (block_versions, positions) in self.version_groups.iter() {
+                    if !block_versions
+                        .iter()
+                        .any(|v| symbol_versions.contains(v.as_str()))
+                    {
+                        candidates.extend(positions.iter().copied());
+                    }
+                }
+            }
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+            .retain(|&i| bounds::block_contains_symbol(symgen.get(&self.keys[i]).unwrap(), symbol));
+        candidates
+    }
+}
+
 type BlockAssignment<'n, 'b> = (Option<PathBuf>, &'n String, &'b mut Block);
 
 impl SymGen {
     /// Merges `other` into `self`.
-    pub fn merge_symgen(&mut self, other: &Self) -> Result<(), MergeError> {
-        self.merge(other).map_err(MergeError::Conflict)
+    ///
+    /// Returns a [`MergeSummary`] if no fatal error was encountered, or a [`MergeError`] if a
+    /// fatal error was encountered, in which case `self` is left unchanged. Merging a [`SymGen`]
+    /// never leaves any symbols unmerged.
+    pub fn merge_symgen(&mut self, other: &Self) -> Result<MergeSummary, MergeError> {
+        let n_before = count_symbols(self);
+        let merged = count_merged_symbols(self, other);
+        // Only the blocks named in other can be touched by the merge, so only those need to be
+        // saved in order to roll back a failed merge
+        let saved: Vec<(&str, Option<(OrdString, Block)>)> = other
+            .iter()
+            .map(|(bname, _)| {
+                let original = self
+                    .block_key(&bname.val)
+                    .map(|k| (k.clone(), self.get(k).unwrap().clone()));
+                (bname.val.as_str(), original)
+            })
+            .collect();
+        if let Err(e) = self.merge(other) {
+            for (bname, original) in saved {
+                match original {
+                    Some((key, block)) => {
+                        self.insert(key, block);
+                    }
+                    None => {
+                        if let Some(key) = self.block_key(bname).cloned() {
+                            self.remove(&key);
+                        }
+                    }
+                }
+            }
+            return Err(MergeError::Conflict(e));
+        }
+        Ok(MergeSummary {
+            added: count_symbols(self) - n_before,
+            merged,
+            unmerged: Vec::new(),
+            blocks: BTreeMap::new(),
+        })
     }
+
+    /// Merges `other` into `self`, collecting every [`MergeConflict`] encountered rather than
+    /// stopping at the first one.
+    ///
+    /// Non-conflicting data is merged as usual, while conflicting values are left as they are in
+    /// `self`. Returns the conflicts encountered, which will be empty if the merge was clean.
+    pub fn merge_collecting(&mut self, other: &Self) -> Vec<MergeConflict> {
+        let mut conflicts = Conflicts::Collect(Vec::new());
+        // Conflicts are never fatal when collecting, so this never fails
+        let _ = self.merge_with(other, &mut conflicts);
+        match conflicts {
+            Conflicts::Collect(conflicts) => conflicts,
+            Conflicts::Abort => Vec::new(),
+        }
+    }
+
     /// Determine which [`Block`], if any, the given [`AddSymbol`] should be merged into.
     ///
     /// The assigned [`Block`] may be either a top-level one in the [`SymGen`] or a subsidiary
     /// [`Block`] within a resolved [`Subregion`].
+    ///
+    /// `index` must be a [`BlockIndex`] built from `self`.
     fn assign_block<'b, 's, 'n>(
         &'b mut self,
         to_add: &'s AddSymbol,
         subregion_path: Option<&Path>,
+        index: &'n BlockIndex,
     ) -> Result<Option<BlockAssignment<'n, 'b>>, MergeError>
     where
         'b: 'n,
         's: 'n,
     {
-        let (bname, block) = if let (Some(name), None) = (&to_add.block_name, subregion_path) {
+        match self.locate_block(to_add, subregion_path, index)? {
+            Some((bname, pos)) => self
+                .assign_block_at(to_add, subregion_path, index, bname, pos)
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Determine which top-level [`Block`], if any, the given [`AddSymbol`] belongs to, without
+    /// descending into subregions. Returns the block name and its position in `index`.
+    ///
+    /// `index` must be a [`BlockIndex`] built from `self`.
+    fn locate_block<'s, 'n>(
+        &self,
+        to_add: &'s AddSymbol,
+        subregion_path: Option<&Path>,
+        index: &'n BlockIndex,
+    ) -> Result<Option<(&'n String, usize)>, MergeError>
+    where
+        's: 'n,
+    {
+        let (bname, pos) = if let (Some(name), None) = (&to_add.block_name, subregion_path) {
             // Not in subregion and block name was explicitly specified, so retrieve it
-            match self.block_key(name) {
-                Some(bkey) => {
-                    let key = bkey.clone();
-                    (name, self.get_mut(&key).unwrap())
-                }
+            match self.block_key(name).and_then(|k| index.position(k)) {
+                Some(pos) => (name, pos),
                 None => {
                     return Err(MergeError::MissingBlock(MissingBlock {
                         block_name: name.clone(),
@@ -634,11 +1034,9 @@ impl SymGen {
             }
         } else {
             // In subregion or no block name, so try to infer the block based on the symbol address
-            let mut block_matches: BlockMatches<InferBlockMatch<_>> = BlockMatches::None;
-            for (bname, block) in self.iter_mut() {
-                if bounds::block_contains_symbol(block, &to_add.symbol) {
-                    block_matches.add((subregion_path, &bname.val, block));
-                }
+            let mut block_matches: BlockMatches<IndexedBlockMatch<_>> = BlockMatches::None;
+            for pos in index.blocks_containing(self, &to_add.symbol) {
+                block_matches.add((subregion_path, &index.keys[pos].val, pos));
             }
             if let Some(assignment) = block_matches.resolve(&to_add.symbol.name)? {
                 // The subregion path was only needed for error reporting
@@ -647,19 +1045,40 @@ impl SymGen {
                 return Ok(None);
             }
         };
+        Ok(Some((bname, pos)))
+    }
+
+    /// Finishes [`SymGen::assign_block()`] for the top-level [`Block`] at position `pos` (named
+    /// `bname`), as determined by [`SymGen::locate_block()`], by searching through its subregions
+    /// for a more specific match.
+    fn assign_block_at<'b, 's, 'n>(
+        &'b mut self,
+        to_add: &'s AddSymbol,
+        subregion_path: Option<&Path>,
+        index: &'n BlockIndex,
+        bname: &'n String,
+        pos: usize,
+    ) -> Result<BlockAssignment<'n, 'b>, MergeError>
+    where
+        'b: 'n,
+        's: 'n,
+    {
+        let block = self.get_mut(&index.keys[pos]).unwrap();
 
         // Search through subregions in the selected block for a match, and assign the matching
         // subregion block instead, if one exists.
         if let Some(subregions) = &mut block.subregions {
             let mut block_matches: BlockMatches<InferBlockMatch<_>> = BlockMatches::None;
-            for subregion in subregions {
-                if let Some(symgen) = &mut subregion.contents {
+            for (subregion, sub_index) in subregions.iter_mut().zip(&index.subregions[pos]) {
+                if let (Some(symgen), Some(sub_index)) = (&mut subregion.contents, sub_index) {
                     let sub_path = if let Some(p) = subregion_path {
                         Cow::Owned(Subregion::subregion_dir(p).join(&subregion.name))
                     } else {
                         Cow::Borrowed(&subregion.name)
                     };
-                    if let Some(assignment) = symgen.assign_block(to_add, Some(&sub_path))? {
+                    if let Some(assignment) =
+                        symgen.assign_block(to_add, Some(&sub_path), sub_index)?
+                    {
                         block_matches.add(assignment);
                     }
                 }
@@ -689,39 +1108,83 @@ impl SymGen {
                         &mut *(block_match.2 as *mut Block),
                     )
                 };
-                return Ok(Some((block_match.0, bname_ref, block_ref)));
+                return Ok((block_match.0, bname_ref, block_ref));
             }
         }
 
         // Assign the matching top-level block
-        Ok(Some((subregion_path.map(|p| p.to_owned()), bname, block)))
+        Ok((subregion_path.map(|p| p.to_owned()), bname, block))
     }
     /// Merges `other` into `self`.
     ///
-    /// Returns a `Vec<Symbol>` containing symbols that were not successfully merged if no
-    /// fatal error was encountered, or a [`MergeError`] if a fatal error was encountered.
-    pub fn merge_symbols<I>(&mut self, other: I) -> Result<Vec<Symbol>, MergeError>
+    /// Returns a [`MergeSummary`] (including the symbols that were not successfully merged) if no
+    /// fatal error was encountered, or a [`MergeError`] if a fatal error was encountered, in which
+    /// case `self` is left unchanged.
+    pub fn merge_symbols<I>(&mut self, other: I) -> Result<MergeSummary, MergeError>
     where
         I: Iterator<Item = AddSymbol>,
     {
-        let mut unmerged_symbols = Vec::new();
-        let mut sym_manager = SymbolManager::new();
+        // Block extents don't change while merging symbols, so the index only needs to be built
+        // once
+        let index = BlockIndex::new(self);
+        // Only the top-level blocks that symbols are assigned to can be touched by the merge, so
+        // only those need to be saved (before their first change) in order to roll back a failed
+        // merge
+        let mut saved: BTreeMap<usize, Block> = BTreeMap::new();
+        match self.merge_symbols_indexed(other, &index, &mut saved) {
+            Ok(summary) => {
+                // Reinit because merging can introduce new OrdStrings/Versions
+                self.init();
+                Ok(summary)
+            }
+            Err(e) => {
+                for (pos, block) in saved {
+                    *self.get_mut(&index.keys[pos]).unwrap() = block;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Does the work of [`SymGen::merge_symbols()`], saving the original contents of each
+    /// top-level block to `saved` before it's first changed.
+    fn merge_symbols_indexed<I>(
+        &mut self,
+        other: I,
+        index: &BlockIndex,
+        saved: &mut BTreeMap<usize, Block>,
+    ) -> Result<MergeSummary, MergeError>
+    where
+        I: Iterator<Item = AddSymbol>,
+    {
+        let mut summary = MergeSummary::default();
         for to_add in other {
-            let assignment = self.assign_block(&to_add, None)?;
-            let (sub_path, bname, block) = match assignment {
-                Some((sub_path, bname, block)) => (sub_path, bname, block),
+            let (bname, pos) = match self.locate_block(&to_add, None, index)? {
+                Some(located) => located,
                 None => {
-                    unmerged_symbols.push(to_add.symbol);
+                    // Keep track of the intended symbol type, since it's otherwise lost
+                    let mut symbol = to_add.symbol;
+                    symbol.stype = Some(to_add.stype);
+                    summary.unmerged.push(symbol);
                     continue;
                 }
             };
+            saved
+                .entry(pos)
+                .or_insert_with(|| self.get(&index.keys[pos]).unwrap().clone());
+            let (sub_path, bname, block) =
+                self.assign_block_at(&to_add, None, index, bname, pos)?;
+            let block_key = match &sub_path {
+                Some(p) => format!("{}::{}", p.display(), bname),
+                None => bname.clone(),
+            };
+            *summary.blocks.entry(block_key).or_default() += 1;
 
             let slist = match to_add.stype {
                 SymbolType::Function => &mut block.functions,
                 SymbolType::Data => &mut block.data,
             };
-            let sym = sym_manager.get(slist, &sub_path, bname, &to_add.stype, &to_add.symbol.name);
-            match sym {
+            match slist.find_by_name_mut(&to_add.symbol.name) {
                 Some(s) => {
                     let to_merge = if let Some(vers) = &block.versions {
                         let mut cpy = to_add.symbol.clone();
@@ -731,26 +1194,22 @@ impl SymGen {
                         Cow::Borrowed(&to_add.symbol)
                     };
                     s.merge(&to_merge).map_err(MergeError::Conflict)?;
+                    summary.merged += 1;
+                }
+                None => {
+                    slist.push(to_add.symbol.clone());
+                    summary.added += 1;
                 }
-                None => sym_manager.insert(
-                    slist,
-                    &sub_path,
-                    bname,
-                    &to_add.stype,
-                    to_add.symbol.clone(),
-                ),
             };
         }
-        // Reinit because merging can introduce new OrdStrings/Versions
-        self.init();
-        Ok(unmerged_symbols)
+        Ok(summary)
     }
 }
 
 impl Merge for Subregion {
-    fn merge(&mut self, other: &Self) -> Result<(), MergeConflict> {
+    fn merge_with(&mut self, other: &Self, conflicts: &mut Conflicts) -> Result<(), MergeConflict> {
         if self.name != other.name {
-            return Err(MergeConflict::new(
+            return conflicts.report(MergeConflict::new(
                 self.name.display(),
                 other.name.display(),
             ));
@@ -758,7 +1217,7 @@ impl Merge for Subregion {
         if let Some(contents) = &mut self.contents {
             if let Some(other_contents) = &other.contents {
                 // Both subregions have contents; merge them
-                contents.merge(other_contents)?;
+                contents.merge_with(other_contents, conflicts)?;
             }
         } else {
             // No contents; copy over the other's
@@ -900,24 +1359,39 @@ mod tests {
     fn test_merge_symbol() {
         let mut x = Symbol {
             name: "function".to_string(),
+            deprecated_aliases: None,
             address: MaybeVersionDep::ByVersion(
                 [("v1".into(), 1.into()), ("v2".into(), 2.into())].into(),
             ),
             length: None,
             description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
         };
         assert!(x
             .merge(&Symbol {
                 name: "function".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::ByVersion([("v3".into(), 3.into())].into()),
                 length: Some(MaybeVersionDep::Common(5)),
-                description: Some("desc".to_string()),
+                description: Some(MaybeVersionDep::Common("desc".to_string())),
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             })
             .is_ok());
         assert_eq!(
             &x,
             &Symbol {
                 name: "function".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::ByVersion(
                     [
                         ("v1".into(), 1.into()),
@@ -927,18 +1401,276 @@ mod tests {
                     .into()
                 ),
                 length: Some(MaybeVersionDep::Common(5)),
-                description: Some("desc".to_string()),
+                description: Some(MaybeVersionDep::Common("desc".to_string())),
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             }
         );
 
         assert!(x
             .merge(&Symbol {
                 name: "function".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::ByVersion([("v3".into(), 3.into())].into()),
                 length: None,
-                description: Some("other desc".to_string()),
+                description: Some(MaybeVersionDep::Common("other desc".to_string())),
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             })
-            .is_err())
+            .is_err());
+    }
+
+    #[test]
+    fn test_merge_symbol_element_size() {
+        let mut x = Symbol {
+            name: "data".to_string(),
+            deprecated_aliases: None,
+            address: MaybeVersionDep::Common(1.into()),
+            length: None,
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        };
+        let mut other = x.clone();
+        other.element_size = Some(4);
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(x.element_size, Some(4));
+
+        other.element_size = Some(8);
+        assert!(x.merge(&other).is_err());
+    }
+
+    #[test]
+    fn test_merge_symbol_data_type() {
+        let mut x = Symbol {
+            name: "data".to_string(),
+            deprecated_aliases: None,
+            address: MaybeVersionDep::Common(1.into()),
+            length: None,
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        };
+        let mut other = x.clone();
+        other.data_type = Some("struct foo".to_string());
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(x.data_type.as_deref(), Some("struct foo"));
+
+        other.data_type = Some("struct bar".to_string());
+        assert!(x.merge(&other).is_err());
+    }
+
+    #[test]
+    fn test_merge_symbol_deprecated_aliases() {
+        let mut x = Symbol {
+            name: "function".to_string(),
+            deprecated_aliases: Some(vec!["old1".to_string()]),
+            address: MaybeVersionDep::Common(1.into()),
+            length: None,
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        };
+        let mut other = x.clone();
+        other.deprecated_aliases = Some(vec!["old2".to_string(), "old1".to_string()]);
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(
+            x.deprecated_aliases,
+            Some(vec!["old1".to_string(), "old2".to_string()])
+        );
+
+        other.deprecated_aliases = None;
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(
+            x.deprecated_aliases,
+            Some(vec!["old1".to_string(), "old2".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_merge_symbol_inlined() {
+        let mut x = Symbol {
+            name: "function".to_string(),
+            deprecated_aliases: None,
+            address: MaybeVersionDep::Common(1.into()),
+            length: None,
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        };
+        let mut other = x.clone();
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(x.inlined, None);
+
+        other.inlined = Some(false);
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(x.inlined, Some(false));
+
+        other.inlined = Some(true);
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(x.inlined, Some(true));
+
+        other.inlined = Some(false);
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(x.inlined, Some(true));
+    }
+
+    #[test]
+    fn test_merge_symbol_type() {
+        let mut x = Symbol {
+            name: "function".to_string(),
+            deprecated_aliases: None,
+            address: MaybeVersionDep::Common(1.into()),
+            length: None,
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        };
+        let mut other = x.clone();
+        other.stype = Some(SymbolType::Function);
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(x.stype, Some(SymbolType::Function));
+
+        other.stype = None;
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(x.stype, Some(SymbolType::Function));
+
+        other.stype = Some(SymbolType::Data);
+        assert!(x.merge(&other).is_err());
+    }
+
+    #[test]
+    fn test_merge_symbol_extra_fields() {
+        let field = |k: &str, v: &str| -> Mapping {
+            let mut fields = Mapping::new();
+            fields.insert(k.into(), v.into());
+            fields
+        };
+        let mut x = Symbol {
+            name: "function".to_string(),
+            deprecated_aliases: None,
+            address: MaybeVersionDep::Common(1.into()),
+            length: None,
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: Some(field("foo", "a")),
+        };
+        let mut other = x.clone();
+        other.extra_fields = Some(field("bar", "b"));
+        assert!(x.merge(&other).is_ok());
+        let mut expected = field("foo", "a");
+        expected.insert("bar".into(), "b".into());
+        assert_eq!(x.extra_fields, Some(expected));
+
+        other.extra_fields = Some(field("foo", "c"));
+        assert!(x.merge(&other).is_err());
+    }
+
+    #[test]
+    fn test_merge_symbol_ranges() {
+        let range1 = SymbolRange {
+            address: MaybeVersionDep::Common(0x10),
+            length: MaybeVersionDep::Common(0x4),
+        };
+        let range2 = SymbolRange {
+            address: MaybeVersionDep::Common(0x20),
+            length: MaybeVersionDep::Common(0x8),
+        };
+        let mut x = Symbol {
+            name: "data".to_string(),
+            deprecated_aliases: None,
+            address: MaybeVersionDep::Common(1.into()),
+            length: None,
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        };
+        let mut other = x.clone();
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(x.ranges, None);
+
+        other.ranges = Some(vec![range1.clone()]);
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(x.ranges, Some(vec![range1.clone()]));
+
+        other.ranges = Some(vec![range2.clone(), range1.clone()]);
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(x.ranges, Some(vec![range1, range2]));
+    }
+
+    #[test]
+    fn test_merge_symbol_versioned_description() {
+        let mut x = Symbol {
+            name: "data".to_string(),
+            deprecated_aliases: None,
+            address: MaybeVersionDep::Common(1.into()),
+            length: None,
+            description: Some(MaybeVersionDep::ByVersion(
+                [("v1".into(), "desc1".to_string())].into(),
+            )),
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        };
+        let mut other = x.clone();
+        other.description = Some(MaybeVersionDep::ByVersion(
+            [("v2".into(), "desc2".to_string())].into(),
+        ));
+        assert!(x.merge(&other).is_ok());
+        assert_eq!(
+            x.description,
+            Some(MaybeVersionDep::ByVersion(
+                [
+                    ("v1".into(), "desc1".to_string()),
+                    ("v2".into(), "desc2".to_string())
+                ]
+                .into()
+            ))
+        );
+
+        other.description = Some(MaybeVersionDep::ByVersion(
+            [("v1".into(), "other desc".to_string())].into(),
+        ));
+        assert!(x.merge(&other).is_err());
     }
 
     #[test]
@@ -946,30 +1678,58 @@ mod tests {
         let mut x = SymbolList::from([
             Symbol {
                 name: "function1".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::Common(1.into()),
                 length: None,
                 description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             },
             Symbol {
                 name: "function2".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::Common(2.into()),
                 length: None,
                 description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             },
         ]);
         assert!(x
             .merge(&SymbolList::from([
                 Symbol {
                     name: "function3".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(3.into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 Symbol {
                     name: "function2".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(4.into()),
                     length: None,
-                    description: Some("desc".to_string()),
+                    description: Some(MaybeVersionDep::Common("desc".to_string())),
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
             ]))
             .is_ok());
@@ -978,21 +1738,42 @@ mod tests {
             &SymbolList::from([
                 Symbol {
                     name: "function1".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(1.into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 Symbol {
                     name: "function2".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common([2, 4].into()),
                     length: None,
-                    description: Some("desc".to_string()),
+                    description: Some(MaybeVersionDep::Common("desc".to_string())),
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 Symbol {
                     name: "function3".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(3.into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
             ])
         );
@@ -1005,15 +1786,24 @@ mod tests {
             address: MaybeVersionDep::ByVersion([("v1".into(), 1)].into()),
             length: MaybeVersionDep::ByVersion([("v1".into(), 10)].into()),
             description: None,
+            space_group: None,
             subregions: None,
             functions: [Symbol {
                 name: "function1".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::ByVersion([("v1".into(), 1.into())].into()),
                 length: None,
                 description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             }]
             .into(),
             data: [].into(),
+            extra_fields: None,
         };
         assert!(x
             .merge(&Block {
@@ -1021,21 +1811,37 @@ mod tests {
                 address: MaybeVersionDep::ByVersion([("v2".into(), 2)].into()),
                 length: MaybeVersionDep::ByVersion([("v1".into(), 10)].into()),
                 description: Some("desc".to_string()),
+                space_group: None,
                 subregions: None,
                 functions: [Symbol {
                     name: "function2".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::ByVersion([("v2".into(), 1.into())].into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 }]
                 .into(),
                 data: [Symbol {
                     name: "data".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::ByVersion([("v1".into(), 1.into())].into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 }]
-                .into()
+                .into(),
+                extra_fields: None,
             })
             .is_ok());
         assert_eq!(
@@ -1045,29 +1851,52 @@ mod tests {
                 address: MaybeVersionDep::ByVersion([("v1".into(), 1), ("v2".into(), 2)].into()),
                 length: MaybeVersionDep::ByVersion([("v1".into(), 10)].into()),
                 description: Some("desc".to_string()),
+                space_group: None,
                 subregions: None,
                 functions: [
                     Symbol {
                         name: "function1".to_string(),
+                        deprecated_aliases: None,
                         address: MaybeVersionDep::ByVersion([("v1".into(), 1.into())].into()),
                         length: None,
                         description: None,
+                        element_size: None,
+                        data_type: None,
+                        inlined: None,
+                        stype: None,
+                        ranges: None,
+                        extra_fields: None,
                     },
                     Symbol {
                         name: "function2".to_string(),
+                        deprecated_aliases: None,
                         address: MaybeVersionDep::ByVersion([("v2".into(), 1.into())].into()),
                         length: None,
                         description: None,
+                        element_size: None,
+                        data_type: None,
+                        inlined: None,
+                        stype: None,
+                        ranges: None,
+                        extra_fields: None,
                     },
                 ]
                 .into(),
                 data: [Symbol {
                     name: "data".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::ByVersion([("v1".into(), 1.into())].into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 }]
-                .into()
+                .into(),
+                extra_fields: None,
             }
         );
     }
@@ -1079,15 +1908,24 @@ mod tests {
             address: MaybeVersionDep::Common(1),
             length: MaybeVersionDep::Common(10),
             description: None,
+            space_group: None,
             subregions: None,
             functions: [Symbol {
                 name: "function1".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::ByVersion([("v1".into(), 1.into())].into()),
                 length: None,
                 description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             }]
             .into(),
             data: [].into(),
+            extra_fields: None,
         };
         assert!(x
             .merge(&Block {
@@ -1095,29 +1933,52 @@ mod tests {
                 address: MaybeVersionDep::Common(2),
                 length: MaybeVersionDep::Common(3),
                 description: None,
+                space_group: None,
                 subregions: None,
                 functions: [
                     Symbol {
                         name: "function1".to_string(),
+                        deprecated_aliases: None,
                         address: MaybeVersionDep::Common(1.into()),
                         length: None,
                         description: None,
+                        element_size: None,
+                        data_type: None,
+                        inlined: None,
+                        stype: None,
+                        ranges: None,
+                        extra_fields: None,
                     },
                     Symbol {
                         name: "function2".to_string(),
+                        deprecated_aliases: None,
                         address: MaybeVersionDep::Common(1.into()),
                         length: None,
                         description: None,
+                        element_size: None,
+                        data_type: None,
+                        inlined: None,
+                        stype: None,
+                        ranges: None,
+                        extra_fields: None,
                     },
                 ]
                 .into(),
                 data: [Symbol {
                     name: "data".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(1.into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 }]
-                .into()
+                .into(),
+                extra_fields: None,
             })
             .is_ok());
         assert_eq!(
@@ -1127,31 +1988,54 @@ mod tests {
                 address: MaybeVersionDep::ByVersion([("v1".into(), 1), ("v2".into(), 2)].into()),
                 length: MaybeVersionDep::ByVersion([("v1".into(), 10), ("v2".into(), 3)].into()),
                 description: None,
+                space_group: None,
                 subregions: None,
                 functions: [
                     Symbol {
                         name: "function1".to_string(),
+                        deprecated_aliases: None,
                         address: MaybeVersionDep::ByVersion(
                             [("v1".into(), 1.into()), ("v2".into(), 1.into())].into()
                         ),
                         length: None,
                         description: None,
+                        element_size: None,
+                        data_type: None,
+                        inlined: None,
+                        stype: None,
+                        ranges: None,
+                        extra_fields: None,
                     },
                     Symbol {
                         name: "function2".to_string(),
+                        deprecated_aliases: None,
                         address: MaybeVersionDep::ByVersion([("v2".into(), 1.into())].into()),
                         length: None,
                         description: None,
+                        element_size: None,
+                        data_type: None,
+                        inlined: None,
+                        stype: None,
+                        ranges: None,
+                        extra_fields: None,
                     },
                 ]
                 .into(),
                 data: [Symbol {
                     name: "data".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::ByVersion([("v2".into(), 1.into())].into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 }]
-                .into()
+                .into(),
+                extra_fields: None,
             }
         );
     }
@@ -1288,6 +2172,189 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_merge_symgen_summary() {
+        let mut x = get_simple_symgen();
+        let other = SymGen::read(
+            r#"
+            main:
+              versions:
+                - v1
+              address:
+                v1: 0x2000000
+              length:
+                v1: 0x100000
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2001000
+                - name: fn_new
+                  address:
+                    v1: 0x2003000
+              data: []
+            "#
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        let summary = x.merge_symgen(&other).expect("Merge failed");
+        assert_eq!(
+            summary,
+            MergeSummary {
+                added: 1,
+                merged: 1,
+                unmerged: Vec::new(),
+                blocks: BTreeMap::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_merge_symgen_summary_new_duplicates() {
+        let mut x = get_simple_symgen();
+        let other = SymGen::read(
+            r#"
+            main:
+              versions:
+                - v1
+              address:
+                v1: 0x2000000
+              length:
+                v1: 0x100000
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2001000
+                - name: fn_new
+                  address:
+                    v1: 0x2003000
+                - name: fn_new
+                  address:
+                    v1: 0x2003000
+              data: []
+            other:
+              address: 0x2400000
+              length: 0x1000
+              functions: []
+              data:
+                - name: OTHER_DATA
+                  address: 0x2400000
+                - name: OTHER_DATA
+                  address: 0x2400010
+            "#
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        let summary = x.merge_symgen(&other).expect("Merge failed");
+        // The second fn_new is merged into the entry added for the first one, but the new block
+        // is copied over as is
+        assert_eq!(summary.added, 3);
+        assert_eq!(summary.merged, 2);
+    }
+
+    #[test]
+    fn test_merge_collecting() {
+        let mut x = get_simple_symgen();
+        let conflicts = x.merge_collecting(
+            &SymGen::read(
+                r#"
+                main:
+                  versions:
+                    - v1
+                  address:
+                    v1: 0x2000000
+                  length:
+                    v1: 0x100004
+                  description: different
+                  functions:
+                    - name: fn1
+                      address:
+                        v1: 0x2001000
+                      length:
+                        v1: 0x1000
+                      description: also different
+                    - name: fn2
+                      address:
+                        v1: 0x2002000
+                  data: []
+                "#
+                .as_bytes(),
+            )
+            .expect("Read failed"),
+        );
+        assert_eq!(
+            conflicts.iter().map(|c| c.to_string()).collect::<Vec<_>>(),
+            vec![
+                "main: description: could not merge 'foo' with 'different'",
+                "main: length: v1: could not merge '0x100000' with '0x100004'",
+                "main: functions: fn1: description: could not merge 'bar' with 'also different'",
+            ]
+        );
+        // Non-conflicting changes are applied, and conflicting values are left unchanged
+        assert_eq!(
+            &x,
+            &SymGen::read(
+                r#"
+                main:
+                  versions:
+                    - v1
+                  address:
+                    v1: 0x2000000
+                  length:
+                    v1: 0x100000
+                  description: foo
+                  functions:
+                    - name: fn1
+                      address:
+                        v1: 0x2001000
+                      length:
+                        v1: 0x1000
+                      description: bar
+                    - name: fn2
+                      address:
+                        v1: 0x2002000
+                  data: []
+                "#
+                .as_bytes(),
+            )
+            .expect("Read failed")
+        );
+
+        // A clean merge has no conflicts
+        let mut y = get_simple_symgen();
+        assert!(y.merge_collecting(&get_simple_symgen()).is_empty());
+    }
+
+    #[test]
+    fn test_merge_symgen_rollback() {
+        let mut x = get_simple_symgen();
+        let original = x.clone();
+        let other = SymGen::read(
+            r#"
+            new_block:
+              address: 0x2100000
+              length: 0x1000
+              functions: []
+              data: []
+            main:
+              versions:
+                - v1
+              address:
+                v1: 0x2000000
+              length:
+                v1: 0x200000
+              functions:
+                - name: fn_new
+                  address:
+                    v1: 0x2003000
+              data: []
+            "#
+            .as_bytes(),
+        )
+        .expect("Read failed");
+        assert!(x.merge_symgen(&other).is_err());
+        assert_eq!(x, original);
+    }
+
     #[test]
     fn test_merge_symgen_with_subregions() {
         // The subregion handling code is in Block not SymGen, but it's easier to construct a
@@ -1472,11 +2539,18 @@ mod tests {
                 AddSymbol {
                     symbol: Symbol {
                         name: "fn1".to_string(),
+                        deprecated_aliases: None,
                         address: MaybeVersionDep::ByVersion(
                             [("v1".into(), 0x2002000.into())].into(),
                         ),
                         length: None,
                         description: None,
+                        element_size: None,
+                        data_type: None,
+                        inlined: None,
+                        stype: None,
+                        ranges: None,
+                        extra_fields: None,
                     },
                     stype: SymbolType::Function,
                     block_name: Some("main".to_string()),
@@ -1484,11 +2558,18 @@ mod tests {
                 AddSymbol {
                     symbol: Symbol {
                         name: "SOME_DATA".to_string(),
+                        deprecated_aliases: None,
                         address: MaybeVersionDep::ByVersion(
                             [("v1".into(), 0x2003000.into())].into(),
                         ),
                         length: None,
                         description: None,
+                        element_size: None,
+                        data_type: None,
+                        inlined: None,
+                        stype: None,
+                        ranges: None,
+                        extra_fields: None,
                     },
                     stype: SymbolType::Data,
                     block_name: Some("main".to_string()),
@@ -1527,7 +2608,7 @@ mod tests {
         let (mut x, add_symbols, expected) = get_merge_symbols_data();
         let res = x.merge_symbols(Box::new(add_symbols.into_iter()));
         assert!(res.is_ok());
-        assert!(res.unwrap().is_empty());
+        assert!(res.unwrap().unmerged.is_empty());
         assert_eq!(&x, &expected);
     }
 
@@ -1541,9 +2622,16 @@ mod tests {
         // Add a symbol for which block inference will fail
         let unmerged_symbol = Symbol {
             name: "fn3".to_string(),
+            deprecated_aliases: None,
             address: MaybeVersionDep::ByVersion([("v1".into(), 0x2200000.into())].into()),
             length: None,
             description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
         };
         add_symbols.push(AddSymbol {
             symbol: unmerged_symbol.clone(),
@@ -1553,7 +2641,13 @@ mod tests {
 
         let res = x.merge_symbols(Box::new(add_symbols.into_iter()));
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(), vec![unmerged_symbol.clone()]);
+        assert_eq!(
+            res.unwrap().unmerged,
+            vec![Symbol {
+                stype: Some(SymbolType::Function),
+                ..unmerged_symbol
+            }]
+        );
         assert_eq!(&x, &expected);
     }
 
@@ -1570,6 +2664,34 @@ mod tests {
         assert!(x.merge_symbols(Box::new(add_symbols.into_iter())).is_err());
     }
 
+    #[test]
+    fn test_merge_symbols_rollback() {
+        let (mut x, mut add_symbols, _) = get_merge_symbols_data();
+        let original = x.clone();
+        // The valid symbols come first, so they'd be merged before the failure
+        let mut missing_block = add_symbols[0].clone();
+        missing_block.block_name = Some("missing".to_string());
+        add_symbols.push(missing_block);
+        assert!(matches!(
+            x.merge_symbols(add_symbols.clone().into_iter()),
+            Err(MergeError::MissingBlock(_))
+        ));
+        assert_eq!(x, original);
+
+        // Conflicting with a symbol added earlier in the same call
+        add_symbols.pop();
+        for desc in ["first", "second"] {
+            let mut data = add_symbols[1].clone();
+            data.symbol.description = Some(MaybeVersionDep::Common(desc.to_string()));
+            add_symbols.push(data);
+        }
+        assert!(matches!(
+            x.merge_symbols(add_symbols.into_iter()),
+            Err(MergeError::Conflict(_))
+        ));
+        assert_eq!(x, original);
+    }
+
     fn get_merge_target_with_subregions() -> SymGen {
         test_utils::get_symgen_with_subregions(
             r#"main:
@@ -1620,17 +2742,31 @@ mod tests {
         let mut x = get_merge_target_with_subregions();
         let unmerged_symbol = Symbol {
             name: "unmerged".to_string(),
+            deprecated_aliases: None,
             address: MaybeVersionDep::Common(0x100.into()),
             length: None,
             description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
         };
         let add_symbols = vec![
             AddSymbol {
                 symbol: Symbol {
                     name: "main_fn".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(0x80.into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 stype: SymbolType::Function,
                 block_name: None,
@@ -1638,9 +2774,16 @@ mod tests {
             AddSymbol {
                 symbol: Symbol {
                     name: "sub1_data".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(0x0.into()),
                     length: Some(MaybeVersionDep::Common(0x4)),
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 stype: SymbolType::Data,
                 block_name: None,
@@ -1648,9 +2791,16 @@ mod tests {
             AddSymbol {
                 symbol: Symbol {
                     name: "sub2_fn".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(0x50.into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 stype: SymbolType::Function,
                 block_name: None,
@@ -1658,9 +2808,16 @@ mod tests {
             AddSymbol {
                 symbol: Symbol {
                     name: "sub3_data".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(0x60.into()),
                     length: Some(MaybeVersionDep::Common(0x4)),
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 stype: SymbolType::Data,
                 // Make sure providing the top-level block name doesn't mess anything up
@@ -1669,9 +2826,16 @@ mod tests {
             AddSymbol {
                 symbol: Symbol {
                     name: "sub3_fn".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(0x64.into()),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 stype: SymbolType::Function,
                 block_name: None,
@@ -1740,7 +2904,13 @@ mod tests {
 
         let res = x.merge_symbols(Box::new(add_symbols.into_iter()));
         assert!(res.is_ok());
-        assert_eq!(res.unwrap(), vec![unmerged_symbol.clone()]);
+        assert_eq!(
+            res.unwrap().unmerged,
+            vec![Symbol {
+                stype: Some(SymbolType::Function),
+                ..unmerged_symbol
+            }]
+        );
         assert_eq!(&x, &expected);
     }
 
@@ -1752,9 +2922,16 @@ mod tests {
                 vec![AddSymbol {
                     symbol: Symbol {
                         name: "fn1".to_string(),
+                        deprecated_aliases: None,
                         address: MaybeVersionDep::Common(0x40.into()), // Fits in both sub1 and sub2
                         length: None,
                         description: None,
+                        element_size: None,
+                        data_type: None,
+                        inlined: None,
+                        stype: None,
+                        ranges: None,
+                        extra_fields: None,
                     },
                     stype: SymbolType::Function,
                     block_name: None,
@@ -1763,4 +2940,123 @@ mod tests {
             ))
             .is_err());
     }
+
+    #[test]
+    fn test_block_index_matches_linear_search() {
+        // Lots of blocks of different kinds, so that many symbols hit edge cases
+        let mut yaml = String::new();
+        for i in 0..100u64 {
+            let (v1, v2) = (0x2000000 + i * 0x1000, 0x2000800 + i * 0x1000);
+            match i % 10 {
+                // Only has one version
+                0 => yaml.push_str(&format!(
+                    "b{i}:\n  versions: [v1]\n  address: {v1:#x}\n  length: 0x1000\n"
+                )),
+                // Duplicates another block, for ambiguity
+                1 => yaml.push_str(&format!(
+                    "b{i}:\n  versions: [v1, v2]\n  address:\n    v1: {0:#x}\n    v2: {1:#x}\n  length: 0x1000\n",
+                    v1 - 0x1000,
+                    v2 - 0x1000
+                )),
+                // Common extent
+                2 => yaml.push_str(&format!(
+                    "b{i}:\n  address: {v1:#x}\n  length: 0x1000\n"
+                )),
+                // Empty version list
+                3 => yaml.push_str(&format!(
+                    "b{i}:\n  versions: []\n  address: {v1:#x}\n  length: 0x1000\n"
+                )),
+                // Different lengths by version
+                4 => yaml.push_str(&format!(
+                    "b{i}:\n  versions: [v1, v2]\n  address:\n    v1: {v1:#x}\n    v2: {v2:#x}\n  length:\n    v1: 0x1000\n    v2: 0x10\n"
+                )),
+                _ => yaml.push_str(&format!(
+                    "b{i}:\n  versions: [v1, v2]\n  address:\n    v1: {v1:#x}\n    v2: {v2:#x}\n  length: 0x1000\n"
+                )),
+            }
+            yaml.push_str("  functions: []\n  data: []\n");
+        }
+        // Overlaps many other blocks
+        yaml.push_str(
+            "big:\n  address: 0x2040000\n  length: 0x100000\n  functions: []\n  data: []\n",
+        );
+        let symgen = SymGen::read(yaml.as_bytes()).expect("Read failed");
+        let index = BlockIndex::new(&symgen);
+
+        let symbol = |address, length| Symbol {
+            name: "sym".to_string(),
+            deprecated_aliases: None,
+            address,
+            length,
+            description: None,
+            element_size: None,
+            data_type: None,
+            inlined: None,
+            stype: None,
+            ranges: None,
+            extra_fields: None,
+        };
+        let mut symbols = Vec::new();
+        for addr in (0x1FFF000..0x2070000).step_by(0xBC4) {
+            for len in [None, Some(MaybeVersionDep::Common(0x400))] {
+                symbols.push(symbol(MaybeVersionDep::Common(addr.into()), len.clone()));
+                symbols.push(symbol(
+                    MaybeVersionDep::Common(Linkable::Multiple(vec![addr, addr + 0x3000])),
+                    len.clone(),
+                ));
+                symbols.push(symbol(
+                    MaybeVersionDep::ByVersion([("v1".into(), addr.into())].into()),
+                    len.clone(),
+                ));
+                symbols.push(symbol(
+                    MaybeVersionDep::ByVersion(
+                        [
+                            ("v1".into(), addr.into()),
+                            ("v2".into(), (addr + 0x800).into()),
+                        ]
+                        .into(),
+                    ),
+                    len.clone(),
+                ));
+                symbols.push(symbol(
+                    MaybeVersionDep::ByVersion([("v2".into(), addr.into())].into()),
+                    len.clone(),
+                ));
+                // Not a version of any block
+                symbols.push(symbol(
+                    MaybeVersionDep::ByVersion([("v3".into(), addr.into())].into()),
+                    len,
+                ));
+            }
+        }
+        symbols.push(symbol(
+            MaybeVersionDep::Common(Linkable::Multiple(vec![])),
+            None,
+        ));
+
+        let mut n_unique = 0;
+        let mut n_ambiguous = 0;
+        for s in symbols.iter() {
+            let linear: Vec<&str> = symgen
+                .iter()
+                .filter(|(_, block)| bounds::block_contains_symbol(block, s))
+                .map(|(bname, _)| bname.val.as_str())
+                .collect();
+            let indexed: Vec<&str> = index
+                .blocks_containing(&symgen, s)
+                .into_iter()
+                .map(|i| index.keys[i].val.as_str())
+                .collect();
+            assert_eq!(indexed, linear, "mismatch for {:?}", s.address);
+            match linear.len() {
+                0 => {}
+                1 => n_unique += 1,
+                _ => n_ambiguous += 1,
+            }
+        }
+        // Make sure all the cases were actually exercised
+        assert!(n_unique > 0);
+        assert!(n_ambiguous > 0);
+        assert!(symbols.len() - n_unique - n_ambiguous > 0);
+    }
 }
@@ -5,27 +5,69 @@ pub use cursor::{BlockCursor, SymGenCursor};
 
 use std::any;
 use std::borrow::Cow;
-use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashMap};
-use std::fmt::{self, Display, Formatter};
+use std::cmp::{self, Ordering};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Debug, Display, Formatter};
 use std::io::{self, Read, Write};
+use std::iter;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::result;
 use std::slice::SliceIndex;
 
 use regex::{Captures, Regex};
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
+use serde::ser::{SerializeMap, Serializer};
 use serde::{Deserialize, Serialize};
-use serde_yaml;
+use serde_yaml::{self, Mapping, Value};
 use syn::{self, LitStr};
 
+use super::adapter::SymbolType;
+use super::bounds;
 use super::error::{Error, Result, SubregionError};
 use super::types::*;
 
 /// Specifies how integers should be formatted during serialization.
-#[derive(Clone, Copy)]
+///
+/// The grouped formats separate digits with underscores for readability, in groups of three for
+/// decimal (e.g., `1_000_000`) and four for hexadecimal (e.g., `0x201_2345`).
+#[derive(Debug, Clone, Copy)]
 pub enum IntFormat {
     Decimal,
     Hexadecimal,
+    GroupedDecimal,
+    GroupedHexadecimal,
+}
+
+impl Default for IntFormat {
+    fn default() -> Self {
+        Self::Hexadecimal
+    }
+}
+
+impl IntFormat {
+    /// Formats `n` as a string according to the [`IntFormat`].
+    pub fn format(&self, n: Uint) -> String {
+        match self {
+            Self::Decimal => n.to_string(),
+            Self::Hexadecimal => format!("{:#X}", n),
+            Self::GroupedDecimal => group_digits(&n.to_string(), 3),
+            Self::GroupedHexadecimal => format!("0x{}", group_digits(&format!("{:X}", n), 4)),
+        }
+    }
+}
+
+/// Inserts an underscore between every `group_size` digits of `digits`, counting from the right.
+fn group_digits(digits: &str, group_size: usize) -> String {
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / group_size);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % group_size == 0 {
+            grouped.push('_');
+        }
+        grouped.push(c);
+    }
+    grouped
 }
 
 /// Information about a [`Block`] to be propagated down to the block's contents.
@@ -34,19 +76,131 @@ struct BlockContext {
 }
 
 /// A symbol in a `resymgen` symbol table, with some metadata.
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+///
+/// When serialized, fields are always written in a canonical order, independent of the order in
+/// which they're declared: identity and location come first (`name`, `deprecated_aliases`,
+/// `address`, `length`), followed by documentation (`description`), then the remaining metadata
+/// (`ranges`, `element_size`, `data_type`, `inlined`, `type`). Unrecognized fields (see
+/// [`Symbol::extra_fields`]) always come last, in their original order.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(deny_unknown_fields))]
 pub struct Symbol {
     /// The symbol name.
     pub name: String,
+    /// Former names of the symbol that are kept for compatibility, but should no longer be used.
+    pub deprecated_aliases: Option<Vec<String>>,
     /// The starting address of the symbol in memory.
     pub address: MaybeVersionDep<Linkable>,
     /// The length of the symbol in memory (in bytes).
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub length: Option<MaybeVersionDep<Uint>>,
-    /// A description of the symbol.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+    /// Additional memory ranges occupied by the symbol, for symbols that are logically a single
+    /// object but are physically split into multiple pieces.
+    pub ranges: Option<Vec<SymbolRange>>,
+    /// A description of the symbol, which can optionally differ by version.
+    pub description: Option<MaybeVersionDep<String>>,
+    /// The size of a single element (in bytes), if the symbol is an array.
+    pub element_size: Option<Uint>,
+    /// The name of the data type of the symbol (e.g., a struct or enum), if known. This is
+    /// free-form, and isn't interpreted by `resymgen` beyond being passed through to outputs that
+    /// can make use of it.
+    pub data_type: Option<String>,
+    /// Whether the symbol was inlined, and so has no standalone address in the binary.
+    pub inlined: Option<bool>,
+    /// The type of the symbol. If present, this takes precedence over the list the symbol is
+    /// defined in, and the symbol will be moved to the matching list on initialization.
+    #[serde(rename = "type")]
+    pub stype: Option<SymbolType>,
+    /// Fields not recognized by `resymgen`, which are only kept if explicitly allowed when
+    /// reading (see [`SymGen::read_allow_unknown_fields()`]).
+    #[serde(flatten)]
+    #[cfg_attr(feature = "schema", schemars(skip))]
+    pub extra_fields: Option<Mapping>,
+}
+
+impl Serialize for Symbol {
+    fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // Destructure exhaustively so that new fields can't be forgotten here
+        let Symbol {
+            name,
+            deprecated_aliases,
+            address,
+            length,
+            ranges,
+            description,
+            element_size,
+            data_type,
+            inlined,
+            stype,
+            extra_fields,
+        } = self;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("name", name)?;
+        if let Some(aliases) = deprecated_aliases {
+            map.serialize_entry("deprecated_aliases", aliases)?;
+        }
+        // Expressions that were expanded by version in order to be resolved are written back in
+        // their original, version-independent form
+        let mut exprs = address.values().map(|l| match l {
+            Linkable::Expr(e) if e.common => Some(e),
+            _ => None,
+        });
+        match exprs.next() {
+            Some(Some(first))
+                if matches!(address, MaybeVersionDep::ByVersion(_))
+                    && exprs.all(|e| e == Some(first)) =>
+            {
+                map.serialize_entry(
+                    "address",
+                    &MaybeVersionDep::Common(Linkable::Expr(first.clone())),
+                )?
+            }
+            _ => map.serialize_entry("address", address)?,
+        }
+        if let Some(length) = length {
+            map.serialize_entry("length", length)?;
+        }
+        if let Some(desc) = description {
+            map.serialize_entry("description", desc)?;
+        }
+        if let Some(ranges) = ranges {
+            map.serialize_entry("ranges", ranges)?;
+        }
+        if let Some(size) = element_size {
+            map.serialize_entry("element_size", size)?;
+        }
+        if let Some(dtype) = data_type {
+            map.serialize_entry("data_type", dtype)?;
+        }
+        if let Some(inlined) = inlined {
+            map.serialize_entry("inlined", inlined)?;
+        }
+        if let Some(stype) = stype {
+            map.serialize_entry("type", stype)?;
+        }
+        if let Some(extra) = extra_fields {
+            for (key, value) in extra {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// An additional contiguous memory range occupied by a [`Symbol`], beyond the one specified by the
+/// [`Symbol`]'s own address and length.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct SymbolRange {
+    /// The starting address of the range in memory.
+    pub address: MaybeVersionDep<Uint>,
+    /// The length of the range in memory (in bytes).
+    pub length: MaybeVersionDep<Uint>,
 }
 
 /// Combines possibly version-dependent `addrs` and `opt_len` into a single `MaybeVersionDep`
@@ -88,6 +242,166 @@ where
     }
 }
 
+/// Gets the versions of the address that `expr` is ultimately relative to (following any chain of
+/// version-independent expressions), using the addresses of all symbols by name in `addrs`.
+///
+/// Returns [`None`] if the address is version-independent, or if the chain can't be followed, in
+/// which case resolving `expr` reports the problem.
+fn address_expr_versions(
+    expr: &AddressExpr,
+    addrs: &HashMap<String, MaybeVersionDep<Linkable>>,
+) -> Option<Vec<Version>> {
+    let mut seen = HashSet::new();
+    let mut base = &expr.base;
+    while seen.insert(base) {
+        match addrs.get(base)? {
+            MaybeVersionDep::Common(Linkable::Expr(e)) => base = &e.base,
+            MaybeVersionDep::Common(_) => return None,
+            by_version => return Some(by_version.versions().cloned().collect()),
+        }
+    }
+    None
+}
+
+/// Resolves `expr` for the given `version`, using the addresses of all symbols by name in
+/// `addrs`.
+///
+/// `chain` contains the names of the symbols currently being resolved, starting with the symbol
+/// whose address is `expr`, and is used to detect cycles.
+fn resolve_address_expr(
+    expr: &AddressExpr,
+    version: Option<&Version>,
+    addrs: &HashMap<String, MaybeVersionDep<Linkable>>,
+    chain: &mut Vec<String>,
+) -> result::Result<Uint, String> {
+    let referrer = chain.last().cloned().unwrap_or_default();
+    if chain.contains(&expr.base) {
+        chain.push(expr.base.clone());
+        return Err(format!(
+            "cyclic address expressions: {}",
+            chain.join(" -> ")
+        ));
+    }
+    let base_addrs = addrs.get(&expr.base).ok_or_else(|| {
+        format!(
+            "symbol \"{}\" has an address relative to undefined symbol \"{}\"",
+            referrer, expr.base
+        )
+    })?;
+    let vers_str = match version {
+        Some(v) => format!("for version \"{}\"", v),
+        None => "common to all versions".to_string(),
+    };
+    let base = match base_addrs.get(version) {
+        Some(Linkable::Single(x)) => *x,
+        Some(Linkable::Multiple(_)) => {
+            return Err(format!(
+                "symbol \"{}\" has an address relative to symbol \"{}\", which has multiple addresses {}",
+                referrer, expr.base, vers_str
+            ))
+        }
+        Some(Linkable::Expr(e)) => {
+            chain.push(expr.base.clone());
+            let base = resolve_address_expr(e, version, addrs, chain)?;
+            chain.pop();
+            base
+        }
+        None => {
+            return Err(format!(
+                "symbol \"{}\" has an address relative to symbol \"{}\", which has no address {}",
+                referrer, expr.base, vers_str
+            ))
+        }
+    };
+    let addr = if expr.offset < 0 {
+        base.checked_sub(expr.offset.unsigned_abs() as Uint)
+    } else {
+        base.checked_add(expr.offset as Uint)
+    };
+    addr.ok_or_else(|| {
+        format!(
+            "symbol \"{}\" has an out-of-range address '{}'",
+            referrer, expr
+        )
+    })
+}
+
+/// Finds the (1-based) line number of the field `key` within the block named `block_name`, or
+/// within the symbol named `symbol_name` in that block, in `resymgen` YAML text.
+///
+/// This is a best-effort search through the raw text, for pointing to unknown fields in error
+/// messages after deserialization.
+fn unknown_field_line(
+    yaml: &str,
+    block_name: &str,
+    symbol_name: Option<&str>,
+    key: &str,
+) -> Option<usize> {
+    fn unquote(s: &str) -> &str {
+        s.trim().trim_matches(|c| c == '"' || c == '\'')
+    }
+    fn has_key(content: &str, key: &str) -> bool {
+        content
+            .strip_prefix(key)
+            .map_or(false, |rest| rest.starts_with(':'))
+    }
+    // (line number, whether the line starts a list item, level of whitespace with any list
+    // hyphens stripped, content)
+    let lines: Vec<(usize, bool, usize, &str)> = yaml
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let trimmed = line.trim_start();
+            let content = trimmed.trim_start_matches('-').trim_start();
+            (
+                i + 1,
+                trimmed.starts_with('-'),
+                line.len() - content.len(),
+                content,
+            )
+        })
+        .collect();
+    let is_top_level = |&(_, _, indent, content): &(usize, bool, usize, &str)| {
+        indent == 0 && !content.is_empty() && !content.starts_with('#')
+    };
+
+    // A block spans from its key up to the next top-level key
+    let start = lines
+        .iter()
+        .position(|l| is_top_level(l) && l.3.split(':').next().map(unquote) == Some(block_name))?
+        + 1;
+    let end = lines[start..]
+        .iter()
+        .position(is_top_level)
+        .map_or(lines.len(), |i| start + i);
+    let mut fields = &lines[start..end];
+    if let Some(symbol_name) = symbol_name {
+        let name_pos = fields.iter().position(|&(_, _, _, content)| {
+            has_key(content, "name") && unquote(&content["name".len() + 1..]) == symbol_name
+        })?;
+        // A symbol spans one list item, with all its fields at the same level
+        let level = fields[name_pos].2;
+        let item_start = fields[..=name_pos]
+            .iter()
+            .rposition(|&(_, item, indent, _)| item && indent == level)?;
+        let item_end = fields[name_pos + 1..]
+            .iter()
+            .position(|&(_, item, indent, content)| {
+                (item && indent == level) || (!content.is_empty() && indent < level)
+            })
+            .map_or(fields.len(), |i| name_pos + 1 + i);
+        fields = &fields[item_start..item_end];
+    }
+    let level = fields
+        .iter()
+        .find(|l| !l.3.is_empty() && !l.3.starts_with('#'))?
+        .2;
+    fields
+        .iter()
+        .find(|&&(_, _, indent, content)| indent == level && has_key(content, key))
+        .map(|l| l.0)
+}
+
 impl Symbol {
     /// Initializes the [`Symbol`] with a given `ctx`.
     fn init(&mut self, ctx: &BlockContext) {
@@ -95,6 +409,120 @@ impl Symbol {
         if let Some(l) = &mut self.length {
             l.init(&ctx.version_order);
         }
+        for r in self.ranges.iter_mut().flatten() {
+            r.address.init(&ctx.version_order);
+            r.length.init(&ctx.version_order);
+        }
+        if let Some(d) = &mut self.description {
+            d.init(&ctx.version_order);
+        }
+    }
+    /// Returns an [`Iterator`] over the deprecated aliases of the [`Symbol`] (if any).
+    pub fn iter_aliases(&self) -> impl Iterator<Item = &str> {
+        self.deprecated_aliases.iter().flatten().map(|a| a.as_str())
+    }
+    /// Returns an [`Iterator`] over the name of the [`Symbol`], followed by its deprecated
+    /// aliases (if any).
+    pub fn iter_names(&self) -> impl Iterator<Item = &str> {
+        iter::once(self.name.as_str()).chain(self.iter_aliases())
+    }
+    /// Whether the [`Symbol`] is marked as inlined.
+    ///
+    /// Inlined symbols have no standalone address in the binary, so they're skipped by the checks
+    /// that deal with occupied address space (like [`Check::NoOverlap`]) and by coverage analysis.
+    /// They're still realized, with [`RealizedSymbol::inlined`] set, and each output format that
+    /// writes addresses filters them out itself.
+    ///
+    /// [`Check::NoOverlap`]: crate::Check::NoOverlap
+    pub fn is_inlined(&self) -> bool {
+        self.inlined.unwrap_or(false)
+    }
+    /// Whether the [`Symbol`] has a non-blank description (for any version).
+    pub fn is_documented(&self) -> bool {
+        self.description
+            .iter()
+            .flat_map(|d| d.values())
+            .any(|d| !d.trim().is_empty())
+    }
+    /// Gets the description of the [`Symbol`] for the given `version`, if there is one.
+    ///
+    /// A version-independent description applies to every version (including [`None`]).
+    pub fn description_for(&self, version: Option<&Version>) -> Option<&str> {
+        self.description
+            .as_ref()
+            .and_then(|d| d.get(version))
+            .map(String::as_str)
+    }
+    /// Gets the address of the [`Symbol`] for the [`Version`] in `block` named `version_name`.
+    ///
+    /// If the address is [`Common`], it is returned regardless of `version_name`.
+    ///
+    /// [`Common`]: MaybeVersionDep::Common
+    pub fn address_for_name(&self, version_name: &str, block: &Block) -> Option<&Linkable> {
+        self.address.get(block.version(version_name))
+    }
+    /// Drops all but the first address of the [`Symbol`] for every version with multiple
+    /// addresses. Returns whether any addresses were dropped.
+    pub fn keep_first_address(&mut self) -> bool {
+        let mut changed = false;
+        for addr in self.address.values_mut() {
+            if let Linkable::Multiple(v) = addr {
+                if let Some(&first) = v.first() {
+                    changed |= v.len() > 1;
+                    *addr = Linkable::Single(first);
+                }
+            }
+        }
+        changed
+    }
+    /// Renames the [`Version`]s in all of the [`Symbol`]'s version-dependent fields according to
+    /// `renames`, which maps old version names to new ones.
+    pub fn rename_versions(&mut self, renames: &HashMap<String, String>) {
+        self.address.rename_versions(renames);
+        if let Some(l) = &mut self.length {
+            l.rename_versions(renames);
+        }
+        if let Some(d) = &mut self.description {
+            d.rename_versions(renames);
+        }
+        for r in self.ranges.iter_mut().flatten() {
+            r.address.rename_versions(renames);
+            r.length.rename_versions(renames);
+        }
+    }
+    /// Fills in the [`Symbol`]'s version-dependent fields for `version` with the values for
+    /// `base`, wherever there's a value for `base` but not for `version`.
+    fn fill_version_from(&mut self, version: &Version, base: &Version) {
+        self.address.fill_version_from(version, base);
+        if let Some(l) = &mut self.length {
+            l.fill_version_from(version, base);
+        }
+        if let Some(d) = &mut self.description {
+            d.fill_version_from(version, base);
+        }
+        for r in self.ranges.iter_mut().flatten() {
+            r.address.fill_version_from(version, base);
+            r.length.fill_version_from(version, base);
+        }
+    }
+    /// Removes all version-dependent data in the [`Symbol`] that doesn't belong to the [`Version`]
+    /// named `version_name`.
+    fn restrict_to_version(&mut self, version_name: &str) {
+        let keep = |v: &Version| v.name() == version_name;
+        self.address.retain_versions(keep);
+        if let Some(l) = &mut self.length {
+            l.retain_versions(keep);
+        }
+        if let Some(d) = &mut self.description {
+            d.retain_versions(keep);
+        }
+        if let Some(ranges) = &mut self.ranges {
+            for r in ranges.iter_mut() {
+                r.address.retain_versions(keep);
+                r.length.retain_versions(keep);
+            }
+            ranges.retain(|r| !r.address.is_empty());
+        }
     }
     /// Coerces the [`Symbol`]'s address and length fields to be [`ByVersion`].
     ///
@@ -107,6 +535,10 @@ impl Symbol {
         if let Some(len) = &mut self.length {
             len.expand_versions(all_versions);
         }
+        for r in self.ranges.iter_mut().flatten() {
+            r.address.expand_versions(all_versions);
+            r.length.expand_versions(all_versions);
+        }
     }
     /// Gets the extents occupied by the [`Symbol`], possibly by version, represented as
     /// address-length pairs.
@@ -128,6 +560,29 @@ impl Symbol {
             None => zip_addr_len(&self.address, self.length.as_ref()),
         }
     }
+    /// Gets the extents of the additional memory ranges occupied by the [`Symbol`] (see
+    /// [`SymbolRange`]), possibly by version, represented as address-length pairs.
+    ///
+    /// If the optional `all_versions` is provided, the returned extents are guaranteed to be
+    /// [`ByVersion`].
+    ///
+    /// [`ByVersion`]: MaybeVersionDep::ByVersion
+    pub fn range_extents(
+        &self,
+        all_versions: Option<&[Version]>,
+    ) -> Vec<MaybeVersionDep<(Uint, Option<Uint>)>> {
+        self.ranges
+            .iter()
+            .flatten()
+            .map(|r| match all_versions {
+                Some(versions) => zip_addr_len(
+                    &MaybeVersionDep::ByVersion(r.address.by_version(versions)),
+                    Some(&r.length),
+                ),
+                None => zip_addr_len(&r.address, Some(&r.length)),
+            })
+            .collect()
+    }
 }
 
 impl Sort for Symbol {
@@ -153,11 +608,19 @@ impl Ord for Symbol {
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub struct RealizedSymbol<'a> {
     pub name: &'a str,
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub deprecated_aliases: Option<&'a [String]>,
     pub address: Uint,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub length: Option<Uint>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<&'a str>,
+    /// Whether the symbol was inlined. The address of an inlined symbol isn't a standalone
+    /// address in the binary, so it should be skipped by anything that deals with addresses.
+    #[serde(skip)]
+    pub inlined: bool,
 }
 
 /// Wraps an [`Iterator`] over [`Symbol`]s to yield a stream of [`RealizedSymbol`]s.
@@ -201,9 +664,12 @@ where
                 self.cur = Some((symbol, addrs, len));
                 return Some(RealizedSymbol {
                     name: &symbol.name,
+                    deprecated_aliases: symbol.deprecated_aliases.as_deref(),
                     address: a,
                     length: len.copied(),
-                    description: symbol.description.as_deref(),
+                    description: symbol.description_for(self.version),
+                    data_type: symbol.data_type.as_deref(),
+                    inlined: symbol.is_inlined(),
                 });
             }
             // cur is depleted; don't put it back and get a new one next loop
@@ -235,11 +701,116 @@ where
     }
 }
 
+/// The kinds of memory regions in a [`LayoutEntry`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutKind {
+    Block,
+    Function,
+    Data,
+}
+
+/// A memory region occupied by a [`Block`] or [`Symbol`] for some [`Version`], as returned by
+/// [`SymGen::memory_layout()`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct LayoutEntry<'a> {
+    /// The starting address of the region.
+    pub start: Uint,
+    /// The end address of the region (exclusive). Equal to `start` if the length is unknown.
+    pub end: Uint,
+    pub name: &'a str,
+    pub kind: LayoutKind,
+    /// The nesting depth of the region. Top-level blocks have a depth of 0, and the contents of a
+    /// block (symbols and subregion blocks) are one level deeper than the block itself.
+    pub depth: usize,
+}
+
+/// An index of the extents covered by symbols for some [`Version`], for looking up symbols by
+/// address. Returned by [`SymGen::symbol_index()`].
+///
+/// Building the index realizes and sorts every symbol once, so it should be reused for repeated
+/// lookups against the same [`Version`].
+#[derive(Debug, Clone)]
+pub struct SymbolIndex<'a> {
+    /// Symbols sorted by starting address, along with the extent each one covers. Symbols with the
+    /// same starting address keep their original order.
+    extents: Vec<(RealizedSymbol<'a>, (Uint, Option<Uint>))>,
+    /// The highest end address (exclusive) among the extents up to and including each position,
+    /// where [`None`] means unbounded.
+    max_ends: Vec<Option<Uint>>,
+}
+
+impl<'a> SymbolIndex<'a> {
+    fn new(mut extents: Vec<(RealizedSymbol<'a>, (Uint, Option<Uint>))>) -> Self {
+        extents.sort_by_key(|&(_, (addr, _))| addr);
+        let mut max_ends = Vec::with_capacity(extents.len());
+        let mut max_end = Some(0);
+        for &(_, (addr, len)) in extents.iter() {
+            let end = len.map(|l| addr.saturating_add(l));
+            max_end = max_end.zip(end).map(|(m, e)| cmp::max(m, e));
+            max_ends.push(max_end);
+        }
+        Self { extents, max_ends }
+    }
+    /// Looks up the symbol at the address `addr`.
+    ///
+    /// See [`SymGen::symbol_at()`] for how symbols are matched.
+    pub fn symbol_at(&self, addr: Uint) -> Option<RealizedSymbol<'a>> {
+        let candidates = self
+            .extents
+            .partition_point(|&(_, (start, _))| start <= addr);
+        // Walk backwards from the last extent starting at or before `addr`, until no earlier
+        // extent can reach `addr`
+        (0..candidates)
+            .rev()
+            .take_while(|&i| !matches!(self.max_ends[i], Some(end) if end <= addr))
+            .map(|i| (i, self.extents[i]))
+            .filter(|&(_, (_, extent))| bounds::contains_address(extent, addr))
+            // Ties go to the symbol that comes first in the original order
+            .min_by_key(|&(i, (_, (_, len)))| (len.is_none(), len, i))
+            .map(|(_, (s, _))| s)
+    }
+}
+
+/// A lookup table from symbol names to positions within a [`SymbolList`]. If multiple symbols
+/// have the same name, the first one takes precedence.
+#[derive(Clone, Default)]
+struct NameIndex {
+    positions: HashMap<String, usize>,
+    /// The position of the last symbol lent out mutably by name, which might have been renamed.
+    lent: Option<usize>,
+}
+
+impl NameIndex {
+    fn new(symbols: &[Symbol]) -> Self {
+        let mut index = Self::default();
+        for (i, symbol) in symbols.iter().enumerate() {
+            index.insert(&symbol.name, i);
+        }
+        index
+    }
+    fn insert(&mut self, name: &str, i: usize) {
+        if !self.positions.contains_key(name) {
+            self.positions.insert(name.to_owned(), i);
+        }
+    }
+    /// Checks whether the index still agrees with `symbols`.
+    fn is_valid(&self, symbols: &[Symbol]) -> bool {
+        // All other modifications are accounted for eagerly. If the lent symbol still has its
+        // original name, it will still be indexed under its own position.
+        self.lent
+            .map_or(true, |i| self.positions.get(&symbols[i].name) == Some(&i))
+    }
+}
+
 /// A list of [`Symbol`]s.
 ///
-/// Implements a similar accessor interface to [`Vec<Symbol>`].
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-pub struct SymbolList(Vec<Symbol>);
+/// Implements a similar accessor interface to [`Vec<Symbol>`]. Symbols can also be looked up by
+/// name, in constant time once the list has been indexed by name (see
+/// [`SymbolList::index_by_name()`]).
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SymbolList(Vec<Symbol>, #[serde(skip)] Option<NameIndex>);
 
 impl SymbolList {
     /// Initializes the [`SymbolList`] with a given `ctx`.
@@ -248,6 +819,21 @@ impl SymbolList {
             symbol.init(ctx);
         }
     }
+    /// Removes and returns all the [`Symbol`]s with an explicit type other than `stype`.
+    fn take_mistyped(&mut self, stype: SymbolType) -> Vec<Symbol> {
+        let (mistyped, kept) = std::mem::take(&mut self.0)
+            .into_iter()
+            .partition(|s| matches!(s.stype, Some(t) if t != stype));
+        self.0 = kept;
+        self.reindex();
+        mistyped
+    }
+    /// Rebuilds the name index if there is one, after the list has been reordered or shrunk.
+    fn reindex(&mut self) {
+        if self.1.is_some() {
+            self.1 = Some(NameIndex::new(&self.0));
+        }
+    }
     /// Expands the versions of all the [`Symbol`]s contained within the [`SymbolList`].
     ///
     /// See [`Symbol::expand_versions()`].
@@ -256,6 +842,42 @@ impl SymbolList {
             symbol.expand_versions(all_versions);
         }
     }
+    /// Indexes the [`SymbolList`] by symbol name, so that subsequent lookups by name take
+    /// constant time rather than scanning the list.
+    ///
+    /// The index is kept up to date as the list is modified through its own methods, except that
+    /// mutable access by position (with [`get_mut()`] or [`get_unchecked_mut()`]) discards it,
+    /// since symbols might be renamed.
+    ///
+    /// [`get_mut()`]: SymbolList::get_mut
+    /// [`get_unchecked_mut()`]: SymbolList::get_unchecked_mut
+    pub fn index_by_name(&mut self) {
+        if !matches!(&self.1, Some(index) if index.is_valid(&self.0)) {
+            self.1 = Some(NameIndex::new(&self.0));
+        }
+    }
+    /// Returns the position of the first [`Symbol`] named `name`, if any.
+    pub fn position_by_name(&self, name: &str) -> Option<usize> {
+        match &self.1 {
+            Some(index) if index.is_valid(&self.0) => index.positions.get(name).copied(),
+            _ => self.0.iter().position(|s| s.name == name),
+        }
+    }
+    /// Returns a reference to the first [`Symbol`] named `name`, if any.
+    pub fn find_by_name(&self, name: &str) -> Option<&Symbol> {
+        self.position_by_name(name).map(|i| &self.0[i])
+    }
+    /// Returns a mutable reference to the first [`Symbol`] named `name`, if any.
+    ///
+    /// This indexes the [`SymbolList`] by name if it isn't already (see
+    /// [`SymbolList::index_by_name()`]), so repeated lookups are fast.
+    pub fn find_by_name_mut(&mut self, name: &str) -> Option<&mut Symbol> {
+        self.index_by_name();
+        let index = self.1.as_mut().unwrap();
+        let i = index.positions.get(name).copied()?;
+        index.lent = Some(i);
+        Some(&mut self.0[i])
+    }
 
     pub fn get<I>(&self, index: I) -> Option<&<I as SliceIndex<[Symbol]>>::Output>
     where
@@ -267,6 +889,7 @@ impl SymbolList {
     where
         I: SliceIndex<[Symbol]>,
     {
+        self.1 = None;
         self.0.get_mut(index)
     }
     /// Returns a mutable reference to a symbol, without doing bounds checking.
@@ -280,6 +903,7 @@ impl SymbolList {
     where
         I: SliceIndex<[Symbol]>,
     {
+        self.1 = None;
         self.0.get_unchecked_mut(index)
     }
     pub fn iter(&self) -> impl Iterator<Item = &Symbol> {
@@ -292,13 +916,58 @@ impl SymbolList {
         self.0.is_empty()
     }
     pub fn push(&mut self, value: Symbol) {
+        if let Some(index) = &mut self.1 {
+            index.insert(&value.name, self.0.len());
+        }
         self.0.push(value)
     }
     pub fn append(&mut self, other: &mut SymbolList) {
-        self.0.append(&mut other.0)
+        self.extend(other.0.drain(..));
+        other.reindex();
+    }
+    fn extend<I: IntoIterator<Item = Symbol>>(&mut self, symbols: I) {
+        for symbol in symbols {
+            self.push(symbol);
+        }
+    }
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Symbol) -> bool,
+    {
+        self.0.retain(f);
+        self.reindex();
+    }
+}
+
+// Implemented manually so the schema still has a SymbolList definition, rather than being inlined
+// as with the derived implementation for a transparent struct
+#[cfg(feature = "schema")]
+impl JsonSchema for SymbolList {
+    fn schema_name() -> String {
+        "SymbolList".to_string()
+    }
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let mut schema = Vec::<Symbol>::json_schema(gen).into_object();
+        schema.metadata().description = Some("A list of [`Symbol`]s.".to_string());
+        schema.into()
+    }
+}
+
+impl Debug for SymbolList {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("SymbolList").field(&self.0).finish()
+    }
+}
+
+// The name index is just a cache, so it doesn't affect equality
+impl PartialEq for SymbolList {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
     }
 }
 
+impl Eq for SymbolList {}
+
 impl Deref for SymbolList {
     type Target = [Symbol];
 
@@ -309,7 +978,7 @@ impl Deref for SymbolList {
 
 impl<const N: usize> From<[Symbol; N]> for SymbolList {
     fn from(arr: [Symbol; N]) -> Self {
-        SymbolList(Vec::from(arr))
+        SymbolList(Vec::from(arr), None)
     }
 }
 
@@ -319,6 +988,7 @@ impl Sort for SymbolList {
             symbol.sort();
         }
         self.0.sort();
+        self.reindex();
     }
 }
 
@@ -338,7 +1008,8 @@ fn option_vec_is_empty<T>(opt: &Option<Vec<T>>) -> bool {
 /// Every [`Block`] contains two separate [`SymbolList`]s: one for function symbols, and one for
 /// data symbols.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[cfg_attr(feature = "schema", schemars(deny_unknown_fields))]
 pub struct Block {
     // Metadata
     /// List of [`Version`]s relevant to the block.
@@ -351,6 +1022,13 @@ pub struct Block {
     /// A description of the block.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// The name of the group of blocks that can occupy memory at the same time.
+    ///
+    /// Blocks in different groups are never resident simultaneously (like overlays that load at
+    /// the same address), so they are allowed to overlap. Blocks without a group are assumed to
+    /// be resident alongside every other block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub space_group: Option<String>,
 
     // Symbols
     /// List of subregions.
@@ -360,6 +1038,11 @@ pub struct Block {
     pub functions: SymbolList,
     /// List of data symbols.
     pub data: SymbolList,
+    /// Fields not recognized by `resymgen`, which are only kept if explicitly allowed when
+    /// reading (see [`SymGen::read_allow_unknown_fields()`]).
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schemars(skip))]
+    pub extra_fields: Option<Mapping>,
 }
 
 impl Block {
@@ -382,6 +1065,12 @@ impl Block {
         self.address.init(&ctx.version_order);
         self.length.init(&ctx.version_order);
 
+        // Move symbols with an explicit type into the matching list
+        let data = self.functions.take_mistyped(SymbolType::Function);
+        let functions = self.data.take_mistyped(SymbolType::Data);
+        self.functions.extend(functions);
+        self.data.extend(data);
+
         // Init symbols
         self.functions.init(&ctx);
         self.data.init(&ctx);
@@ -391,6 +1080,19 @@ impl Block {
     /// [`Subregion`]s are read from files using `file_opener`, with file paths based on the root
     /// directory specified by `dir_path`.
     pub fn resolve_subregions<P, R, F>(&mut self, dir_path: P, file_opener: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        R: Read,
+        F: Fn(&Path) -> io::Result<R> + Copy,
+    {
+        self.resolve_subregions_impl(dir_path, file_opener, false)
+    }
+    fn resolve_subregions_impl<P, R, F>(
+        &mut self,
+        dir_path: P,
+        file_opener: F,
+        allow_unknown_fields: bool,
+    ) -> Result<()>
     where
         P: AsRef<Path>,
         R: Read,
@@ -398,7 +1100,7 @@ impl Block {
     {
         if let Some(subregions) = &mut self.subregions {
             for s in subregions.iter_mut() {
-                s.resolve(&dir_path, file_opener)?;
+                s.resolve_impl(&dir_path, file_opener, allow_unknown_fields)?;
                 // Recursively resolve
                 let subdir_path = dir_path.as_ref().join(Subregion::subregion_dir(&s.name));
                 // Explicitly block symlinks, which could lead to infinite recursion.
@@ -412,7 +1114,7 @@ impl Block {
                 s.contents
                     .as_mut()
                     .expect("subregion not resolved after Subregion::resolve()")
-                    .resolve_subregions(&subdir_path, file_opener)?;
+                    .resolve_subregions_impl(&subdir_path, file_opener, allow_unknown_fields)?;
             }
         }
         Ok(())
@@ -433,6 +1135,164 @@ impl Block {
             }
         }
     }
+    /// Retains only the [`Symbol`]s for which `f` returns `true`, including [`Symbol`]s within
+    /// resolved [`Subregion`]s.
+    pub fn retain_symbols<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Symbol) -> bool,
+    {
+        self.retain_symbols_dyn(&mut f)
+    }
+    fn retain_symbols_dyn(&mut self, f: &mut dyn FnMut(&Symbol) -> bool) {
+        self.functions.retain(|s| f(s));
+        self.data.retain(|s| f(s));
+        for subregion in self.subregions.iter_mut().flatten() {
+            if let Some(symgen) = &mut subregion.contents {
+                for block in symgen.blocks_mut() {
+                    block.retain_symbols_dyn(f);
+                }
+            }
+        }
+    }
+    /// Replaces the name and deprecated aliases of every [`Symbol`] with the result of calling `f`
+    /// on them, including [`Symbol`]s within resolved [`Subregion`]s.
+    pub fn rename_symbols<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&str) -> String,
+    {
+        self.rename_symbols_dyn(&mut f)
+    }
+    fn rename_symbols_dyn(&mut self, f: &mut dyn FnMut(&str) -> String) {
+        for list in [&mut self.functions, &mut self.data] {
+            for i in 0..list.len() {
+                let symbol = list.get_mut(i).unwrap();
+                symbol.name = f(&symbol.name);
+                for alias in symbol.deprecated_aliases.iter_mut().flatten() {
+                    *alias = f(alias);
+                }
+            }
+        }
+        for subregion in self.subregions.iter_mut().flatten() {
+            if let Some(symgen) = &mut subregion.contents {
+                for block in symgen.blocks_mut() {
+                    block.rename_symbols_dyn(f);
+                }
+            }
+        }
+    }
+    /// Restricts the [`Block`] to the [`Version`] named `version_name`.
+    ///
+    /// All version-dependent data for other versions is removed, including from the version list
+    /// and from resolved [`Subregion`]s. Symbols left without an address are removed entirely.
+    pub fn restrict_to_version(&mut self, version_name: &str) {
+        let keep = |v: &Version| v.name() == version_name;
+        if let Some(vers) = &mut self.versions {
+            vers.retain(|v| keep(v));
+        }
+        self.address.retain_versions(keep);
+        self.length.retain_versions(keep);
+        for list in [&mut self.functions, &mut self.data] {
+            for i in 0..list.len() {
+                list.get_mut(i).unwrap().restrict_to_version(version_name);
+            }
+            list.retain(|s| !s.address.is_empty());
+        }
+        for subregion in self.subregions.iter_mut().flatten() {
+            if let Some(symgen) = &mut subregion.contents {
+                symgen.restrict_to_version(version_name);
+            }
+        }
+    }
+    /// Renames [`Version`]s throughout the [`Block`] according to `renames`, which maps old
+    /// version names to new ones.
+    ///
+    /// This includes the version list, block metadata, all [`Symbol`]s, and resolved
+    /// [`Subregion`]s.
+    pub fn rename_versions(&mut self, renames: &HashMap<String, String>) {
+        for v in self.versions.iter_mut().flatten() {
+            v.rename(renames);
+        }
+        self.address.rename_versions(renames);
+        self.length.rename_versions(renames);
+        for list in [&mut self.functions, &mut self.data] {
+            for i in 0..list.len() {
+                list.get_mut(i).unwrap().rename_versions(renames);
+            }
+        }
+        for subregion in self.subregions.iter_mut().flatten() {
+            if let Some(symgen) = &mut subregion.contents {
+                symgen.rename_versions(renames);
+            }
+        }
+    }
+    /// Uses the data for the [`Version`] named `base_name` as a fallback for the [`Version`] named
+    /// `version_name`, wherever the [`Block`] has data for the former but not the latter. If the
+    /// [`Block`]'s version list doesn't include `version_name`, it's appended.
+    ///
+    /// Nothing is changed if the [`Block`]'s version list doesn't include `base_name`. Resolved
+    /// [`Subregion`]s are filled in as well.
+    pub fn fill_version_from(&mut self, version_name: &str, base_name: &str) {
+        for subregion in self.subregions.iter_mut().flatten() {
+            if let Some(symgen) = &mut subregion.contents {
+                symgen.fill_version_from(version_name, base_name);
+            }
+        }
+        let base = match self.version(base_name) {
+            Some(base) => base.clone(),
+            None => return,
+        };
+        let version = match self.version(version_name) {
+            Some(version) => version.clone(),
+            None => {
+                let versions = self.versions.get_or_insert_with(Vec::new);
+                let version = Version::from((version_name, versions.len() as u64));
+                versions.push(version.clone());
+                version
+            }
+        };
+        self.address.fill_version_from(&version, &base);
+        self.length.fill_version_from(&version, &base);
+        for list in [&mut self.functions, &mut self.data] {
+            for i in 0..list.len() {
+                list.get_mut(i).unwrap().fill_version_from(&version, &base);
+            }
+        }
+    }
+    /// Moves all symbols within the [`Subregion`] named `name` (including symbols within its own
+    /// nested [`Subregion`]s) into the [`Block`]'s main symbol lists, destroying the
+    /// [`Subregion`] in the process. Other [`Subregion`]s are left untouched.
+    ///
+    /// Fails if the [`Block`] has no [`Subregion`] named `name`, or if the [`Subregion`] is not
+    /// resolved.
+    pub fn collapse_subregion<P: AsRef<Path>>(&mut self, name: P) -> Result<()> {
+        let name = name.as_ref();
+        let subregions = self
+            .subregions
+            .as_mut()
+            .ok_or_else(|| Error::Subregion(SubregionError::Missing(name.to_owned())))?;
+        let idx = subregions
+            .iter()
+            .position(|s| s.name == name)
+            .ok_or_else(|| Error::Subregion(SubregionError::Missing(name.to_owned())))?;
+        if !subregions[idx].is_resolved() {
+            return Err(Error::Subregion(SubregionError::Unresolved(
+                name.to_owned(),
+            )));
+        }
+        let subregion = subregions.remove(idx);
+        if subregions.is_empty() {
+            self.subregions = None;
+        }
+        if let Some(mut symgen) = subregion.contents {
+            // Recursively collapse
+            symgen.collapse_subregions();
+            for blocks in symgen.blocks_mut() {
+                self.functions.append(&mut blocks.functions);
+                self.data.append(&mut blocks.data);
+            }
+        }
+        Ok(())
+    }
     /// Gets the extent occupied by the [`Block`], possibly by version, represented as
     /// address-length pairs.
     pub fn extent(&self) -> MaybeVersionDep<(Uint, Option<Uint>)> {
@@ -482,7 +1342,10 @@ impl Block {
     }
     /// Returns a combined iterator over both function and data symbols in the [`Block`], realized
     /// for the [`Version`] corresponding to `version_name`.
-    pub fn iter_realized(&self, version_name: &str) -> impl Iterator<Item = RealizedSymbol> + '_ {
+    pub fn iter_realized(
+        &self,
+        version_name: &str,
+    ) -> impl Iterator<Item = RealizedSymbol<'_>> + '_ {
         let version = self.version(version_name);
         self.iter().realize(version)
     }
@@ -491,16 +1354,62 @@ impl Block {
     pub fn functions_realized(
         &self,
         version_name: &str,
-    ) -> impl Iterator<Item = RealizedSymbol> + '_ {
+    ) -> impl Iterator<Item = RealizedSymbol<'_>> + '_ {
         let version = self.version(version_name);
         self.functions.iter().realize(version)
     }
     /// Returns an iterator over data symbols in the [`Block`], realized for the [`Version`]
     /// corresponding to `version_name`.
-    pub fn data_realized(&self, version_name: &str) -> impl Iterator<Item = RealizedSymbol> + '_ {
+    pub fn data_realized(
+        &self,
+        version_name: &str,
+    ) -> impl Iterator<Item = RealizedSymbol<'_>> + '_ {
         let version = self.version(version_name);
         self.data.iter().realize(version)
     }
+    /// Returns all symbols in the [`Block`], realized for the [`Version`] corresponding to
+    /// `version_name`, along with the extent each one covers (as an address and an optional
+    /// length, where [`None`] means unbounded).
+    ///
+    /// Symbols with a length cover exactly that length. Functions without a length are assumed to
+    /// extend up to the next symbol in the [`Block`] (or the end of the [`Block`] if there is no
+    /// next symbol), while data symbols without a length only cover their own address.
+    fn realized_extents(
+        &self,
+        version_name: &str,
+    ) -> Vec<(RealizedSymbol<'_>, (Uint, Option<Uint>))> {
+        let version = self.version(version_name);
+        let block_end = match (self.address.get(version), self.length.get(version)) {
+            (Some(&addr), Some(&len)) => Some(addr + len),
+            _ => None,
+        };
+        let mut starts: Vec<Uint> = self
+            .iter_realized(version_name)
+            .filter(|s| !s.inlined)
+            .map(|s| s.address)
+            .collect();
+        starts.sort_unstable();
+        starts.dedup();
+
+        let functions = self.functions_realized(version_name).map(|s| (s, true));
+        let data = self.data_realized(version_name).map(|s| (s, false));
+        functions
+            .chain(data)
+            .filter(|(s, _)| !s.inlined)
+            .map(|(s, is_function)| {
+                let length = match s.length {
+                    Some(len) => Some(len),
+                    None if is_function => starts
+                        .get(starts.partition_point(|&a| a <= s.address))
+                        .copied()
+                        .or(block_end)
+                        .map(|end| end.saturating_sub(s.address)),
+                    None => Some(1),
+                };
+                (s, (s.address, length))
+            })
+            .collect()
+    }
 
     /// Returns a [`BlockCursor`] for this [`Block`] with the given block name and file path.
     pub fn cursor<'s, 'p>(&'s self, name: &'s str, path: &'p Path) -> BlockCursor<'s, 'p> {
@@ -540,6 +1449,7 @@ impl Sort for Block {
 /// At its core, a [`SymGen`] is just a mapping between block names and [`Block`]s, along with
 /// convenient methods for manipulating the data within those [`Block`]s.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct SymGen(BTreeMap<OrdString, Block>);
 
 impl SymGen {
@@ -564,8 +1474,23 @@ impl SymGen {
         }
     }
     /// Reads an uninitialized [`SymGen`] from `rdr`.
+    ///
+    /// Integer literals in address and length fields may contain underscores between digits
+    /// (e.g., `0x0201_2345`) for readability.
     pub fn read_no_init<R: Read>(rdr: R) -> Result<SymGen> {
-        serde_yaml::from_reader(rdr).map_err(Error::Yaml)
+        SymGen::read_no_init_impl(rdr, false)
+    }
+    fn read_no_init_impl<R: Read>(mut rdr: R, allow_unknown_fields: bool) -> Result<SymGen> {
+        // serde_yaml::from_reader reads everything into memory anyway, so it doesn't cost much
+        // to do the same here for preprocessing.
+        let mut bytes = Vec::new();
+        rdr.read_to_end(&mut bytes).map_err(Error::Io)?;
+        let yaml = String::from_utf8(bytes).map_err(Error::FromUtf8)?;
+        let mut symgen: SymGen =
+            serde_yaml::from_str(&SymGen::strip_int_underscores(&yaml)).map_err(Error::Yaml)?;
+        symgen.check_unknown_fields(allow_unknown_fields, &yaml)?;
+        symgen.resolve_address_exprs()?;
+        Ok(symgen)
     }
     /// Reads a [`SymGen`] from `rdr`. The returned [`SymGen`] will be initialized.
     pub fn read<R: Read>(rdr: R) -> Result<SymGen> {
@@ -573,10 +1498,152 @@ impl SymGen {
         symgen.init();
         Ok(symgen)
     }
-    /// Reads a [`SymGen`] from `rdr`. The returned [`SymGen`] will be initialized and sorted.
+    /// Reads a [`SymGen`] from `rdr`, like [`SymGen::read()`], except that fields not recognized
+    /// by `resymgen` are kept in the `extra_fields` of the containing [`Block`] or [`Symbol`]
+    /// rather than rejected. The returned [`SymGen`] will be initialized.
     ///
-    /// [`Block`]s and their contained [`Symbol`]s are sorted by address. For version-dependent
-    /// addresses, comparison is lexicographic in [`Version`] order.
+    /// Unknown fields are ignored by checks and symbol realization, but are preserved when the
+    /// [`SymGen`] is written. To read subregion files the same way, resolve them with
+    /// [`SymGen::resolve_subregions_allow_unknown_fields()`].
+    pub fn read_allow_unknown_fields<R: Read>(rdr: R) -> Result<SymGen> {
+        let mut symgen: SymGen = SymGen::read_no_init_impl(rdr, true)?;
+        symgen.init();
+        Ok(symgen)
+    }
+    /// Resolves every [`AddressExpr`] in the [`SymGen`] for every version, based on the addresses
+    /// of the symbols they reference. This is done automatically when reading a [`SymGen`].
+    ///
+    /// Expressions can reference any symbol in the [`SymGen`], including symbols whose addresses
+    /// are expressions themselves. Symbols in [`Subregion`]s can't be referenced, since
+    /// expressions are resolved when a file is read, before its [`Subregion`]s are. A
+    /// version-independent expression relative to a symbol with version-dependent addresses is
+    /// expanded into a separate expression for each of those versions. Fails if an expression
+    /// references an undefined symbol, a symbol with no address or multiple addresses for a
+    /// version, or if expressions reference each other in a cycle.
+    pub fn resolve_address_exprs(&mut self) -> Result<()> {
+        let has_expr =
+            |a: &MaybeVersionDep<Linkable>| a.values().any(|l| matches!(l, Linkable::Expr(_)));
+        if !self.symbols().any(|s| has_expr(&s.address)) {
+            return Ok(());
+        }
+        let mut addrs = HashMap::new();
+        for s in self.symbols() {
+            addrs
+                .entry(s.name.clone())
+                .or_insert_with(|| s.address.clone());
+        }
+        for block in self.blocks_mut() {
+            for list in [&mut block.functions, &mut block.data] {
+                for i in 0..list.len() {
+                    let symbol = list.get_mut(i).unwrap();
+                    let mut chain = vec![symbol.name.clone()];
+                    if let MaybeVersionDep::Common(Linkable::Expr(e)) = &symbol.address {
+                        if let Some(versions) = address_expr_versions(e, &addrs) {
+                            let e = AddressExpr {
+                                common: true,
+                                ..e.clone()
+                            };
+                            symbol.address = MaybeVersionDep::ByVersion(
+                                versions
+                                    .into_iter()
+                                    .map(|v| (v, Linkable::Expr(e.clone())))
+                                    .collect(),
+                            );
+                        }
+                    }
+                    let exprs: Vec<(Option<&Version>, &mut AddressExpr)> = match &mut symbol.address
+                    {
+                        MaybeVersionDep::Common(Linkable::Expr(e)) => vec![(None, e)],
+                        MaybeVersionDep::Common(_) => Vec::new(),
+                        MaybeVersionDep::ByVersion(v) => v
+                            .iter_mut()
+                            .filter_map(|(vers, l)| match l {
+                                Linkable::Expr(e) => Some((Some(vers), e)),
+                                _ => None,
+                            })
+                            .collect(),
+                    };
+                    for (vers, e) in exprs {
+                        e.resolved = Some(
+                            resolve_address_expr(e, vers, &addrs, &mut chain)
+                                .map_err(Error::AddressExpr)?,
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Validates the unknown fields captured while deserializing. Empty collections of unknown
+    /// fields are discarded. Unless `allow` is set, any unknown field results in an error, which
+    /// points to the field's line within `yaml` (the text that was deserialized) if it can be found.
+    fn check_unknown_fields(&mut self, allow: bool, yaml: &str) -> Result<()> {
+        fn check<F: FnOnce() -> String>(
+            fields: &mut Option<Mapping>,
+            allow: bool,
+            desc: F,
+            find_line: impl FnOnce(&str) -> Option<usize>,
+        ) -> Result<()> {
+            if let Some(f) = fields {
+                if let Some((key, _)) = f.iter().next() {
+                    if !allow {
+                        let key = match key {
+                            Value::String(s) => s.clone(),
+                            k => format!("{:?}", k),
+                        };
+                        let location = find_line(&key)
+                            .map(|l| format!(" at line {}", l))
+                            .unwrap_or_default();
+                        return Err(Error::Yaml(
+                            <serde_yaml::Error as serde::de::Error>::custom(format!(
+                                "{}: unknown field `{}`{}",
+                                desc(),
+                                key,
+                                location
+                            )),
+                        ));
+                    }
+                } else {
+                    *fields = None;
+                }
+            }
+            Ok(())
+        }
+
+        for (bname, block) in self.0.iter_mut() {
+            check(
+                &mut block.extra_fields,
+                allow,
+                || format!("block \"{}\"", bname),
+                |key| unknown_field_line(yaml, &bname.val, None, key),
+            )?;
+            for list in [&mut block.functions, &mut block.data] {
+                for i in 0..list.len() {
+                    let symbol = list.get_mut(i).unwrap();
+                    let sname = &symbol.name;
+                    check(
+                        &mut symbol.extra_fields,
+                        allow,
+                        || format!("block \"{}\", symbol \"{}\"", bname, sname),
+                        |key| unknown_field_line(yaml, &bname.val, Some(sname), key),
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Generates a JSON Schema describing the `resymgen` YAML format.
+    ///
+    /// Note that YAML-specific conveniences, like hexadecimal integers with underscores, are not
+    /// representable in the schema.
+    #[cfg(feature = "schema")]
+    pub fn json_schema() -> schemars::schema::RootSchema {
+        schemars::schema_for!(SymGen)
+    }
+    /// Reads a [`SymGen`] from `rdr`. The returned [`SymGen`] will be initialized and sorted.
+    ///
+    /// [`Block`]s and their contained [`Symbol`]s are sorted by address. For version-dependent
+    /// addresses, comparison is lexicographic in [`Version`] order.
     pub fn read_sorted<R: Read>(rdr: R) -> Result<SymGen> {
         let mut symgen = SymGen::read(rdr)?;
         symgen.sort();
@@ -588,6 +1655,9 @@ impl SymGen {
     /// `F` injects modified lines into the final YAML string accumulator, based on the given line
     /// to be modified and the current indentation level, and returns a success flag.
     ///
+    /// The contents of block scalars (like multiline descriptions) are always left unmodified,
+    /// since they can contain arbitrary text that might look like a target field.
+    ///
     /// This is kind of a hack. Might be worth investigating whether it's easy to mod `yaml-rust`
     /// and `serde-yaml` to serialize in the desired format directly, rather than doing it via
     /// post-processing. But this is serviceable for now.
@@ -599,11 +1669,25 @@ impl SymGen {
     where
         F: Fn(&mut String, &str, usize) -> bool,
     {
+        let re_block_scalar_start = Regex::new(r":\s+[|>][1-9+-]*\s*(?:#.*)?$").unwrap();
         // If under a target field, the level of whitespace for the field so we can tell when
         // the field has ended.
         let mut field_whitespace_level: Option<usize> = None;
+        // If within a block scalar, the level of whitespace for its key. The block scalar
+        // continues as long as lines are blank or indented further than the key.
+        let mut block_scalar_whitespace_level: Option<usize> = None;
         let mut converted_yaml = String::with_capacity(yaml.len());
         for line in yaml.lines() {
+            if let Some(l) = block_scalar_whitespace_level {
+                let content = line.trim_start();
+                if content.is_empty() || line.len() - content.len() > l {
+                    converted_yaml.push_str(line);
+                    converted_yaml.push('\n');
+                    continue;
+                }
+                block_scalar_whitespace_level = None;
+            }
+
             // Strip out hyphens so that the first field in a SymbolList entry we'll still match
             // properly against it. Currently this is always be "name" (see Symbol), but this
             // is pretty easy to do and makes things less fragile if we change the field order.
@@ -660,6 +1744,9 @@ impl SymGen {
                 // No conversion happened. Add the line unmodified
                 converted_yaml.push_str(line);
             }
+            if re_block_scalar_start.is_match(line) {
+                block_scalar_whitespace_level = Some(whitespace_level);
+            }
             // Add the newline back in. Note: Rust has standardized on '\n' for newlines on all platforms.
             // - https://doc.rust-lang.org/std/macro.println.html
             // - https://stackoverflow.com/questions/66450942/in-rust-is-there-a-way-to-make-literal-newlines-in-r-using-windows-c
@@ -669,32 +1756,63 @@ impl SymGen {
         }
         converted_yaml
     }
-    /// Converts all integer values in a `resymgen` YAML string from decimal to hexadecimal.
-    fn convert_dec_to_hex(yaml: &str) -> String {
+    /// Replaces all integer matches of `re_int` within the values of integer fields in a
+    /// `resymgen` YAML string with the output of `convert`.
+    fn convert_int_fields<F, const N: usize>(
+        yaml: &str,
+        field_prefixes: [&str; N],
+        re_int: &Regex,
+        convert: F,
+    ) -> String
+    where
+        F: Fn(&str) -> String,
+    {
+        SymGen::convert_fields_inline(yaml, field_prefixes, |converted_yaml, line, indent| {
+            // Skip past any colons. This prevents us from replacing "numbers" that appear
+            // within quoted version string keys, and we never expect to see any colons
+            // after the key-value separator. Even if there's no colon, we can still skip
+            // past the whitespace for free, since we have that stored already anyway.
+            let start_idx = line.rfind(':').unwrap_or(indent);
+            let converted =
+                re_int.replace_all(&line[start_idx..], |caps: &Captures| convert(&caps[0]));
+            converted_yaml.push_str(&line[..start_idx]);
+            converted_yaml.push_str(&converted);
+            true
+        })
+    }
+    /// Converts all integer values in a `resymgen` YAML string from decimal to hexadecimal,
+    /// optionally grouping the hexadecimal digits with underscores.
+    fn convert_dec_to_hex(yaml: &str, grouped: bool) -> String {
+        let re_int = Regex::new(r"\b\d+\b").unwrap();
+        let fields = ["address:", "length:", "element_size:"];
+        SymGen::convert_int_fields(yaml, fields, &re_int, |dec| {
+            let int = dec.parse::<Uint>().unwrap_or_else(|_| {
+                panic!("Could not parse {} as {}", dec, any::type_name::<Uint>())
+            });
+            if grouped {
+                IntFormat::GroupedHexadecimal.format(int)
+            } else {
+                IntFormat::Hexadecimal.format(int)
+            }
+        })
+    }
+    /// Groups the digits of all decimal integer values in a `resymgen` YAML string with
+    /// underscores.
+    fn group_dec_digits(yaml: &str) -> String {
         let re_int = Regex::new(r"\b\d+\b").unwrap();
-        SymGen::convert_fields_inline(
+        let fields = ["address:", "length:", "element_size:"];
+        SymGen::convert_int_fields(yaml, fields, &re_int, |dec| group_digits(dec, 3))
+    }
+    /// Strips underscores separating digits (e.g., `0x0201_2345` or `1_000`) from all integer
+    /// values in a `resymgen` YAML string, so that they can be parsed normally.
+    fn strip_int_underscores(yaml: &str) -> String {
+        let re_grouped_int =
+            Regex::new(r"\b(?:0[xX][[:xdigit:]]+|\d+)(?:_[[:xdigit:]]+)+\b").unwrap();
+        SymGen::convert_int_fields(
             yaml,
-            ["address:", "length:"],
-            |converted_yaml, line, indent| {
-                // Skip past any colons. This prevents us from replacing "numbers" that appear
-                // within quoted version string keys, and we never expect to see any colons
-                // after the key-value separator. Even if there's no colon, we can still skip
-                // past the whitespace for free, since we have that stored already anyway.
-                let start_idx = line.rfind(':').unwrap_or(indent);
-                let converted = re_int.replace_all(&line[start_idx..], |caps: &Captures| {
-                    let int = caps[0].parse::<Uint>().unwrap_or_else(|_| {
-                        panic!(
-                            "Could not parse {} as {}",
-                            &caps[0],
-                            any::type_name::<Uint>()
-                        )
-                    });
-                    format!("{:#X}", int)
-                });
-                converted_yaml.push_str(&line[..start_idx]);
-                converted_yaml.push_str(&converted);
-                true
-            },
+            ["address:", "length:", "element_size:"],
+            &re_grouped_int,
+            |int| int.replace('_', ""),
         )
     }
     /// Converts all multiline description strings in a `resymgen` YAML string to block scalar
@@ -702,6 +1820,9 @@ impl SymGen {
     fn convert_multiline_desc_to_block_scalar(yaml: &str) -> String {
         SymGen::convert_fields_inline(yaml, ["description:"], |converted_yaml, line, indent| {
             const SUB_INDENT: usize = 2;
+            // Version-dependent descriptions are nested one level deeper than the field itself,
+            // so block scalars need to be indented relative to the line being converted.
+            let indent = cmp::max(indent, line.len() - line.trim_start().len());
             let start_idx;
             let contents;
             if let Some(idx) = line.find('"') {
@@ -719,18 +1840,33 @@ impl SymGen {
                 return false;
             }
             if let Ok(l) = syn::parse_str::<LitStr>(&contents) {
-                // Only convert multiline strings
-                if l.value().trim_end().lines().count() > 1 {
+                let value = l.value();
+                // Trailing whitespace is never intentional, so strip it from every line
+                let desc_lns: Vec<&str> = value.trim_end().lines().map(str::trim_end).collect();
+                if desc_lns.len() > 1 {
+                    // Convert multiline strings
                     converted_yaml.push_str(&line[..start_idx]);
                     converted_yaml.push_str("|-"); // There's no reason to have trailing newlines
-                    for desc_ln in l.value().trim_end().lines() {
+                    for desc_ln in desc_lns {
                         converted_yaml.push('\n');
-                        for _ in 0..indent + SUB_INDENT {
-                            converted_yaml.push(' ');
+                        // Don't indent blank lines, since that would be trailing whitespace
+                        if !desc_ln.is_empty() {
+                            for _ in 0..indent + SUB_INDENT {
+                                converted_yaml.push(' ');
+                            }
+                            converted_yaml.push_str(desc_ln);
                         }
-                        converted_yaml.push_str(desc_ln);
                     }
                     return true;
+                } else if desc_lns.len() == 1 && desc_lns[0].len() < value.len() {
+                    // Single-line strings only need to be rewritten if there was trailing
+                    // whitespace. Let serde_yaml decide how to quote the trimmed string.
+                    if let Ok(trimmed) = serde_yaml::to_string(desc_lns[0]) {
+                        converted_yaml.push_str(&line[..start_idx]);
+                        converted_yaml
+                            .push_str(trimmed.strip_prefix("---").unwrap_or(&trimmed).trim());
+                        return true;
+                    }
                 }
             }
             false
@@ -739,20 +1875,72 @@ impl SymGen {
     /// Writes the [`SymGen`] data to `writer` in `resymgen` YAML format.
     ///
     /// Integers will be written with the given `int_format`.
-    pub fn write<W: Write>(&self, mut writer: W, int_format: IntFormat) -> Result<()> {
-        // I don't expect these YAML files to be too big to fit in memory, so it's easier and
-        // faster to keep the serialized data in memory for processing. And anyway,
-        // serde_yaml::from_reader already uses read_to_end()
-        // (https://github.com/dtolnay/serde-yaml/blob/644be1654d382627c4419f613e300c5e4df3650f/src/de.rs#L141)
-        // so this shouldn't be much worse. If it ever becomes an issue (like with merging huge
-        // files or something) this can be refactored to use another intermediate tempfile and a
-        // BufReader/BufWriter or something.
-        let mut yaml = serde_yaml::to_string(self).map_err(Error::Yaml)?;
+    pub fn write<W: Write>(&self, writer: W, int_format: IntFormat) -> Result<()> {
+        self.write_with_spacing(writer, int_format, 0)
+    }
+    /// Writes the [`SymGen`] data to `writer` in `resymgen` YAML format, with `block_spacing`
+    /// blank lines between consecutive blocks.
+    ///
+    /// Integers will be written with the given `int_format`.
+    pub fn write_with_spacing<W: Write>(
+        &self,
+        mut writer: W,
+        int_format: IntFormat,
+        block_spacing: usize,
+    ) -> Result<()> {
+        if self.0.is_empty() {
+            // An empty map is serialized inline ("{}"), so there are no blocks to stream
+            let yaml = SymGen::postprocess_yaml(
+                serde_yaml::to_string(self).map_err(Error::Yaml)?,
+                int_format,
+            );
+            return writer.write_all(yaml.as_bytes()).map_err(Error::Io);
+        }
+        // Serialize and postprocess one block at a time and write each one out immediately, so
+        // that only a single block's worth of YAML is ever held in memory, rather than the
+        // whole file (which can be large for merged files). This produces the same output as
+        // serializing everything at once, since yaml-rust doesn't indent top-level keys and
+        // every postprocessing step works line-by-line within a block.
+        for (i, (bname, block)) in self.0.iter().enumerate() {
+            if i > 0 {
+                writer
+                    .write_all("\n".repeat(block_spacing).as_bytes())
+                    .map_err(Error::Io)?;
+            }
+            let chunk: BTreeMap<&OrdString, &Block> = iter::once((bname, block)).collect();
+            let yaml = SymGen::postprocess_yaml(
+                serde_yaml::to_string(&chunk).map_err(Error::Yaml)?,
+                int_format,
+            );
+            writer.write_all(yaml.as_bytes()).map_err(Error::Io)?;
+        }
+        Ok(())
+    }
+    /// Writes a bare list of [`Symbol`]s to `writer`, formatted the same way as a symbol list
+    /// within a `resymgen` YAML block.
+    ///
+    /// Integers will be written with the given `int_format`.
+    pub fn write_symbols<W: Write>(
+        mut writer: W,
+        symbols: &[Symbol],
+        int_format: IntFormat,
+    ) -> Result<()> {
+        let yaml = SymGen::postprocess_yaml(
+            serde_yaml::to_string(symbols).map_err(Error::Yaml)?,
+            int_format,
+        );
+        writer.write_all(yaml.as_bytes()).map_err(Error::Io)
+    }
+    /// Applies `resymgen` YAML formatting conventions to raw `serde_yaml` output.
+    fn postprocess_yaml(mut yaml: String, int_format: IntFormat) -> String {
         // yaml-rust's built-in behavior is to dump integers in decimal
         // (https://github.com/chyh1990/yaml-rust/blob/4fffe95cddbcf444f8a3f080364caf16a6c11ca6/src/emitter.rs#L173)
         // so writing in hex format requires further processing.
-        if let IntFormat::Hexadecimal = int_format {
-            yaml = SymGen::convert_dec_to_hex(&yaml);
+        match int_format {
+            IntFormat::Decimal => (),
+            IntFormat::Hexadecimal => yaml = SymGen::convert_dec_to_hex(&yaml, false),
+            IntFormat::GroupedDecimal => yaml = SymGen::group_dec_digits(&yaml),
+            IntFormat::GroupedHexadecimal => yaml = SymGen::convert_dec_to_hex(&yaml, true),
         }
         yaml = SymGen::convert_multiline_desc_to_block_scalar(&yaml);
 
@@ -761,19 +1949,28 @@ impl SymGen {
         // We aren't using any YAML directives, we only ever serialize one object/document, and
         // serde_yaml doesn't support deserializing multiple documents anyway, so it's totally
         // optional.
-        let yaml_bytes = yaml
-            .strip_prefix("---")
+        yaml.strip_prefix("---")
             .unwrap_or(&yaml)
             .trim_start()
-            .as_bytes();
-        writer.write_all(yaml_bytes).map_err(Error::Io)
+            .to_string()
     }
     /// Writes the [`SymGen`] data to a [`String`] in `resymgen` YAML format.
     ///
     /// Integers will be written with the given `int_format`.
     pub fn write_to_str(&self, int_format: IntFormat) -> Result<String> {
+        self.write_to_str_with_spacing(int_format, 0)
+    }
+    /// Writes the [`SymGen`] data to a [`String`] in `resymgen` YAML format, with
+    /// `block_spacing` blank lines between consecutive blocks.
+    ///
+    /// Integers will be written with the given `int_format`.
+    pub fn write_to_str_with_spacing(
+        &self,
+        int_format: IntFormat,
+        block_spacing: usize,
+    ) -> Result<String> {
         let mut bytes = Vec::<u8>::new();
-        self.write(&mut bytes, int_format)?;
+        self.write_with_spacing(&mut bytes, int_format, block_spacing)?;
         String::from_utf8(bytes).map_err(Error::FromUtf8)
     }
 
@@ -783,13 +1980,41 @@ impl SymGen {
     /// [`Subregion`]s are read from files using `file_opener`, with file paths based on the root
     /// directory specified by `dir_path`.
     pub fn resolve_subregions<P, R, F>(&mut self, dir_path: P, file_opener: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        R: Read,
+        F: Fn(&Path) -> io::Result<R> + Copy,
+    {
+        self.resolve_subregions_impl(dir_path, file_opener, false)
+    }
+    /// Recursively resolves the contents of all [`Subregion`]s, like
+    /// [`SymGen::resolve_subregions()`], except that subregion files are read like
+    /// [`SymGen::read_allow_unknown_fields()`].
+    pub fn resolve_subregions_allow_unknown_fields<P, R, F>(
+        &mut self,
+        dir_path: P,
+        file_opener: F,
+    ) -> Result<()>
+    where
+        P: AsRef<Path>,
+        R: Read,
+        F: Fn(&Path) -> io::Result<R> + Copy,
+    {
+        self.resolve_subregions_impl(dir_path, file_opener, true)
+    }
+    fn resolve_subregions_impl<P, R, F>(
+        &mut self,
+        dir_path: P,
+        file_opener: F,
+        allow_unknown_fields: bool,
+    ) -> Result<()>
     where
         P: AsRef<Path>,
         R: Read,
         F: Fn(&Path) -> io::Result<R> + Copy,
     {
         for block in self.0.values_mut() {
-            block.resolve_subregions(&dir_path, file_opener)?;
+            block.resolve_subregions_impl(&dir_path, file_opener, allow_unknown_fields)?;
         }
         Ok(())
     }
@@ -800,6 +2025,59 @@ impl SymGen {
             block.collapse_subregions();
         }
     }
+    /// Retains only the [`Symbol`]s for which `f` returns `true`, in all the contained [`Block`]s.
+    ///
+    /// See [`Block::retain_symbols()`].
+    pub fn retain_symbols<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Symbol) -> bool,
+    {
+        for block in self.0.values_mut() {
+            block.retain_symbols_dyn(&mut f);
+        }
+    }
+
+    /// Replaces the name and deprecated aliases of every [`Symbol`] with the result of calling `f`
+    /// on them, in all the contained [`Block`]s.
+    ///
+    /// See [`Block::rename_symbols()`].
+    pub fn rename_symbols<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&str) -> String,
+    {
+        for block in self.0.values_mut() {
+            block.rename_symbols_dyn(&mut f);
+        }
+    }
+
+    /// Renames [`Version`]s in every [`Block`] in the [`SymGen`] according to `renames`, which maps
+    /// old version names to new ones.
+    ///
+    /// See [`Block::rename_versions()`].
+    pub fn rename_versions(&mut self, renames: &HashMap<String, String>) {
+        for b in self.blocks_mut() {
+            b.rename_versions(renames);
+        }
+    }
+
+    /// Uses the data for the [`Version`] named `base_name` as a fallback for the [`Version`] named
+    /// `version_name` in every [`Block`] in the [`SymGen`].
+    ///
+    /// See [`Block::fill_version_from()`].
+    pub fn fill_version_from(&mut self, version_name: &str, base_name: &str) {
+        for block in self.0.values_mut() {
+            block.fill_version_from(version_name, base_name);
+        }
+    }
+
+    /// Restricts every [`Block`] in the [`SymGen`] to the [`Version`] named `version_name`.
+    ///
+    /// See [`Block::restrict_to_version()`].
+    pub fn restrict_to_version(&mut self, version_name: &str) {
+        for block in self.0.values_mut() {
+            block.restrict_to_version(version_name);
+        }
+    }
 
     /// Expands the versions of all the addresses and lengths contained within the [`SymGen`]
     /// (in all the contained [`Block`]s).
@@ -830,6 +2108,10 @@ impl SymGen {
     pub fn insert(&mut self, key: OrdString, value: Block) -> Option<Block> {
         self.0.insert(key, value)
     }
+    /// Removes the [`Block`] keyed by `key` from the [`SymGen`], returning it if present.
+    pub fn remove(&mut self, key: &OrdString) -> Option<Block> {
+        self.0.remove(key)
+    }
 
     /// Returns an [`Iterator`] over references to (block name, [`Block`]) pairs in the [`SymGen`].
     pub fn iter(&self) -> impl Iterator<Item = (&OrdString, &Block)> {
@@ -848,17 +2130,85 @@ impl SymGen {
     pub fn blocks_mut(&mut self) -> impl Iterator<Item = &mut Block> {
         self.0.values_mut()
     }
+    /// Whether any [`Block`] in the [`SymGen`] has a [`Version`] named `version_name`.
+    pub fn has_version(&self, version_name: &str) -> bool {
+        self.blocks().any(|b| b.version(version_name).is_some())
+    }
+    /// Returns references to the [`Block`]s in the [`SymGen`], sorted by base address for the
+    /// [`Version`] corresponding to `version_name`.
+    ///
+    /// If `version_name` is [`None`], only [`Common`] block addresses are used. Blocks with equal
+    /// addresses retain their name order, and blocks without an address for the given version are
+    /// placed at the end in name order.
+    ///
+    /// [`Common`]: MaybeVersionDep::Common
+    pub fn blocks_by_address(&self, version_name: Option<&str>) -> Vec<&Block> {
+        let mut blocks: Vec<(Option<Uint>, &Block)> = self
+            .blocks()
+            .map(|b| {
+                let version = version_name.and_then(|v| b.version(v));
+                (b.address.get(version).copied(), b)
+            })
+            .collect();
+        // Stable sort, so ties preserve name order
+        blocks.sort_by_key(|&(addr, _)| (addr.is_none(), addr));
+        blocks.into_iter().map(|(_, b)| b).collect()
+    }
     /// Returns a flat [`Iterator`] over references to the [`Symbol`]s contained within every
     /// [`Block`] in the [`SymGen`].
     pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
         self.blocks().flat_map(|b| b.iter())
     }
+    /// Builds an index that maps every symbol name and deprecated alias in the [`SymGen`] to the
+    /// block name and [`Symbol`] it belongs to, for fast repeated lookups.
+    ///
+    /// If the same name belongs to multiple symbols, the first occurrence takes precedence
+    /// (blocks in order, then functions before data), as when merging.
+    ///
+    /// The index borrows from the [`SymGen`], so it must be rebuilt after the [`SymGen`] is
+    /// modified.
+    pub fn build_name_index(&self) -> HashMap<&str, (&OrdString, &Symbol)> {
+        let mut index = HashMap::new();
+        for (bname, b) in self.iter() {
+            for s in b.iter() {
+                for name in s.iter_names() {
+                    index.entry(name).or_insert((bname, s));
+                }
+            }
+        }
+        index
+    }
+    /// Finds the [`Symbol`] with the given name or deprecated alias, along with the name of the
+    /// [`Block`] containing it, with the same precedence as [`SymGen::build_name_index()`].
+    ///
+    /// This scans the [`SymGen`] lazily, stopping at the first match, so it's suited to one-off
+    /// lookups. For many lookups, use [`SymGen::build_name_index()`] instead.
+    pub fn find_symbol(&self, name: &str) -> Option<(&OrdString, &Symbol)> {
+        self.iter()
+            .flat_map(|(bname, b)| b.iter().map(move |s| (bname, s)))
+            .find(|(_, s)| s.iter_names().any(|n| n == name))
+    }
+    /// Returns the [`Symbol`]s contained within every [`Block`] in the [`SymGen`] with an address
+    /// explicitly defined for the [`Version`] named `have`, but not for the one named `missing`.
+    ///
+    /// Unlike symbol realization, this doesn't fall back to [`Common`] addresses, which apply to
+    /// every version and so are never missing.
+    ///
+    /// [`Common`]: MaybeVersionDep::Common
+    pub fn symbols_missing_version(&self, have: &str, missing: &str) -> Vec<&Symbol> {
+        self.symbols()
+            .filter(|s| {
+                let defines = |name| s.address.versions().any(|v| v.name() == name);
+                defines(have) && !defines(missing)
+            })
+            .collect()
+    }
     /// Returns a flat [`Iterator`] over all symbols contained within every [`Block`] in
     /// the [`SymGen`], realized for the [`Version`] corresponding to `version_name`.
     pub fn symbols_realized(
         &self,
         version_name: &str,
-    ) -> impl Iterator<Item = RealizedSymbol> + '_ {
+    ) -> impl Iterator<Item = RealizedSymbol<'_>> + '_ {
         let v = String::from(version_name);
         self.blocks().flat_map(move |b| b.iter_realized(&v))
     }
@@ -867,16 +2217,110 @@ impl SymGen {
     pub fn functions_realized(
         &self,
         version_name: &str,
-    ) -> impl Iterator<Item = RealizedSymbol> + '_ {
+    ) -> impl Iterator<Item = RealizedSymbol<'_>> + '_ {
         let v = String::from(version_name);
         self.blocks().flat_map(move |b| b.functions_realized(&v))
     }
     /// Returns a flat [`Iterator`] over all data symbols contained within every [`Block`] in
     /// the [`SymGen`], realized for the [`Version`] corresponding to `version_name`.
-    pub fn data_realized(&self, version_name: &str) -> impl Iterator<Item = RealizedSymbol> + '_ {
+    pub fn data_realized(
+        &self,
+        version_name: &str,
+    ) -> impl Iterator<Item = RealizedSymbol<'_>> + '_ {
         let v = String::from(version_name);
         self.blocks().flat_map(move |b| b.data_realized(&v))
     }
+    /// Looks up the symbol at the address `addr` for the [`Version`] corresponding to
+    /// `version_name`, among all symbols contained within every [`Block`] in the [`SymGen`].
+    ///
+    /// A symbol matches if `addr` lies within its extent. Functions without a length are assumed
+    /// to extend up to the next symbol in the same [`Block`], and data symbols without a length
+    /// only match their own address. If multiple symbols match, the one with the smallest extent
+    /// is returned.
+    ///
+    /// This builds a new [`SymbolIndex`] on every call. For repeated lookups, build the index once
+    /// with [`SymGen::symbol_index()`] instead.
+    pub fn symbol_at(&self, version_name: &str, addr: Uint) -> Option<RealizedSymbol<'_>> {
+        self.symbol_index(version_name).symbol_at(addr)
+    }
+    /// Builds a [`SymbolIndex`] for looking up symbols by address for the [`Version`]
+    /// corresponding to `version_name`, among all symbols contained within every [`Block`] in
+    /// the [`SymGen`].
+    pub fn symbol_index(&self, version_name: &str) -> SymbolIndex<'_> {
+        SymbolIndex::new(
+            self.blocks()
+                .flat_map(|b| b.realized_extents(version_name))
+                .collect(),
+        )
+    }
+    /// Returns an [`Iterator`] over all symbols contained within every [`Block`] in the
+    /// [`SymGen`] with an address in the range [`start`, `end`), realized for the [`Version`]
+    /// corresponding to `version_name`. Symbols are yielded in address order.
+    pub fn symbols_in_range(
+        &self,
+        version_name: &str,
+        start: Uint,
+        end: Uint,
+    ) -> impl Iterator<Item = RealizedSymbol<'_>> + '_ {
+        let mut symbols: Vec<_> = self
+            .symbols_realized(version_name)
+            .filter(|s| !s.inlined && s.address >= start && s.address < end)
+            .collect();
+        // Stable sort, so symbols with the same address keep their original order
+        symbols.sort_by_key(|s| s.address);
+        symbols.into_iter()
+    }
+    /// Returns the memory layout of the [`SymGen`] for the [`Version`] corresponding to
+    /// `version_name`, as a flat list of [`LayoutEntry`]s.
+    ///
+    /// Blocks are included as containers for the symbols they contain, as are the blocks within
+    /// resolved [`Subregion`]s. Entries are sorted by starting address; entries with the same
+    /// starting address are sorted from largest to smallest, so that containers always precede
+    /// their contents. Blocks and symbols without an address for the given version are omitted.
+    /// Only the main extent of each symbol is included, as with symbol realization.
+    pub fn memory_layout(&self, version_name: &str) -> Vec<LayoutEntry<'_>> {
+        let mut layout = Vec::new();
+        self.add_layout_entries(version_name, 0, &mut layout);
+        layout.sort_by_key(|e| (e.start, cmp::Reverse(e.end), e.depth, e.kind));
+        layout
+    }
+    fn add_layout_entries<'s>(
+        &'s self,
+        version_name: &str,
+        depth: usize,
+        layout: &mut Vec<LayoutEntry<'s>>,
+    ) {
+        for (name, b) in self.iter() {
+            let version = b.version(version_name);
+            if let Some(&start) = b.address.get(version) {
+                layout.push(LayoutEntry {
+                    start,
+                    end: start + b.length.get(version).copied().unwrap_or(0),
+                    name: &name.val,
+                    kind: LayoutKind::Block,
+                    depth,
+                });
+            }
+            let functions = b
+                .functions_realized(version_name)
+                .map(|s| (s, LayoutKind::Function));
+            let data = b.data_realized(version_name).map(|s| (s, LayoutKind::Data));
+            for (s, kind) in functions.chain(data).filter(|(s, _)| !s.inlined) {
+                layout.push(LayoutEntry {
+                    start: s.address,
+                    end: s.address + s.length.unwrap_or(0),
+                    name: s.name,
+                    kind,
+                    depth: depth + 1,
+                });
+            }
+            for subregion in b.subregions.iter().flatten() {
+                if let Some(contents) = &subregion.contents {
+                    contents.add_layout_entries(version_name, depth + 1, layout);
+                }
+            }
+        }
+    }
 
     /// Returns a [`SymGenCursor`] for this [`SymGen`] with the given file path.
     pub fn cursor<'s, 'p>(&'s self, path: &'p Path) -> SymGenCursor<'s, 'p> {
@@ -918,6 +2362,7 @@ impl Sort for SymGen {
 /// The contents of a resolved [`Subregion`] are logically grouped together, but are ultimately
 /// owned by the parent [`Block`].
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(transparent)]
 pub struct Subregion {
     pub name: PathBuf,
@@ -943,6 +2388,19 @@ impl Subregion {
     /// The file is read using `file_opener`, with the file path derived from the directory
     /// specified by `dir_path` and the [`Subregion`]'s name.
     pub fn resolve<P, R, F>(&mut self, dir_path: P, file_opener: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        R: Read,
+        F: Fn(&Path) -> io::Result<R> + Copy,
+    {
+        self.resolve_impl(dir_path, file_opener, false)
+    }
+    fn resolve_impl<P, R, F>(
+        &mut self,
+        dir_path: P,
+        file_opener: F,
+        allow_unknown_fields: bool,
+    ) -> Result<()>
     where
         P: AsRef<Path>,
         R: Read,
@@ -960,7 +2418,12 @@ impl Subregion {
                 Box::new(Error::Io(e)),
             )))
         })?;
-        self.contents = Some(Box::new(SymGen::read(rdr).map_err(|e| {
+        let read = if allow_unknown_fields {
+            SymGen::read_allow_unknown_fields(rdr)
+        } else {
+            SymGen::read(rdr)
+        };
+        self.contents = Some(Box::new(read.map_err(|e| {
             Error::Subregion(SubregionError::SymGen((filepath.clone(), Box::new(e))))
         })?));
         Ok(())
@@ -1042,6 +2505,41 @@ mod tests {
     mod symbol_tests {
         use super::*;
 
+        #[test]
+        fn test_keep_first_address() {
+            let mut symbol = Symbol {
+                name: "fn".to_string(),
+                deprecated_aliases: None,
+                address: MaybeVersionDep::ByVersion(
+                    [
+                        ("v1".into(), Linkable::from([0x2000100, 0x2000000])),
+                        ("v2".into(), Linkable::from(0x2000200)),
+                    ]
+                    .into(),
+                ),
+                length: None,
+                description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
+            };
+            assert!(symbol.keep_first_address());
+            assert_eq!(
+                symbol.address,
+                MaybeVersionDep::ByVersion(
+                    [
+                        ("v1".into(), Linkable::from(0x2000100)),
+                        ("v2".into(), Linkable::from(0x2000200)),
+                    ]
+                    .into(),
+                )
+            );
+            assert!(!symbol.keep_first_address());
+        }
+
         #[test]
         fn test_init_sort() {
             let versions = ["SI", "imperial", "natural"];
@@ -1050,6 +2548,7 @@ mod tests {
 
             let mut symbol = Symbol {
                 name: "c".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::ByVersion(
                     [
                         ("SI".into(), Linkable::from([1080000000, 299792458])),
@@ -1066,7 +2565,13 @@ mod tests {
                     ]
                     .into(),
                 )),
-                description: Some("the speed of light".to_string()),
+                description: Some(MaybeVersionDep::Common("the speed of light".to_string())),
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             };
             symbol.init(&ctx);
             symbol.sort();
@@ -1075,6 +2580,7 @@ mod tests {
                 &symbol,
                 &Symbol {
                     name: "c".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::ByVersion(
                         [
                             (("SI", 0).into(), Linkable::from([299792458, 1080000000])),
@@ -1091,7 +2597,13 @@ mod tests {
                         ]
                         .into()
                     )),
-                    description: Some("the speed of light".to_string()),
+                    description: Some(MaybeVersionDep::Common("the speed of light".to_string())),
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 }
             );
         }
@@ -1114,9 +2626,16 @@ mod tests {
             );
             let mut function = Symbol {
                 name: "function".to_string(),
+                deprecated_aliases: None,
                 address: address.clone(),
                 length: Some(MaybeVersionDep::Common(0x100)),
                 description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             };
             function.init(&ctx);
             function.sort();
@@ -1126,6 +2645,7 @@ mod tests {
                 &function,
                 &Symbol {
                     name: "function".to_string(),
+                    deprecated_aliases: None,
                     address: address.clone(),
                     length: Some(MaybeVersionDep::ByVersion(
                         [
@@ -1136,6 +2656,12 @@ mod tests {
                         .into()
                     )),
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 }
             )
         }
@@ -1149,6 +2675,7 @@ mod tests {
             ];
             let function1 = Symbol {
                 name: "function1".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::ByVersion(
                     [
                         (versions[0].clone(), Linkable::from([0x2100000, 0x2100100])),
@@ -1160,6 +2687,12 @@ mod tests {
                     [(versions[0].clone(), 0x100), (versions[2].clone(), 0x200)].into(),
                 )),
                 description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             };
             let expected_extents = MaybeVersionDep::ByVersion(
                 [
@@ -1177,11 +2710,18 @@ mod tests {
             // Weird edge case where we have versions for length but not address
             let function2 = Symbol {
                 name: "function2".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::Common(Linkable::from(0x2100000)),
                 length: Some(MaybeVersionDep::ByVersion(
                     [(versions[0].clone(), 0x100), (versions[1].clone(), 0x200)].into(),
                 )),
                 description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             };
             assert_eq!(
                 &function2.extents(Some(&versions)),
@@ -1219,6 +2759,42 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_range_extents() {
+            let versions = [Version::from(("NA", 0)), Version::from(("EU", 1))];
+            let data = Symbol {
+                name: "data".to_string(),
+                deprecated_aliases: None,
+                address: MaybeVersionDep::Common(Linkable::from(0x2100000)),
+                length: Some(MaybeVersionDep::Common(0x100)),
+                description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: Some(vec![SymbolRange {
+                    address: MaybeVersionDep::ByVersion(
+                        [
+                            (versions[0].clone(), 0x2100800),
+                            (versions[1].clone(), 0x2100900),
+                        ]
+                        .into(),
+                    ),
+                    length: MaybeVersionDep::Common(0x40),
+                }]),
+                extra_fields: None,
+            };
+            let expected_extents = vec![MaybeVersionDep::ByVersion(
+                [
+                    (versions[0].clone(), (0x2100800, Some(0x40))),
+                    (versions[1].clone(), (0x2100900, Some(0x40))),
+                ]
+                .into(),
+            )];
+            assert_eq!(data.range_extents(Some(&versions)), expected_extents);
+            assert_eq!(data.range_extents(None), expected_extents);
+        }
+
         #[test]
         fn test_cmp() {
             let versions = ["NA", "EU", "JP"];
@@ -1227,6 +2803,7 @@ mod tests {
 
             let mut function1 = Symbol {
                 name: "function1".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::ByVersion(
                     [
                         ("NA".into(), Linkable::from(0x2100000)),
@@ -1236,12 +2813,19 @@ mod tests {
                 ),
                 length: None,
                 description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             };
             function1.init(&ctx);
             function1.sort();
 
             let mut function2 = Symbol {
                 name: "function2".to_string(),
+                deprecated_aliases: None,
                 address: MaybeVersionDep::ByVersion(
                     [
                         ("NA".into(), Linkable::from(0x2101000)),
@@ -1252,6 +2836,12 @@ mod tests {
                 ),
                 length: None,
                 description: None,
+                element_size: None,
+                data_type: None,
+                inlined: None,
+                stype: None,
+                ranges: None,
+                extra_fields: None,
             };
             function2.init(&ctx);
             function2.sort();
@@ -1280,12 +2870,20 @@ mod tests {
             SymbolList::from([
                 Symbol {
                     name: "function2".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(Linkable::from([0x2101000, 0x2101100])),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 Symbol {
                     name: "function1".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::ByVersion(
                         [
                             ("EU".into(), Linkable::from(0x2100c00)),
@@ -1295,11 +2893,18 @@ mod tests {
                     ),
                     length: Some(MaybeVersionDep::Common(0x100)),
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
             ]),
             SymbolList::from([
                 Symbol {
                     name: "function1".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::ByVersion(
                         [
                             (("NA", 0).into(), Linkable::from([0x2100000, 0x2100100])),
@@ -1309,12 +2914,25 @@ mod tests {
                     ),
                     length: Some(MaybeVersionDep::Common(0x100)),
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 Symbol {
                     name: "function2".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::Common(Linkable::from([0x2101000, 0x2101100])),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
             ]),
         )
@@ -1343,28 +2961,40 @@ mod tests {
             let mut iter0 = list.iter().realize(Some(&versions[0]));
             let exp0 = [
                 RealizedSymbol {
-                    name: &"function1",
+                    name: "function1",
+                    deprecated_aliases: None,
                     address: 0x2100000,
                     length: Some(0x100),
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"function1",
+                    name: "function1",
+                    deprecated_aliases: None,
                     address: 0x2100100,
                     length: Some(0x100),
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"function2",
+                    name: "function2",
+                    deprecated_aliases: None,
                     address: 0x2101000,
                     length: None,
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"function2",
+                    name: "function2",
+                    deprecated_aliases: None,
                     address: 0x2101100,
                     length: None,
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
             ];
             for e in exp0.iter() {
@@ -1375,22 +3005,31 @@ mod tests {
             let mut iter1 = list.iter().realize(Some(&versions[1]));
             let exp1 = [
                 RealizedSymbol {
-                    name: &"function1",
+                    name: "function1",
+                    deprecated_aliases: None,
                     address: 0x2100c00,
                     length: Some(0x100),
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"function2",
+                    name: "function2",
+                    deprecated_aliases: None,
                     address: 0x2101000,
                     length: None,
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"function2",
+                    name: "function2",
+                    deprecated_aliases: None,
                     address: 0x2101100,
                     length: None,
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
             ];
             for e in exp1.iter() {
@@ -1405,16 +3044,22 @@ mod tests {
             let mut iter = list.iter().realize(None);
             let exp = [
                 RealizedSymbol {
-                    name: &"function2",
+                    name: "function2",
+                    deprecated_aliases: None,
                     address: 0x2101000,
                     length: None,
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"function2",
+                    name: "function2",
+                    deprecated_aliases: None,
                     address: 0x2101100,
                     length: None,
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
             ];
             for e in exp.iter() {
@@ -1422,6 +3067,59 @@ mod tests {
             }
             assert_eq!(iter.next(), None);
         }
+
+        fn positions_by_name(list: &SymbolList) -> Vec<Option<usize>> {
+            ["function1", "function2", "function3"]
+                .iter()
+                .map(|name| list.position_by_name(name))
+                .collect()
+        }
+
+        #[test]
+        fn test_index_by_name_push() {
+            let (_, _, _, _, mut list, _) = get_block_data();
+            list.index_by_name();
+            assert_eq!(positions_by_name(&list), vec![Some(1), Some(0), None]);
+
+            let mut function3 = list[0].clone();
+            function3.name = "function3".to_string();
+            list.push(function3);
+            // Duplicate names don't replace the first appearance
+            list.push(list[1].clone());
+            assert_eq!(positions_by_name(&list), vec![Some(1), Some(0), Some(2)]);
+            assert_eq!(
+                list.find_by_name("function3").map(|s| s.name.as_str()),
+                Some("function3")
+            );
+        }
+
+        #[test]
+        fn test_index_by_name_sort() {
+            let (versions, _, _, _, mut list, _) = get_block_data();
+            let version_order = OrdString::get_order_map(Some(&versions));
+            let ctx = BlockContext { version_order };
+            list.init(&ctx);
+            list.index_by_name();
+            assert_eq!(positions_by_name(&list), vec![Some(1), Some(0), None]);
+
+            list.sort();
+            assert_eq!(positions_by_name(&list), vec![Some(0), Some(1), None]);
+            assert_eq!(
+                list.find_by_name_mut("function2").map(|s| s.name.as_str()),
+                Some("function2")
+            );
+        }
+
+        #[test]
+        fn test_index_by_name_rename() {
+            let (_, _, _, _, mut list, _) = get_block_data();
+            list.find_by_name_mut("function1").unwrap().name = "function3".to_string();
+            assert_eq!(positions_by_name(&list), vec![None, Some(0), Some(1)]);
+            list.get_mut(0).unwrap().name = "function1".to_string();
+            assert_eq!(positions_by_name(&list), vec![Some(0), None, Some(1)]);
+            assert!(list.find_by_name_mut("function2").is_none());
+            assert!(list.find_by_name_mut("function1").is_some());
+        }
     }
 
     #[cfg(test)]
@@ -1436,15 +3134,107 @@ mod tests {
                 address: addresses.clone(),
                 length: addresses.clone(),
                 description: None,
+                space_group: None,
                 subregions: None,
                 functions: symbols.clone(),
                 data: symbols.clone(),
+                extra_fields: None,
             };
             block.init();
             block.sort();
             block
         }
 
+        #[test]
+        fn test_restrict_to_version() {
+            let mut block = get_sorted_block();
+            block.functions.get_mut(1).unwrap().address = MaybeVersionDep::ByVersion(
+                [(
+                    block.version("NA").unwrap().clone(),
+                    Linkable::from(0x2101000),
+                )]
+                .into(),
+            );
+            block.restrict_to_version("EU");
+
+            let eu = block.version("EU").unwrap().clone();
+            assert_eq!(block.versions, Some(vec![eu.clone()]));
+            assert_eq!(
+                block.address,
+                MaybeVersionDep::ByVersion([(eu.clone(), 0x2000004)].into())
+            );
+            // function2 only had an NA address, so it's removed
+            assert_eq!(block.functions.len(), 1);
+            assert_eq!(
+                block.functions[0].address,
+                MaybeVersionDep::ByVersion([(eu.clone(), Linkable::from(0x2100c00))].into())
+            );
+            // Version-independent values are kept as-is
+            assert_eq!(
+                block.functions[0].length,
+                Some(MaybeVersionDep::Common(0x100))
+            );
+            assert_eq!(block.data.len(), 2);
+        }
+
+        #[test]
+        fn test_fill_version_from() {
+            let mut block = get_sorted_block();
+            block.functions.get_mut(1).unwrap().address = MaybeVersionDep::ByVersion(
+                [(
+                    block.version("NA").unwrap().clone(),
+                    Linkable::from(0x2101000),
+                )]
+                .into(),
+            );
+            let realized = |block: &Block, vers| {
+                block
+                    .functions_realized(vers)
+                    .map(|s| (s.name.to_string(), s.address))
+                    .collect::<Vec<_>>()
+            };
+            let na = realized(&block, "NA");
+            let eu = realized(&block, "EU");
+
+            // EU data takes precedence, but NA is used as a fallback
+            block.fill_version_from("EU", "NA");
+            assert_eq!(
+                realized(&block, "EU"),
+                [&eu[..], &na[na.len() - 1..]].concat()
+            );
+            // Unknown versions are added to the version list
+            block.fill_version_from("JP", "NA");
+            assert_eq!(
+                block.versions.as_ref().unwrap().last().map(|v| v.name()),
+                Some("JP")
+            );
+            assert_eq!(realized(&block, "JP"), na);
+            // Unknown base versions have no effect
+            let before = block.clone();
+            block.fill_version_from("EU", "US");
+            assert_eq!(block, before);
+        }
+
+        #[test]
+        fn test_address_for_name() {
+            let block = get_sorted_block();
+            let function1 = &block.functions[0];
+            let function2 = &block.functions[1];
+            assert_eq!(
+                function1.address_for_name("NA", &block),
+                Some(&Linkable::from([0x2100000, 0x2100100]))
+            );
+            assert_eq!(
+                function1.address_for_name("EU", &block),
+                Some(&Linkable::from(0x2100c00))
+            );
+            assert_eq!(function1.address_for_name("JP", &block), None);
+            assert_eq!(
+                function2.address_for_name("JP", &block),
+                Some(&Linkable::from([0x2101000, 0x2101100]))
+            );
+        }
+
         #[test]
         fn test_init_sort() {
             let mut block = get_sorted_block();
@@ -1462,9 +3252,11 @@ mod tests {
                     address: final_addresses.clone(),
                     length: final_addresses.clone(),
                     description: None,
+                    space_group: None,
                     subregions: Some(final_subregions.clone()),
                     functions: final_symbols.clone(),
                     data: final_symbols.clone(),
+                    extra_fields: None,
                 }
             )
         }
@@ -1479,6 +3271,7 @@ mod tests {
             let expanded_symbols = SymbolList::from([
                 Symbol {
                     name: "function1".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::ByVersion(
                         [
                             (("NA", 0).into(), Linkable::from([0x2100000, 0x2100100])),
@@ -1490,9 +3283,16 @@ mod tests {
                         [(("NA", 0).into(), 0x100), (("EU", 1).into(), 0x100)].into(),
                     )),
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
                 Symbol {
                     name: "function2".to_string(),
+                    deprecated_aliases: None,
                     address: MaybeVersionDep::ByVersion(
                         [
                             (("NA", 0).into(), Linkable::from([0x2101000, 0x2101100])),
@@ -1502,6 +3302,12 @@ mod tests {
                     ),
                     length: None,
                     description: None,
+                    element_size: None,
+                    data_type: None,
+                    inlined: None,
+                    stype: None,
+                    ranges: None,
+                    extra_fields: None,
                 },
             ]);
             block.expand_versions();
@@ -1512,9 +3318,11 @@ mod tests {
                     address,
                     length,
                     description,
+                    space_group: None,
                     subregions: None,
                     functions: expanded_symbols.clone(),
                     data: expanded_symbols.clone(),
+                    extra_fields: None,
                 }
             )
         }
@@ -1553,28 +3361,40 @@ mod tests {
             let mut data_iter = block.data_realized("NA");
             let exp = [
                 RealizedSymbol {
-                    name: &"function1",
+                    name: "function1",
+                    deprecated_aliases: None,
                     address: 0x2100000,
                     length: Some(0x100),
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"function1",
+                    name: "function1",
+                    deprecated_aliases: None,
                     address: 0x2100100,
                     length: Some(0x100),
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"function2",
+                    name: "function2",
+                    deprecated_aliases: None,
                     address: 0x2101000,
                     length: None,
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"function2",
+                    name: "function2",
+                    deprecated_aliases: None,
                     address: 0x2101100,
                     length: None,
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
             ];
             for e in exp.iter().chain(exp.iter()) {
@@ -1595,22 +3415,68 @@ mod tests {
         }
 
         #[test]
-        fn test_iter_realized_missing_key() {
+        fn test_iter_realized_inlined() {
+            let mut block = get_sorted_block();
+            let n_realized = block.iter_realized("NA").count();
+            let inlined = block.functions.get_mut(0).unwrap();
+            inlined.inlined = Some(true);
+            let inlined_name = inlined.name.clone();
+
+            // Inlined symbols are still realized, but marked as inlined
+            assert_eq!(block.iter_realized("NA").count(), n_realized);
+            assert!(block
+                .functions_realized("NA")
+                .all(|s| s.inlined == (s.name == inlined_name)));
+        }
+
+        #[test]
+        fn test_retain_symbols() {
+            let mut block = get_sorted_block();
+            block.functions.get_mut(0).unwrap().description =
+                Some(MaybeVersionDep::Common("  ".to_string()));
+            block.data.get_mut(0).unwrap().description =
+                Some(MaybeVersionDep::Common("documented".to_string()));
+            block.retain_symbols(Symbol::is_documented);
+            assert!(block.functions.is_empty());
+            assert_eq!(block.data.len(), 1);
+            assert_eq!(block.data[0].description_for(None), Some("documented"));
+        }
+
+        #[test]
+        fn test_rename_symbols() {
+            let mut block = get_sorted_block();
+            block.data.get_mut(0).unwrap().deprecated_aliases = Some(vec!["old".to_string()]);
+            block.rename_symbols(|name| format!("pmd_{}", name));
+            assert!(block.iter().all(|s| s.name.starts_with("pmd_")));
+            assert_eq!(
+                block.data[0].deprecated_aliases,
+                Some(vec!["pmd_old".to_string()])
+            );
+        }
+
+        #[test]
+        fn test_iter_realized_missing_key() {
             let block = get_sorted_block();
             let mut iter = block.iter_realized("JP");
             // Should still yield the Common info.
             let exp = [
                 RealizedSymbol {
-                    name: &"function2",
+                    name: "function2",
+                    deprecated_aliases: None,
                     address: 0x2101000,
                     length: None,
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"function2",
+                    name: "function2",
+                    deprecated_aliases: None,
                     address: 0x2101100,
                     length: None,
                     description: None,
+                    data_type: None,
+                    inlined: false,
                 },
             ];
             for e in exp.iter().chain(exp.iter()) {
@@ -1624,6 +3490,118 @@ mod tests {
     mod symgen_tests {
         use super::*;
 
+        #[test]
+        fn test_symbols_missing_version() {
+            let symgen = SymGen::read(
+                r"
+                main:
+                  versions:
+                    - NA
+                    - EU
+                  address: 0x2000000
+                  length: 0x100000
+                  functions:
+                    - name: fn_both
+                      address:
+                        NA: 0x2001000
+                        EU: 0x2001000
+                    - name: fn_na_only
+                      address:
+                        NA: 0x2002000
+                    - name: fn_common
+                      address: 0x2003000
+                  data:
+                    - name: DATA_EU_ONLY
+                      address:
+                        EU: 0x2004000
+                other:
+                  versions:
+                    - NA
+                  address: 0x2100000
+                  length: 0x100000
+                  functions: []
+                  data:
+                    - name: OTHER_NA_ONLY
+                      address:
+                        NA: 0x2100000
+                "
+                .as_bytes(),
+            )
+            .expect("Read failed");
+            let names = |symbols: Vec<&Symbol>| {
+                symbols
+                    .into_iter()
+                    .map(|s| s.name.clone())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                names(symgen.symbols_missing_version("NA", "EU")),
+                vec!["fn_na_only", "OTHER_NA_ONLY"]
+            );
+            assert_eq!(
+                names(symgen.symbols_missing_version("EU", "NA")),
+                vec!["DATA_EU_ONLY"]
+            );
+            assert!(symgen.symbols_missing_version("JP", "NA").is_empty());
+        }
+
+        #[test]
+        fn test_memory_layout() {
+            let symgen = SymGen::read(
+                r"
+                main:
+                  versions:
+                    - NA
+                    - EU
+                  address:
+                    NA: 0x2000000
+                    EU: 0x2000100
+                  length: 0x1000
+                  functions:
+                    - name: fn1
+                      address:
+                        NA: 0x2000000
+                        EU: 0x2000100
+                      length: 0x10
+                    - name: fn_na_only
+                      address:
+                        NA: 0x2000800
+                  data:
+                    - name: SOME_DATA
+                      address:
+                        NA: 0x2000400
+                        EU: 0x2000500
+                      length: 0x4
+                "
+                .as_bytes(),
+            )
+            .expect("Read failed");
+            let entry = |start, end, name, kind, depth| LayoutEntry {
+                start,
+                end,
+                name,
+                kind,
+                depth,
+            };
+            assert_eq!(
+                symgen.memory_layout("NA"),
+                vec![
+                    entry(0x2000000, 0x2001000, "main", LayoutKind::Block, 0),
+                    entry(0x2000000, 0x2000010, "fn1", LayoutKind::Function, 1),
+                    entry(0x2000400, 0x2000404, "SOME_DATA", LayoutKind::Data, 1),
+                    entry(0x2000800, 0x2000800, "fn_na_only", LayoutKind::Function, 1),
+                ]
+            );
+            assert_eq!(
+                symgen.memory_layout("EU"),
+                vec![
+                    entry(0x2000100, 0x2001100, "main", LayoutKind::Block, 0),
+                    entry(0x2000100, 0x2000110, "fn1", LayoutKind::Function, 1),
+                    entry(0x2000500, 0x2000504, "SOME_DATA", LayoutKind::Data, 1),
+                ]
+            );
+        }
+
         /// Returns a tuple of (symgen string, inited+sorted SymGen)
         fn get_symgen_data() -> (String, SymGen) {
             (
@@ -1687,10 +3665,12 @@ other:
                                 [(("v1", 0).into(), 0x100000), (("v2", 1).into(), 0x100004)].into(),
                             ),
                             description: Some("foo".to_string()),
+                            space_group: None,
                             subregions: None,
                             functions: [
                                 Symbol {
                                     name: "fn1".to_string(),
+                                    deprecated_aliases: None,
                                     address: MaybeVersionDep::ByVersion(
                                         [
                                             (("v1", 0).into(), 0x2001000.into()),
@@ -1699,10 +3679,19 @@ other:
                                         .into(),
                                     ),
                                     length: Some(MaybeVersionDep::Common(0x1000)),
-                                    description: Some("multi\nline\ndescription".to_string()),
+                                    description: Some(MaybeVersionDep::Common(
+                                        "multi\nline\ndescription".to_string(),
+                                    )),
+                                    element_size: None,
+                                    data_type: None,
+                                    inlined: None,
+                                    stype: None,
+                                    ranges: None,
+                                    extra_fields: None,
                                 },
                                 Symbol {
                                     name: "fn2".to_string(),
+                                    deprecated_aliases: None,
                                     address: MaybeVersionDep::ByVersion(
                                         [
                                             (("v1", 0).into(), [0x2002000, 0x2003000].into()),
@@ -1711,12 +3700,19 @@ other:
                                         .into(),
                                     ),
                                     length: None,
-                                    description: Some("baz".to_string()),
+                                    description: Some(MaybeVersionDep::Common("baz".to_string())),
+                                    element_size: None,
+                                    data_type: None,
+                                    inlined: None,
+                                    stype: None,
+                                    ranges: None,
+                                    extra_fields: None,
                                 },
                             ]
                             .into(),
                             data: [Symbol {
                                 name: "SOME_DATA".to_string(),
+                                deprecated_aliases: None,
                                 address: MaybeVersionDep::ByVersion(
                                     [
                                         (("v1", 0).into(), 0x2000000.into()),
@@ -1727,9 +3723,18 @@ other:
                                 length: Some(MaybeVersionDep::ByVersion(
                                     [(("v1", 0).into(), 0x1000), (("v2", 1).into(), 0x2000)].into(),
                                 )),
-                                description: Some("foo bar baz".to_string()),
+                                description: Some(MaybeVersionDep::Common(
+                                    "foo bar baz".to_string(),
+                                )),
+                                element_size: None,
+                                data_type: None,
+                                inlined: None,
+                                stype: None,
+                                ranges: None,
+                                extra_fields: None,
                             }]
                             .into(),
+                            extra_fields: None,
                         },
                     ),
                     (
@@ -1739,15 +3744,24 @@ other:
                             address: MaybeVersionDep::Common(0x2100000),
                             length: MaybeVersionDep::Common(0x100000),
                             description: None,
+                            space_group: None,
                             subregions: None,
                             functions: [Symbol {
                                 name: "fn3".to_string(),
+                                deprecated_aliases: None,
                                 address: MaybeVersionDep::Common(0x2100000.into()),
                                 length: None,
                                 description: None,
+                                element_size: None,
+                                data_type: None,
+                                inlined: None,
+                                stype: None,
+                                ranges: None,
+                                extra_fields: None,
                             }]
                             .into(),
                             data: [].into(),
+                            extra_fields: None,
                         },
                     ),
                 ]),
@@ -1824,10 +3838,12 @@ other:
                                 .into(),
                             ),
                             description: Some("foo".to_string()),
+                            space_group: None,
                             subregions: None,
                             functions: [
                                 Symbol {
                                     name: "fn1".to_string(),
+                                    deprecated_aliases: None,
                                     address: MaybeVersionDep::ByVersion(
                                         [
                                             (("v1", 0).into(), 0x2001000FF.into()),
@@ -1836,10 +3852,19 @@ other:
                                         .into(),
                                     ),
                                     length: Some(MaybeVersionDep::Common(0x1000)),
-                                    description: Some("multi\nline\ndescription".to_string()),
+                                    description: Some(MaybeVersionDep::Common(
+                                        "multi\nline\ndescription".to_string(),
+                                    )),
+                                    element_size: None,
+                                    data_type: None,
+                                    inlined: None,
+                                    stype: None,
+                                    ranges: None,
+                                    extra_fields: None,
                                 },
                                 Symbol {
                                     name: "fn2".to_string(),
+                                    deprecated_aliases: None,
                                     address: MaybeVersionDep::ByVersion(
                                         [
                                             (("v1", 0).into(), [0x2002000FF, 0x2003000FF].into()),
@@ -1848,12 +3873,19 @@ other:
                                         .into(),
                                     ),
                                     length: None,
-                                    description: Some("baz".to_string()),
+                                    description: Some(MaybeVersionDep::Common("baz".to_string())),
+                                    element_size: None,
+                                    data_type: None,
+                                    inlined: None,
+                                    stype: None,
+                                    ranges: None,
+                                    extra_fields: None,
                                 },
                             ]
                             .into(),
                             data: [Symbol {
                                 name: "SOME_DATA".to_string(),
+                                deprecated_aliases: None,
                                 address: MaybeVersionDep::ByVersion(
                                     [
                                         (("v1", 0).into(), 0x2000000FF.into()),
@@ -1864,9 +3896,18 @@ other:
                                 length: Some(MaybeVersionDep::ByVersion(
                                     [(("v1", 0).into(), 0x1000), (("v2", 1).into(), 0x2000)].into(),
                                 )),
-                                description: Some("foo bar baz".to_string()),
+                                description: Some(MaybeVersionDep::Common(
+                                    "foo bar baz".to_string(),
+                                )),
+                                element_size: None,
+                                data_type: None,
+                                inlined: None,
+                                stype: None,
+                                ranges: None,
+                                extra_fields: None,
                             }]
                             .into(),
+                            extra_fields: None,
                         },
                     ),
                     (
@@ -1876,15 +3917,24 @@ other:
                             address: MaybeVersionDep::Common(0x2100000FFFF),
                             length: MaybeVersionDep::Common(0x100000FFFF),
                             description: None,
+                            space_group: None,
                             subregions: None,
                             functions: [Symbol {
                                 name: "fn3".to_string(),
+                                deprecated_aliases: None,
                                 address: MaybeVersionDep::Common(0x2100000FFFF.into()),
                                 length: None,
                                 description: None,
+                                element_size: None,
+                                data_type: None,
+                                inlined: None,
+                                stype: None,
+                                ranges: None,
+                                extra_fields: None,
                             }]
                             .into(),
                             data: [].into(),
+                            extra_fields: None,
                         },
                     ),
                 ]),
@@ -1907,6 +3957,553 @@ other:
             read_test_template(get_symgen_data_64bit);
         }
 
+        #[test]
+        fn test_read_int_underscores() {
+            let (input, expected) = get_symgen_data();
+            let input = input
+                .replace("0x2000000", "0x0200_0000")
+                .replace("0x2003000", "0x200_3000")
+                .replace("length: 0x1000\n", "length: 4_096\n");
+            let obj = SymGen::read(input.as_bytes()).expect("Read failed");
+            assert_eq!(&obj, &expected);
+        }
+
+        #[test]
+        fn test_read_int_underscores_ignores_block_scalars() {
+            let input = r"main:
+  address: 0x2000000
+  length: 0x100000
+  functions:
+    - name: fn1
+      address: 0x200_1000
+      description: |-
+        Example:
+          address: 0x1_0
+          length: 2_0
+      length: 0x100
+  data: []
+";
+            let obj = SymGen::read(input.as_bytes()).expect("Read failed");
+            let function = obj.symbols().next().unwrap();
+            assert_eq!(function.address, MaybeVersionDep::Common(0x2001000.into()));
+            assert_eq!(function.length, Some(MaybeVersionDep::Common(0x100)));
+            assert_eq!(
+                function.description,
+                Some(MaybeVersionDep::Common(
+                    "Example:\n  address: 0x1_0\n  length: 2_0".to_string()
+                ))
+            );
+        }
+
+        #[test]
+        fn test_read_write_ranges() {
+            let input = r"main:
+  versions:
+    - NA
+    - EU
+  address: 0x2000000
+  length: 0x100000
+  functions: []
+  data:
+    - name: data1
+      address: 0x2001000
+      length: 0x100
+      ranges:
+        - address:
+            NA: 0x2003000
+            EU: 0x2003100
+          length: 0x80
+";
+            let obj = SymGen::read(input.as_bytes()).expect("Read failed");
+            let symbol = obj.iter().next().unwrap().1.data.iter().next().unwrap();
+            assert_eq!(symbol.ranges.as_ref().map(|r| r.len()), Some(1));
+            assert_eq!(
+                obj.write_to_str(IntFormat::Hexadecimal)
+                    .expect("Write failed"),
+                input
+            );
+        }
+
+        #[test]
+        fn test_read_write_versioned_description() {
+            let input = r"main:
+  versions:
+    - NA
+    - EU
+  address: 0x2000000
+  length: 0x100000
+  functions:
+    - name: fn1
+      address: 0x2001000
+      description:
+        NA: NA description
+        EU: |-
+          multi
+          line
+  data: []
+";
+            let mut obj = SymGen::read(input.as_bytes()).expect("Read failed");
+            obj.init();
+            let symbol = obj
+                .iter()
+                .next()
+                .unwrap()
+                .1
+                .functions
+                .iter()
+                .next()
+                .unwrap();
+            assert_eq!(
+                symbol.description_for(Some(&"NA".into())),
+                Some("NA description")
+            );
+            assert_eq!(
+                symbol.description_for(Some(&"EU".into())),
+                Some("multi\nline")
+            );
+            assert_eq!(symbol.description_for(None), None);
+            assert_eq!(
+                obj.write_to_str(IntFormat::Hexadecimal)
+                    .expect("Write failed"),
+                input
+            );
+        }
+
+        #[test]
+        fn test_read_write_symbol_type() {
+            let input = r"main:
+  address: 0x2000000
+  length: 0x100000
+  functions:
+    - name: fn1
+      address: 0x2001000
+    - name: SOME_DATA
+      address: 0x2002000
+      type: data
+  data:
+    - name: fn2
+      address: 0x2003000
+      type: function
+";
+            let obj = SymGen::read(input.as_bytes()).expect("Read failed");
+            let block = obj.get(&obj.block_key("main").unwrap().clone()).unwrap();
+            let names = |list: &SymbolList| list.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+            assert_eq!(names(&block.functions), vec!["fn1", "fn2"]);
+            assert_eq!(names(&block.data), vec!["SOME_DATA"]);
+            assert_eq!(block.data[0].stype, Some(SymbolType::Data));
+            assert_eq!(
+                obj.write_to_str(IntFormat::Hexadecimal)
+                    .expect("Write failed"),
+                r"main:
+  address: 0x2000000
+  length: 0x100000
+  functions:
+    - name: fn1
+      address: 0x2001000
+    - name: fn2
+      address: 0x2003000
+      type: function
+  data:
+    - name: SOME_DATA
+      address: 0x2002000
+      type: data
+"
+            );
+        }
+
+        #[test]
+        fn test_read_write_data_type() {
+            let input = r"main:
+  address: 0x2000000
+  length: 0x100000
+  functions: []
+  data:
+    - name: SOME_STRUCT
+      address: 0x2001000
+      length: 0x40
+      data_type: struct some_struct
+    - name: SOME_TABLE
+      address: 0x2002000
+      length: 0x40
+      element_size: 0x4
+      data_type: u32
+";
+            let obj = SymGen::read(input.as_bytes()).expect("Read failed");
+            assert_eq!(
+                obj.data_realized("v1")
+                    .map(|s| s.data_type)
+                    .collect::<Vec<_>>(),
+                vec![Some("struct some_struct"), Some("u32")]
+            );
+            assert_eq!(
+                obj.write_to_str(IntFormat::Hexadecimal)
+                    .expect("Write failed"),
+                input
+            );
+        }
+
+        #[test]
+        fn test_write_canonical_field_order() {
+            let input = r"main:
+  address: 0x2000000
+  length: 0x100000
+  functions: []
+  data:
+    - custom: 1
+      type: data
+      inlined: false
+      data_type: u32
+      element_size: 4
+      ranges:
+        - address: 0x2001100
+          length: 0x10
+      description: foo
+      length: 0x40
+      address: 0x2001000
+      deprecated_aliases:
+        - OLD_TABLE
+      name: SOME_TABLE
+";
+            let expected = r"main:
+  address: 0x2000000
+  length: 0x100000
+  functions: []
+  data:
+    - name: SOME_TABLE
+      deprecated_aliases:
+        - OLD_TABLE
+      address: 0x2001000
+      length: 0x40
+      description: foo
+      ranges:
+        - address: 0x2001100
+          length: 0x10
+      element_size: 0x4
+      data_type: u32
+      inlined: false
+      type: data
+      custom: 1
+";
+            let obj = SymGen::read_allow_unknown_fields(input.as_bytes()).expect("Read failed");
+            assert_eq!(
+                obj.write_to_str(IntFormat::Hexadecimal)
+                    .expect("Write failed"),
+                expected
+            );
+        }
+
+        #[test]
+        fn test_read_write_address_exprs() {
+            let input = r"main:
+  versions:
+    - NA
+    - EU
+  address: 0x2000000
+  length: 0x100000
+  functions:
+    - name: fn1
+      address: 0x2001000
+    - name: fn1_entry
+      address: fn1 + 0x10
+    - name: fn1_prologue
+      address:
+        NA: fn1_entry - 0x8
+        EU: 0x2001808
+  data: []
+";
+            let obj = SymGen::read(input.as_bytes()).expect("Read failed");
+            let addrs = |vers| {
+                obj.functions_realized(vers)
+                    .map(|s| (s.name, s.address))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                addrs("NA"),
+                vec![
+                    ("fn1", 0x2001000),
+                    ("fn1_entry", 0x2001010),
+                    ("fn1_prologue", 0x2001008)
+                ]
+            );
+            assert_eq!(
+                addrs("EU"),
+                vec![
+                    ("fn1", 0x2001000),
+                    ("fn1_entry", 0x2001010),
+                    ("fn1_prologue", 0x2001808)
+                ]
+            );
+            assert_eq!(
+                obj.write_to_str(IntFormat::Hexadecimal)
+                    .expect("Write failed"),
+                input
+            );
+        }
+
+        #[test]
+        fn test_address_expr_errors() {
+            let read_err = |functions: &str| {
+                SymGen::read(
+                    format!(
+                        "main:\n  address: 0x2000000\n  length: 0x100000\n  functions:\n{}  data: []\n",
+                        functions
+                    )
+                    .as_bytes(),
+                )
+                .expect_err("Read succeeded")
+                .to_string()
+            };
+            assert!(read_err("    - name: fn1\n      address: missing + 4\n")
+                .contains("undefined symbol \"missing\""));
+            assert_eq!(
+                read_err(
+                    "    - name: fn1\n      address: fn2 + 4\n    - name: fn2\n      address: fn1 - 4\n"
+                ),
+                "cyclic address expressions: fn1 -> fn2 -> fn1"
+            );
+            assert!(read_err(
+                "    - name: fn1\n      address: [0x2000000, 0x2001000]\n    - name: fn2\n      address: fn1\n"
+            )
+            .contains("multiple addresses"));
+            assert!(read_err(
+                "    - name: fn1\n      address:\n        v1: 0x2000000\n    - name: fn2\n      address:\n        v2: fn1\n"
+            )
+            .contains("no address for version \"v2\""));
+            assert!(read_err(
+                "    - name: fn1\n      address:\n        v1: [0x2000000, 0x2001000]\n    - name: fn2\n      address: fn1 + 4\n"
+            )
+            .contains("multiple addresses for version \"v1\""));
+        }
+
+        #[test]
+        fn test_address_exprs_by_version_base() {
+            let input = r"main:
+  versions:
+    - NA
+    - EU
+  address: 0x2000000
+  length: 0x100000
+  functions:
+    - name: fn1
+      address:
+        NA: 0x2001000
+        EU: 0x2002000
+    - name: fn1_entry
+      address: fn1 + 0x10
+    - name: fn1_body
+      address: fn1_entry + 0x8
+  data: []
+";
+            let obj = SymGen::read(input.as_bytes()).expect("Read failed");
+            let addrs = |vers| {
+                obj.functions_realized(vers)
+                    .map(|s| (s.name, s.address))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                addrs("NA"),
+                vec![
+                    ("fn1", 0x2001000),
+                    ("fn1_entry", 0x2001010),
+                    ("fn1_body", 0x2001018)
+                ]
+            );
+            assert_eq!(
+                addrs("EU"),
+                vec![
+                    ("fn1", 0x2002000),
+                    ("fn1_entry", 0x2002010),
+                    ("fn1_body", 0x2002018)
+                ]
+            );
+            // The expressions are still written as version-independent addresses
+            assert_eq!(
+                obj.write_to_str(IntFormat::Hexadecimal)
+                    .expect("Write failed"),
+                input
+            );
+        }
+
+        #[test]
+        fn test_address_exprs_ignore_subregions() {
+            let mut obj = SymGen::read(
+                r"main:
+  address: 0x2000000
+  length: 0x100000
+  functions:
+    - name: fn1
+      address: 0x2001000
+  data: []
+  subregions:
+    - sub.yml
+"
+                .as_bytes(),
+            )
+            .expect("Read failed");
+            obj.resolve_subregions("", |_| {
+                Ok(r"sub:
+  address: 0x2010000
+  length: 0x1000
+  functions:
+    - name: sub_fn
+      address: 0x2010000
+  data: []
+"
+                .as_bytes())
+            })
+            .expect("Failed to resolve subregions");
+            let block = obj.blocks_mut().next().unwrap();
+            let mut fn2 = block.functions.get(0).unwrap().clone();
+            fn2.name = "fn2".to_string();
+            fn2.address = MaybeVersionDep::Common(Linkable::Expr(AddressExpr::new("sub_fn", 4)));
+            block.functions.push(fn2);
+            // Symbols in subregions can't be referenced, even once the subregions are resolved
+            assert!(obj
+                .resolve_address_exprs()
+                .expect_err("Resolution succeeded")
+                .to_string()
+                .contains("undefined symbol \"sub_fn\""));
+        }
+
+        #[test]
+        fn test_read_write_unknown_fields() {
+            let input = r"main:
+  address: 0x2000000
+  length: 0x100000
+  functions:
+    - name: fn1
+      address: 0x2001000
+      calling_convention: thumb
+  data: []
+  origin:
+    source: ram dump
+";
+            assert!(SymGen::read(input.as_bytes()).is_err());
+
+            let obj = SymGen::read_allow_unknown_fields(input.as_bytes()).expect("Read failed");
+            let block = obj.iter().next().unwrap().1;
+            assert!(block.extra_fields.is_some());
+            let symbol = block.functions.iter().next().unwrap();
+            assert_eq!(
+                symbol
+                    .extra_fields
+                    .as_ref()
+                    .and_then(|f| f.get(&"calling_convention".into())),
+                Some(&"thumb".into())
+            );
+            assert_eq!(
+                obj.write_to_str(IntFormat::Hexadecimal)
+                    .expect("Write failed"),
+                input
+            );
+        }
+
+        #[test]
+        fn test_unknown_field_line() {
+            let input = r#"main:
+  address: 0x2000000
+  length: 0x100000
+  functions:
+    - name: fn1
+      address: 0x2001000
+    - address: 0x2002000
+      calling_convention: arm
+      name: "fn2"
+      deprecated_aliases:
+        - calling_convention
+  data: []
+  origin: ram dump
+other:
+  address: 0x0
+  length: 0x10
+  functions: []
+  data: []
+  origin: elsewhere
+"#;
+            assert_eq!(unknown_field_line(input, "main", None, "origin"), Some(13));
+            assert_eq!(unknown_field_line(input, "other", None, "origin"), Some(19));
+            assert_eq!(
+                unknown_field_line(input, "main", Some("fn2"), "calling_convention"),
+                Some(8)
+            );
+            assert_eq!(
+                unknown_field_line(input, "main", Some("fn1"), "calling_convention"),
+                None
+            );
+            assert_eq!(unknown_field_line(input, "missing", None, "origin"), None);
+
+            let err = SymGen::read(input.as_bytes()).expect_err("Read succeeded");
+            assert!(err
+                .to_string()
+                .contains("unknown field `origin` at line 13"));
+        }
+
+        #[test]
+        fn test_write_grouped() {
+            let (input, expected) = get_symgen_data();
+            let yaml = expected
+                .write_to_str(IntFormat::GroupedHexadecimal)
+                .expect("Write failed");
+            assert_eq!(
+                &yaml,
+                &input
+                    .replace("0x2000000", "0x200_0000")
+                    .replace("0x2001000", "0x200_1000")
+                    .replace("0x2002000", "0x200_2000")
+                    .replace("0x2003000", "0x200_3000")
+                    .replace("0x2100000", "0x210_0000")
+                    .replace("0x100000", "0x10_0000")
+                    .replace("0x100004", "0x10_0004")
+            );
+            // Grouping should be stable under round-tripping
+            assert_eq!(
+                &SymGen::read(yaml.as_bytes()).expect("Read failed"),
+                &expected
+            );
+
+            let yaml = expected
+                .write_to_str(IntFormat::GroupedDecimal)
+                .expect("Write failed");
+            assert!(yaml.contains("v1: 33_558_528\n"));
+            assert!(yaml.contains("length: 4_096\n"));
+            assert_eq!(
+                &SymGen::read(yaml.as_bytes()).expect("Read failed"),
+                &expected
+            );
+        }
+
+        #[cfg(feature = "schema")]
+        #[test]
+        fn test_json_schema() {
+            let schema = serde_json::to_value(SymGen::json_schema()).expect("Serialize failed");
+            let defs = &schema["definitions"];
+            assert_eq!(defs["Block"]["type"], "object");
+            assert_eq!(defs["Symbol"]["additionalProperties"], false);
+            assert!(defs["Symbol"]["properties"]["inlined"].is_object());
+            assert_eq!(
+                defs["Symbol"]["required"],
+                serde_json::json!(["address", "name"])
+            );
+        }
+
+        #[test]
+        fn test_group_digits() {
+            assert_eq!(group_digits("1", 3), "1");
+            assert_eq!(group_digits("123", 3), "123");
+            assert_eq!(group_digits("1234", 3), "1_234");
+            assert_eq!(group_digits("1234567", 3), "1_234_567");
+            assert_eq!(group_digits("2012345", 4), "201_2345");
+            assert_eq!(group_digits("ABCD1234", 4), "ABCD_1234");
+        }
+
+        #[test]
+        fn test_int_format() {
+            assert_eq!(IntFormat::Decimal.format(33628997), "33628997");
+            assert_eq!(IntFormat::Hexadecimal.format(33628997), "0x2012345");
+            assert_eq!(IntFormat::GroupedDecimal.format(33628997), "33_628_997");
+            assert_eq!(IntFormat::GroupedHexadecimal.format(33628997), "0x201_2345");
+        }
+
         fn write_test_template<F: FnOnce() -> (String, SymGen)>(get_data: F) {
             let (expected, input) = get_data();
             let yaml = input
@@ -1925,6 +4522,140 @@ other:
             write_test_template(get_symgen_data_64bit);
         }
 
+        /// Writes a [`SymGen`] by serializing and postprocessing it all at once, which is how
+        /// [`SymGen::write_with_spacing()`] worked before it was made to stream blocks.
+        fn write_to_str_unstreamed(
+            symgen: &SymGen,
+            int_format: IntFormat,
+            block_spacing: usize,
+        ) -> String {
+            let yaml = SymGen::postprocess_yaml(
+                serde_yaml::to_string(symgen).expect("Serialize failed"),
+                int_format,
+            );
+            // Insert blank lines before each top-level key (i.e., block name), except the first
+            let mut spaced_yaml = String::with_capacity(yaml.len() + block_spacing * 16);
+            let mut first = true;
+            for line in yaml.lines() {
+                if line.starts_with(|c: char| !c.is_whitespace()) {
+                    if !first {
+                        spaced_yaml.push_str(&"\n".repeat(block_spacing));
+                    }
+                    first = false;
+                }
+                spaced_yaml.push_str(line);
+                spaced_yaml.push('\n');
+            }
+            spaced_yaml
+        }
+
+        #[test]
+        fn test_write_streamed_matches_unstreamed() {
+            let formats = [
+                IntFormat::Decimal,
+                IntFormat::Hexadecimal,
+                IntFormat::GroupedDecimal,
+                IntFormat::GroupedHexadecimal,
+            ];
+            for (_, symgen) in [get_symgen_data(), get_symgen_data_64bit()] {
+                for int_format in formats {
+                    for block_spacing in [0, 2] {
+                        let mut streamed = Vec::new();
+                        symgen
+                            .write_with_spacing(&mut streamed, int_format, block_spacing)
+                            .expect("Write failed");
+                        assert_eq!(
+                            String::from_utf8(streamed).expect("Invalid UTF-8"),
+                            write_to_str_unstreamed(&symgen, int_format, block_spacing)
+                        );
+                    }
+                }
+            }
+            let empty = SymGen::from([]);
+            assert_eq!(
+                empty
+                    .write_to_str(IntFormat::Hexadecimal)
+                    .expect("Write failed"),
+                write_to_str_unstreamed(&empty, IntFormat::Hexadecimal, 0)
+            );
+        }
+
+        #[test]
+        fn test_write_with_spacing() {
+            let (expected, input) = get_symgen_data();
+            let yaml = input
+                .write_to_str_with_spacing(IntFormat::Hexadecimal, 2)
+                .expect("Write failed");
+            assert_eq!(
+                &yaml,
+                &expected.replace(
+                    "      description: foo bar baz\nother:",
+                    "      description: foo bar baz\n\n\nother:"
+                )
+            );
+        }
+
+        #[test]
+        fn test_write_strips_description_trailing_whitespace() {
+            let input = SymGen::from([(
+                ("main", 0).into(),
+                Block {
+                    versions: None,
+                    address: MaybeVersionDep::Common(0x2000000),
+                    length: MaybeVersionDep::Common(0x100000),
+                    description: Some("foo \t".to_string()),
+                    space_group: None,
+                    subregions: None,
+                    functions: [Symbol {
+                        name: "fn1".to_string(),
+                        deprecated_aliases: None,
+                        address: MaybeVersionDep::Common(0x2000000.into()),
+                        length: None,
+                        description: Some(MaybeVersionDep::Common(
+                            "multi  \n\n  line\t\ndescription \n".to_string(),
+                        )),
+                        element_size: None,
+                        data_type: None,
+                        inlined: None,
+                        stype: None,
+                        ranges: None,
+                        extra_fields: None,
+                    }]
+                    .into(),
+                    data: [].into(),
+                    extra_fields: None,
+                },
+            )]);
+            let yaml = input
+                .write_to_str(IntFormat::Hexadecimal)
+                .expect("Write failed");
+            assert_eq!(
+                &yaml,
+                r#"main:
+  address: 0x2000000
+  length: 0x100000
+  description: foo
+  functions:
+    - name: fn1
+      address: 0x2000000
+      description: |-
+        multi
+
+          line
+        description
+  data: []
+"#
+            );
+            // Stripping whitespace should be idempotent
+            let reread = SymGen::read(yaml.as_bytes()).expect("Read failed");
+            assert_eq!(
+                &reread
+                    .write_to_str(IntFormat::Hexadecimal)
+                    .expect("Write failed"),
+                &yaml
+            );
+        }
+
         #[test]
         fn test_expand_versions() {
             let (_, mut symgen) = get_symgen_data();
@@ -1963,41 +4694,107 @@ other:
             assert_eq!(iter.next(), None);
         }
 
+        #[test]
+        fn test_blocks_by_address() {
+            let symgen = SymGen::read(
+                r#"
+                a:
+                  versions:
+                    - v1
+                    - v2
+                  address:
+                    v1: 0x3000
+                    v2: 0x1000
+                  length: 0x100
+                  functions: []
+                  data: []
+                b:
+                  address: 0x2000
+                  length: 0x100
+                  functions: []
+                  data: []
+                c:
+                  versions:
+                    - v1
+                    - v2
+                  address:
+                    v1: 0x1000
+                  length: 0x100
+                  functions: []
+                  data: []
+                d:
+                  address: 0x2000
+                  length: 0x100
+                  functions: []
+                  data: []
+                "#
+                .as_bytes(),
+            )
+            .expect("Read failed");
+            let get_block = |name| symgen.get(symgen.block_key(name).unwrap()).unwrap();
+            let (a, b, c, d) = (
+                get_block("a"),
+                get_block("b"),
+                get_block("c"),
+                get_block("d"),
+            );
+
+            assert_eq!(symgen.blocks_by_address(Some("v1")), vec![c, b, d, a]);
+            assert_eq!(symgen.blocks_by_address(Some("v2")), vec![a, b, d, c]);
+            // Blocks without a common address go last
+            assert_eq!(&symgen.blocks_by_address(None)[..2], &[b, d]);
+        }
+
         #[test]
         fn test_symbols_realized() {
             let (_, symgen) = get_symgen_data();
             let version_str = "v1";
             let functions_main_exp = [
                 RealizedSymbol {
-                    name: &"fn1",
+                    name: "fn1",
+                    deprecated_aliases: None,
                     address: 0x2001000,
                     length: Some(0x1000),
-                    description: Some(&"multi\nline\ndescription"),
+                    description: Some("multi\nline\ndescription"),
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"fn2",
+                    name: "fn2",
+                    deprecated_aliases: None,
                     address: 0x2002000,
                     length: None,
-                    description: Some(&"baz"),
+                    description: Some("baz"),
+                    data_type: None,
+                    inlined: false,
                 },
                 RealizedSymbol {
-                    name: &"fn2",
+                    name: "fn2",
+                    deprecated_aliases: None,
                     address: 0x2003000,
                     length: None,
-                    description: Some(&"baz"),
+                    description: Some("baz"),
+                    data_type: None,
+                    inlined: false,
                 },
             ];
             let data_main_exp = [RealizedSymbol {
-                name: &"SOME_DATA",
+                name: "SOME_DATA",
+                deprecated_aliases: None,
                 address: 0x2000000,
                 length: Some(0x1000),
-                description: Some(&"foo bar baz"),
+                description: Some("foo bar baz"),
+                data_type: None,
+                inlined: false,
             }];
             let functions_other_exp = [RealizedSymbol {
-                name: &"fn3",
+                name: "fn3",
+                deprecated_aliases: None,
                 address: 0x2100000,
                 length: None,
                 description: None,
+                data_type: None,
+                inlined: false,
             }];
 
             let mut iter = symgen.symbols_realized(version_str);
@@ -2023,6 +4820,199 @@ other:
             }
             assert_eq!(data_iter.next(), None);
         }
+
+        #[test]
+        fn test_name_index() {
+            let symgen = SymGen::read(
+                r"
+                main:
+                  address: 0x2000000
+                  length: 0x100000
+                  functions:
+                    - name: fn1
+                      deprecated_aliases:
+                        - OldFn1
+                        - shared
+                      address: 0x2001000
+                    - name: fn2
+                      deprecated_aliases:
+                        - OldFn1
+                      address: 0x2002000
+                  data:
+                    - name: shared
+                      address: 0x2003000
+                other:
+                  address: 0x2100000
+                  length: 0x100000
+                  functions:
+                    - name: fn2
+                      address: 0x2100000
+                    - name: fn3
+                      deprecated_aliases:
+                        - OldFn3
+                      address: 0x2101000
+                  data: []
+                "
+                .as_bytes(),
+            )
+            .expect("Read failed");
+            let index = symgen.build_name_index();
+            let found = |name| {
+                index
+                    .get(name)
+                    .map(|&(bname, s)| (bname.val.as_str(), s.name.as_str()))
+            };
+            assert_eq!(index.len(), 6);
+            assert_eq!(found("fn1"), Some(("main", "fn1")));
+            assert_eq!(found("OldFn3"), Some(("other", "fn3")));
+            // The first occurrence of a name wins
+            assert_eq!(found("OldFn1"), Some(("main", "fn1")));
+            assert_eq!(found("shared"), Some(("main", "fn1")));
+            assert_eq!(found("fn2"), Some(("main", "fn2")));
+            assert_eq!(found("fn4"), None);
+
+            // find_symbol() has the same precedence as the index
+            for name in ["fn1", "fn2", "fn3", "OldFn1", "OldFn3", "shared", "fn4"] {
+                assert_eq!(
+                    symgen.find_symbol(name).map(|(_, s)| s as *const Symbol),
+                    index.get(name).map(|&(_, s)| s as *const Symbol),
+                );
+            }
+        }
+
+        fn get_lookup_symgen() -> SymGen {
+            SymGen::read(
+                r"
+                main:
+                  versions:
+                    - v1
+                    - v2
+                  address: 0x2000000
+                  length: 0x10000
+                  functions:
+                    - name: big_fn
+                      address: 0x2001000
+                      length: 0x1000
+                    - name: nested_fn
+                      address: 0x2001100
+                      length: 0x100
+                    - name: unsized_fn
+                      address:
+                        v1: 0x2003000
+                        v2: 0x2005000
+                    - name: last_fn
+                      address: 0x2008000
+                  data:
+                    - name: NESTED_DATA
+                      address: 0x2001180
+                      length: 0x10
+                    - name: UNSIZED_DATA
+                      address: 0x2004000
+                other:
+                  versions:
+                    - v1
+                    - v2
+                  address: 0x2100000
+                  length:
+                    v2: 0x1000
+                  functions:
+                    - name: other_fn
+                      address: 0x2100000
+                  data: []
+                "
+                .as_bytes(),
+            )
+            .expect("Read failed")
+        }
+
+        #[test]
+        fn test_symbol_at() {
+            let symgen = get_lookup_symgen();
+            let name_at = |version, addr| symgen.symbol_at(version, addr).map(|s| s.name);
+            assert_eq!(name_at("v1", 0x2000FFF), None);
+            assert_eq!(name_at("v1", 0x2001000), Some("big_fn"));
+            assert_eq!(name_at("v1", 0x2001FFF), Some("big_fn"));
+            assert_eq!(name_at("v1", 0x2002000), None);
+            // Overlapping symbols prefer the tightest extent
+            assert_eq!(name_at("v1", 0x2001100), Some("nested_fn"));
+            assert_eq!(name_at("v1", 0x2001180), Some("NESTED_DATA"));
+            assert_eq!(name_at("v1", 0x2001190), Some("nested_fn"));
+            // Unsized functions extend to the next symbol
+            assert_eq!(name_at("v1", 0x2003000), Some("unsized_fn"));
+            assert_eq!(name_at("v1", 0x2003FFF), Some("unsized_fn"));
+            // Unsized data only covers its own address
+            assert_eq!(name_at("v1", 0x2004000), Some("UNSIZED_DATA"));
+            assert_eq!(name_at("v1", 0x2004001), None);
+            assert_eq!(name_at("v2", 0x2004001), None);
+            assert_eq!(name_at("v2", 0x2005000), Some("unsized_fn"));
+            assert_eq!(name_at("v2", 0x2007FFF), Some("unsized_fn"));
+            // The last unsized function extends to the end of its block...
+            assert_eq!(name_at("v1", 0x200FFFF), Some("last_fn"));
+            assert_eq!(name_at("v1", 0x2010000), None);
+            // ...or indefinitely if the block has no length for the version
+            assert_eq!(name_at("v1", 0x2100000), Some("other_fn"));
+            assert_eq!(name_at("v1", 0x2FFFFFF), Some("other_fn"));
+            assert_eq!(name_at("v2", 0x2100FFF), Some("other_fn"));
+            assert_eq!(name_at("v2", 0x2101000), None);
+        }
+
+        #[test]
+        fn test_symbol_index() {
+            let symgen = get_lookup_symgen();
+            for version in ["v1", "v2"] {
+                let index = symgen.symbol_index(version);
+                let extents: Vec<_> = symgen
+                    .blocks()
+                    .flat_map(|b| b.realized_extents(version))
+                    .collect();
+                // Compare against a linear scan over every extent
+                let scan = |addr| {
+                    extents
+                        .iter()
+                        .filter(|&&(_, extent)| bounds::contains_address(extent, addr))
+                        .min_by_key(|&&(_, (_, len))| (len.is_none(), len))
+                        .map(|&(s, _)| s)
+                };
+                for addr in (0x2000000..0x2010000)
+                    .step_by(0x80)
+                    .chain([0x2100000, 0x2FFFFFF])
+                {
+                    assert_eq!(
+                        index.symbol_at(addr),
+                        scan(addr),
+                        "version {}, address {:#X}",
+                        version,
+                        addr
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn test_symbols_in_range() {
+            let symgen = get_lookup_symgen();
+            let names_in = |version, start, end| {
+                symgen
+                    .symbols_in_range(version, start, end)
+                    .map(|s| s.name)
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                names_in("v1", 0x2001100, 0x2004000),
+                vec!["nested_fn", "NESTED_DATA", "unsized_fn"]
+            );
+            assert_eq!(
+                names_in("v2", 0x2000000, 0x2006000),
+                vec![
+                    "big_fn",
+                    "nested_fn",
+                    "NESTED_DATA",
+                    "UNSIZED_DATA",
+                    "unsized_fn"
+                ]
+            );
+            assert!(names_in("v1", 0x2001001, 0x2001100).is_empty());
+        }
     }
 
     #[cfg(test)]
@@ -2175,6 +5165,52 @@ other:
             assert_eq!(block_subregions[1], &sub2);
         }
 
+        #[test]
+        fn test_resolve_subregions_allow_unknown_fields() {
+            let name = "sub.yml";
+            let text = format!(
+                "{}:\n  address: 0x0\n  length: 0x10\n  origin: ram dump\n  functions: []\n  data: []\n",
+                name
+            );
+            let get_symgen = || {
+                SymGen::read(
+                    format!(
+                        "main:\n  address: 0x0\n  length: 0x100\n  subregions:\n    - {}\n  functions: []\n  data: []\n",
+                        name
+                    )
+                    .as_bytes(),
+                )
+                .expect("Failed to read SymGen")
+            };
+            let root_dir = Path::new(file!());
+            let file_opener = |p: &Path| {
+                if p == root_dir.join(name) {
+                    Ok(text.as_bytes())
+                } else {
+                    Err(io::Error::new(io::ErrorKind::NotFound, p.to_string_lossy()))
+                }
+            };
+
+            let err = get_symgen()
+                .resolve_subregions(root_dir, file_opener)
+                .expect_err("Resolved subregion with unknown fields");
+            assert!(err.to_string().contains("unknown field `origin` at line 4"));
+
+            let mut symgen = get_symgen();
+            symgen
+                .resolve_subregions_allow_unknown_fields(root_dir, file_opener)
+                .expect("Failed to resolve subregions");
+            let subregion = &symgen.blocks().next().unwrap().subregions.as_ref().unwrap()[0];
+            let sub_block = subregion
+                .contents
+                .as_ref()
+                .unwrap()
+                .blocks()
+                .next()
+                .unwrap();
+            assert!(sub_block.extra_fields.is_some());
+        }
+
         #[test]
         fn test_recursive_collapse_subregions() {
             let (name1, name2, name3) = ("sub1.yml", "sub2.yml", "sub3.yml");
@@ -2279,5 +5315,96 @@ other:
             symgen.collapse_subregions();
             assert_eq!(&symgen, &collapsed_symgen);
         }
+
+        #[test]
+        fn test_collapse_subregion() {
+            let (name1, name2) = ("sub1.yml", "sub2.yml");
+            let mut symgen = test_utils::get_symgen_with_subregions(
+                &format!(
+                    r#"main:
+                    address: 0x0
+                    length: 0x100
+                    subregions:
+                      - {}
+                      - {}
+                    functions:
+                      - name: fn0
+                        address: 0x0
+                    data: []
+                    "#,
+                    name1, name2
+                ),
+                &[
+                    (
+                        name1,
+                        r#"sub1:
+                        address: 0x0
+                        length: 0x100
+                        functions: []
+                        data:
+                          - name: data1
+                            address: 0x10
+                            length: 0x4
+                        "#,
+                    ),
+                    (
+                        name2,
+                        r#"sub2:
+                        address: 0x0
+                        length: 0x100
+                        functions:
+                          - name: fn2
+                            address: 0x8
+                        data: []
+                        "#,
+                    ),
+                ],
+            );
+            let block = symgen
+                .get_mut(&symgen.block_key("main").unwrap().clone())
+                .unwrap();
+
+            block
+                .collapse_subregion(name1)
+                .expect("Failed to collapse subregion");
+            let subregions = block.subregions.as_ref().unwrap();
+            assert_eq!(subregions.len(), 1);
+            assert!(subregions[0] == name2);
+            assert_eq!(
+                block
+                    .data
+                    .iter()
+                    .map(|s| s.name.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["data1"]
+            );
+            assert_eq!(
+                block
+                    .functions
+                    .iter()
+                    .map(|s| s.name.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["fn0"]
+            );
+
+            // Collapsing the same subregion again should fail
+            assert!(matches!(
+                block.collapse_subregion(name1),
+                Err(Error::Subregion(SubregionError::Missing(_)))
+            ));
+
+            block
+                .collapse_subregion(name2)
+                .expect("Failed to collapse subregion");
+            assert!(block.subregions.is_none());
+            assert_eq!(
+                block
+                    .functions
+                    .iter()
+                    .map(|s| s.name.as_str())
+                    .collect::<Vec<_>>(),
+                vec!["fn0", "fn2"]
+            );
+        }
     }
 }
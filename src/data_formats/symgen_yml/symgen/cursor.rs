@@ -373,10 +373,10 @@ mod tests {
     }
 
     fn get_test_subregions(symgen: &SymGen) -> (&SymGen, &SymGen, &SymGen, &SymGen) {
-        let sub1 = get_subregion(&symgen, "main", 0);
-        let sub2 = get_subregion(&symgen, "main", 1);
-        let sub3 = get_subregion(&sub1, "sub1", 0);
-        let sub4 = get_subregion(&sub1, "sub1", 1);
+        let sub1 = get_subregion(symgen, "main", 0);
+        let sub2 = get_subregion(symgen, "main", 1);
+        let sub3 = get_subregion(sub1, "sub1", 0);
+        let sub4 = get_subregion(sub1, "sub1", 1);
         (sub1, sub2, sub3, sub4)
     }
 
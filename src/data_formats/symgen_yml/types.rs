@@ -2,11 +2,14 @@
 
 use std::cmp::{self, Ordering};
 use std::collections::{btree_map::Entry, BTreeMap, HashMap};
+use std::convert::TryFrom;
 use std::fmt::{self, Display, Formatter};
 use std::iter::{self, FromIterator};
 use std::ops::{Deref, DerefMut};
 use std::slice;
 
+#[cfg(feature = "schema")]
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Unsigned integer type for addresses and lengths. This should be at least as large as the
@@ -84,6 +87,7 @@ pub type LinkableIter<'a> = OrOnce<&'a Uint, slice::Iter<'a, Uint>>;
 /// A serializable string whose sorting order can be controlled dynamically by assigning it an
 /// ordinal at runtime.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(transparent)]
 pub struct OrdString {
     /// An ordinal that dynamically controls the sort order or the [`OrdString`].
@@ -160,31 +164,145 @@ where
 /// but a function or constant can be assigned to multiple separate addresses if defined with
 /// internal linkage and included in multiple sources, which is a common pattern in C/C++.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(untagged)]
 pub enum Linkable {
     Single(Uint),
     Multiple(Vec<Uint>),
+    /// An address relative to another symbol's address. See [`AddressExpr`].
+    Expr(AddressExpr),
 }
 
 impl Linkable {
-    /// The value to use for comparisons.
-    fn cmp_key(&self) -> Uint {
-        match self {
-            Self::Single(x) => *x,
-            Self::Multiple(v) => *v.iter().min().unwrap_or(&0),
-        }
+    /// The lowest value within the [`Linkable`], or [`None`] if it has no values (including an
+    /// unresolved [`AddressExpr`]).
+    pub fn lowest(&self) -> Option<Uint> {
+        self.iter().min().copied()
     }
     /// Returns an iterator over the values within a [`Linkable`].
     ///
+    /// An unresolved [`AddressExpr`] has no values.
+    ///
     /// This is defined to return a concrete type so it can be stored in struct fields.
-    pub fn iter(&self) -> LinkableIter {
+    pub fn iter(&self) -> LinkableIter<'_> {
         match self {
             Self::Single(x) => OrOnce::Once(iter::once(x)),
             Self::Multiple(v) => OrOnce::Iter(v.iter()),
+            Self::Expr(e) => match &e.resolved {
+                Some(x) => OrOnce::Once(iter::once(x)),
+                None => OrOnce::Iter([].iter()),
+            },
+        }
+    }
+}
+
+/// A symbol address written as an offset from the address of another symbol in the same file,
+/// like `SomeFunction + 0x10` or `SomeFunction - 4`. A bare symbol name is also allowed.
+///
+/// Expressions are resolved for every version when a [`SymGen`] is read, and written back in
+/// their original form. A version-independent expression relative to a symbol with
+/// version-dependent addresses is resolved separately for each of that symbol's versions.
+///
+/// [`SymGen`]: super::SymGen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AddressExpr {
+    /// The name of the symbol that the address is relative to.
+    pub base: String,
+    /// The offset from the address of the base symbol.
+    pub offset: i64,
+    /// The resolved address, if the expression has been resolved.
+    pub resolved: Option<Uint>,
+    /// Whether the expression was written as a version-independent address, but was expanded into
+    /// a separate expression for each version of its base symbol's address in order to be
+    /// resolved. Such expressions are written back as a single version-independent address.
+    pub common: bool,
+}
+
+impl AddressExpr {
+    /// Creates a new, unresolved [`AddressExpr`].
+    pub fn new(base: &str, offset: i64) -> Self {
+        Self {
+            base: base.to_string(),
+            offset,
+            resolved: None,
+            common: false,
+        }
+    }
+}
+
+/// Compare expressions as written, irrespective of whether or how they've been resolved.
+impl PartialEq for AddressExpr {
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base && self.offset == other.offset
+    }
+}
+
+impl Eq for AddressExpr {}
+
+impl TryFrom<String> for AddressExpr {
+    type Error = String;
+
+    fn try_from(expr: String) -> Result<Self, Self::Error> {
+        let err = || format!("invalid address expression '{}'", expr);
+        let (base, offset) = match expr.find(['+', '-']) {
+            Some(i) => {
+                let magnitude = expr[i + 1..].trim();
+                // The operator is the only sign allowed
+                if magnitude.starts_with(['+', '-']) {
+                    return Err(err());
+                }
+                let magnitude = match magnitude.strip_prefix("0x") {
+                    Some(hex) => i64::from_str_radix(hex, 16),
+                    None => magnitude.parse::<i64>(),
+                }
+                .map_err(|_| err())?;
+                let offset = if expr[i..].starts_with('-') {
+                    magnitude.checked_neg().ok_or_else(err)?
+                } else {
+                    magnitude
+                };
+                (expr[..i].trim(), offset)
+            }
+            None => (expr.trim(), 0),
+        };
+        let is_ident = base.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && base.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_ident {
+            return Err(err());
+        }
+        Ok(Self::new(base, offset))
+    }
+}
+
+impl From<AddressExpr> for String {
+    fn from(expr: AddressExpr) -> Self {
+        // Offsets are written in decimal so they get the same integer formatting as other
+        // addresses when written to a resymgen YAML file
+        match expr.offset.cmp(&0) {
+            Ordering::Equal => expr.base,
+            Ordering::Greater => format!("{} + {}", expr.base, expr.offset),
+            Ordering::Less => format!("{} - {}", expr.base, expr.offset.unsigned_abs()),
         }
     }
 }
 
+impl Display for AddressExpr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", String::from(self.clone()))
+    }
+}
+
+#[cfg(feature = "schema")]
+impl JsonSchema for AddressExpr {
+    fn schema_name() -> String {
+        "AddressExpr".to_string()
+    }
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
 impl From<Uint> for Linkable {
     fn from(val: Uint) -> Self {
         Self::Single(val)
@@ -213,12 +331,18 @@ impl PartialOrd for Linkable {
 
 impl Ord for Linkable {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.cmp_key().cmp(&other.cmp_key())
+        // Linkables without any values (e.g., unresolved expressions) sort last
+        let key = |l: &Self| {
+            let lowest = l.lowest();
+            (lowest.is_none(), lowest)
+        };
+        key(self).cmp(&key(other))
     }
 }
 
 /// A version of a binary.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct Version(OrdString);
 
 impl Version {
@@ -230,6 +354,12 @@ impl Version {
     pub fn name(&self) -> &str {
         &self.0.val
     }
+    /// Renames the [`Version`] if its name is a key in `renames`. The ordinal is left as-is.
+    pub fn rename(&mut self, renames: &HashMap<String, String>) {
+        if let Some(new_name) = renames.get(&self.0.val) {
+            self.0.val = new_name.clone();
+        }
+    }
 }
 
 impl From<(&str, u64)> for Version {
@@ -265,6 +395,7 @@ where
 ///
 /// [`VersionDep<T>`] implements a similar API to a [`HashMap<Version, T>`], including the entry API.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 pub struct VersionDep<T>(BTreeMap<Version, T>);
 
 impl<T> VersionDep<T> {
@@ -281,6 +412,21 @@ impl<T> VersionDep<T> {
         }
     }
 
+    /// Renames the [`Version`] keys in the [`VersionDep<T>`] according to `renames`, which maps
+    /// old version names to new ones.
+    ///
+    /// If multiple versions are renamed to the same name, the value of the last one (in version
+    /// order) is kept.
+    pub fn rename_versions(&mut self, renames: &HashMap<String, String>) {
+        // Remove each element, rename the key, and reinsert
+        let keys: Vec<_> = self.0.keys().cloned().collect();
+        for mut vers in keys {
+            let val = self.0.remove(&vers).unwrap();
+            vers.rename(renames);
+            self.0.insert(vers, val);
+        }
+    }
+
     /// Searches for a [`Version`] in the [`VersionDep<T>`] with the given name.
     pub fn find_version(&self, name: &str) -> Option<&Version> {
         self.versions().find(|&v| v.name() == name)
@@ -292,7 +438,7 @@ impl<T> VersionDep<T> {
 
     /// Gets the given [`Version`]'s corresponding entry in the [`VersionDep<T>`] for in-place
     /// manipulation, where the given key is matched by name.
-    pub fn entry(&mut self, key: Version) -> Entry<Version, T> {
+    pub fn entry(&mut self, key: Version) -> Entry<'_, Version, T> {
         self.entry_native(self.find_native_version(&key).cloned().unwrap_or(key))
     }
     /// Gets the given native [`Version`]'s corresponding entry in the [`VersionDep<T>`] for
@@ -302,7 +448,7 @@ impl<T> VersionDep<T> {
     /// This method is less flexible than the `entry()` method, but is less work
     /// (it is a pure map lookup), so it's useful if you are already working within the
     /// [`VersionDep<T>`]'s native [`Version`] space.
-    pub fn entry_native(&mut self, native_key: Version) -> Entry<Version, T> {
+    pub fn entry_native(&mut self, native_key: Version) -> Entry<'_, Version, T> {
         self.0.entry(native_key)
     }
     /// Returns a reference to the value in the [`VersionDep<T>`] corresponding to the [`Version`],
@@ -384,6 +530,13 @@ impl<T> VersionDep<T> {
     pub fn versions(&self) -> impl Iterator<Item = &Version> {
         self.0.keys()
     }
+    /// Retains only the [`Version`]-value pairs specified by the predicate `f`.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&Version, &mut T) -> bool,
+    {
+        self.0.retain(f)
+    }
 }
 
 impl<T, const N: usize> From<[(Version, T); N]> for VersionDep<T> {
@@ -432,6 +585,7 @@ impl<T: Ord> Ord for VersionDep<T> {
 /// [`MaybeVersionDep<T>`] implements a similar but more restricted access API to
 /// [`VersionDep<T>`].
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(untagged)]
 pub enum MaybeVersionDep<T> {
     /// A single scalar value common to all versions.
@@ -537,6 +691,16 @@ impl<T> MaybeVersionDep<T> {
             Self::ByVersion(v) => OrOnce::Iter(v.values_mut()),
         }
     }
+    /// Returns an [`Iterator`] over all the values in the [`MaybeVersionDep`], paired with their
+    /// [`Version`]s. The version is `None` for a [`Common`] value.
+    ///
+    /// [`Common`]: MaybeVersionDep::Common
+    pub fn iter_versioned(&self) -> impl Iterator<Item = (Option<&Version>, &T)> {
+        match self {
+            Self::Common(x) => OrOnce::Once(iter::once((None, x))),
+            Self::ByVersion(v) => OrOnce::Iter(v.iter().map(|(v, x)| (Some(v), x))),
+        }
+    }
     /// Returns an [`Iterator`] over all the [`Version`] keys in the [`MaybeVersionDep`].
     pub fn versions(&self) -> impl Iterator<Item = &Version> {
         match self {
@@ -544,6 +708,28 @@ impl<T> MaybeVersionDep<T> {
             Self::ByVersion(v) => OrEmpty::Iter(v.versions()),
         }
     }
+    /// Renames the [`Version`]s according to `renames`. [`Common`] values are left untouched.
+    ///
+    /// See [`VersionDep::rename_versions()`].
+    ///
+    /// [`Common`]: MaybeVersionDep::Common
+    pub fn rename_versions(&mut self, renames: &HashMap<String, String>) {
+        if let Self::ByVersion(v) = self {
+            v.rename_versions(renames);
+        }
+    }
+    /// Retains only the [`Version`]s specified by the predicate `f`. [`Common`] values are left
+    /// untouched.
+    ///
+    /// [`Common`]: MaybeVersionDep::Common
+    pub fn retain_versions<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&Version) -> bool,
+    {
+        if let Self::ByVersion(v) = self {
+            v.retain(|vers, _| f(vers));
+        }
+    }
 }
 
 impl<T: Clone> MaybeVersionDep<T> {
@@ -573,6 +759,22 @@ impl<T: Clone> MaybeVersionDep<T> {
             *self = Self::ByVersion(self.by_version(all_versions));
         }
     }
+    /// Copies the value for the `base` [`Version`] to `version` if the [`MaybeVersionDep<T>`] is
+    /// [`ByVersion`] and has a value for `base` but not for `version`. Versions are matched by
+    /// name. Returns whether a value was copied.
+    ///
+    /// [`ByVersion`]: MaybeVersionDep::ByVersion
+    pub fn fill_version_from(&mut self, version: &Version, base: &Version) -> bool {
+        if let Self::ByVersion(v) = self {
+            if v.get(version).is_none() {
+                if let Some(val) = v.get(base).cloned() {
+                    v.insert(version.clone(), val);
+                    return true;
+                }
+            }
+        }
+        false
+    }
 }
 
 impl<T: Sort> Sort for MaybeVersionDep<T> {
@@ -632,6 +834,31 @@ impl<T: Ord> Ord for MaybeVersionDep<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[test]
+    fn test_parse_address_expr() {
+        let parse = |s: &str| AddressExpr::try_from(s.to_string());
+        assert_eq!(parse("fn1 + 0x10"), Ok(AddressExpr::new("fn1", 0x10)));
+        assert_eq!(parse("fn1-8"), Ok(AddressExpr::new("fn1", -8)));
+        assert_eq!(parse("SOME_DATA"), Ok(AddressExpr::new("SOME_DATA", 0)));
+        assert!(parse("1fn + 4").is_err());
+        assert!(parse("fn1 + ").is_err());
+        assert!(parse("fn1 * 2").is_err());
+        // Only the operator can carry a sign
+        assert!(parse("fn1 - -5").is_err());
+        assert!(parse("fn1 + +5").is_err());
+        assert!(parse("fn1 - -9223372036854775808").is_err());
+        assert_eq!(
+            parse("fn1 - 9223372036854775807"),
+            Ok(AddressExpr::new("fn1", -i64::MAX))
+        );
+        assert_eq!(
+            parse("fn1 - 0x8000000000000000"),
+            Err("invalid address expression 'fn1 - 0x8000000000000000'".to_string())
+        );
+        assert_eq!(String::from(AddressExpr::new("fn1", -8)), "fn1 - 8");
+        assert_eq!(String::from(AddressExpr::new("fn1", 16)), "fn1 + 16");
+    }
+
     #[test]
     fn test_ord_string() {
         let versions = ["v1", "v2", "v3"];
@@ -690,6 +917,28 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_cmp_expr() {
+            let (single, _) = get_sorted_linkables();
+            let mut expr = AddressExpr::new("fn1", 4);
+            // Unresolved expressions sort after everything else
+            assert_eq!(single.cmp(&Linkable::Expr(expr.clone())), Ordering::Less);
+            assert_eq!(Linkable::Expr(expr.clone()).lowest(), None);
+            expr.resolved = Some(0xdddd);
+            assert_eq!(single.cmp(&Linkable::Expr(expr.clone())), Ordering::Greater);
+            assert_eq!(Linkable::Expr(expr).lowest(), Some(0xdddd));
+            assert_eq!(Linkable::Multiple(Vec::new()).lowest(), None);
+        }
+
+        #[test]
+        fn test_expr_eq_ignores_resolution() {
+            let mut resolved = AddressExpr::new("fn1", 4);
+            resolved.resolved = Some(0x2000004);
+            assert_eq!(resolved, AddressExpr::new("fn1", 4));
+            assert_ne!(resolved, AddressExpr::new("fn1", 8));
+            assert_ne!(resolved, AddressExpr::new("fn2", 4));
+        }
+
         #[test]
         fn test_iter() {
             let (single, multiple) = get_sorted_linkables();
@@ -798,7 +1047,7 @@ mod tests {
                 ("v1".into(), vec![1, 2, 3]),
                 (("v2", 100).into(), vec![1, 2, 3]),
             ]);
-            assert_eq!(vals.get(&("v2".into(), 0).into()), Some(&vec![1, 2, 3]));
+            assert_eq!(vals.get(&("v2", 0).into()), Some(&vec![1, 2, 3]));
 
             vals.get_mut(&("v2", 0).into()).unwrap().push(4);
             assert_eq!(
@@ -930,6 +1179,29 @@ mod tests {
             assert_eq!(by_version_vals.next(), None);
         }
 
+        #[test]
+        fn test_iter_versioned() {
+            let (common, by_version) = get_maybe_version_deps();
+            let mut common_iter = common.iter_versioned();
+            assert_eq!(common_iter.next(), Some((None, &500)));
+            assert_eq!(common_iter.next(), None);
+
+            let mut by_version_iter = by_version.iter_versioned();
+            assert_eq!(
+                by_version_iter.next(),
+                Some((Some(&("v3", 0).into()), &300))
+            );
+            assert_eq!(
+                by_version_iter.next(),
+                Some((Some(&("v1", 1).into()), &100))
+            );
+            assert_eq!(
+                by_version_iter.next(),
+                Some((Some(&("v2", 2).into()), &200))
+            );
+            assert_eq!(by_version_iter.next(), None);
+        }
+
         #[test]
         fn test_expand_versions() {
             let versions = [
@@ -0,0 +1,1165 @@
+//! Comparison of two `resymgen` YAML files. Implements the `diff` command.
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::AsRef;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+use std::path::Path;
+
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+use super::analysis::{format_linkable, format_maybe_version_dep, read_collapsed};
+use super::data_formats::symgen_yml::{
+    Block, SymGen, Symbol, SymbolRange, SymbolType, Uint, Version,
+};
+use super::util::ResymgenError;
+
+/// A field of a block that can differ between two [`SymGen`]s.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BlockField {
+    Versions,
+    Address,
+    Length,
+    Description,
+}
+
+impl Display for BlockField {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Versions => write!(f, "versions"),
+            Self::Address => write!(f, "address"),
+            Self::Length => write!(f, "length"),
+            Self::Description => write!(f, "description"),
+        }
+    }
+}
+
+/// A field of a symbol that can differ between two [`SymGen`]s.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SymbolField {
+    /// Whether the symbol is a function or data.
+    Type,
+    Address,
+    Length,
+    Description,
+    Aliases,
+    Ranges,
+    ElementSize,
+    DataType,
+    Inlined,
+    /// Fields not recognized by `resymgen`.
+    ExtraFields,
+}
+
+impl Display for SymbolField {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Type => write!(f, "type"),
+            Self::Address => write!(f, "address"),
+            Self::Length => write!(f, "length"),
+            Self::Description => write!(f, "description"),
+            Self::Aliases => write!(f, "deprecated aliases"),
+            Self::Ranges => write!(f, "ranges"),
+            Self::ElementSize => write!(f, "element size"),
+            Self::DataType => write!(f, "data type"),
+            Self::Inlined => write!(f, "inlined"),
+            Self::ExtraFields => write!(f, "extra fields"),
+        }
+    }
+}
+
+/// A change to one field of a block or symbol, with the old and new values formatted for display.
+/// A value of [`None`] means the field was absent.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FieldChange<F = SymbolField> {
+    pub field: F,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Collects the fields in `fields` with differing old and new values into [`FieldChange`]s.
+fn field_changes<F: Copy>(fields: &[(F, Option<&String>, Option<&String>)]) -> Vec<FieldChange<F>> {
+    fields
+        .iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| FieldChange {
+            field: *field,
+            old: old.cloned(),
+            new: new.cloned(),
+        })
+        .collect()
+}
+
+/// A block that exists in both [`SymGen`]s, but with different metadata.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BlockChange {
+    pub block: String,
+    pub changes: Vec<FieldChange<BlockField>>,
+}
+
+/// A symbol that exists in both [`SymGen`]s, but with different field values.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SymbolChange {
+    pub block: String,
+    pub name: String,
+    pub changes: Vec<FieldChange>,
+}
+
+/// The differences between two [`SymGen`]s.
+///
+/// Blocks are matched by name, and symbols are matched by block name and symbol name (if a block
+/// has multiple symbols with the same name, they are matched in order). Functions and data are
+/// matched together, so a symbol that moved between the two is reported as a change in type. The
+/// symbols within added and removed blocks are not listed individually.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SymGenDiff {
+    pub added_blocks: Vec<String>,
+    pub removed_blocks: Vec<String>,
+    pub changed_blocks: Vec<BlockChange>,
+    /// (block name, symbol name) pairs.
+    pub added_symbols: Vec<(String, String)>,
+    /// (block name, symbol name) pairs.
+    pub removed_symbols: Vec<(String, String)>,
+    pub changed_symbols: Vec<SymbolChange>,
+}
+
+impl SymGenDiff {
+    /// Whether there are no differences at all.
+    pub fn is_empty(&self) -> bool {
+        self.added_blocks.is_empty()
+            && self.removed_blocks.is_empty()
+            && self.changed_blocks.is_empty()
+            && self.added_symbols.is_empty()
+            && self.removed_symbols.is_empty()
+            && self.changed_symbols.is_empty()
+    }
+}
+
+fn format_uint(x: &u64) -> String {
+    format!("{:#X}", x)
+}
+
+/// The comparable metadata of a block, formatted for display.
+struct BlockView {
+    versions: Option<String>,
+    address: Option<String>,
+    length: Option<String>,
+    description: Option<String>,
+}
+
+impl BlockView {
+    fn new(b: &Block) -> Self {
+        Self {
+            versions: b
+                .versions
+                .as_ref()
+                .map(|vs| vs.iter().map(|v| v.name()).collect::<Vec<_>>().join(", ")),
+            address: Some(format_maybe_version_dep(&b.address, format_uint)),
+            length: Some(format_maybe_version_dep(&b.length, format_uint)),
+            description: b.description.as_ref().map(|d| format!("{:?}", d)),
+        }
+    }
+
+    /// Gets the view of a block realized for the version named `version_name`. The version list
+    /// isn't compared, since it's implied by the version being realized.
+    fn realized(b: &Block, version_name: &str) -> Self {
+        let version = b.version(version_name);
+        Self {
+            versions: None,
+            address: b.address.get(version).map(format_uint),
+            length: b.length.get(version).map(format_uint),
+            description: b.description.as_ref().map(|d| format!("{:?}", d)),
+        }
+    }
+
+    fn changes(&self, new: &Self) -> Vec<FieldChange<BlockField>> {
+        field_changes(&[
+            (
+                BlockField::Versions,
+                self.versions.as_ref(),
+                new.versions.as_ref(),
+            ),
+            (
+                BlockField::Address,
+                self.address.as_ref(),
+                new.address.as_ref(),
+            ),
+            (
+                BlockField::Length,
+                self.length.as_ref(),
+                new.length.as_ref(),
+            ),
+            (
+                BlockField::Description,
+                self.description.as_ref(),
+                new.description.as_ref(),
+            ),
+        ])
+    }
+}
+
+/// The comparable fields of a symbol, formatted for display.
+struct SymbolView<'a> {
+    name: &'a str,
+    stype: String,
+    address: String,
+    length: Option<String>,
+    description: Option<String>,
+    aliases: Option<String>,
+    ranges: Option<String>,
+    element_size: Option<String>,
+    data_type: Option<String>,
+    inlined: Option<String>,
+    extra_fields: Option<String>,
+}
+
+impl<'a> SymbolView<'a> {
+    fn new(s: &'a Symbol, stype: SymbolType) -> Self {
+        Self {
+            name: &s.name,
+            stype: Self::stype(stype),
+            address: format_maybe_version_dep(&s.address, format_linkable),
+            length: s
+                .length
+                .as_ref()
+                .map(|l| format_maybe_version_dep(l, format_uint)),
+            description: s
+                .description
+                .as_ref()
+                .map(|d| format_maybe_version_dep(d, |d| format!("{:?}", d))),
+            aliases: Self::aliases(s),
+            ranges: Self::ranges(s, |r| {
+                Some(format!(
+                    "{} (length {})",
+                    format_maybe_version_dep(&r.address, format_uint),
+                    format_maybe_version_dep(&r.length, format_uint)
+                ))
+            }),
+            element_size: s.element_size.as_ref().map(format_uint),
+            data_type: s.data_type.clone(),
+            inlined: s.inlined.map(|i| i.to_string()),
+            extra_fields: Self::extra_fields(s),
+        }
+    }
+
+    /// Gets the view of a symbol realized for the version named `version_name` within `block`, or
+    /// [`None`] if the symbol has no address for that version.
+    fn realized(
+        s: &'a Symbol,
+        stype: SymbolType,
+        block: &Block,
+        version_name: &str,
+    ) -> Option<Self> {
+        if s.is_inlined() {
+            return None;
+        }
+        let address = s.address_for_name(version_name, block)?;
+        let version = block.version(version_name);
+        Some(Self {
+            name: &s.name,
+            stype: Self::stype(stype),
+            address: format_linkable(address),
+            length: s
+                .length
+                .as_ref()
+                .and_then(|l| l.get(version))
+                .map(format_uint),
+            description: s.description_for(version).map(|d| format!("{:?}", d)),
+            aliases: Self::aliases(s),
+            ranges: Self::ranges(s, |r| Self::realized_range(r, version)),
+            element_size: s.element_size.as_ref().map(format_uint),
+            data_type: s.data_type.clone(),
+            inlined: s.inlined.map(|i| i.to_string()),
+            extra_fields: Self::extra_fields(s),
+        })
+    }
+
+    fn stype(stype: SymbolType) -> String {
+        match stype {
+            SymbolType::Function => "function".to_string(),
+            SymbolType::Data => "data".to_string(),
+        }
+    }
+
+    fn aliases(s: &Symbol) -> Option<String> {
+        s.deprecated_aliases
+            .as_ref()
+            .filter(|a| !a.is_empty())
+            .map(|a| a.join(", "))
+    }
+
+    fn ranges<F>(s: &Symbol, fmt_range: F) -> Option<String>
+    where
+        F: Fn(&SymbolRange) -> Option<String>,
+    {
+        let ranges: Vec<_> = s.ranges.iter().flatten().filter_map(fmt_range).collect();
+        if ranges.is_empty() {
+            None
+        } else {
+            Some(ranges.join("; "))
+        }
+    }
+
+    fn realized_range(r: &SymbolRange, version: Option<&Version>) -> Option<String> {
+        let address = r.address.get(version)?;
+        let length = r.length.get(version)?;
+        Some(format!("{:#X} (length {:#X})", address, length))
+    }
+
+    fn extra_fields(s: &Symbol) -> Option<String> {
+        s.extra_fields
+            .as_ref()
+            .filter(|m| !m.is_empty())
+            .map(|m| match serde_yaml::to_string(m) {
+                Ok(yaml) => yaml
+                    .trim_start_matches("---")
+                    .trim()
+                    .lines()
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                Err(_) => format!("{:?}", m),
+            })
+    }
+
+    fn changes(&self, new: &Self) -> Vec<FieldChange> {
+        field_changes(&[
+            (SymbolField::Type, Some(&self.stype), Some(&new.stype)),
+            (
+                SymbolField::Address,
+                Some(&self.address),
+                Some(&new.address),
+            ),
+            (
+                SymbolField::Length,
+                self.length.as_ref(),
+                new.length.as_ref(),
+            ),
+            (
+                SymbolField::Description,
+                self.description.as_ref(),
+                new.description.as_ref(),
+            ),
+            (
+                SymbolField::Aliases,
+                self.aliases.as_ref(),
+                new.aliases.as_ref(),
+            ),
+            (
+                SymbolField::Ranges,
+                self.ranges.as_ref(),
+                new.ranges.as_ref(),
+            ),
+            (
+                SymbolField::ElementSize,
+                self.element_size.as_ref(),
+                new.element_size.as_ref(),
+            ),
+            (
+                SymbolField::DataType,
+                self.data_type.as_ref(),
+                new.data_type.as_ref(),
+            ),
+            (
+                SymbolField::Inlined,
+                self.inlined.as_ref(),
+                new.inlined.as_ref(),
+            ),
+            (
+                SymbolField::ExtraFields,
+                self.extra_fields.as_ref(),
+                new.extra_fields.as_ref(),
+            ),
+        ])
+    }
+}
+
+/// Gets the views of all symbols in `block`, realized for `version_name` if specified.
+fn block_views<'a>(block: &'a Block, version_name: Option<&str>) -> Vec<SymbolView<'a>> {
+    let symbols = block
+        .functions
+        .iter()
+        .map(|s| (s, SymbolType::Function))
+        .chain(block.data.iter().map(|s| (s, SymbolType::Data)));
+    match version_name {
+        Some(v) => symbols
+            .filter_map(|(s, t)| SymbolView::realized(s, t, block, v))
+            .collect(),
+        None => symbols.map(|(s, t)| SymbolView::new(s, t)).collect(),
+    }
+}
+
+fn diff_blocks(
+    diff: &mut SymGenDiff,
+    bname: &str,
+    old: &Block,
+    new: &Block,
+    version_name: Option<&str>,
+) {
+    let block_changes = match version_name {
+        Some(v) => BlockView::realized(old, v).changes(&BlockView::realized(new, v)),
+        None => BlockView::new(old).changes(&BlockView::new(new)),
+    };
+    if !block_changes.is_empty() {
+        diff.changed_blocks.push(BlockChange {
+            block: bname.to_string(),
+            changes: block_changes,
+        });
+    }
+
+    let old_views = block_views(old, version_name);
+    let new_views = block_views(new, version_name);
+    // Match symbols with the same name in order of appearance
+    let mut new_by_name: HashMap<&str, VecDeque<usize>> = HashMap::new();
+    for (i, v) in new_views.iter().enumerate() {
+        new_by_name.entry(v.name).or_default().push_back(i);
+    }
+    let mut matched = vec![false; new_views.len()];
+    for old_view in old_views.iter() {
+        match new_by_name
+            .get_mut(old_view.name)
+            .and_then(|idxs| idxs.pop_front())
+        {
+            Some(i) => {
+                matched[i] = true;
+                let changes = old_view.changes(&new_views[i]);
+                if !changes.is_empty() {
+                    diff.changed_symbols.push(SymbolChange {
+                        block: bname.to_string(),
+                        name: old_view.name.to_string(),
+                        changes,
+                    });
+                }
+            }
+            None => diff
+                .removed_symbols
+                .push((bname.to_string(), old_view.name.to_string())),
+        }
+    }
+    diff.added_symbols.extend(
+        new_views
+            .iter()
+            .zip(matched)
+            .filter(|(_, m)| !m)
+            .map(|(v, _)| (bname.to_string(), v.name.to_string())),
+    );
+}
+
+/// Computes the differences from `old` to `new`, comparing the full contents of each block and
+/// symbol (for all versions).
+pub fn diff_symgen(old: &SymGen, new: &SymGen) -> SymGenDiff {
+    diff_symgen_impl(old, new, None)
+}
+
+/// Computes the differences from `old` to `new`, comparing the blocks and symbols realized for
+/// the version named `version_name`. Symbols without an address for that version
+/// are ignored, and version-dependent fields are only compared for that version.
+pub fn diff_symgen_realized(old: &SymGen, new: &SymGen, version_name: &str) -> SymGenDiff {
+    diff_symgen_impl(old, new, Some(version_name))
+}
+
+fn diff_symgen_impl(old: &SymGen, new: &SymGen, version_name: Option<&str>) -> SymGenDiff {
+    let mut diff = SymGenDiff::default();
+    for (bname, old_block) in old.iter() {
+        match new.get(bname) {
+            Some(new_block) => {
+                diff_blocks(&mut diff, &bname.val, old_block, new_block, version_name)
+            }
+            None => diff.removed_blocks.push(bname.val.clone()),
+        }
+    }
+    diff.added_blocks = new
+        .iter()
+        .filter(|(bname, _)| old.get(bname).is_none())
+        .map(|(bname, _)| bname.val.clone())
+        .collect();
+    diff
+}
+
+/// A neighboring symbol shown for orientation around an added, removed, or changed symbol.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct ContextSymbol {
+    name: String,
+    /// The formatted address (or addresses) of the symbol.
+    address: String,
+}
+
+/// The neighboring symbols on either side of a symbol, in address order.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+struct SymbolContext {
+    before: Vec<ContextSymbol>,
+    after: Vec<ContextSymbol>,
+}
+
+/// Gets the functions and data in `block`, merged together and sorted by address.
+///
+/// If `version_name` is specified, only symbols realized for that version are included, and they
+/// are sorted by their first address for that version. Otherwise symbols are sorted as they would
+/// be by `fmt`.
+fn sorted_symbols(block: &Block, version_name: Option<&str>) -> Vec<ContextSymbol> {
+    let symbols = block.functions.iter().chain(block.data.iter());
+    match version_name {
+        Some(v) => {
+            let mut realized: Vec<(Uint, &Symbol)> = symbols
+                .filter(|s| !s.is_inlined())
+                .filter_map(|s| {
+                    let addrs = s.address_for_name(v, block)?;
+                    Some((*addrs.iter().next()?, s))
+                })
+                .collect();
+            realized.sort_by_key(|&(addr, _)| addr);
+            realized
+                .into_iter()
+                .map(|(_, s)| ContextSymbol {
+                    name: s.name.clone(),
+                    address: format_linkable(s.address_for_name(v, block).unwrap()),
+                })
+                .collect()
+        }
+        None => {
+            let mut all: Vec<&Symbol> = symbols.collect();
+            all.sort();
+            all.into_iter()
+                .map(|s| ContextSymbol {
+                    name: s.name.clone(),
+                    address: format_maybe_version_dep(&s.address, format_linkable),
+                })
+                .collect()
+        }
+    }
+}
+
+/// Gets up to `lines` symbols on either side of the symbol named `name` in the block named
+/// `bname` within `symgen`, in address order. If multiple symbols share the name, the first one
+/// in address order is used. Returns [`None`] if there's no such symbol.
+fn symbol_context(
+    symgen: &SymGen,
+    bname: &str,
+    name: &str,
+    version_name: Option<&str>,
+    lines: usize,
+) -> Option<SymbolContext> {
+    let block = symgen.get(symgen.block_key(bname)?)?;
+    let sorted = sorted_symbols(block, version_name);
+    let pos = sorted.iter().position(|s| s.name == name)?;
+    Some(SymbolContext {
+        before: sorted[pos.saturating_sub(lines)..pos].to_vec(),
+        after: sorted[pos + 1..].iter().take(lines).cloned().collect(),
+    })
+}
+
+/// The files being compared, for printing the symbols around each difference.
+struct DiffContext<'a> {
+    old: &'a SymGen,
+    new: &'a SymGen,
+    version_name: Option<&'a str>,
+    /// The number of neighboring symbols to print on either side of each symbol.
+    lines: usize,
+}
+
+/// Prints a line with a colored change marker and description.
+fn print_marked<W: WriteColor>(
+    writer: &mut W,
+    color: Color,
+    marker: &str,
+    line: &str,
+) -> io::Result<()> {
+    writer.set_color(ColorSpec::new().set_fg(Some(color)))?;
+    write!(writer, "{} {}", marker, line)?;
+    writer.reset()?;
+    writeln!(writer)
+}
+
+/// Prints the old and new values of each changed field.
+fn print_changes<W: Write, F: Display>(
+    writer: &mut W,
+    changes: &[FieldChange<F>],
+) -> io::Result<()> {
+    for c in changes.iter() {
+        writeln!(
+            writer,
+            "    {}: {} -> {}",
+            c.field,
+            c.old.as_deref().unwrap_or("(none)"),
+            c.new.as_deref().unwrap_or("(none)")
+        )?;
+    }
+    Ok(())
+}
+
+/// Prints an added, removed, or changed symbol with a colored change marker, along with its
+/// changed fields. If `context` is given, the neighboring symbols from the given [`SymGen`] are
+/// printed around it.
+fn print_symbol<W: WriteColor>(
+    writer: &mut W,
+    (color, marker): (Color, &str),
+    (block, name): (&str, &str),
+    changes: &[FieldChange],
+    context: Option<(&DiffContext, &SymGen)>,
+) -> io::Result<()> {
+    let neighbors = context
+        .and_then(|(ctx, symgen)| symbol_context(symgen, block, name, ctx.version_name, ctx.lines))
+        .unwrap_or_default();
+    for s in neighbors.before.iter() {
+        writeln!(writer, "  {} ({})", s.name, s.address)?;
+    }
+    let line = format!("{} (block \"{}\")", name, block);
+    print_marked(writer, color, marker, &line)?;
+    print_changes(writer, changes)?;
+    for s in neighbors.after.iter() {
+        writeln!(writer, "  {} ({})", s.name, s.address)?;
+    }
+    if context.is_some() {
+        // Separate each symbol from the next one's context
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+fn print_diff(diff: &SymGenDiff, context: Option<&DiffContext>) -> io::Result<()> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut print_colored_diff = || -> io::Result<()> {
+        for b in diff.removed_blocks.iter() {
+            print_marked(&mut stdout, Color::Red, "-", &format!("block \"{}\"", b))?;
+        }
+        for b in diff.added_blocks.iter() {
+            print_marked(&mut stdout, Color::Green, "+", &format!("block \"{}\"", b))?;
+        }
+        for b in diff.changed_blocks.iter() {
+            let line = format!("block \"{}\"", b.block);
+            print_marked(&mut stdout, Color::Yellow, "~", &line)?;
+            print_changes(&mut stdout, &b.changes)?;
+        }
+        // Removed symbols are shown among their old neighbors, and the rest among their new ones
+        let old_context = context.map(|ctx| (ctx, ctx.old));
+        let new_context = context.map(|ctx| (ctx, ctx.new));
+        for (b, s) in diff.removed_symbols.iter() {
+            let marker = (Color::Red, "-");
+            print_symbol(&mut stdout, marker, (b, s), &[], old_context)?;
+        }
+        for (b, s) in diff.added_symbols.iter() {
+            let marker = (Color::Green, "+");
+            print_symbol(&mut stdout, marker, (b, s), &[], new_context)?;
+        }
+        for s in diff.changed_symbols.iter() {
+            let marker = (Color::Yellow, "~");
+            let id = (s.block.as_str(), s.name.as_str());
+            print_symbol(&mut stdout, marker, id, &s.changes, new_context)?;
+        }
+        Ok(())
+    };
+    let res = print_colored_diff();
+    // Always try to clean up color settings before returning
+    if let Err(e) = stdout.reset() {
+        Err(e)
+    } else {
+        res
+    }
+}
+
+/// Compares `old_file` to `new_file` (and their subregion files) with [`diff_symgen`], or with
+/// [`diff_symgen_realized`] if `version_name` is specified, and prints the differences.
+///
+/// If `context` is nonzero, each added, removed, or changed symbol is printed along with up to
+/// `context` of its neighbors on either side (in address order within its block), for
+/// orientation. Removed symbols are shown among their neighbors in `old_file`, and other symbols
+/// among their neighbors in `new_file`.
+///
+/// Returns `true` if the files have no differences, and `false` otherwise.
+///
+/// # Examples
+/// ```ignore
+/// let same = print_symgen_diff("/path/to/old.yml", "/path/to/new.yml", Some("NA"), 2)
+///     .expect("failed to diff files");
+/// ```
+pub fn print_symgen_diff<P: AsRef<Path>, Q: AsRef<Path>>(
+    old_file: P,
+    new_file: Q,
+    version_name: Option<&str>,
+    context: usize,
+) -> Result<bool, ResymgenError> {
+    let old = read_collapsed(old_file.as_ref())?;
+    let new = read_collapsed(new_file.as_ref())?;
+    let diff = diff_symgen_impl(&old, &new, version_name);
+    let diff_context = DiffContext {
+        old: &old,
+        new: &new,
+        version_name,
+        lines: context,
+    };
+    print_diff(&diff, Some(&diff_context).filter(|_| context > 0))?;
+    if diff.is_empty() {
+        println!("No differences");
+    } else {
+        println!(
+            "{} block(s) added, {} block(s) removed, {} block(s) changed, {} symbol(s) added, {} symbol(s) removed, {} symbol(s) changed",
+            diff.added_blocks.len(),
+            diff.removed_blocks.len(),
+            diff.changed_blocks.len(),
+            diff.added_symbols.len(),
+            diff.removed_symbols.len(),
+            diff.changed_symbols.len()
+        );
+    }
+    Ok(diff.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_old_symgen() -> SymGen {
+        SymGen::read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2001000
+                    v2: 0x2002000
+                  length: 0x100
+                  description: foo
+                - name: fn2
+                  address: 0x2003000
+                - name: fn_removed
+                  address: 0x2004000
+              data:
+                - name: SOME_DATA
+                  address:
+                    v1: 0x2005000
+                    v2: 0x2006000
+                  length:
+                    v1: 0x4
+                    v2: 0x8
+            removed_block:
+              address: 0x2100000
+              length: 0x1000
+              functions: []
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    fn get_new_symgen() -> SymGen {
+        SymGen::read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: fn1
+                  address:
+                    v1: 0x2001000
+                    v2: 0x2002004
+                  length: 0x100
+                  description: bar
+                - name: fn2
+                  deprecated_aliases:
+                    - OldFn2
+                  address: 0x2003000
+                  length: 0x10
+                - name: fn_added
+                  address:
+                    v2: 0x2004000
+              data:
+                - name: SOME_DATA
+                  address:
+                    v1: 0x2005000
+                    v2: 0x2006000
+                  length:
+                    v1: 0x4
+                    v2: 0xC
+            added_block:
+              address: 0x2200000
+              length: 0x1000
+              functions: []
+              data: []
+            "
+            .as_bytes(),
+        )
+        .expect("Read failed")
+    }
+
+    fn change(field: SymbolField, old: Option<&str>, new: Option<&str>) -> FieldChange {
+        FieldChange {
+            field,
+            old: old.map(String::from),
+            new: new.map(String::from),
+        }
+    }
+
+    fn symbol(block: &str, name: &str) -> (String, String) {
+        (block.to_string(), name.to_string())
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let symgen = get_old_symgen();
+        assert!(diff_symgen(&symgen, &symgen).is_empty());
+        assert!(diff_symgen_realized(&symgen, &symgen, "v1").is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        let diff = diff_symgen(&get_old_symgen(), &get_new_symgen());
+        assert_eq!(diff.added_blocks, vec!["added_block"]);
+        assert_eq!(diff.removed_blocks, vec!["removed_block"]);
+        assert_eq!(diff.added_symbols, vec![symbol("main", "fn_added")]);
+        assert_eq!(diff.removed_symbols, vec![symbol("main", "fn_removed")]);
+        assert_eq!(
+            diff.changed_symbols,
+            vec![
+                SymbolChange {
+                    block: "main".to_string(),
+                    name: "fn1".to_string(),
+                    changes: vec![
+                        change(
+                            SymbolField::Address,
+                            Some("v1=0x2001000, v2=0x2002000"),
+                            Some("v1=0x2001000, v2=0x2002004")
+                        ),
+                        change(SymbolField::Description, Some("\"foo\""), Some("\"bar\"")),
+                    ],
+                },
+                SymbolChange {
+                    block: "main".to_string(),
+                    name: "fn2".to_string(),
+                    changes: vec![
+                        change(SymbolField::Length, None, Some("0x10")),
+                        change(SymbolField::Aliases, None, Some("OldFn2")),
+                    ],
+                },
+                SymbolChange {
+                    block: "main".to_string(),
+                    name: "SOME_DATA".to_string(),
+                    changes: vec![change(
+                        SymbolField::Length,
+                        Some("v1=0x4, v2=0x8"),
+                        Some("v1=0x4, v2=0xC")
+                    )],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_realized() {
+        let old = get_old_symgen();
+        let new = get_new_symgen();
+
+        // fn_added has no v1 address, and SOME_DATA is only different in v2
+        let diff = diff_symgen_realized(&old, &new, "v1");
+        assert!(diff.added_symbols.is_empty());
+        assert_eq!(diff.removed_symbols, vec![symbol("main", "fn_removed")]);
+        let changed: Vec<_> = diff
+            .changed_symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.changes.clone()))
+            .collect();
+        assert_eq!(
+            changed,
+            vec![
+                (
+                    "fn1",
+                    vec![change(
+                        SymbolField::Description,
+                        Some("\"foo\""),
+                        Some("\"bar\"")
+                    )]
+                ),
+                (
+                    "fn2",
+                    vec![
+                        change(SymbolField::Length, None, Some("0x10")),
+                        change(SymbolField::Aliases, None, Some("OldFn2")),
+                    ]
+                ),
+            ]
+        );
+
+        let diff = diff_symgen_realized(&old, &new, "v2");
+        assert_eq!(diff.added_symbols, vec![symbol("main", "fn_added")]);
+        let fn1 = &diff.changed_symbols[0];
+        assert_eq!(fn1.name, "fn1");
+        assert_eq!(
+            fn1.changes[0],
+            change(SymbolField::Address, Some("0x2002000"), Some("0x2002004"))
+        );
+        assert_eq!(diff.changed_symbols[2].name, "SOME_DATA");
+    }
+
+    #[test]
+    fn test_diff_duplicate_names() {
+        let read = |yml: &str| SymGen::read(yml.as_bytes()).expect("Read failed");
+        let old = read(
+            r"
+            main:
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: dup
+                  address: 0x2001000
+                - name: dup
+                  address: 0x2002000
+              data: []
+            ",
+        );
+        let new = read(
+            r"
+            main:
+              address: 0x2000000
+              length: 0x100000
+              functions:
+                - name: dup
+                  address: 0x2001000
+                - name: dup
+                  address: 0x2002000
+                - name: dup
+                  address: 0x2003000
+              data: []
+            ",
+        );
+        let diff = diff_symgen(&old, &new);
+        assert!(diff.changed_symbols.is_empty());
+        assert_eq!(diff.added_symbols, vec![symbol("main", "dup")]);
+        assert_eq!(
+            diff_symgen(&new, &old).removed_symbols,
+            vec![symbol("main", "dup")]
+        );
+    }
+
+    #[test]
+    fn test_diff_block_metadata() {
+        let read = |yml: &str| SymGen::read(yml.as_bytes()).expect("Read failed");
+        let old = read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+              address:
+                v1: 0x2000000
+                v2: 0x2000000
+              length: 0x100000
+              description: foo
+              functions: []
+              data: []
+            ",
+        );
+        let new = read(
+            r"
+            main:
+              versions:
+                - v1
+                - v2
+                - v3
+              address:
+                v1: 0x2000000
+                v2: 0x2000100
+              length: 0x200000
+              functions: []
+              data: []
+            ",
+        );
+        let diff = diff_symgen(&old, &new);
+        assert!(!diff.is_empty());
+        assert!(diff.changed_symbols.is_empty());
+        assert_eq!(
+            diff.changed_blocks,
+            vec![BlockChange {
+                block: "main".to_string(),
+                changes: vec![
+                    FieldChange {
+                        field: BlockField::Versions,
+                        old: Some("v1, v2".to_string()),
+                        new: Some("v1, v2, v3".to_string()),
+                    },
+                    FieldChange {
+                        field: BlockField::Address,
+                        old: Some("v1=0x2000000, v2=0x2000000".to_string()),
+                        new: Some("v1=0x2000000, v2=0x2000100".to_string()),
+                    },
+                    FieldChange {
+                        field: BlockField::Length,
+                        old: Some("0x100000".to_string()),
+                        new: Some("0x200000".to_string()),
+                    },
+                    FieldChange {
+                        field: BlockField::Description,
+                        old: Some("\"foo\"".to_string()),
+                        new: None,
+                    },
+                ],
+            }]
+        );
+
+        // The version list isn't compared, and the address only changed in v2
+        let diff = diff_symgen_realized(&old, &new, "v1");
+        assert_eq!(
+            diff.changed_blocks[0].changes,
+            vec![
+                FieldChange {
+                    field: BlockField::Length,
+                    old: Some("0x100000".to_string()),
+                    new: Some("0x200000".to_string()),
+                },
+                FieldChange {
+                    field: BlockField::Description,
+                    old: Some("\"foo\"".to_string()),
+                    new: None,
+                },
+            ]
+        );
+    }
+
+    /// Diffs two single-symbol SymGens, where the symbol is defined in the `old_list` and
+    /// `new_list` lists ("functions" or "data"), with the given extra field lines.
+    fn diff_single_symbol(
+        old_list: &str,
+        old_fields: &str,
+        new_list: &str,
+        new_fields: &str,
+    ) -> Vec<FieldChange> {
+        let read = |list: &str, fields: &str| {
+            let other = if list == "functions" {
+                "data"
+            } else {
+                "functions"
+            };
+            let yml = format!(
+                "main:\n  address: 0x2000000\n  length: 0x100000\n  {}: []\n  {}:\n    - name: sym\n      address: 0x2001000\n{}",
+                other,
+                list,
+                fields
+                    .lines()
+                    .map(|l| format!("      {}\n", l))
+                    .collect::<String>()
+            );
+            SymGen::read(yml.as_bytes()).expect("Read failed")
+        };
+        let diff = diff_symgen(&read(old_list, old_fields), &read(new_list, new_fields));
+        assert!(diff.added_symbols.is_empty());
+        assert!(diff.removed_symbols.is_empty());
+        diff.changed_symbols
+            .into_iter()
+            .flat_map(|s| s.changes)
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_symbol_type() {
+        assert_eq!(
+            diff_single_symbol("functions", "", "data", ""),
+            vec![change(SymbolField::Type, Some("function"), Some("data"))]
+        );
+        assert!(diff_single_symbol("data", "", "data", "").is_empty());
+    }
+
+    #[test]
+    fn test_diff_data_type() {
+        assert_eq!(
+            diff_single_symbol("data", "data_type: int", "data", "data_type: uint"),
+            vec![change(SymbolField::DataType, Some("int"), Some("uint"))]
+        );
+    }
+
+    #[test]
+    fn test_diff_inlined() {
+        assert_eq!(
+            diff_single_symbol("functions", "", "functions", "inlined: true"),
+            vec![change(SymbolField::Inlined, None, Some("true"))]
+        );
+    }
+
+    #[test]
+    fn test_diff_ranges() {
+        assert_eq!(
+            diff_single_symbol(
+                "functions",
+                "ranges:\n  - address: 0x2002000\n    length: 0x10",
+                "functions",
+                "ranges:\n  - address: 0x2002000\n    length: 0x20",
+            ),
+            vec![change(
+                SymbolField::Ranges,
+                Some("0x2002000 (length 0x10)"),
+                Some("0x2002000 (length 0x20)")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_diff_element_size() {
+        assert_eq!(
+            diff_single_symbol("data", "element_size: 0x4", "data", "element_size: 0x8"),
+            vec![change(SymbolField::ElementSize, Some("0x4"), Some("0x8"))]
+        );
+    }
+
+    fn context_names(context: &[ContextSymbol]) -> Vec<&str> {
+        context.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_symbol_context() {
+        let new = get_new_symgen();
+        // Functions and data are merged in address order
+        let ctx = symbol_context(&new, "main", "fn_added", Some("v2"), 1).unwrap();
+        assert_eq!(
+            ctx.before,
+            vec![ContextSymbol {
+                name: "fn2".to_string(),
+                address: "0x2003000".to_string(),
+            }]
+        );
+        assert_eq!(
+            ctx.after,
+            vec![ContextSymbol {
+                name: "SOME_DATA".to_string(),
+                address: "0x2006000".to_string(),
+            }]
+        );
+
+        // Context is cut off at the ends of the block
+        let ctx = symbol_context(&new, "main", "fn1", Some("v2"), 10).unwrap();
+        assert!(ctx.before.is_empty());
+        assert_eq!(
+            context_names(&ctx.after),
+            vec!["fn2", "fn_added", "SOME_DATA"]
+        );
+
+        // fn_added has no v1 address, so it's left out
+        let ctx = symbol_context(&new, "main", "fn2", Some("v1"), 2).unwrap();
+        assert_eq!(context_names(&ctx.before), vec!["fn1"]);
+        assert_eq!(context_names(&ctx.after), vec!["SOME_DATA"]);
+        assert!(symbol_context(&new, "main", "fn_added", Some("v1"), 2).is_none());
+
+        let old = get_old_symgen();
+        let ctx = symbol_context(&old, "main", "fn_removed", None, 1).unwrap();
+        assert_eq!(
+            ctx.before,
+            vec![ContextSymbol {
+                name: "fn2".to_string(),
+                address: "0x2003000".to_string(),
+            }]
+        );
+        assert_eq!(
+            ctx.after,
+            vec![ContextSymbol {
+                name: "SOME_DATA".to_string(),
+                address: "v1=0x2005000, v2=0x2006000".to_string(),
+            }]
+        );
+        assert!(symbol_context(&old, "main", "missing", None, 1).is_none());
+        assert!(symbol_context(&old, "missing", "fn1", None, 1).is_none());
+    }
+}
@@ -0,0 +1,265 @@
+//! Running a broad health report on `resymgen` YAML files. Implements the `doctor` command.
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+
+use super::checks::{self, Check};
+use super::data_formats::symgen_yml::{IntFormat, Subregion, SymGen};
+use super::formatting;
+use super::util::ResymgenError;
+
+/// The checks run by the `doctor` command, in order.
+const DOCTOR_CHECKS: [Check; 4] = [
+    Check::UniqueSymbols,
+    Check::NoOverlap,
+    Check::InBoundsSymbols,
+    Check::CompleteVersionList,
+];
+
+/// A problem found by the `doctor` command.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Diagnosis {
+    /// The file with the problem.
+    pub file: PathBuf,
+    /// A short description of the problem.
+    pub problem: String,
+    /// Additional details about the problem, if any.
+    pub details: Option<String>,
+    /// A suggestion for how to fix the problem.
+    pub hint: String,
+}
+
+/// Returns a suggestion for how to fix a failure of the given check.
+fn check_hint(check: &Check) -> &'static str {
+    match check {
+        Check::UniqueSymbols | Check::UniqueSymbolsAcrossSubregions => {
+            "rename the duplicate symbols, or combine them into a single symbol"
+        }
+        Check::NoOverlap => "fix the addresses or lengths of the overlapping symbols",
+        Check::InBoundsSymbols => {
+            "fix the addresses of the out-of-bounds symbols, or move them to the right block"
+        }
+        Check::CompleteVersionList => {
+            "add the missing versions to the `versions` list of each block, and remove duplicates"
+        }
+        _ => "see `resymgen check --help` for details on this check",
+    }
+}
+
+/// Examines a given `input_file` and its subregion files, and returns a list of the problems
+/// found.
+///
+/// The file is checked for subregion resolvability, formatting (which includes sorting), and
+/// the checks in [`DOCTOR_CHECKS`]. Problems with the file contents are reported as
+/// [`Diagnosis`] values rather than errors; an `Err` is only returned if the examination itself
+/// couldn't be carried out (e.g., because the file couldn't be opened).
+///
+/// # Examples
+/// ```ignore
+/// let problems = diagnose("/path/to/symbols.yml").expect("Fatal error occurred");
+/// ```
+pub fn diagnose<P: AsRef<Path>>(input_file: P) -> Result<Vec<Diagnosis>, ResymgenError> {
+    let input_file = input_file.as_ref();
+    let diagnosis = |problem: &str, details: Option<String>, hint: String| Diagnosis {
+        file: input_file.to_owned(),
+        problem: problem.to_string(),
+        details,
+        hint,
+    };
+
+    // Everything else depends on the file tree being readable, so bail early if it isn't
+    let mut contents = match SymGen::read(&File::open(input_file)?) {
+        Ok(contents) => contents,
+        Err(e) => {
+            return Ok(vec![diagnosis(
+                "file could not be read",
+                Some(e.to_string()),
+                "fix the YAML error above; `resymgen fmt` can't format a file it can't read"
+                    .to_string(),
+            )])
+        }
+    };
+    if let Err(e) =
+        contents.resolve_subregions(Subregion::subregion_dir(input_file), |p| File::open(p))
+    {
+        return Ok(vec![diagnosis(
+            "subregions could not be resolved",
+            Some(e.to_string()),
+            format!(
+                "run `resymgen resolve {}` to see which subregion files are found",
+                input_file.display()
+            ),
+        )]);
+    }
+
+    let mut problems = Vec::new();
+    for (path, _, _) in
+        formatting::find_unformatted_files(input_file, true, false, IntFormat::Hexadecimal, 0)?
+    {
+        problems.push(Diagnosis {
+            file: path,
+            problem: "file is not formatted".to_string(),
+            details: None,
+            hint: format!(
+                "run `resymgen fmt -r {}` to format and sort the file",
+                input_file.display()
+            ),
+        });
+    }
+    for (path, r) in checks::run_checks(input_file, &DOCTOR_CHECKS, true, None)? {
+        if !r.succeeded {
+            problems.push(Diagnosis {
+                file: path,
+                problem: format!("{} check failed", r.check),
+                details: r.details,
+                hint: check_hint(&r.check).to_string(),
+            });
+        }
+    }
+    Ok(problems)
+}
+
+/// Prints a friendly summary of the problems found by the `doctor` command.
+fn print_diagnoses(problems: &[Diagnosis], n_files: usize) -> io::Result<()> {
+    let mut stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut print_colored_diagnoses = || -> io::Result<()> {
+        let mut color = ColorSpec::new();
+
+        for d in problems {
+            stdout.set_color(color.set_fg(Some(Color::Red)).set_bold(true))?;
+            write!(&mut stdout, "problem")?;
+            stdout.reset()?;
+            writeln!(&mut stdout, ": [{}] {}", d.file.display(), d.problem)?;
+            if let Some(details) = &d.details {
+                for line in details.lines() {
+                    writeln!(&mut stdout, "  {}", line)?;
+                }
+            }
+            stdout.set_color(color.set_fg(Some(Color::Cyan)).set_bold(true))?;
+            write!(&mut stdout, "  help")?;
+            stdout.reset()?;
+            writeln!(&mut stdout, ": {}", d.hint)?;
+            writeln!(&mut stdout)?;
+        }
+
+        write!(&mut stdout, "doctor result: ")?;
+        if problems.is_empty() {
+            stdout.set_color(color.set_fg(Some(Color::Green)).set_bold(false))?;
+            write!(&mut stdout, "ok")?;
+            stdout.reset()?;
+            writeln!(
+                &mut stdout,
+                ". {} file(s) examined, no problems found",
+                n_files
+            )?;
+        } else {
+            stdout.set_color(color.set_fg(Some(Color::Red)).set_bold(false))?;
+            write!(&mut stdout, "FAILED")?;
+            stdout.reset()?;
+            writeln!(
+                &mut stdout,
+                ". {} file(s) examined, {} problem(s) found",
+                n_files,
+                problems.len()
+            )?;
+        }
+        Ok(())
+    };
+    let res = print_colored_diagnoses();
+    // Always try to clean up color settings before returning
+    if let Err(e) = stdout.reset() {
+        Err(e)
+    } else {
+        res
+    }
+}
+
+/// Examines a given set of `input_files` with [`diagnose`], and prints a friendly summary of the
+/// problems found, along with suggestions for how to fix them.
+///
+/// If all files were examined without encountering a fatal error, returns `true` if no problems
+/// were found and `false` otherwise.
+///
+/// # Examples
+/// ```ignore
+/// let healthy = run_and_print_doctor(["/path/to/symbols.yml", "/path/to/other_symbols.yml"])
+///     .expect("Fatal error occurred");
+/// ```
+pub fn run_and_print_doctor<I, P>(input_files: I) -> Result<bool, ResymgenError>
+where
+    P: AsRef<Path>,
+    I: AsRef<[P]>,
+{
+    let input_files = input_files.as_ref();
+    let mut problems = Vec::new();
+    for input_file in input_files {
+        problems.extend(diagnose(input_file)?);
+    }
+    print_diagnoses(&problems, input_files.len())?;
+    Ok(problems.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_file(path: &Path, contents: &str) {
+        fs::write(path, contents).expect("Failed to write file");
+    }
+
+    #[test]
+    fn test_diagnose_healthy() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let path = dir.path().join("main.yml");
+        write_file(
+            &path,
+            "main:\n  versions:\n    - v1\n  address: 0x2000000\n  length: 0x1000\n  description: foo\n  functions:\n    - name: fn1\n      address: 0x2000000\n      length: 0x10\n  data: []\n",
+        );
+        assert_eq!(diagnose(&path).expect("diagnose failed"), vec![]);
+    }
+
+    #[test]
+    fn test_diagnose_problems() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let path = dir.path().join("main.yml");
+        write_file(
+            &path,
+            "main:\n  versions:\n    - v1\n  address: 0x2000000\n  length: 0x1000\n  description: foo\n  functions:\n    - name: fn1\n      address: 0x2000010\n      length: 0x10\n    - name: fn1\n      address: 0x2000000\n      length: 0x20\n  data: []\n",
+        );
+        let problems: Vec<String> = diagnose(&path)
+            .expect("diagnose failed")
+            .into_iter()
+            .map(|d| d.problem)
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                "file is not formatted",
+                "UniqueSymbols check failed",
+                "NoOverlap check failed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagnose_unreadable() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let path = dir.path().join("main.yml");
+        write_file(&path, "main: [");
+        let problems = diagnose(&path).expect("diagnose failed");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].problem, "file could not be read");
+
+        write_file(
+            &path,
+            "main:\n  address: 0x2000000\n  length: 0x1000\n  subregions:\n    - missing.yml\n  functions: []\n  data: []\n",
+        );
+        let problems = diagnose(&path).expect("diagnose failed");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].problem, "subregions could not be resolved");
+    }
+}
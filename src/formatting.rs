@@ -1,83 +1,194 @@
 //! Formatting `resymgen` YAML files. Implements the `fmt` command.
 
+use std::env;
 use std::error::Error;
 use std::fmt::Display;
 use std::fs::{self, File};
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 use similar::TextDiff;
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 use super::data_formats::symgen_yml::{IntFormat, Sort, Subregion, SymGen};
-use super::util;
+use super::util::{self, ResymgenError};
 
-/// Formats a given `input_file` using the given `int_format`.
+/// Reads the [`SymGen`] in `input_file`, optionally keeping unknown fields.
+fn read_input(
+    input_file: &Path,
+    allow_unknown_fields: bool,
+) -> Result<SymGen, Box<dyn Error + Send + Sync>> {
+    let _timer = util::time_phase("read");
+    let f = File::open(input_file)?;
+    if allow_unknown_fields {
+        Ok(SymGen::read_allow_unknown_fields(&f)?)
+    } else {
+        Ok(SymGen::read(&f)?)
+    }
+}
+
+/// Recursively resolves the subregions of `contents`, which was read from `input_file`,
+/// optionally keeping unknown fields.
+fn resolve_subregions(
+    contents: &mut SymGen,
+    input_file: &Path,
+    allow_unknown_fields: bool,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let _timer = util::time_phase("resolve");
+    let dir_path = Subregion::subregion_dir(input_file);
+    if allow_unknown_fields {
+        contents.resolve_subregions_allow_unknown_fields(dir_path, |p| File::open(p))?;
+    } else {
+        contents.resolve_subregions(dir_path, |p| File::open(p))?;
+    }
+    Ok(())
+}
+
+/// Formats a given `input_file` using the given `int_format`, with `block_spacing` blank lines
+/// between blocks.
 ///
-/// In `recursive` mode, subregion files are also formatted.
+/// In `recursive` mode, subregion files are also formatted. If `allow_unknown_fields` is set,
+/// unknown fields are preserved rather than rejected.
 ///
 /// # Examples
 /// ```ignore
-/// format_file("/path/to/symbols.yml", false, IntFormat::Hexadecimal).expect("Format failed");
+/// format_file("/path/to/symbols.yml", false, false, IntFormat::Hexadecimal, 0)
+///     .expect("Format failed");
 /// ```
 pub fn format_file<P: AsRef<Path>>(
     input_file: P,
     recursive: bool,
+    allow_unknown_fields: bool,
     int_format: IntFormat,
-) -> Result<(), Box<dyn Error>> {
+    block_spacing: usize,
+) -> Result<(), ResymgenError> {
     let input_file = input_file.as_ref();
-    let mut contents = {
-        let f = File::open(input_file)?;
-        SymGen::read(&f)?
-    };
+    let mut contents = read_input(input_file, allow_unknown_fields)?;
     if recursive {
-        contents.resolve_subregions(Subregion::subregion_dir(input_file), |p| File::open(p))?;
+        resolve_subregions(&mut contents, input_file, allow_unknown_fields)?;
     }
-    contents.sort();
-    util::symgen_write_recursive(&contents, input_file, int_format)
+    {
+        let _timer = util::time_phase("sort");
+        contents.sort();
+    }
+    util::symgen_write_recursive(&contents, input_file, int_format, block_spacing)
 }
 
-/// Checks the format of a given `input_file`, subject to the given `int_format`.
-///
-/// In `recursive` mode, subregion files are also checked.
-///
-/// On success, returns `true`. On failure, returns `false` and prints a diff.
+/// The path, current text, and formatted text of a file that isn't formatted.
+pub(crate) type UnformattedFile = (PathBuf, String, String);
+
+/// Finds the files whose contents differ from their formatted versions under the given
+/// `int_format` and `block_spacing`.
 ///
-/// # Examples
-/// ```ignore
-/// let succeeded = format_check_file("/path/to/symbols.yml", false, IntFormat::Hexadecimal)
-///     .expect("Format check failed");
-/// ```
-pub fn format_check_file<P: AsRef<Path>>(
-    input_file: P,
+/// In `recursive` mode, subregion files are also checked. Unformatted files are returned in
+/// depth-first order.
+pub(crate) fn find_unformatted_files(
+    input_file: &Path,
     recursive: bool,
+    allow_unknown_fields: bool,
     int_format: IntFormat,
-) -> Result<bool, Box<dyn Error>> {
-    let input_file = input_file.as_ref();
-    let mut contents = {
-        let f = File::open(input_file)?;
-        SymGen::read(&f)?
-    };
+    block_spacing: usize,
+) -> Result<Vec<UnformattedFile>, Box<dyn Error + Send + Sync>> {
+    let mut contents = read_input(input_file, allow_unknown_fields)?;
     if recursive {
-        contents.resolve_subregions(Subregion::subregion_dir(input_file), |p| File::open(p))?;
+        resolve_subregions(&mut contents, input_file, allow_unknown_fields)?;
+    }
+    {
+        let _timer = util::time_phase("sort");
+        contents.sort();
     }
-    contents.sort();
 
-    let mut success = true;
+    let mut unformatted = Vec::new();
     // Depth-first traversal is more intuitive for reporting formatting issues
     for cursor in contents.cursor(input_file).dtraverse() {
         // It's unfortunate we're reading the same file twice, but it's simpler than trying to
         // resolve subregions manually, and less memory intensive than caching. If this ever
         // becomes a performance issue, it can be optimized.
         let text = fs::read_to_string(cursor.path())?;
-        let formatted_text = cursor.symgen().write_to_str(int_format)?;
+        let formatted_text = cursor
+            .symgen()
+            .write_to_str_with_spacing(int_format, block_spacing)?;
         if text != formatted_text {
-            print_format_diff(&text, &formatted_text, cursor.path().display())?;
-            // Keep going to check any other subregion files, but fail the check as a whole
-            success = false;
+            unformatted.push((cursor.path().to_owned(), text, formatted_text));
+        }
+    }
+    Ok(unformatted)
+}
+
+/// Checks the format of a given `input_file`, subject to the given `int_format` and
+/// `block_spacing`.
+///
+/// In `recursive` mode, subregion files are also checked. If `allow_unknown_fields` is set,
+/// unknown fields are preserved rather than rejected.
+///
+/// On success, returns `true`. On failure, returns `false` and prints a diff. If `patch` is
+/// provided, a patch that fixes the formatting issues (in a form that can be applied with
+/// `git apply`) is also appended to it.
+///
+/// # Examples
+/// ```ignore
+/// let mut patch = String::new();
+/// let succeeded = format_check_file(
+///     "/path/to/symbols.yml",
+///     false,
+///     false,
+///     IntFormat::Hexadecimal,
+///     0,
+///     Some(&mut patch),
+/// )
+/// .expect("Format check failed");
+/// ```
+pub fn format_check_file<P: AsRef<Path>>(
+    input_file: P,
+    recursive: bool,
+    allow_unknown_fields: bool,
+    int_format: IntFormat,
+    block_spacing: usize,
+    mut patch: Option<&mut String>,
+) -> Result<bool, ResymgenError> {
+    let unformatted = find_unformatted_files(
+        input_file.as_ref(),
+        recursive,
+        allow_unknown_fields,
+        int_format,
+        block_spacing,
+    )?;
+    for (path, text, formatted_text) in unformatted.iter() {
+        print_format_diff(text, formatted_text, path.display())?;
+        if let Some(patch) = patch.as_deref_mut() {
+            patch.push_str(&format_patch(text, formatted_text, path));
         }
     }
-    Ok(success)
+    Ok(unformatted.is_empty())
+}
+
+/// Creates a patch between a file and its formatted version in unified diff format, with `git`
+/// style file headers.
+///
+/// Patch paths must be relative, so absolute paths are made relative to the current directory if
+/// possible, and otherwise have their root stripped.
+fn format_patch(old: &str, new: &str, path: &Path) -> String {
+    let cwd = env::current_dir().ok();
+    let path = cwd
+        .as_deref()
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path);
+    // git always uses forward slashes in patch paths
+    let path = path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(_) | Component::ParentDir => Some(c.as_os_str().to_string_lossy()),
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    format!(
+        "diff --git a/{0} b/{0}\n{1}",
+        path,
+        TextDiff::from_lines(old, new)
+            .unified_diff()
+            .header(&format!("a/{}", path), &format!("b/{}", path))
+    )
 }
 
 /// Prints a diff between a file and its formatted version in unified diff format.
@@ -137,3 +248,39 @@ fn print_format_diff<D: Display>(old: &str, new: &str, title: D) -> io::Result<(
         res
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_patch() {
+        let old = "main:\n  address: 0x2000000\n  length:    0x1000\n";
+        let new = "main:\n  address: 0x2000000\n  length: 0x1000\n";
+        assert_eq!(
+            format_patch(old, new, Path::new("symbols/main.yml")),
+            "diff --git a/symbols/main.yml b/symbols/main.yml
+--- a/symbols/main.yml
++++ b/symbols/main.yml
+@@ -1,3 +1,3 @@
+ main:
+   address: 0x2000000
+-  length:    0x1000
++  length: 0x1000
+"
+        );
+    }
+
+    #[test]
+    fn test_format_patch_absolute_path() {
+        let old = "main:\n  length:    0x1000\n";
+        let new = "main:\n  length: 0x1000\n";
+        let cwd = env::current_dir().expect("No current directory");
+        let patch = format_patch(old, new, &cwd.join("symbols").join("main.yml"));
+        assert!(patch.starts_with("diff --git a/symbols/main.yml b/symbols/main.yml\n"));
+
+        let patch = format_patch(old, new, Path::new("/nonexistent/./main.yml"));
+        assert!(patch.starts_with("diff --git a/nonexistent/main.yml b/nonexistent/main.yml\n"));
+        assert!(patch.contains("--- a/nonexistent/main.yml\n+++ b/nonexistent/main.yml\n"));
+    }
+}
@@ -6,20 +6,26 @@
 //! The top-level library functions implement the subcommands of `resymgen`. That is, they take
 //! input files containing symbol data, process the file contents based on specified configuration
 //! parameters, and generate output related to the symbol data in the form of console output or
-//! files.
+//! files. Failures are reported as a [`ResymgenError`].
 //!
 //! The [`data_formats`] module defines structures and methods related to parsing and manipulating
 //! raw symbol data in various formats.
 
+mod analysis;
 mod checks;
 pub mod data_formats;
+mod diff;
+mod doctor;
 mod formatting;
 mod transform;
 mod util;
 
+pub use analysis::*;
 pub use checks::*;
-pub use data_formats::symgen_yml::{IntFormat, LoadParams, SymbolType};
-pub use data_formats::{InFormat, OutFormat};
+pub use data_formats::symgen_yml::{AddressNotation, IntFormat, LoadParams, SymbolType};
+pub use data_formats::{FormatOptions, InFormat, OutFormat};
+pub use diff::*;
+pub use doctor::*;
 pub use formatting::*;
 pub use transform::*;
 pub use util::*;
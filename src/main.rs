@@ -1,45 +1,79 @@
 #[macro_use]
 extern crate clap;
 
+use std::collections::HashMap;
 use std::convert::AsRef;
 use std::error::Error;
+use std::fs;
 use std::io::{self, Write};
-use std::path::Path;
+use std::iter;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::time::{Duration, Instant};
 
 use clap::{App, AppSettings, Arg, ArgSettings, SubCommand};
 use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
-use resymgen::{self, MultiFileError};
+use resymgen::{self, ErrorCategory, MultiFileError, UsageError};
 
-fn int_format(write_as_decimal: bool) -> resymgen::IntFormat {
-    if write_as_decimal {
-        resymgen::IntFormat::Decimal
-    } else {
-        resymgen::IntFormat::Hexadecimal
+fn int_format(write_as_decimal: bool, group_digits: bool) -> resymgen::IntFormat {
+    match (write_as_decimal, group_digits) {
+        (true, false) => resymgen::IntFormat::Decimal,
+        (false, false) => resymgen::IntFormat::Hexadecimal,
+        (true, true) => resymgen::IntFormat::GroupedDecimal,
+        (false, true) => resymgen::IntFormat::GroupedHexadecimal,
     }
 }
 
-const SUPPORTED_NAMING_CONVENTIONS: [&str; 5] = [
+const SUPPORTED_NAMING_CONVENTIONS: [&str; 6] = [
     "identifier",
     "snake_case",
     "SCREAMING_SNAKE_CASE",
     "camelCase",
     "PascalCase",
+    "kebab-case",
 ];
 
-// name is assumed to be in SUPPORTED_NAMING_CONVENTIONS (case-insensitive)
-fn naming_convention(name: &str) -> resymgen::NamingConvention {
+/// Prefix for custom naming conventions given as regular expressions
+const REGEX_NAMING_CONVENTION_PREFIX: &str = "regex:";
+
+// name is either in SUPPORTED_NAMING_CONVENTIONS (case-insensitive), or a regex with
+// REGEX_NAMING_CONVENTION_PREFIX
+fn naming_convention(name: &str) -> Result<resymgen::NamingConvention, UsageError> {
+    if let Some(pattern) = name.strip_prefix(REGEX_NAMING_CONVENTION_PREFIX) {
+        return resymgen::NamingConvention::from_regex(pattern);
+    }
     match name.to_ascii_lowercase().as_ref() {
-        "identifier" => resymgen::NamingConvention::Identifier,
-        "snake_case" => resymgen::NamingConvention::SnakeCase,
-        "screaming_snake_case" => resymgen::NamingConvention::ScreamingSnakeCase,
-        "camelcase" => resymgen::NamingConvention::CamelCase,
-        "pascalcase" => resymgen::NamingConvention::PascalCase,
-        _ => panic!("Unsupported naming convention '{}'", name), // control should never reach this point
+        "identifier" => Ok(resymgen::NamingConvention::Identifier),
+        "snake_case" => Ok(resymgen::NamingConvention::SnakeCase),
+        "screaming_snake_case" => Ok(resymgen::NamingConvention::ScreamingSnakeCase),
+        "camelcase" => Ok(resymgen::NamingConvention::CamelCase),
+        "pascalcase" => Ok(resymgen::NamingConvention::PascalCase),
+        "kebab-case" => Ok(resymgen::NamingConvention::KebabCase),
+        _ => Err(UsageError(format!(
+            "Invalid naming convention: '{}' (expected one of {}, or {}PATTERN)",
+            name,
+            SUPPORTED_NAMING_CONVENTIONS.join(", "),
+            REGEX_NAMING_CONVENTION_PREFIX
+        ))),
     }
 }
 
+fn validate_naming_convention(name: String) -> Result<(), String> {
+    naming_convention(&name)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+fn naming_convention_help(symbol_type: &str) -> String {
+    format!(
+        "Enforce a naming convention for {} symbols. Supported conventions are {}, or {}PATTERN for a custom regular expression that must match the whole name. Note that all conventions except kebab-case and custom regular expressions implicitly enforce valid identifiers.",
+        symbol_type,
+        SUPPORTED_NAMING_CONVENTIONS.join(", "),
+        REGEX_NAMING_CONVENTION_PREFIX
+    )
+}
+
 const SUPPORTED_SYMBOL_TYPES: [&str; 2] = ["function", "data"];
 
 // stype is assumed to be in SUPPORTED_SYMBOL_TYPES
@@ -52,14 +86,35 @@ fn symbol_type(stype: &str) -> resymgen::SymbolType {
 }
 
 fn run_resymgen() -> Result<(), Box<dyn Error>> {
-    let gen_formats: Vec<_> = resymgen::OutFormat::all().map(|f| f.extension()).collect();
+    let gen_formats: Vec<_> = resymgen::OutFormat::all()
+        .map(|f| f.name())
+        .chain(resymgen::OutFormat::aliases().map(String::from))
+        .collect();
+    let address_notations: Vec<_> = resymgen::AddressNotation::all().map(|n| n.name()).collect();
     let merge_formats: Vec<_> = resymgen::InFormat::all().map(|f| f.extension()).collect();
+    let function_names_help = naming_convention_help("function");
+    let data_names_help = naming_convention_help("data");
 
-    let matches = App::new(crate_name!())
+    let app = App::new(crate_name!())
         .version(crate_version!())
         .author("UsernameFodder")
         .about("Generates symbol tables for reverse engineering applications from a YAML specification.")
         .setting(AppSettings::ArgRequiredElseHelp)
+        .arg(
+            Arg::with_name("time")
+                .help("Print how long each phase of the run (reading, resolving subregions, sorting, checking, merging, and writing) took, to stderr")
+                .long("time")
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("threads")
+                .help("Maximum number of threads to use for work done in parallel (like gen --jobs), or 0 to choose automatically")
+                .takes_value(true)
+                .value_name("N")
+                .default_value("0")
+                .long("threads")
+                .global(true),
+        )
         .subcommand(
             SubCommand::with_name("gen")
                 .about("Generates one or more symbol tables from a resymgen YAML file and its subregion files")
@@ -72,6 +127,10 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
                         .multiple(true)
                         .number_of_values(1)
                         .possible_values(&gen_formats.iter().map(|f| f.as_ref()).collect::<Vec<_>>()),
+                    Arg::with_name("all formats")
+                        .help("Generate every supported output format, and write a list of all generated files to manifest.txt in the output directory. Implies --first-address-only.")
+                        .long("all-formats")
+                        .conflicts_with("format"),
                     Arg::with_name("binary version")
                         .help("Version of the binary to generate a symbol table for")
                         .takes_value(true)
@@ -83,6 +142,73 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
                         .help("Within each symbol category (functions, data), generate symbols in order by address")
                         .short("s")
                         .long("sort"),
+                    Arg::with_name("documented only")
+                        .help("Only generate symbols that have a description")
+                        .long("documented-only"),
+                    Arg::with_name("exclude version in")
+                        .help("Omit the symbols in a specific block when generating a specific version, in the form BLOCK=VERSION (e.g., overlay11=EU). The version is still generated for all other blocks. Can be specified multiple times.")
+                        .takes_value(true)
+                        .value_name("BLOCK=VERSION")
+                        .long("exclude-version-in")
+                        .multiple(true)
+                        .number_of_values(1),
+                    Arg::with_name("blocks")
+                        .help("Comma-separated names of the blocks to generate symbols for. Symbols in all other blocks are omitted.")
+                        .takes_value(true)
+                        .value_name("BLOCKS")
+                        .long("blocks")
+                        .use_delimiter(true),
+                    Arg::with_name("base version")
+                        .help("Version to fall back on for symbols that have no data for the version being generated (e.g., generating JP with NA as a base). This is a best-effort approximation, since addresses usually differ between versions. Data for the version being generated always takes precedence.")
+                        .takes_value(true)
+                        .value_name("VERSION")
+                        .long("base-version"),
+                    Arg::with_name("first address only")
+                        .help("In output formats that only allow one address per symbol (like ld), keep only the first address of symbols with multiple addresses, with a warning for each address skipped. Otherwise, such symbols are an error in those formats.")
+                        .long("first-address-only"),
+                    Arg::with_name("decimal")
+                        .help("Write integers in decimal format, for output formats that allow a choice (like csv). By default integers are written as hexadecimal.")
+                        .long("decimal"),
+                    Arg::with_name("group digits")
+                        .help("Separate groups of digits in integers with underscores (e.g., 0x201_2345), for output formats that allow a choice (like csv)")
+                        .long("group-digits"),
+                    Arg::with_name("address notation")
+                        .help("Notation for addresses (and lengths) in output formats that write them as plain hexadecimal numbers (ld, r2, idc, and nocashsym): a 0x prefix, a $ prefix, an h suffix, or bare. By default each format uses its usual notation (0x, except for nocashsym, which is bare). Note that the tools these formats are meant for might not accept other notations.")
+                        .takes_value(true)
+                        .value_name("NOTATION")
+                        .long("address-notation")
+                        .possible_values(&address_notations),
+                    Arg::with_name("comment descriptions")
+                        .help("Append symbol descriptions as comments, for output formats where they're optional (like nocashsym)")
+                        .long("comment-descriptions"),
+                    Arg::with_name("name transform")
+                        .help("Transform symbol names (and deprecated aliases) in the generated output, without changing the input file. RULE is one of prefix=TEXT, suffix=TEXT, or replace=PATTERN=REPLACEMENT (where PATTERN is a regular expression). Can be specified multiple times; rules are applied in order.")
+                        .takes_value(true)
+                        .value_name("RULE")
+                        .long("name-transform")
+                        .multiple(true)
+                        .number_of_values(1),
+                    Arg::with_name("quiet")
+                        .help("Don't print a summary of each output file written")
+                        .short("q")
+                        .long("quiet"),
+                    Arg::with_name("combine")
+                        .help("Merge all input files together and generate a single set of symbol tables, with output file names based on the given name")
+                        .takes_value(true)
+                        .value_name("NAME")
+                        .long("combine"),
+                    Arg::with_name("jobs")
+                        .help("Number of input files to generate symbol tables for concurrently, up to the limit set by --threads. Failures are still reported in input file order.")
+                        .takes_value(true)
+                        .value_name("N")
+                        .default_value("1")
+                        .short("j")
+                        .long("jobs"),
+                    Arg::with_name("manifest")
+                        .help("Write a manifest of SHA-256 hashes of all generated files to the given file, in the same format as sha256sum. Requires resymgen to be built with the 'manifest' feature.")
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .long("manifest"),
                     Arg::with_name("output directory")
                         .help("Output directory")
                         .takes_value(true)
@@ -99,7 +225,7 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
         )
         .subcommand(
             SubCommand::with_name("fmt")
-                .about("Formats a resymgen YAML file")
+                .about("Formats a resymgen YAML file, sorting the symbols in each block by address")
                 .args(&[
                     Arg::with_name("recursive")
                         .help("Recursively format the given file and its subregion files")
@@ -109,10 +235,26 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
                         .help("Run in 'check' mode. If the input is improperly formatted, exit with 1 and print a diff.")
                         .short("c")
                         .long("check"),
+                    Arg::with_name("diff output")
+                        .help("In 'check' mode, also write a patch that fixes any formatting issues to the given file. The patch can be applied with 'git apply'.")
+                        .takes_value(true)
+                        .requires("check")
+                        .long("diff-output"),
                     Arg::with_name("decimal")
                         .help("Write integers in decimal format. By default integers are written as hexadecimal.")
                         .short("d")
                         .long("decimal"),
+                    Arg::with_name("group digits")
+                        .help("Separate groups of digits in integers with underscores (e.g., 0x201_2345)")
+                        .long("group-digits"),
+                    Arg::with_name("allow unknown fields")
+                        .help("Preserve fields not recognized by resymgen in blocks and symbols, rather than failing, including in subregion files.")
+                        .long("allow-unknown-fields"),
+                    Arg::with_name("block spacing")
+                        .help("Number of blank lines to insert between top-level blocks")
+                        .takes_value(true)
+                        .long("block-spacing")
+                        .default_value("0"),
                     Arg::with_name("input")
                         .help("Input resymgen YAML file name(s)")
                         .required(true)
@@ -124,10 +266,30 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
             SubCommand::with_name("check")
                 .about("Validates the contents of a resymgen YAML file")
                 .args(&[
+                    Arg::with_name("preset")
+                        .help("Run a built-in preset of checks, which can be augmented with additional check flags. 'basic' runs the unique symbols, in-bounds symbols, no overlap, and complete version list checks. 'ci' (the checks run by this project's CI) adds explicit versions, nonempty maps, PascalCase function names, and SCREAMING_SNAKE_CASE data names. 'strict' adds data length multiple, unique block addresses, no duplicate symbol addresses, consistent version order, and safe descriptions to 'ci'. Explicit naming convention flags override those in presets.")
+                        .takes_value(true)
+                        .short("p")
+                        .long("preset")
+                        .set(ArgSettings::CaseInsensitive)
+                        .possible_values(&resymgen::CHECK_PRESETS)
+                        .multiple(true)
+                        .number_of_values(1),
                     Arg::with_name("recursive")
                         .help("Recursively validate the given file and its subregion files")
                         .short("r")
                         .long("recursive"),
+                    Arg::with_name("fix")
+                        .help("Before validating, automatically fix failures of the selected checks that can be corrected mechanically, rewriting the changed files in place. Currently supported for the no duplicate symbol addresses, consistent version order, sorted symbols, and trimmed descriptions checks. The checks are then run as usual to report any remaining problems.")
+                        .long("fix"),
+                    Arg::with_name("decimal")
+                        .help("With --fix, write integers in fixed files in decimal format. By default integers are written as hexadecimal.")
+                        .long("decimal")
+                        .requires("fix"),
+                    Arg::with_name("group digits")
+                        .help("With --fix, separate groups of digits in integers in fixed files with underscores (e.g., 0x201_2345)")
+                        .long("group-digits")
+                        .requires("fix"),
                     Arg::with_name("explicit versions")
                         .help("Require versions to be explicitly specified in maps")
                         .short("v")
@@ -152,22 +314,133 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
                         .help("Disallow per-version overlap between functions within a block. If the --recursive option is specified, also disallow per-version overlap between a subregion and any other subregion, function, or data within a block.")
                         .short("o")
                         .long("no-overlap"),
+                    Arg::with_name("data length multiple")
+                        .help("Require the lengths of data symbols with an element size to be multiples of the element size")
+                        .short("e")
+                        .long("data-length-multiple"),
+                    Arg::with_name("unique block addresses")
+                        .help("Disallow multiple blocks with the same base address for a given version")
+                        .short("a")
+                        .long("unique-block-addresses"),
+                    Arg::with_name("no duplicate symbol addresses")
+                        .help("Disallow symbols from listing the same address more than once for a given version")
+                        .short("D")
+                        .long("no-duplicate-symbol-addresses"),
+                    Arg::with_name("globally unique names")
+                        .help("Require symbol names and deprecated aliases to be unique across all blocks (including subregions, with --recursive)")
+                        .long("globally-unique-names"),
+                    Arg::with_name("no block overlap")
+                        .help("Disallow blocks from overlapping with each other for a given version (blocks in different space groups may overlap)")
+                        .long("no-block-overlap"),
+                    Arg::with_name("sorted symbols")
+                        .help("Require the function and data symbols in each block to already be sorted by address, as they would be after formatting")
+                        .long("sorted-symbols"),
+                    Arg::with_name("sorted versions")
+                        .help("Require block version lists to be sorted by name, or in the order given by --version-order")
+                        .long("sorted-versions"),
+                    Arg::with_name("version order")
+                        .help("Comma-separated version order for --sorted-versions. Versions not in the list must come after the listed ones, sorted by name.")
+                        .takes_value(true)
+                        .value_name("VERSIONS")
+                        .long("version-order")
+                        .use_delimiter(true)
+                        .requires("sorted versions"),
+                    Arg::with_name("no shared addresses")
+                        .help("Disallow distinct symbols (functions or data) within a block from sharing an address for a given version, even if they don't have lengths")
+                        .long("no-shared-addresses"),
+                    Arg::with_name("consistent version order")
+                        .help("Require blocks with the same set of versions to list them in the same order as the first block with a version list")
+                        .short("O")
+                        .long("consistent-version-order"),
+                    Arg::with_name("subregion metadata consistency")
+                        .help("Require subregion blocks to be within the per-version bounds of their parent blocks, and to only use versions that their parent blocks have")
+                        .short("S")
+                        .long("subregion-metadata-consistency")
+                        .requires("recursive"),
+                    Arg::with_name("only version")
+                        .help("Only consider the given version in checks that compare addresses and lengths per version (in-bounds symbols, no overlap, no block overlap, data length multiple, unique block addresses, no duplicate symbol addresses, no shared addresses, block alignment, nonzero lengths, and address regions). Other checks are unaffected.")
+                        .takes_value(true)
+                        .long("only-version"),
+                    Arg::with_name("safe descriptions")
+                        .help("Disallow single-line descriptions that would be misparsed if written as plain YAML scalars (e.g., containing ': ')")
+                        .long("safe-descriptions"),
+                    Arg::with_name("trimmed descriptions")
+                        .help("Disallow trailing whitespace on any line of a block or symbol description, including trailing blank lines")
+                        .long("trimmed-descriptions"),
+                    Arg::with_name("incomplete symbols")
+                        .help("Require symbols to have either a length or a non-blank description, to flag name-only placeholder symbols")
+                        .long("incomplete-symbols"),
+                    Arg::with_name("require descriptions")
+                        .help("Require all symbols to have a nonblank description")
+                        .long("require-descriptions"),
+                    Arg::with_name("require function descriptions")
+                        .help("Require function symbols to have a nonblank description")
+                        .long("require-function-descriptions"),
+                    Arg::with_name("require data descriptions")
+                        .help("Require data symbols to have a nonblank description")
+                        .long("require-data-descriptions"),
+                    Arg::with_name("max description length")
+                        .help("Disallow block and symbol descriptions longer than the given number of bytes")
+                        .takes_value(true)
+                        .value_name("N")
+                        .long("max-description-length"),
+                    Arg::with_name("max name length")
+                        .help("Disallow symbol names and deprecated aliases longer than the given number of bytes")
+                        .takes_value(true)
+                        .value_name("N")
+                        .long("max-name-length"),
+                    Arg::with_name("forbidden prefix")
+                        .help("Disallow symbol names starting with the given prefix (deprecated aliases are exempt). Can be specified multiple times.")
+                        .takes_value(true)
+                        .value_name("PREFIX")
+                        .long("forbidden-prefix")
+                        .multiple(true)
+                        .number_of_values(1),
+                    Arg::with_name("address regions")
+                        .help("Require every symbol (including any additional ranges) to fall entirely within one of the memory regions listed in the given YAML file (a map from region names to 'start' and 'end' addresses)")
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .long("address-regions"),
+                    Arg::with_name("nonzero lengths")
+                        .help("Disallow explicit block and symbol lengths of 0")
+                        .long("nonzero-lengths"),
+                    Arg::with_name("lengths within block")
+                        .help("Disallow symbol lengths that exceed the length of the parent block (for the same version)")
+                        .long("lengths-within-block")
+                        .requires("nonzero lengths"),
+                    Arg::with_name("round trip")
+                        .help("Require the file contents to be unchanged after formatting and reading back the file, to catch formatter or parser bugs that lose or mutate data")
+                        .long("round-trip"),
+                    Arg::with_name("block alignment")
+                        .help("Require block base addresses to be multiples of the given alignment (a power of two, in decimal or 0x-prefixed hexadecimal) for every version")
+                        .takes_value(true)
+                        .long("block-alignment"),
                     Arg::with_name("function names")
-                        .help("Enforce a naming convention for function symbols. Note that all conventions implicitly enforce valid identifiers.")
+                        .help(&function_names_help)
                         .takes_value(true)
+                        .value_name("CONVENTION")
                         .short("f")
                         .long("function-names")
-                        .set(ArgSettings::CaseInsensitive)
-                        .possible_values(&SUPPORTED_NAMING_CONVENTIONS),
+                        .validator(validate_naming_convention),
                     Arg::with_name("data names")
-                        .help("Enforce a naming convention for data symbols. Note that all conventions implicitly enforce valid identifiers.")
+                        .help(&data_names_help)
                         .takes_value(true)
+                        .value_name("CONVENTION")
                         .short("d")
                         .long("data-names")
+                        .validator(validate_naming_convention),
+                    Arg::with_name("check aliases")
+                        .help("Also enforce the function and data naming conventions (from --function-names, --data-names, or a preset) on deprecated aliases")
+                        .long("check-aliases"),
+                    Arg::with_name("format")
+                        .help("Output format for check results. The 'github' format prints failures as GitHub Actions workflow commands, so they show up as annotations in CI.")
+                        .takes_value(true)
+                        .long("format")
                         .set(ArgSettings::CaseInsensitive)
-                        .possible_values(&SUPPORTED_NAMING_CONVENTIONS),
+                        .possible_values(&["text", "github"])
+                        .default_value("text"),
                     Arg::with_name("input")
-                        .help("Input resymgen YAML file name(s)")
+                        .help("Input resymgen YAML file name(s). Use '-' to read from standard input (not supported with --recursive).")
                         .required(true)
                         .multiple(true)
                         .index(1),
@@ -196,6 +469,12 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
                         .takes_value(true)
                         .short("v")
                         .long("binary-version"),
+                    Arg::with_name("map version")
+                        .help("Rename a version in the input data before merging, in the form OLD=NEW (e.g., US=NA). Can be specified multiple times.")
+                        .takes_value(true)
+                        .long("map-version")
+                        .multiple(true)
+                        .number_of_values(1),
                     Arg::with_name("block")
                         .help("Default block to assume if input data blocks are unlabeled")
                         .takes_value(true)
@@ -205,25 +484,210 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
                         .help("Write integers in decimal format. By default integers are written as hexadecimal.")
                         .short("d")
                         .long("decimal"),
+                    Arg::with_name("group digits")
+                        .help("Separate groups of digits in integers with underscores (e.g., 0x201_2345)")
+                        .long("group-digits"),
                     Arg::with_name("fix formatting")
                         .help("Run the formatter on the final resymgen YAML file after the merge.")
                         .short("x")
                         .long("fix-formatting"),
+                    Arg::with_name("report unmerged")
+                        .help("Also write the unmerged symbols from all input files to the given file, so they can be fixed up and merged again. The format is determined by the file extension: '.csv' writes the CSV input format (which can be merged again with '-f csv -b BLOCK'), and '.yml' writes a list of symbols to be pasted into the appropriate block.")
+                        .takes_value(true)
+                        .long("report-unmerged-file"),
+                    Arg::with_name("json")
+                        .help("Print a JSON summary of the merge instead of listing unmerged symbols. For each input file, the summary reports the number of symbols added and merged, the unmerged symbols, the number of symbols merged into each block, and the error, if the file failed to merge.")
+                        .long("json"),
                     Arg::with_name("input")
                         .help("input data file")
-                        .required(true)
+                        .required_unless("input glob")
                         .takes_value(true)
                         .short("i")
                         .long("input")
                         .multiple(true)
                         .number_of_values(1),
+                    Arg::with_name("input glob")
+                        .help("Glob pattern for input data files (e.g., 'exports/*.csv'). Matched files are merged in file name order, after any files specified with --input.")
+                        .takes_value(true)
+                        .short("g")
+                        .long("input-glob")
+                        .multiple(true)
+                        .number_of_values(1),
                     Arg::with_name("symgen file")
                         .help("resymgen YAML file to modify")
                         .required(true)
                         .index(1),
                 ]),
         )
-        .get_matches();
+        .subcommand(
+            SubCommand::with_name("import")
+                .about("Imports a legacy flat symbol table (one 'name,address' pair per line) into a resymgen YAML file, assigning each symbol to an existing block by address")
+                .args(&[
+                    Arg::with_name("symbol type")
+                        .help("Symbol type of the imported symbols")
+                        .takes_value(true)
+                        .short("s")
+                        .long("symbol-type")
+                        .possible_values(&SUPPORTED_SYMBOL_TYPES)
+                        .required(true),
+                    Arg::with_name("binary version")
+                        .help("Binary version of the imported addresses")
+                        .takes_value(true)
+                        .short("v")
+                        .long("binary-version"),
+                    Arg::with_name("decimal")
+                        .help("Write integers in decimal format. By default integers are written as hexadecimal.")
+                        .short("d")
+                        .long("decimal"),
+                    Arg::with_name("group digits")
+                        .help("Separate groups of digits in integers with underscores (e.g., 0x201_2345)")
+                        .long("group-digits"),
+                    Arg::with_name("symgen file")
+                        .help("resymgen YAML file to modify")
+                        .required(true)
+                        .index(1),
+                    Arg::with_name("input")
+                        .help("Flat symbol table file(s) to import")
+                        .required(true)
+                        .multiple(true)
+                        .index(2),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("version-delta")
+                .about("Reports the address offsets of symbols between two versions in a resymgen YAML file and its subregion files")
+                .args(&[
+                    Arg::with_name("input")
+                        .help("Input resymgen YAML file name")
+                        .required(true)
+                        .index(1),
+                    Arg::with_name("from version")
+                        .help("Version to compute offsets from")
+                        .required(true)
+                        .index(2),
+                    Arg::with_name("to version")
+                        .help("Version to compute offsets to")
+                        .required(true)
+                        .index(3),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("diff")
+                .about("Reports the symbols and blocks added, removed, or changed between two resymgen YAML files and their subregion files")
+                .long_about("Reports the symbols and blocks added, removed, or changed between two resymgen YAML files and their subregion files. Changed symbols list which of their addresses, lengths, descriptions, and deprecated aliases differ. Exits with a nonzero status if there are any differences.")
+                .args(&[
+                    Arg::with_name("binary version")
+                        .help("Only compare symbols as realized for the given binary version, rather than all versions")
+                        .takes_value(true)
+                        .short("v")
+                        .long("binary-version"),
+                    Arg::with_name("context")
+                        .help("Print up to N neighboring symbols (by address, within the same block) on either side of each added, removed, or changed symbol, for orientation")
+                        .takes_value(true)
+                        .value_name("N")
+                        .default_value("0")
+                        .short("C")
+                        .long("context"),
+                    Arg::with_name("old")
+                        .help("Old resymgen YAML file name")
+                        .required(true)
+                        .index(1),
+                    Arg::with_name("new")
+                        .help("New resymgen YAML file name")
+                        .required(true)
+                        .index(2),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("lookup")
+                .about("Looks up a symbol by name in a resymgen YAML file and its subregion files")
+                .args(&[
+                    Arg::with_name("fuzzy")
+                        .help("If no symbol matches the name exactly, suggest the closest symbol names")
+                        .long("fuzzy"),
+                    Arg::with_name("input")
+                        .help("Input resymgen YAML file name")
+                        .required(true)
+                        .index(1),
+                    Arg::with_name("name")
+                        .help("Symbol name to look up")
+                        .required(true)
+                        .index(2),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("versions")
+                .about("Lists the versions used in a resymgen YAML file, with the number of symbols for each version")
+                .args(&[
+                    Arg::with_name("recursive")
+                        .help("Include symbols from subregion files")
+                        .short("r")
+                        .long("recursive"),
+                    Arg::with_name("input")
+                        .help("Input resymgen YAML file name")
+                        .required(true)
+                        .index(1),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("stats")
+                .about("Reports symbol counts, missing fields, and symbol coverage per block in a resymgen YAML file and its subregion files")
+                .args(&[
+                    Arg::with_name("json")
+                        .help("Print the statistics as JSON")
+                        .long("json"),
+                    Arg::with_name("input")
+                        .help("Input resymgen YAML file name")
+                        .required(true)
+                        .index(1),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("resolve")
+                .about("Checks that the full subregion tree of a resymgen YAML file resolves, without modifying anything")
+                .args(&[Arg::with_name("input")
+                    .help("Input resymgen YAML file name(s)")
+                    .required(true)
+                    .multiple(true)]),
+        )
+        .subcommand(
+            SubCommand::with_name("doctor")
+                .about("Runs a broad health report on resymgen YAML files, with suggestions for fixing any problems found")
+                .long_about("Runs a broad health report on resymgen YAML files, with suggestions for fixing any problems found. This checks that subregions resolve, that files are formatted and sorted, and that files pass the unique symbols, no overlap, in-bounds symbols, and complete version list checks (recursively). It's a good starting point if you aren't sure which checks to run.")
+                .args(&[Arg::with_name("input")
+                    .help("Input resymgen YAML file name(s)")
+                    .required(true)
+                    .multiple(true)]),
+        );
+    #[cfg(feature = "schema")]
+    let app = app.subcommand(SubCommand::with_name("schema").about(
+        "Prints a JSON Schema describing the resymgen YAML format, for use with editor tooling",
+    ));
+    let matches = app.get_matches_safe().unwrap_or_else(|e| match e.kind {
+        // Not actually errors; let clap print the message and exit normally
+        clap::ErrorKind::HelpDisplayed | clap::ErrorKind::VersionDisplayed => e.exit(),
+        // clap already formats usage errors nicely, so print them as-is
+        _ => {
+            eprintln!("{}", e.message);
+            process::exit(ErrorCategory::Usage.exit_code());
+        }
+    });
+
+    // --quiet suppresses all non-error output, including the timing report
+    let quiet = matches
+        .subcommand()
+        .1
+        .map_or(false, |m| m.is_present("quiet"));
+    if matches.is_present("time") && !quiet {
+        resymgen::enable_timing();
+    }
+    let threads_str = matches.value_of("threads").unwrap();
+    let threads = threads_str
+        .parse::<usize>()
+        .map_err(|_| UsageError(format!("Invalid number of threads: '{}'", threads_str)))?;
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()?;
 
     match matches.subcommand_name() {
         Some("gen") => {
@@ -231,70 +695,157 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
 
             let input_files = matches.values_of("input").unwrap();
             let output_dir = matches.value_of("output directory").unwrap();
+            let all_formats = matches.is_present("all formats");
             let output_formats = match matches.values_of("format") {
                 Some(v) => Some(
                     v.map(|name| {
                         resymgen::OutFormat::from(name)
-                            .ok_or_else(|| format!("Invalid output format: '{}'", name))
+                            .ok_or_else(|| UsageError(format!("Invalid output format: '{}'", name)))
                     })
                     .collect::<Result<Vec<_>, _>>()?,
                 ),
+                None if all_formats => Some(resymgen::OutFormat::all().collect()),
                 None => None,
             };
             let output_versions: Option<Vec<_>> =
                 matches.values_of("binary version").map(|v| v.collect());
-            let sort_output = matches.is_present("sort");
-
-            let mut errors = Vec::with_capacity(input_files.len());
-            for input_file in input_files {
-                let run_gen = || -> Result<(), Box<dyn Error>> {
-                    let input_file_stem = Path::new(input_file)
-                        .file_stem()
-                        .ok_or("Empty input file name")?;
-                    let output_base = Path::new(output_dir).join(input_file_stem);
-                    resymgen::generate_symbol_tables(
-                        input_file,
-                        output_formats.clone(),
-                        output_versions.clone(),
-                        sort_output,
-                        output_base,
-                    )?;
-                    Ok(())
-                };
-                if let Err(e) = run_gen() {
-                    errors.push((input_file.to_string(), e));
-                }
+            let mut block_version_excludes = Vec::new();
+            for exclusion in matches
+                .values_of("exclude version in")
+                .into_iter()
+                .flatten()
+            {
+                let (block, version) = exclusion
+                    .split_once('=')
+                    .filter(|(block, version)| !block.is_empty() && !version.is_empty())
+                    .ok_or_else(|| {
+                        UsageError(format!(
+                            "Invalid block version exclusion: '{}' (expected BLOCK=VERSION)",
+                            exclusion
+                        ))
+                    })?;
+                block_version_excludes.push((block.to_string(), version.to_string()));
             }
-            if errors.is_empty() {
-                Ok(())
-            } else {
-                Err(MultiFileError {
-                    base_msg: "Failed to generate symbols".to_string(),
-                    errors,
+            let gen_params = resymgen::GenParams {
+                sort_output: matches.is_present("sort"),
+                documented_only: matches.is_present("documented only"),
+                quiet: matches.is_present("quiet"),
+                block_version_excludes,
+                blocks: matches
+                    .values_of("blocks")
+                    .map(|b| b.map(String::from).collect()),
+                base_version: matches.value_of("base version").map(String::from),
+                name_transforms: matches
+                    .values_of("name transform")
+                    .into_iter()
+                    .flatten()
+                    .map(resymgen::NameTransform::parse)
+                    .collect::<Result<_, _>>()?,
+                format_options: resymgen::FormatOptions {
+                    int_format: int_format(
+                        matches.is_present("decimal"),
+                        matches.is_present("group digits"),
+                    ),
+                    // possible_values guarantees that the notation exists
+                    address_notation: matches
+                        .value_of("address notation")
+                        .map(|n| resymgen::AddressNotation::from(n).unwrap()),
+                    comment_descriptions: matches.is_present("comment descriptions"),
+                    // Generating every format shouldn't fail just because one of them can't
+                    // represent symbols with multiple addresses
+                    first_address_only: all_formats || matches.is_present("first address only"),
+                    quiet: matches.is_present("quiet"),
+                },
+            };
+            let manifest_file = matches.value_of("manifest");
+            #[cfg(not(feature = "manifest"))]
+            if manifest_file.is_some() {
+                return Err(UsageError(
+                    "--manifest requires resymgen to be built with the 'manifest' feature"
+                        .to_string(),
+                )
+                .into());
+            }
+            let write_manifest = |written: &[PathBuf]| -> Result<(), Box<dyn Error>> {
+                if all_formats {
+                    resymgen::write_file_list(Path::new(output_dir).join("manifest.txt"), written)?;
                 }
-                .into())
+                #[cfg(feature = "manifest")]
+                if let Some(manifest_file) = manifest_file {
+                    resymgen::write_manifest(manifest_file, written)?;
+                }
+                #[cfg(not(feature = "manifest"))]
+                let _ = written;
+                Ok(())
+            };
+
+            if let Some(combined_name) = matches.value_of("combine") {
+                let written = resymgen::generate_combined_symbol_tables(
+                    input_files.collect::<Vec<_>>(),
+                    output_formats,
+                    output_versions,
+                    Path::new(output_dir).join(combined_name),
+                    &gen_params,
+                )?;
+                return write_manifest(&written);
             }
+
+            let jobs_str = matches.value_of("jobs").unwrap();
+            let jobs = jobs_str
+                .parse::<usize>()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| UsageError(format!("Invalid number of jobs: '{}'", jobs_str)))?;
+            let written = resymgen::generate_symbol_tables_many(
+                input_files.collect::<Vec<_>>(),
+                output_formats,
+                output_versions,
+                output_dir,
+                &gen_params,
+                jobs,
+            )?;
+            write_manifest(&written)
         }
         Some("fmt") => {
             let matches = matches.subcommand_matches("fmt").unwrap();
 
             let input_files = matches.values_of("input").unwrap();
             let recursive = matches.is_present("recursive");
-            let iformat = int_format(matches.is_present("decimal"));
+            let allow_unknown_fields = matches.is_present("allow unknown fields");
+            let iformat = int_format(
+                matches.is_present("decimal"),
+                matches.is_present("group digits"),
+            );
+            let block_spacing_str = matches.value_of("block spacing").unwrap();
+            let block_spacing = block_spacing_str.parse::<usize>().map_err(|_| {
+                UsageError(format!("Invalid block spacing: '{}'", block_spacing_str))
+            })?;
             if matches.is_present("check") {
+                let diff_output = matches.value_of("diff output");
+                let mut patch = String::new();
                 let mut errors = Vec::with_capacity(input_files.len());
                 let mut failed = false;
                 for input_file in input_files {
-                    match resymgen::format_check_file(input_file, recursive, iformat) {
+                    match resymgen::format_check_file(
+                        input_file,
+                        recursive,
+                        allow_unknown_fields,
+                        iformat,
+                        block_spacing,
+                        diff_output.map(|_| &mut patch),
+                    ) {
                         Ok(success) => {
                             if !success {
                                 println!();
                                 failed = true;
                             }
                         }
-                        Err(e) => errors.push((input_file.to_string(), e)),
+                        Err(e) => errors.push((input_file.to_string(), e.into())),
                     };
                 }
+                if let Some(diff_output) = diff_output {
+                    fs::write(diff_output, &patch)?;
+                }
                 if !errors.is_empty() {
                     return Err(MultiFileError {
                         base_msg: "Could not complete format check".to_string(),
@@ -308,8 +859,14 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
             } else {
                 let mut errors = Vec::with_capacity(input_files.len());
                 for input_file in input_files {
-                    if let Err(e) = resymgen::format_file(input_file, recursive, iformat) {
-                        errors.push((input_file.to_string(), e));
+                    if let Err(e) = resymgen::format_file(
+                        input_file,
+                        recursive,
+                        allow_unknown_fields,
+                        iformat,
+                        block_spacing,
+                    ) {
+                        errors.push((input_file.to_string(), e.into()));
                     }
                 }
                 if !errors.is_empty() {
@@ -329,6 +886,10 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
             let recursive = matches.is_present("recursive");
 
             let mut checks = Vec::new();
+            for preset in matches.values_of("preset").into_iter().flatten() {
+                // possible_values guarantees that the preset exists
+                checks.extend(resymgen::Check::preset(&preset.to_lowercase()).unwrap());
+            }
             if matches.is_present("explicit versions") {
                 checks.push(resymgen::Check::ExplicitVersions);
             }
@@ -347,16 +908,164 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
             if matches.is_present("no overlap") {
                 checks.push(resymgen::Check::NoOverlap);
             }
+            if matches.is_present("data length multiple") {
+                checks.push(resymgen::Check::DataLengthMultiple);
+            }
+            if matches.is_present("unique block addresses") {
+                checks.push(resymgen::Check::UniqueBlockAddresses);
+            }
+            if matches.is_present("no duplicate symbol addresses") {
+                checks.push(resymgen::Check::NoDuplicateSymbolAddresses);
+            }
+            if matches.is_present("globally unique names") {
+                checks.push(resymgen::Check::GloballyUniqueNames);
+            }
+            if matches.is_present("no block overlap") {
+                checks.push(resymgen::Check::NoBlockOverlap);
+            }
+            if matches.is_present("sorted symbols") {
+                checks.push(resymgen::Check::SortedSymbols);
+            }
+            if matches.is_present("sorted versions") {
+                checks.push(resymgen::Check::SortedVersions(
+                    matches
+                        .values_of("version order")
+                        .map(|vals| vals.map(String::from).collect()),
+                ));
+            }
+            if matches.is_present("no shared addresses") {
+                checks.push(resymgen::Check::NoSharedAddresses);
+            }
+            if matches.is_present("consistent version order") {
+                checks.push(resymgen::Check::ConsistentVersionOrder);
+            }
+            if matches.is_present("subregion metadata consistency") {
+                checks.push(resymgen::Check::SubregionMetadataConsistency);
+            }
+            if matches.is_present("safe descriptions") {
+                checks.push(resymgen::Check::SafeDescriptions);
+            }
+            if matches.is_present("trimmed descriptions") {
+                checks.push(resymgen::Check::TrimmedDescriptions);
+            }
+            if matches.is_present("incomplete symbols") {
+                checks.push(resymgen::Check::IncompleteSymbols);
+            }
+            let require_all_descriptions = matches.is_present("require descriptions");
+            let require_function_descriptions =
+                require_all_descriptions || matches.is_present("require function descriptions");
+            let require_data_descriptions =
+                require_all_descriptions || matches.is_present("require data descriptions");
+            if require_function_descriptions || require_data_descriptions {
+                checks.push(resymgen::Check::RequireDescriptions {
+                    functions: require_function_descriptions,
+                    data: require_data_descriptions,
+                });
+            }
+            if let Some(max_len_str) = matches.value_of("max description length") {
+                let max_len = max_len_str.parse::<usize>().map_err(|_| {
+                    UsageError(format!(
+                        "Invalid maximum description length: '{}'",
+                        max_len_str
+                    ))
+                })?;
+                checks.push(resymgen::Check::MaxDescriptionLength(max_len));
+            }
+            if let Some(max_len_str) = matches.value_of("max name length") {
+                let max_len = max_len_str.parse::<usize>().map_err(|_| {
+                    UsageError(format!("Invalid maximum name length: '{}'", max_len_str))
+                })?;
+                checks.push(resymgen::Check::MaxNameLength(max_len));
+            }
+            if let Some(prefixes) = matches.values_of("forbidden prefix") {
+                checks.push(resymgen::Check::ForbiddenPrefixes(
+                    prefixes.map(String::from).collect(),
+                ));
+            }
+            if let Some(regions_file) = matches.value_of("address regions") {
+                let regions =
+                    resymgen::AddressRegionSet::read(regions_file, fs::File::open(regions_file)?)?;
+                checks.push(resymgen::Check::AddressRegions(regions));
+            }
+            if matches.is_present("nonzero lengths") {
+                checks.push(resymgen::Check::NonZeroLengths {
+                    within_block: matches.is_present("lengths within block"),
+                });
+            }
+            if matches.is_present("round trip") {
+                checks.push(resymgen::Check::RoundTripStable);
+            }
+            if let Some(align_str) = matches.value_of("block alignment") {
+                let align = match align_str.strip_prefix("0x") {
+                    Some(hex) => resymgen::data_formats::symgen_yml::Uint::from_str_radix(hex, 16),
+                    None => align_str.parse::<resymgen::data_formats::symgen_yml::Uint>(),
+                }
+                .ok()
+                .filter(|a| a.is_power_of_two())
+                .ok_or_else(|| {
+                    UsageError(format!(
+                        "Invalid block alignment: '{}' (must be a power of two)",
+                        align_str
+                    ))
+                })?;
+                checks.push(resymgen::Check::BlockAlignment(align));
+            }
+            // Explicit naming conventions override those from presets
             if let Some(conv) = matches.value_of("function names") {
-                checks.push(resymgen::Check::FunctionNames(naming_convention(conv)));
+                checks.retain(|c| !matches!(c, resymgen::Check::FunctionNames(_)));
+                checks.push(resymgen::Check::FunctionNames(naming_convention(conv)?));
             }
             if let Some(conv) = matches.value_of("data names") {
-                checks.push(resymgen::Check::DataNames(naming_convention(conv)));
+                checks.retain(|c| !matches!(c, resymgen::Check::DataNames(_)));
+                checks.push(resymgen::Check::DataNames(naming_convention(conv)?));
+            }
+            if matches.is_present("check aliases") {
+                let alias_checks: Vec<_> = checks
+                    .iter()
+                    .filter_map(|c| match c {
+                        resymgen::Check::FunctionNames(conv) => {
+                            Some(resymgen::Check::FunctionAliasNames(conv.clone()))
+                        }
+                        resymgen::Check::DataNames(conv) => {
+                            Some(resymgen::Check::DataAliasNames(conv.clone()))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                checks.extend(alias_checks);
+            }
+            // Presets might overlap with each other and with individual flags
+            let mut unique_checks = Vec::with_capacity(checks.len());
+            for chk in checks {
+                if !unique_checks.contains(&chk) {
+                    unique_checks.push(chk);
+                }
+            }
+            let checks = unique_checks;
+            let input_files: Vec<_> = input_files.collect();
+            if matches.is_present("fix") {
+                resymgen::run_and_print_fixes(
+                    &input_files,
+                    &checks,
+                    recursive,
+                    int_format(
+                        matches.is_present("decimal"),
+                        matches.is_present("group digits"),
+                    ),
+                )?;
             }
             // This one handles multiple files internally so that check result printing
             // can be merged appropriately
-            if !resymgen::run_and_print_checks(input_files.collect::<Vec<_>>(), &checks, recursive)?
-            {
+            if !resymgen::run_and_print_checks(
+                input_files,
+                &checks,
+                recursive,
+                matches.value_of("only version"),
+                match matches.value_of("format").unwrap().to_lowercase().as_str() {
+                    "github" => resymgen::ReportFormat::Github,
+                    _ => resymgen::ReportFormat::Text,
+                },
+            )? {
                 return Err("Checks did not pass".into());
             }
             Ok(())
@@ -364,42 +1073,232 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
         Some("merge") => {
             let matches = matches.subcommand_matches("merge").unwrap();
 
-            let input_files: Vec<_> = matches.values_of("input").unwrap().collect();
+            let mut input_files: Vec<String> = matches
+                .values_of("input")
+                .map(|v| v.map(String::from).collect())
+                .unwrap_or_default();
+            // A bad glob pattern is reported along with the per-file merge errors, rather than
+            // aborting the whole batch
+            let mut errors = Vec::new();
+            for pattern in matches.values_of("input glob").into_iter().flatten() {
+                match resymgen::expand_glob(pattern) {
+                    Ok(files) => input_files
+                        .extend(files.into_iter().map(|p| p.to_string_lossy().into_owned())),
+                    Err(e) => errors.push((pattern.to_string(), e.into())),
+                }
+            }
             let symgen_file = matches.value_of("symgen file").unwrap();
             let input_format_name = matches.value_of("format").unwrap();
-            let input_format = resymgen::InFormat::from(input_format_name)
-                .ok_or_else(|| format!("Invalid input format: '{}'", input_format_name))?;
+            let input_format = resymgen::InFormat::from(input_format_name).ok_or_else(|| {
+                UsageError(format!("Invalid input format: '{}'", input_format_name))
+            })?;
+            let mut version_renames = HashMap::new();
+            for mapping in matches.values_of("map version").into_iter().flatten() {
+                let (old, new) = mapping
+                    .split_once('=')
+                    .filter(|(old, new)| !old.is_empty() && !new.is_empty())
+                    .ok_or_else(|| {
+                        UsageError(format!(
+                            "Invalid version mapping: '{}' (expected OLD=NEW)",
+                            mapping
+                        ))
+                    })?;
+                version_renames.insert(old.to_string(), new.to_string());
+            }
             let merge_params = resymgen::LoadParams {
                 default_block_name: matches.value_of("block").map(String::from),
                 default_symbol_type: matches.value_of("symbol type").map(symbol_type),
                 default_version_name: matches.value_of("binary version").map(String::from),
+                version_renames,
             };
-            let iformat = int_format(matches.is_present("decimal"));
+            let iformat = int_format(
+                matches.is_present("decimal"),
+                matches.is_present("group digits"),
+            );
             let fix_formatting = matches.is_present("fix formatting");
-            let unmerged_symbols = resymgen::merge_symbols(
-                &symgen_file,
+            // Validate the report format up front, before anything is merged
+            let unmerged_report = match matches.value_of("report unmerged") {
+                Some(report_file) => {
+                    let report_format = match Path::new(report_file)
+                        .extension()
+                        .and_then(|e| e.to_str())
+                    {
+                        Some("csv") => resymgen::InFormat::Csv,
+                        Some("yml") | Some("yaml") => resymgen::InFormat::Yaml,
+                        _ => {
+                            return Err(UsageError(format!(
+                                "Unsupported unmerged symbol report file: '{}' (expected a .csv or .yml file)",
+                                report_file
+                            ))
+                            .into())
+                        }
+                    };
+                    Some((report_file, report_format))
+                }
+                None => None,
+            };
+            let merge_results = resymgen::merge_symbols(
+                symgen_file,
                 &input_files,
                 input_format,
                 &merge_params,
                 iformat,
             )?;
             if fix_formatting {
-                resymgen::format_file(&symgen_file, true, iformat)?;
+                resymgen::format_file(symgen_file, true, false, iformat, 0)?;
+            }
+            if let Some((report_file, report_format)) = unmerged_report {
+                let unmerged: Vec<_> = merge_results
+                    .iter()
+                    .flatten()
+                    .flat_map(|summary| summary.unmerged.iter().cloned())
+                    .collect();
+                resymgen::write_unmerged_symbols(report_file, &unmerged, report_format, iformat)?;
+            }
+
+            let json_summary = matches.is_present("json");
+            if json_summary {
+                // Report results in input order, including files that failed to merge
+                let files: Vec<_> = input_files
+                    .iter()
+                    .zip(merge_results.iter())
+                    .map(|(fname, res)| match res {
+                        Ok(summary) => serde_json::json!({
+                            "file": fname,
+                            "added": summary.added,
+                            "merged": summary.merged,
+                            "unmerged": summary.unmerged,
+                            "blocks": summary.blocks,
+                            "error": null,
+                        }),
+                        Err(e) => serde_json::json!({
+                            "file": fname,
+                            "added": 0,
+                            "merged": 0,
+                            "unmerged": [],
+                            "blocks": {},
+                            "error": e.to_string(),
+                        }),
+                    })
+                    .collect();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({ "files": files }))?
+                );
             }
 
-            // Print the unmerged symbols from each file, with terminal colors
+            let mut summaries = Vec::with_capacity(merge_results.len());
+            for (fname, res) in input_files.iter().zip(merge_results) {
+                match res {
+                    Ok(summary) => summaries.push((fname, summary)),
+                    Err(e) => errors.push((fname.clone(), e.into())),
+                }
+            }
+
+            if !json_summary {
+                // Print the unmerged symbols from each file, with terminal colors
+                let mut stdout = StandardStream::stdout(ColorChoice::Always);
+                let mut print_unmerged_colored = || -> io::Result<()> {
+                    let mut color = ColorSpec::new();
+                    for (fname, summary) in summaries.iter() {
+                        let unmerged = &summary.unmerged;
+                        if !unmerged.is_empty() {
+                            stdout.set_color(color.set_fg(Some(Color::Yellow)))?;
+                            write!(&mut stdout, "* Unmerged symbols from \"{}\":", fname)?;
+                            stdout.reset()?;
+                            writeln!(
+                                &mut stdout,
+                                " {}",
+                                unmerged
+                                    .iter()
+                                    .map(|s| s.name.clone())
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            )?;
+                        }
+                    }
+                    stdout.reset()?;
+                    Ok(())
+                };
+                let res = print_unmerged_colored();
+                // Always try to clean up color settings before returning
+                if let Err(e) = stdout.reset() {
+                    return Err(e.into());
+                } else {
+                    res?;
+                }
+            }
+
+            if !errors.is_empty() {
+                return Err(MultiFileError {
+                    base_msg: "Failed to merge".to_string(),
+                    errors,
+                }
+                .into());
+            }
+            Ok(())
+        }
+        Some("import") => {
+            let matches = matches.subcommand_matches("import").unwrap();
+
+            let input_files: Vec<&str> = matches.values_of("input").unwrap().collect();
+            let params = resymgen::LoadParams {
+                // Blocks are always inferred from addresses
+                default_block_name: None,
+                default_symbol_type: matches.value_of("symbol type").map(symbol_type),
+                default_version_name: matches.value_of("binary version").map(String::from),
+                version_renames: HashMap::new(),
+            };
+            let results = resymgen::merge_symbols(
+                matches.value_of("symgen file").unwrap(),
+                &input_files,
+                resymgen::InFormat::Flat,
+                &params,
+                int_format(
+                    matches.is_present("decimal"),
+                    matches.is_present("group digits"),
+                ),
+            )?;
+
+            // Report where each file's symbols went, with terminal colors
+            let mut errors = Vec::new();
             let mut stdout = StandardStream::stdout(ColorChoice::Always);
-            let mut print_unmerged_colored = || -> io::Result<()> {
+            let print_import_colored = || -> io::Result<()> {
                 let mut color = ColorSpec::new();
-                for (fname, unmerged) in input_files.iter().zip(unmerged_symbols.iter()) {
-                    if !unmerged.is_empty() {
+                for (fname, res) in input_files.iter().zip(results) {
+                    let summary = match res {
+                        Ok(summary) => summary,
+                        Err(e) => {
+                            errors.push((fname.to_string(), e.into()));
+                            continue;
+                        }
+                    };
+                    stdout.set_color(color.set_fg(Some(Color::Green)))?;
+                    write!(&mut stdout, "* Imported \"{}\":", fname)?;
+                    stdout.reset()?;
+                    writeln!(
+                        &mut stdout,
+                        " {} symbol(s) ({} added, {} merged)",
+                        summary.added + summary.merged,
+                        summary.added,
+                        summary.merged
+                    )?;
+                    for (bname, n) in summary.blocks.iter() {
+                        writeln!(&mut stdout, "    {}: {}", bname, n)?;
+                    }
+                    if !summary.unmerged.is_empty() {
                         stdout.set_color(color.set_fg(Some(Color::Yellow)))?;
-                        write!(&mut stdout, "* Unmerged symbols from \"{}\":", fname)?;
+                        write!(
+                            &mut stdout,
+                            "* Symbols from \"{}\" that don't fit in any block:",
+                            fname
+                        )?;
                         stdout.reset()?;
                         writeln!(
                             &mut stdout,
                             " {}",
-                            unmerged
+                            summary
+                                .unmerged
                                 .iter()
                                 .map(|s| s.name.clone())
                                 .collect::<Vec<_>>()
@@ -410,14 +1309,117 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
                 stdout.reset()?;
                 Ok(())
             };
-            let res = print_unmerged_colored();
+            let res = print_import_colored();
             // Always try to clean up color settings before returning
-            if let Err(e) = stdout.reset() {
-                return Err(e.into());
-            } else {
-                res?;
+            stdout.reset()?;
+            res?;
+
+            if !errors.is_empty() {
+                return Err(MultiFileError {
+                    base_msg: "Failed to import".to_string(),
+                    errors,
+                }
+                .into());
+            }
+            Ok(())
+        }
+        Some("version-delta") => {
+            let matches = matches.subcommand_matches("version-delta").unwrap();
+
+            resymgen::print_version_deltas(
+                matches.value_of("input").unwrap(),
+                matches.value_of("from version").unwrap(),
+                matches.value_of("to version").unwrap(),
+            )?;
+            Ok(())
+        }
+        Some("diff") => {
+            let matches = matches.subcommand_matches("diff").unwrap();
+            let context_str = matches.value_of("context").unwrap();
+            let context = context_str.parse::<usize>().map_err(|_| {
+                UsageError(format!(
+                    "Invalid number of context symbols: '{}'",
+                    context_str
+                ))
+            })?;
+
+            if !resymgen::print_symgen_diff(
+                matches.value_of("old").unwrap(),
+                matches.value_of("new").unwrap(),
+                matches.value_of("binary version"),
+                context,
+            )? {
+                return Err("Files differ".into());
+            }
+            Ok(())
+        }
+        Some("lookup") => {
+            let matches = matches.subcommand_matches("lookup").unwrap();
+
+            let name = matches.value_of("name").unwrap();
+            if !resymgen::print_lookup(
+                matches.value_of("input").unwrap(),
+                name,
+                matches.is_present("fuzzy"),
+            )? {
+                return Err(format!("No symbol named \"{}\"", name).into());
+            }
+            Ok(())
+        }
+        Some("versions") => {
+            let matches = matches.subcommand_matches("versions").unwrap();
+
+            resymgen::print_versions(
+                matches.value_of("input").unwrap(),
+                matches.is_present("recursive"),
+            )?;
+            Ok(())
+        }
+        Some("stats") => {
+            let matches = matches.subcommand_matches("stats").unwrap();
+
+            resymgen::print_stats(
+                matches.value_of("input").unwrap(),
+                matches.is_present("json"),
+            )?;
+            Ok(())
+        }
+        Some("resolve") => {
+            let matches = matches.subcommand_matches("resolve").unwrap();
+
+            let input_files = matches.values_of("input").unwrap();
+            let mut errors = Vec::with_capacity(input_files.len());
+            for input_file in input_files {
+                if let Err(e) = resymgen::print_resolve(input_file) {
+                    errors.push((input_file.to_string(), e.into()));
+                }
+            }
+            if !errors.is_empty() {
+                return Err(MultiFileError {
+                    base_msg: "Failed to resolve subregions".to_string(),
+                    errors,
+                }
+                .into());
             }
+            Ok(())
+        }
+        Some("doctor") => {
+            let matches = matches.subcommand_matches("doctor").unwrap();
 
+            let input_files = matches.values_of("input").unwrap();
+            if !resymgen::run_and_print_doctor(input_files.collect::<Vec<_>>())? {
+                return Err("Problems were found".into());
+            }
+            Ok(())
+        }
+        #[cfg(feature = "schema")]
+        Some("schema") => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(
+                    &resymgen::data_formats::symgen_yml::SymGen::json_schema()
+                )?
+            );
             Ok(())
         }
         Some(s) => panic!("Subcommand '{}' not implemented", s), // control should never reach this point
@@ -425,10 +1427,24 @@ fn run_resymgen() -> Result<(), Box<dyn Error>> {
     }
 }
 
+/// Prints the per-phase durations recorded during the run, along with the `total` duration.
+fn print_phase_times(times: &[(&str, Duration)], total: Duration) {
+    eprintln!("timing:");
+    for (phase, duration) in times.iter().chain(iter::once(&("total", total))) {
+        eprintln!("  {:<8} {:>12}", phase, format!("{:.3?}", duration));
+    }
+}
+
 fn main() {
-    process::exit(match run_resymgen() {
+    let start = Instant::now();
+    let result = run_resymgen();
+    if let Some(times) = resymgen::phase_times() {
+        print_phase_times(&times, start.elapsed());
+    }
+    process::exit(match result {
         Ok(_) => 0,
         Err(err) => {
+            let exit_code = ErrorCategory::of(err.as_ref()).exit_code();
             let mut stderr = StandardStream::stderr(ColorChoice::Always);
             let mut print_err = || -> io::Result<()> {
                 // Print the "ERROR" in red to be eye-catching
@@ -447,7 +1463,37 @@ fn main() {
             if let Err(e) = stderr.reset() {
                 eprintln!("Internal error: {}", e);
             }
-            1
+            exit_code
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_naming_convention() {
+        assert_eq!(
+            naming_convention("SCREAMING_SNAKE_CASE").unwrap(),
+            resymgen::NamingConvention::ScreamingSnakeCase
+        );
+        assert_eq!(
+            naming_convention("pascalcase").unwrap(),
+            resymgen::NamingConvention::PascalCase
+        );
+        assert_eq!(
+            naming_convention("regex:[a-z]+").unwrap(),
+            resymgen::NamingConvention::from_regex("[a-z]+").unwrap()
+        );
+        assert!(naming_convention("unknown").is_err());
+    }
+
+    #[test]
+    fn test_naming_convention_invalid_regex() {
+        let err = naming_convention("regex:(").expect_err("invalid regex should fail");
+        assert!(err.to_string().contains("'('"));
+        assert!(validate_naming_convention("regex:[a-z".to_string()).is_err());
+        assert!(validate_naming_convention("regex:[a-z]".to_string()).is_ok());
+    }
+}
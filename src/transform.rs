@@ -6,13 +6,21 @@ use std::collections::BTreeSet;
 use std::convert::AsRef;
 use std::error::Error;
 use std::fs::{self, File};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
-use tempfile::NamedTempFile;
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use regex::Regex;
+#[cfg(feature = "manifest")]
+use sha2::{Digest, Sha256};
 
-use super::data_formats::symgen_yml::{IntFormat, LoadParams, Sort, Subregion, SymGen, Symbol};
-use super::data_formats::{Generate, InFormat, OutFormat};
-use super::util;
+use super::data_formats::ghidra_csv;
+use super::data_formats::symgen_yml::{
+    IntFormat, LoadParams, MergeSummary, Sort, Subregion, SymGen, Symbol,
+};
+use super::data_formats::{FormatOptions, InFormat, OutFormat};
+use super::util::{self, ResymgenError, UsageError};
 
 /// Forms the output file path from the base, version, and format.
 fn output_file_name(base: &Path, version: &str, format: &OutFormat) -> PathBuf {
@@ -29,27 +37,166 @@ fn output_file_name(base: &Path, version: &str, format: &OutFormat) -> PathBuf {
         .with_extension(format.extension())
 }
 
+/// A rule for transforming symbol names in generated output.
+#[derive(Debug, Clone)]
+pub enum NameTransform {
+    /// Prepends a string to each name.
+    Prefix(String),
+    /// Appends a string to each name.
+    Suffix(String),
+    /// Replaces all matches of a regular expression within each name. The replacement string can
+    /// refer to capture groups, as in [`Regex::replace_all()`].
+    Replace(Regex, String),
+}
+
+impl NameTransform {
+    /// Parses a transformation rule of the form `prefix=TEXT`, `suffix=TEXT`, or
+    /// `replace=PATTERN=REPLACEMENT`.
+    pub fn parse(rule: &str) -> Result<Self, UsageError> {
+        let invalid = |reason: &str| {
+            UsageError(format!(
+                "Invalid name transform: '{}' ({})",
+                rule.escape_debug(),
+                reason
+            ))
+        };
+        match rule.split_once('=') {
+            Some(("prefix", prefix)) => Ok(Self::Prefix(prefix.to_string())),
+            Some(("suffix", suffix)) => Ok(Self::Suffix(suffix.to_string())),
+            Some(("replace", spec)) => {
+                let (pattern, replacement) = spec
+                    .split_once('=')
+                    .ok_or_else(|| invalid("expected replace=PATTERN=REPLACEMENT"))?;
+                let re = Regex::new(pattern).map_err(|e| invalid(&e.to_string()))?;
+                Ok(Self::Replace(re, replacement.to_string()))
+            }
+            _ => Err(invalid(
+                "expected prefix=TEXT, suffix=TEXT, or replace=PATTERN=REPLACEMENT",
+            )),
+        }
+    }
+
+    /// Applies the transformation to a symbol name.
+    pub fn apply(&self, name: &str) -> String {
+        match self {
+            Self::Prefix(prefix) => format!("{}{}", prefix, name),
+            Self::Suffix(suffix) => format!("{}{}", name, suffix),
+            Self::Replace(re, replacement) => {
+                re.replace_all(name, replacement.as_str()).into_owned()
+            }
+        }
+    }
+}
+
+/// Parameters to control how symbol tables are generated from a [`SymGen`].
+#[derive(Debug, Clone, Default)]
+pub struct GenParams {
+    /// Whether to sort the function and data sections of the output symbol tables by symbol
+    /// address.
+    pub sort_output: bool,
+    /// Whether to only include symbols with a description.
+    pub documented_only: bool,
+    /// Whether to suppress the one-line summary printed for each output file written.
+    pub quiet: bool,
+    /// (block name, version name) pairs. When generating the named version, symbols in the named
+    /// block are omitted, even though the version is still generated for all other blocks.
+    /// Generation fails if there's no input block with one of the block names.
+    pub block_version_excludes: Vec<(String, String)>,
+    /// Names of the blocks to generate symbols for. If provided, symbols in all other blocks are
+    /// omitted from the output. The same list can be used for multiple input files, so names only
+    /// need to match a block in one of them; names that don't match any block are warned about.
+    pub blocks: Option<Vec<String>>,
+    /// A version to fall back on when generating other versions. Symbols without data for the
+    /// version being generated use the data for this version instead, if there is any. This is a
+    /// best-effort approximation, since the base version's addresses won't generally be correct
+    /// for other versions. Generation fails if no input block has this version.
+    pub base_version: Option<String>,
+    /// Transformations applied in order to symbol names (and deprecated aliases) in the output.
+    /// The input file is left untouched.
+    pub name_transforms: Vec<NameTransform>,
+    /// Options for output formats that support them. `format_options.quiet` is ignored in favor
+    /// of `quiet`.
+    pub format_options: FormatOptions,
+}
+
+/// Returns a copy of `symgen` without any symbols in the blocks named in `block_names`.
+fn without_block_symbols(symgen: &SymGen, block_names: &[&str]) -> SymGen {
+    let mut filtered = symgen.clone();
+    for (bname, block) in filtered.iter_mut() {
+        if block_names.contains(&bname.val.as_str()) {
+            block.retain_symbols(|_| false);
+        }
+    }
+    filtered
+}
+
 /// Generates symbol tables from a given SymGen struct for multiple different formats/versions.
+///
+/// Per-block version exclusions and base version fallbacks are applied according to `params`.
+/// Unless `params.quiet` is true, a one-line summary is printed for each output file written.
+/// Returns the paths of the output files written.
 fn generate_symbols<P: AsRef<Path>>(
     symgen: &SymGen,
     formats: &[OutFormat],
     versions: &[&str],
     output_base: P,
-) -> Result<(), Box<dyn Error>> {
-    for fmt in formats.iter() {
-        for version in versions.iter() {
-            // Write to a tempfile first, then persist atomically.
+    params: &GenParams,
+) -> Result<Vec<PathBuf>, ResymgenError> {
+    let mut written = Vec::with_capacity(formats.len() * versions.len());
+    let format_options = FormatOptions {
+        quiet: params.quiet,
+        ..params.format_options
+    };
+    for version in versions.iter() {
+        let excluded_blocks: Vec<&str> = params
+            .block_version_excludes
+            .iter()
+            .filter(|(_, v)| v == version)
+            .map(|(b, _)| b.as_str())
+            .collect();
+        let mut adjusted = None;
+        if !excluded_blocks.is_empty() {
+            adjusted = Some(without_block_symbols(symgen, &excluded_blocks));
+        }
+        if let Some(base) = params.base_version.as_deref().filter(|b| b != version) {
+            adjusted
+                .get_or_insert_with(|| symgen.clone())
+                .fill_version_from(version, base);
+        }
+        if !params.name_transforms.is_empty() {
+            adjusted
+                .get_or_insert_with(|| symgen.clone())
+                .rename_symbols(|name| {
+                    params
+                        .name_transforms
+                        .iter()
+                        .fold(name.to_string(), |name, t| t.apply(&name))
+                });
+        }
+        let symgen = adjusted.as_ref().unwrap_or(symgen);
+        for fmt in formats.iter() {
             let output_file = output_file_name(output_base.as_ref(), version, fmt);
-            let f_gen = NamedTempFile::new()?;
-            fmt.generate(&f_gen, symgen, version)?;
             // Make sure the parent directory exists first
             if let Some(parent) = output_file.parent() {
                 fs::create_dir_all(parent)?;
             }
-            util::persist_named_temp_file_safe(f_gen, output_file)?;
+            // Write to a tempfile first, then persist atomically.
+            let _timer = util::time_phase("write");
+            let f_gen = util::named_temp_file_for(&output_file)?;
+            let count = fmt.generate_with(&f_gen, symgen, version, &format_options)?;
+            util::persist_named_temp_file_safe(f_gen, &output_file)?;
+            if !params.quiet {
+                println!(
+                    "wrote {} ({} symbols, version {})",
+                    output_file.display(),
+                    count,
+                    version
+                );
+            }
+            written.push(output_file);
         }
     }
-    Ok(())
+    Ok(written)
 }
 
 /// Gets a list of all version names explicitly specified by blocks within a SymGen.
@@ -65,12 +212,75 @@ fn all_version_names(symgen: &SymGen) -> Vec<&str> {
     vers.into_iter().collect()
 }
 
+/// The names of all the blocks and versions in the input to `gen`, used to check the names given
+/// in [`GenParams`].
+#[derive(Debug, Default)]
+struct InputNames {
+    blocks: BTreeSet<String>,
+    versions: BTreeSet<String>,
+}
+
+impl InputNames {
+    fn of(symgen: &SymGen) -> Self {
+        let mut names = Self::default();
+        names.add(symgen);
+        names
+    }
+    fn add(&mut self, symgen: &SymGen) {
+        self.blocks
+            .extend(symgen.iter().map(|(bname, _)| bname.val.clone()));
+        self.versions
+            .extend(all_version_names(symgen).into_iter().map(String::from));
+    }
+}
+
+impl GenParams {
+    /// Checks that the names in the [`GenParams`] refer to blocks and versions in `names`.
+    ///
+    /// Names in `blocks` that don't match are only warned about (unless `quiet` is true), since
+    /// they don't affect the symbols that are generated.
+    fn check_names(&self, names: &InputNames) -> Result<(), UsageError> {
+        if !self.quiet {
+            for block in self.unmatched_blocks(names) {
+                eprintln!(
+                    "Warning: block \"{}\" was not found in any input file.",
+                    block
+                );
+            }
+        }
+        if let Some(base) = &self.base_version {
+            if !names.versions.contains(base) {
+                return Err(UsageError(format!("Unknown base version: '{}'", base)));
+            }
+        }
+        for (block, version) in self.block_version_excludes.iter() {
+            if !names.blocks.contains(block) {
+                return Err(UsageError(format!(
+                    "Unknown block in block version exclusion: '{}={}'",
+                    block, version
+                )));
+            }
+        }
+        Ok(())
+    }
+    /// Gets the names in `blocks` that aren't in `names`.
+    fn unmatched_blocks<'a>(&'a self, names: &InputNames) -> Vec<&'a str> {
+        self.blocks
+            .iter()
+            .flatten()
+            .filter(|b| !names.blocks.contains(*b))
+            .map(|b| b.as_str())
+            .collect()
+    }
+}
+
 /// Generates symbol tables from a given `input_file` for multiple different `output_formats` and
 /// `output_versions`.
 ///
 /// Output is written to filepaths based on `output_base`. Both `output_formats` and
-/// `output_versions` default to all formats/versions if `None`. If `sort_output` is true, the
-/// function and data sections of the output symbol tables will each be sorted by symbol address.
+/// `output_versions` default to all formats/versions if `None`. Additional configuration (like
+/// sorting, filtering, and per-block version exclusions) is specified with `params`. Returns the
+/// paths of the output files written.
 ///
 /// # Examples
 /// ```ignore
@@ -78,8 +288,8 @@ fn all_version_names(symgen: &SymGen) -> Vec<&str> {
 ///     "/path/to/symbols.yml",
 ///     Some([OutFormat::Ghidra]),
 ///     Some("v1"),
-///     false,
 ///     "/path/to/out/symbols",
+///     &GenParams::default(),
 /// )
 /// .expect("failed to generate symbol tables");
 /// ```
@@ -87,23 +297,290 @@ pub fn generate_symbol_tables<'v, I, F, V, O>(
     input_file: I,
     output_formats: Option<F>,
     output_versions: Option<V>,
-    sort_output: bool,
     output_base: O,
-) -> Result<(), Box<dyn Error>>
+    params: &GenParams,
+) -> Result<Vec<PathBuf>, ResymgenError>
 where
     I: AsRef<Path>,
     F: AsRef<[OutFormat]>,
     V: AsRef<[&'v str]>,
     O: AsRef<Path>,
 {
-    let input_file = input_file.as_ref();
+    let contents = read_collapsed(input_file.as_ref())?;
+    params.check_names(&InputNames::of(&contents))?;
+    generate_from_symgen(
+        contents,
+        output_formats,
+        output_versions,
+        output_base,
+        params,
+    )
+}
+
+/// Generates a single set of symbol tables from multiple `input_files` combined into one.
+///
+/// The input files are merged in order, as with [`SymGen::merge_symgen`]. If any input file can't
+/// be read or conflicts with the preceding ones, nothing is generated and an error is returned
+/// listing every such file. Otherwise, this behaves like [`generate_symbol_tables`] on the
+/// combined symbols.
+///
+/// # Examples
+/// ```ignore
+/// generate_combined_symbol_tables(
+///     ["/path/to/arm9.yml", "/path/to/overlay00.yml"],
+///     Some([OutFormat::Ghidra]),
+///     Some("v1"),
+///     "/path/to/out/combined",
+///     &GenParams::default(),
+/// )
+/// .expect("failed to generate symbol tables");
+/// ```
+pub fn generate_combined_symbol_tables<'v, P, I, F, V, O>(
+    input_files: I,
+    output_formats: Option<F>,
+    output_versions: Option<V>,
+    output_base: O,
+    params: &GenParams,
+) -> Result<Vec<PathBuf>, ResymgenError>
+where
+    P: AsRef<Path>,
+    I: AsRef<[P]>,
+    F: AsRef<[OutFormat]>,
+    V: AsRef<[&'v str]>,
+    O: AsRef<Path>,
+{
+    let contents = combine_symgens(input_files.as_ref())?;
+    params.check_names(&InputNames::of(&contents))?;
+    generate_from_symgen(
+        contents,
+        output_formats,
+        output_versions,
+        output_base,
+        params,
+    )
+}
+
+/// Generates symbol tables separately for each of multiple `input_files`, as with
+/// [`generate_symbol_tables`].
+///
+/// Output for each input file is written to filepaths based on its file stem within
+/// `output_dir`. Up to `jobs` input files are processed concurrently; if `jobs` is 0 or 1, they
+/// are processed one at a time, in order. Every file is processed even if an earlier one fails,
+/// so that all failures can be reported at once in a [`util::MultiFileError`], in the same order
+/// as `input_files`. Returns the paths of the output files written, in the same order as
+/// `input_files`.
+///
+/// All the input files are read before anything is generated. Names in `params` are checked
+/// against all the input files together, so a name only needs to match in one of them.
+///
+/// The number of files processed concurrently is also capped by the size of the global `rayon`
+/// thread pool. Phase durations recorded by [`util::time_phase`] on worker threads are added to
+/// those of the calling thread.
+///
+/// # Examples
+/// ```ignore
+/// generate_symbol_tables_many(
+///     ["/path/to/arm9.yml", "/path/to/overlay00.yml"],
+///     Some([OutFormat::Ghidra]),
+///     Some("v1"),
+///     "/path/to/out",
+///     &GenParams::default(),
+///     4,
+/// )
+/// .expect("failed to generate symbol tables");
+/// ```
+pub fn generate_symbol_tables_many<'v, P, I, F, V, O>(
+    input_files: I,
+    output_formats: Option<F>,
+    output_versions: Option<V>,
+    output_dir: O,
+    params: &GenParams,
+    jobs: usize,
+) -> Result<Vec<PathBuf>, ResymgenError>
+where
+    P: AsRef<Path> + Sync,
+    I: AsRef<[P]>,
+    F: AsRef<[OutFormat]> + Sync,
+    V: AsRef<[&'v str]> + Sync,
+    O: AsRef<Path> + Sync,
+{
+    // Read every file before generating anything, so that the names in `params` can be checked
+    // against all the input files at once
+    let input_files: Vec<&Path> = input_files.as_ref().iter().map(|f| f.as_ref()).collect();
+    let read_results = map_jobs(input_files.clone(), jobs, |input_file| {
+        read_collapsed(input_file).map_err(Box::<dyn Error + Send + Sync>::from)
+    })?;
+
+    let mut names = InputNames::default();
+    let mut results = Vec::with_capacity(input_files.len());
+    let mut to_generate = Vec::with_capacity(input_files.len());
+    for (i, result) in read_results.into_iter().enumerate() {
+        match result {
+            Ok(contents) => {
+                names.add(&contents);
+                to_generate.push((i, contents));
+                results.push(Ok(Vec::new()));
+            }
+            Err(e) => results.push(Err(e)),
+        }
+    }
+    // An unreadable file might have contained the names that seem to be missing
+    if results.iter().all(|r| r.is_ok()) {
+        params.check_names(&names)?;
+    }
+
+    let gen_results = map_jobs(to_generate, jobs, |(i, contents)| {
+        let gen_file = || -> Result<Vec<PathBuf>, ResymgenError> {
+            let input_file_stem = input_files[i].file_stem().ok_or("Empty input file name")?;
+            generate_from_symgen(
+                contents,
+                output_formats.as_ref().map(|f| f.as_ref()),
+                output_versions.as_ref().map(|v| v.as_ref()),
+                output_dir.as_ref().join(input_file_stem),
+                params,
+            )
+        };
+        (i, gen_file().map_err(Box::<dyn Error + Send + Sync>::from))
+    })?;
+    for (i, result) in gen_results {
+        results[i] = result;
+    }
+
+    let mut written = Vec::new();
+    let mut errors = Vec::new();
+    for (input_file, result) in input_files.iter().zip(results) {
+        match result {
+            Ok(files) => written.extend(files),
+            Err(e) => errors.push((input_file.display().to_string(), e)),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(util::MultiFileError {
+            base_msg: "Failed to generate symbols".to_string(),
+            errors,
+        }
+        .into());
+    }
+    Ok(written)
+}
+
+/// Applies `f` to each of `items`, running up to `jobs` calls concurrently (capped by the size of
+/// the global `rayon` thread pool). If `jobs` is 0 or 1, the calls are made one at a time, in
+/// order. Returns the results in the same order as `items`.
+///
+/// Phase durations recorded by [`util::time_phase`] on worker threads are added to those of the
+/// calling thread.
+fn map_jobs<T, R, F>(items: Vec<T>, jobs: usize, f: F) -> Result<Vec<R>, ResymgenError>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    if jobs <= 1 {
+        return Ok(items.into_iter().map(f).collect());
+    }
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(jobs.min(rayon::current_num_threads()))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let timing = util::phase_times().is_some();
+    Ok(pool
+        .install(|| {
+            items
+                .into_par_iter()
+                .map(|item| {
+                    if timing {
+                        util::enable_timing();
+                    }
+                    let result = f(item);
+                    (result, util::take_phase_times())
+                })
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+        .map(|(result, times)| {
+            if let Some(times) = times {
+                util::record_phase_times(&times);
+            }
+            result
+        })
+        .collect())
+}
+
+/// Reads a `resymgen` YAML file along with all its subregion files, and collapses them into a
+/// single [`SymGen`].
+fn read_collapsed(input_file: &Path) -> Result<SymGen, ResymgenError> {
     let mut contents = {
+        let _timer = util::time_phase("read");
         let file = File::open(input_file)?;
         SymGen::read(&file)?
     };
+    let _timer = util::time_phase("resolve");
     contents.resolve_subregions(Subregion::subregion_dir(input_file), |p| File::open(p))?;
     contents.collapse_subregions();
-    if sort_output {
+    Ok(contents)
+}
+
+/// Reads and merges multiple `resymgen` YAML files (along with their subregion files) into a
+/// single [`SymGen`], in order.
+///
+/// Every file is processed even if an earlier one fails, so that all failures can be reported at
+/// once in a [`util::MultiFileError`].
+fn combine_symgens<P: AsRef<Path>>(input_files: &[P]) -> Result<SymGen, ResymgenError> {
+    let mut combined: Option<SymGen> = None;
+    let mut errors = Vec::new();
+    for input_file in input_files {
+        let input_file = input_file.as_ref();
+        let combine_file = |combined: &mut Option<SymGen>| -> Result<(), ResymgenError> {
+            let contents = read_collapsed(input_file)?;
+            match combined {
+                // A failed merge leaves the combined contents unchanged
+                Some(c) => {
+                    c.merge_symgen(&contents)?;
+                }
+                None => *combined = Some(contents),
+            }
+            Ok(())
+        };
+        if let Err(e) = combine_file(&mut combined) {
+            errors.push((input_file.display().to_string(), e.into()));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(util::MultiFileError {
+            base_msg: "Failed to combine input files".to_string(),
+            errors,
+        }
+        .into());
+    }
+    combined.ok_or_else(|| "No input files to combine".into())
+}
+
+/// Generates symbol tables from already-read `contents`. See [`generate_symbol_tables`].
+fn generate_from_symgen<'v, F, V, O>(
+    mut contents: SymGen,
+    output_formats: Option<F>,
+    output_versions: Option<V>,
+    output_base: O,
+    params: &GenParams,
+) -> Result<Vec<PathBuf>, ResymgenError>
+where
+    F: AsRef<[OutFormat]>,
+    V: AsRef<[&'v str]>,
+    O: AsRef<Path>,
+{
+    if params.documented_only {
+        contents.retain_symbols(Symbol::is_documented);
+    }
+    if let Some(blocks) = &params.blocks {
+        for (bname, block) in contents.iter_mut() {
+            if !blocks.contains(&bname.val) {
+                block.retain_symbols(|_| false);
+            }
+        }
+    }
+    if params.sort_output {
+        let _timer = util::time_phase("sort");
         contents.sort();
     }
 
@@ -116,22 +593,105 @@ where
         None => Cow::Owned(all_version_names(&contents)),
     };
 
-    generate_symbols(&contents, &formats, &versions, output_base)
+    generate_symbols(&contents, &formats, &versions, output_base, params)
+}
+
+/// Writes a manifest of SHA-256 hashes for a list of `output_files` (e.g., as returned by
+/// [`generate_symbol_tables`]) to `manifest_file`.
+///
+/// The manifest lists one file per line, sorted by path, in the same format as the output of
+/// `sha256sum`, so it can be verified with `sha256sum -c` from the same working directory.
+///
+/// # Examples
+/// ```ignore
+/// let written = generate_symbol_tables(
+///     "/path/to/symbols.yml",
+///     None::<&[OutFormat]>,
+///     None::<&[&str]>,
+///     "/path/to/out/symbols",
+///     &GenParams::default(),
+/// )
+/// .expect("failed to generate symbol tables");
+/// write_manifest("/path/to/out/manifest.sha256", &written).expect("failed to write manifest");
+/// ```
+#[cfg(feature = "manifest")]
+pub fn write_manifest<M, P>(manifest_file: M, output_files: &[P]) -> Result<(), ResymgenError>
+where
+    M: AsRef<Path>,
+    P: AsRef<Path>,
+{
+    let mut files: Vec<&Path> = output_files.iter().map(|p| p.as_ref()).collect();
+    files.sort_unstable();
+    files.dedup();
+
+    if let Some(parent) = manifest_file.as_ref().parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut f_manifest = util::named_temp_file_for(&manifest_file)?;
+    for file in files {
+        let digest = Sha256::digest(fs::read(file)?);
+        for byte in digest {
+            write!(f_manifest, "{:02x}", byte)?;
+        }
+        writeln!(f_manifest, "  {}", file.display())?;
+    }
+    util::persist_named_temp_file_safe(f_manifest, manifest_file)?;
+    Ok(())
 }
 
+/// Writes a plain list of `output_files` (e.g., as returned by [`generate_symbol_tables`]) to
+/// `list_file`.
+///
+/// The list contains one file per line, sorted by path. Files within the directory containing
+/// `list_file` are listed relative to that directory.
+pub fn write_file_list<L, P>(list_file: L, output_files: &[P]) -> Result<(), ResymgenError>
+where
+    L: AsRef<Path>,
+    P: AsRef<Path>,
+{
+    let list_dir = list_file.as_ref().parent().unwrap_or_else(|| Path::new(""));
+    let mut files: Vec<&Path> = output_files
+        .iter()
+        .map(|p| p.as_ref())
+        .map(|p| p.strip_prefix(list_dir).unwrap_or(p))
+        .collect();
+    files.sort_unstable();
+    files.dedup();
+
+    fs::create_dir_all(list_dir)?;
+    let mut f_list = util::named_temp_file_for(&list_file)?;
+    for file in files {
+        writeln!(f_list, "{}", file.display())?;
+    }
+    util::persist_named_temp_file_safe(f_list, list_file)?;
+    Ok(())
+}
+
+/// The result of merging a single input file: either a summary of the merge (including the symbols
+/// that could not be merged), or the error that caused the merge to fail.
+pub type MergeResult = Result<MergeSummary, ResymgenError>;
+
 /// Merges symbols from a collection of `input_files` of the format `input_format` into a given
 /// `symgen_file`.
 ///
 /// Additional configuration is specified with `merge_params`. Integers are written in `int_format`.
 ///
+/// Input files are merged in order. A failure to merge one input file does not abort the whole
+/// batch; that file's changes are discarded, and the error is returned in its slot of the result,
+/// while the other files are still merged. On success, each input file's slot contains a
+/// [`MergeSummary`] with the number of symbols added and merged, and the symbols from that file
+/// that could not be merged. The outer [`Result`] is only an [`Err`] if
+/// the `symgen_file` itself can't be read or written.
+///
 /// # Examples
 /// ```ignore
 /// let params = LoadParams {
 ///     default_block_name: None,
 ///     default_symbol_type: None,
 ///     default_version_name: Some("v1".into()),
+///     version_renames: HashMap::new(),
 /// };
-/// merge_symbols(
+/// let results = merge_symbols(
 ///     "/path/to/symbols.yml",
 ///     ["/path/to/input.csv"],
 ///     InFormat::Csv,
@@ -139,6 +699,7 @@ where
 ///     IntFormat::Hexadecimal,
 /// )
 /// .expect("failed to merge symbols");
+/// let unmerged = &results[0].as_ref().expect("failed to merge input.csv").unmerged;
 /// ```
 pub fn merge_symbols<P, P2, I>(
     symgen_file: P,
@@ -146,7 +707,7 @@ pub fn merge_symbols<P, P2, I>(
     input_format: InFormat,
     merge_params: &LoadParams,
     int_format: IntFormat,
-) -> Result<Vec<Vec<Symbol>>, Box<dyn Error>>
+) -> Result<Vec<MergeResult>, ResymgenError>
 where
     P: AsRef<Path>,
     P2: AsRef<Path>,
@@ -154,29 +715,69 @@ where
 {
     let symgen_file = symgen_file.as_ref();
     let mut contents = {
+        let _timer = util::time_phase("read");
         let file = File::open(symgen_file)?;
         SymGen::read(&file)?
     };
-    contents.resolve_subregions(Subregion::subregion_dir(symgen_file), |p| File::open(p))?;
+    {
+        let _timer = util::time_phase("resolve");
+        contents.resolve_subregions(Subregion::subregion_dir(symgen_file), |p| File::open(p))?;
+    }
 
-    let mut unmerged_symbols = Vec::with_capacity(input_files.as_ref().len());
+    let mut results = Vec::with_capacity(input_files.as_ref().len());
     for input_name in input_files.as_ref() {
-        let input = File::open(input_name)?;
-        unmerged_symbols.push(input_format.merge(
-            &mut contents,
-            input,
-            Some(input_name),
-            merge_params,
-        )?);
+        // A failed merge rolls itself back, so it doesn't leave partial changes behind
+        let mut merge_file = || -> MergeResult {
+            let _timer = util::time_phase("merge");
+            let input = File::open(input_name)?;
+            Ok(input_format.merge(&mut contents, input, Some(input_name), merge_params)?)
+        };
+        results.push(merge_file());
     }
 
-    util::symgen_write_recursive(&contents, symgen_file, int_format)?;
-    Ok(unmerged_symbols)
+    util::symgen_write_recursive(&contents, symgen_file, int_format, 0)?;
+    Ok(results)
+}
+
+/// Writes `unmerged` symbols (as reported in a [`MergeSummary`]) to `output_file`, in a format
+/// that can be merged again with `format` after the symbols have been fixed up.
+///
+/// Only [`InFormat::Csv`] and [`InFormat::Yaml`] are supported. Since unmerged symbols don't
+/// belong to any block, [`InFormat::Yaml`] output is a bare symbol list (with each symbol's type
+/// recorded), meant to be pasted into the appropriate block. [`InFormat::Csv`] output skips
+/// symbols without a recorded type. Integers are written in `int_format`.
+pub fn write_unmerged_symbols<P: AsRef<Path>>(
+    output_file: P,
+    unmerged: &[Symbol],
+    format: InFormat,
+    int_format: IntFormat,
+) -> Result<(), ResymgenError> {
+    let _timer = util::time_phase("write");
+    let file = File::create(output_file)?;
+    match format {
+        InFormat::Csv => ghidra_csv::write_symbols(
+            file,
+            unmerged
+                .iter()
+                .filter_map(|s| s.stype.map(|stype| (s, stype))),
+        )?,
+        InFormat::Yaml => SymGen::write_symbols(file, unmerged, int_format)?,
+        _ => {
+            return Err(format!(
+                "unmerged symbols can't be written in '{}' format",
+                format.extension()
+            )
+            .into())
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::data_formats::symgen_yml::SymbolType;
     use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn test_output_file_name() {
@@ -191,7 +792,7 @@ mod tests {
         ];
         for ((base, version, format), exp) in cases {
             assert_eq!(
-                output_file_name(&Path::new(base), version, &format),
+                output_file_name(Path::new(base), version, &format),
                 Path::new(exp)
             );
         }
@@ -240,4 +841,842 @@ mod tests {
 
         assert_eq!(all_version_names(&s), Vec::<&str>::new());
     }
+
+    #[test]
+    fn test_combine_symgens() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let write = |name: &str, contents: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, contents).expect("Failed to write file");
+            path
+        };
+        let arm9 = write(
+            "arm9.yml",
+            r"arm9:
+  address: 0x2000000
+  length: 0x1000
+  functions:
+    - name: fn1
+      address: 0x2000100
+      description: original
+  data: []
+",
+        );
+        let arm9_more = write(
+            "arm9_more.yml",
+            r"arm9:
+  address: 0x2000000
+  length: 0x1000
+  functions:
+    - name: fn2
+      address: 0x2000200
+  data: []
+",
+        );
+        let overlay = write(
+            "overlay.yml",
+            r"overlay:
+  address: 0x2100000
+  length: 0x1000
+  functions: []
+  data:
+    - name: data1
+      address: 0x2100100
+",
+        );
+        let conflict = write(
+            "conflict.yml",
+            r"arm9:
+  address: 0x2000000
+  length: 0x1000
+  functions:
+    - name: fn1
+      address: 0x2000100
+      description: conflicting
+  data: []
+",
+        );
+        let missing = dir.path().join("missing.yml");
+
+        let combined = combine_symgens(&[&arm9, &arm9_more, &overlay]).expect("Failed to combine");
+        let names: Vec<&str> = combined.symbols().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["fn1", "fn2", "data1"]);
+
+        let err = combine_symgens(&[&arm9, &conflict, &missing, &overlay])
+            .expect_err("Combine should fail");
+        let err = match err {
+            ResymgenError::MultiFile(err) => err,
+            err => panic!("Expected a MultiFileError, got {:?}", err),
+        };
+        let failed: Vec<&str> = err.errors.iter().map(|(f, _)| f.as_str()).collect();
+        assert_eq!(
+            failed,
+            vec![
+                conflict.display().to_string().as_str(),
+                missing.display().to_string().as_str()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_block_version_excludes() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let input_file = dir.path().join("symbols.yml");
+        fs::write(
+            &input_file,
+            r"main:
+  versions:
+    - v1
+    - v2
+  address: 0x2000000
+  length: 0x1000
+  functions:
+    - name: fn1
+      address: 0x2000000
+  data: []
+overlay:
+  versions:
+    - v1
+    - v2
+  address: 0x2100000
+  length: 0x1000
+  functions:
+    - name: fn2
+      address: 0x2100000
+  data: []
+",
+        )
+        .expect("Failed to write file");
+        let params = GenParams {
+            quiet: true,
+            block_version_excludes: vec![("overlay".to_string(), "v2".to_string())],
+            ..Default::default()
+        };
+        let written = generate_symbol_tables(
+            &input_file,
+            Some([OutFormat::Sym]),
+            None::<&[&str]>,
+            dir.path().join("out"),
+            &params,
+        )
+        .expect("Generation failed");
+        assert_eq!(
+            written,
+            vec![dir.path().join("out_v1.sym"), dir.path().join("out_v2.sym")]
+        );
+        let contents = |f: &Path| fs::read_to_string(f).expect("Failed to read file");
+        assert_eq!(contents(&written[0]), "02000000 fn1\n02100000 fn2\n");
+        assert_eq!(contents(&written[1]), "02000000 fn1\n");
+
+        let params = GenParams {
+            quiet: true,
+            block_version_excludes: vec![("overlay2".to_string(), "v2".to_string())],
+            ..Default::default()
+        };
+        match generate_symbol_tables(
+            &input_file,
+            Some([OutFormat::Sym]),
+            None::<&[&str]>,
+            dir.path().join("out"),
+            &params,
+        ) {
+            Err(ResymgenError::Usage(e)) => assert_eq!(
+                e.to_string(),
+                "Unknown block in block version exclusion: 'overlay2=v2'"
+            ),
+            res => panic!("Expected a UsageError, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_generate_blocks() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let input_file = dir.path().join("symbols.yml");
+        fs::write(
+            &input_file,
+            r"main:
+  address: 0x2000000
+  length: 0x1000
+  functions:
+    - name: fn1
+      address: 0x2000000
+  data:
+    - name: DATA1
+      address: 0x2000800
+overlay1:
+  address: 0x2100000
+  length: 0x1000
+  functions:
+    - name: fn2
+      address: 0x2100000
+  data:
+    - name: DATA2
+      address: 0x2100800
+overlay2:
+  address: 0x2200000
+  length: 0x1000
+  functions:
+    - name: fn3
+      address: 0x2200000
+  data: []
+",
+        )
+        .expect("Failed to write file");
+        let gen = |blocks: &[&str]| {
+            let params = GenParams {
+                quiet: true,
+                blocks: Some(blocks.iter().map(|b| b.to_string()).collect()),
+                ..Default::default()
+            };
+            let written = generate_symbol_tables(
+                &input_file,
+                Some([OutFormat::Sym]),
+                Some([""]),
+                dir.path().join("out"),
+                &params,
+            )
+            .expect("Generation failed");
+            fs::read_to_string(&written[0]).expect("Failed to read file")
+        };
+        assert_eq!(
+            gen(&["main", "overlay2"]),
+            "02000000 fn1\n02000800 DATA1\n02200000 fn3\n"
+        );
+        assert_eq!(
+            gen(&["overlay1", "missing"]),
+            "02100000 fn2\n02100800 DATA2\n"
+        );
+        assert_eq!(gen(&[]), "");
+    }
+
+    #[test]
+    fn test_unmatched_blocks() {
+        let mut names = InputNames::default();
+        for yaml in [
+            "main:\n  address: 0x0\n  length: 0x100\n  functions: []\n  data: []\n",
+            "overlay:\n  address: 0x100\n  length: 0x100\n  functions: []\n  data: []\n",
+        ] {
+            names.add(&SymGen::read(yaml.as_bytes()).expect("Read failed"));
+        }
+        let params = GenParams {
+            blocks: Some(vec![
+                "overlay".to_string(),
+                "missing".to_string(),
+                "main".to_string(),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(params.unmatched_blocks(&names), ["missing"]);
+        assert!(GenParams::default().unmatched_blocks(&names).is_empty());
+    }
+
+    #[test]
+    fn test_generate_base_version() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let input_file = dir.path().join("symbols.yml");
+        fs::write(
+            &input_file,
+            r"main:
+  versions:
+    - NA
+    - JP
+  address: 0x2000000
+  length: 0x1000
+  functions:
+    - name: fn1
+      address:
+        NA: 0x2000000
+        JP: 0x2000010
+    - name: fn2
+      address:
+        NA: 0x2000100
+  data: []
+",
+        )
+        .expect("Failed to write file");
+        let params = GenParams {
+            quiet: true,
+            base_version: Some("NA".to_string()),
+            ..Default::default()
+        };
+        let written = generate_symbol_tables(
+            &input_file,
+            Some([OutFormat::Sym]),
+            Some(["JP", "NA"]),
+            dir.path().join("out"),
+            &params,
+        )
+        .expect("Generation failed");
+        let contents = |f: &Path| fs::read_to_string(f).expect("Failed to read file");
+        assert_eq!(contents(&written[0]), "02000010 fn1\n02000100 fn2\n");
+        assert_eq!(contents(&written[1]), "02000000 fn1\n02000100 fn2\n");
+    }
+
+    #[test]
+    fn test_generate_unknown_base_version() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let input_files: Vec<PathBuf> = ["NA", "JP"]
+            .iter()
+            .map(|v| {
+                let input_file = dir.path().join(format!("symbols_{}.yml", v));
+                fs::write(
+                    &input_file,
+                    format!(
+                        "main:\n  versions:\n    - {}\n  address: 0x0\n  length: 0x100\n  functions: []\n  data: []\n",
+                        v
+                    ),
+                )
+                .expect("Failed to write file");
+                input_file
+            })
+            .collect();
+        let params = |base: &str| GenParams {
+            quiet: true,
+            base_version: Some(base.to_string()),
+            ..Default::default()
+        };
+        let out_dir = dir.path().join("out");
+        match generate_symbol_tables(
+            &input_files[0],
+            Some([OutFormat::Sym]),
+            None::<&[&str]>,
+            out_dir.join("symbols"),
+            &params("EU"),
+        ) {
+            Err(ResymgenError::Usage(e)) => assert_eq!(e.to_string(), "Unknown base version: 'EU'"),
+            res => panic!("Expected a UsageError, got {:?}", res),
+        }
+        assert!(matches!(
+            generate_symbol_tables_many(
+                &input_files,
+                Some([OutFormat::Sym]),
+                None::<&[&str]>,
+                &out_dir,
+                &params("EU"),
+                2,
+            ),
+            Err(ResymgenError::Usage(_))
+        ));
+        assert!(!out_dir.exists());
+        // The base version only needs to be in one of the input files
+        generate_symbol_tables_many(
+            &input_files,
+            Some([OutFormat::Sym]),
+            None::<&[&str]>,
+            &out_dir,
+            &params("JP"),
+            2,
+        )
+        .expect("Generation failed");
+    }
+
+    #[test]
+    fn test_generate_many_parallel() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let input_files: Vec<PathBuf> = (0..4)
+            .map(|i| {
+                let input_file = dir.path().join(format!("symbols{}.yml", i));
+                fs::write(
+                    &input_file,
+                    format!(
+                        r"main:
+  versions:
+    - v1
+    - v2
+  address: 0x2000000
+  length: 0x1000
+  functions:
+    - name: fn{0}
+      address:
+        v1: 0x20000{0}0
+        v2: 0x20001{0}0
+  data: []
+",
+                        i
+                    ),
+                )
+                .expect("Failed to write file");
+                input_file
+            })
+            .collect();
+        let params = GenParams {
+            quiet: true,
+            ..Default::default()
+        };
+        let gen = |out: &str, jobs| {
+            generate_symbol_tables_many(
+                &input_files,
+                Some([OutFormat::Sym, OutFormat::Ghidra]),
+                None::<&[&str]>,
+                dir.path().join(out),
+                &params,
+                jobs,
+            )
+            .expect("Generation failed")
+        };
+        let serial = gen("serial", 1);
+        // Phase durations from the worker threads should be recorded on this thread
+        util::enable_timing();
+        let parallel = gen("parallel", 3);
+        assert!(util::phase_times()
+            .expect("timing is disabled")
+            .iter()
+            .any(|&(phase, _)| phase == "write"));
+        assert_eq!(serial.len(), 16);
+        assert_eq!(
+            serial
+                .iter()
+                .map(|f| f.strip_prefix(dir.path().join("serial")).unwrap())
+                .collect::<Vec<_>>(),
+            parallel
+                .iter()
+                .map(|f| f.strip_prefix(dir.path().join("parallel")).unwrap())
+                .collect::<Vec<_>>()
+        );
+        for (s, p) in serial.iter().zip(parallel.iter()) {
+            assert_eq!(
+                fs::read(s).expect("Failed to read file"),
+                fs::read(p).expect("Failed to read file")
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_many_parallel_errors() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let valid = dir.path().join("valid.yml");
+        fs::write(
+            &valid,
+            "main:\n  address: 0x0\n  length: 0x100\n  functions: []\n  data: []\n",
+        )
+        .expect("Failed to write file");
+        let invalid = dir.path().join("invalid.yml");
+        fs::write(&invalid, "main: [").expect("Failed to write file");
+        let input_files = [
+            dir.path().join("missing.yml"),
+            valid,
+            invalid,
+            dir.path().join("also_missing.yml"),
+        ];
+        let params = GenParams {
+            quiet: true,
+            ..Default::default()
+        };
+        let errors = |jobs| match generate_symbol_tables_many(
+            &input_files,
+            Some([OutFormat::Sym]),
+            None::<&[&str]>,
+            dir.path().join("out"),
+            &params,
+            jobs,
+        ) {
+            Err(ResymgenError::MultiFile(e)) => e
+                .errors
+                .iter()
+                .map(|(f, e)| {
+                    (
+                        f.clone(),
+                        e.to_string(),
+                        util::ErrorCategory::of(e.as_ref()),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            res => panic!("Expected a MultiFileError, got {:?}", res),
+        };
+        let serial = errors(1);
+        assert_eq!(
+            serial.iter().map(|(f, _, _)| f.clone()).collect::<Vec<_>>(),
+            [&input_files[0], &input_files[2], &input_files[3]]
+                .iter()
+                .map(|f| f.display().to_string())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(serial[0].2, util::ErrorCategory::Io);
+        assert_eq!(serial[1].2, util::ErrorCategory::Failure);
+        assert_eq!(errors(4), serial);
+    }
+
+    #[test]
+    fn test_name_transform() {
+        let transform = |rule| NameTransform::parse(rule).expect("Failed to parse rule");
+        assert_eq!(transform("prefix=pmd_").apply("Foo"), "pmd_Foo");
+        assert_eq!(transform("suffix=_v2").apply("Foo"), "Foo_v2");
+        assert_eq!(transform("replace=::=__").apply("a::b::c"), "a__b__c");
+        assert_eq!(
+            transform(r"replace=^(\w+)Func$=${1}_fn").apply("DoFunc"),
+            "Do_fn"
+        );
+        assert!(NameTransform::parse("prefix").is_err());
+        assert!(NameTransform::parse("upper=x").is_err());
+        assert!(NameTransform::parse("replace=abc").is_err());
+        assert!(NameTransform::parse("replace=(=x").is_err());
+    }
+
+    #[test]
+    fn test_generate_name_transforms() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let input_file = dir.path().join("symbols.yml");
+        let input = r"main:
+  versions:
+    - NA
+  address: 0x2000000
+  length: 0x1000
+  functions:
+    - name: Ns::Fn1
+      address: 0x2000000
+  data: []
+";
+        fs::write(&input_file, input).expect("Failed to write file");
+        let params = GenParams {
+            quiet: true,
+            name_transforms: vec![
+                NameTransform::parse("replace=::=_").unwrap(),
+                NameTransform::parse("prefix=pmd_").unwrap(),
+            ],
+            ..Default::default()
+        };
+        let written = generate_symbol_tables(
+            &input_file,
+            Some([OutFormat::Sym]),
+            Some(["NA"]),
+            dir.path().join("out"),
+            &params,
+        )
+        .expect("Generation failed");
+        assert_eq!(
+            fs::read_to_string(&written[0]).expect("Failed to read file"),
+            "02000000 pmd_Ns_Fn1\n"
+        );
+        // The input file is left untouched
+        assert_eq!(
+            fs::read_to_string(&input_file).expect("Failed to read file"),
+            input
+        );
+    }
+
+    #[test]
+    fn test_generate_first_address_only() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let input_file = dir.path().join("symbols.yml");
+        fs::write(
+            &input_file,
+            r"main:
+  address: 0x2000000
+  length: 0x1000
+  functions:
+    - name: fn1
+      address:
+        - 0x2000100
+        - 0x2000000
+  data: []
+",
+        )
+        .expect("Failed to write file");
+        let mut params = GenParams {
+            quiet: true,
+            ..Default::default()
+        };
+        let generate = |params: &GenParams| {
+            generate_symbol_tables(
+                &input_file,
+                Some([OutFormat::Ld]),
+                Some([""]),
+                dir.path().join("out"),
+                params,
+            )
+        };
+        assert!(generate(&params).is_err());
+
+        params.format_options.first_address_only = true;
+        let written = generate(&params).expect("Generation failed");
+        assert_eq!(
+            fs::read_to_string(&written[0]).expect("Failed to read file"),
+            "PROVIDE(fn1 = 0x2000100);\n"
+        );
+    }
+
+    #[test]
+    fn test_merge_symbols_continues_after_failure() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let symgen_file = dir.path().join("symbols.yml");
+        fs::write(
+            &symgen_file,
+            r"main:
+  versions:
+    - v1
+  address: 0x2000000
+  length: 0x1000
+  functions: []
+  data: []
+",
+        )
+        .expect("Failed to write file");
+        let inputs = [
+            dir.path().join("bad.csv"),
+            dir.path().join("good.csv"),
+            dir.path().join("missing.csv"),
+        ];
+        fs::write(
+            &inputs[0],
+            "Name,Location,Type\nbad,not an address,Function\n",
+        )
+        .expect("Failed to write file");
+        fs::write(&inputs[1], "Name,Location,Type\nfn1,02000100,Function\n")
+            .expect("Failed to write file");
+        let params = LoadParams {
+            default_block_name: None,
+            default_symbol_type: None,
+            default_version_name: Some("v1".into()),
+            version_renames: HashMap::new(),
+        };
+
+        let results = merge_symbols(
+            &symgen_file,
+            &inputs,
+            InFormat::Csv,
+            &params,
+            IntFormat::Hexadecimal,
+        )
+        .expect("Merge failed");
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_err());
+        let summary = results[1].as_ref().expect("Merge failed");
+        assert_eq!(summary.added, 1);
+        assert_eq!(summary.merged, 0);
+        assert!(summary.unmerged.is_empty());
+        assert!(results[2].is_err());
+
+        let merged = SymGen::read(File::open(&symgen_file).expect("Failed to open file"))
+            .expect("Read failed");
+        let names: Vec<_> = merged.symbols_realized("v1").map(|s| s.name).collect();
+        assert_eq!(names, vec!["fn1"]);
+    }
+
+    #[test]
+    fn test_merge_symbols_version_renames() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let symgen_file = dir.path().join("symbols.yml");
+        fs::write(
+            &symgen_file,
+            r"main:
+  versions:
+    - NA
+  address: 0x2000000
+  length: 0x1000
+  functions: []
+  data: []
+",
+        )
+        .expect("Failed to write file");
+        let input = dir.path().join("input.csv");
+        fs::write(&input, "Name,Location,Type\nfn1,02000100,Function\n")
+            .expect("Failed to write file");
+        let params = LoadParams {
+            default_block_name: None,
+            default_symbol_type: None,
+            default_version_name: Some("US".into()),
+            version_renames: [("US".to_string(), "NA".to_string())].into(),
+        };
+
+        let results = merge_symbols(
+            &symgen_file,
+            [&input],
+            InFormat::Csv,
+            &params,
+            IntFormat::Hexadecimal,
+        )
+        .expect("Merge failed");
+        assert!(results[0].is_ok());
+
+        let merged = SymGen::read(File::open(&symgen_file).expect("Failed to open file"))
+            .expect("Read failed");
+        let names: Vec<_> = merged.symbols_realized("NA").map(|s| s.name).collect();
+        assert_eq!(names, vec!["fn1"]);
+        assert_eq!(merged.symbols_realized("US").count(), 0);
+    }
+
+    #[test]
+    fn test_merge_symbols_flat() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let symgen_file = dir.path().join("symbols.yml");
+        fs::write(
+            &symgen_file,
+            r"main:
+  address: 0x2000000
+  length: 0x1000
+  functions: []
+  data: []
+overlay:
+  address: 0x2100000
+  length: 0x1000
+  functions: []
+  data: []
+",
+        )
+        .expect("Failed to write file");
+        let input = dir.path().join("dump.txt");
+        fs::write(
+            &input,
+            "name,address\nfn1,02000100\nfn2,02000200\nfn3,02100000\nstray,03000000\n",
+        )
+        .expect("Failed to write file");
+        let params = LoadParams {
+            default_block_name: None,
+            default_symbol_type: Some(SymbolType::Function),
+            default_version_name: None,
+            version_renames: HashMap::new(),
+        };
+
+        let results = merge_symbols(
+            &symgen_file,
+            [&input],
+            InFormat::Flat,
+            &params,
+            IntFormat::Hexadecimal,
+        )
+        .expect("Merge failed");
+        let summary = results[0].as_ref().expect("Merge failed");
+        assert_eq!(summary.added, 3);
+        assert_eq!(
+            summary.blocks,
+            [("main".to_string(), 2), ("overlay".to_string(), 1)].into()
+        );
+        assert_eq!(
+            summary
+                .unmerged
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["stray"]
+        );
+    }
+
+    #[test]
+    fn test_write_unmerged_symbols() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let symgen_file = dir.path().join("symbols.yml");
+        fs::write(
+            &symgen_file,
+            r"main:
+  address: 0x2000000
+  length: 0x1000
+  functions: []
+  data: []
+",
+        )
+        .expect("Failed to write file");
+        let input = dir.path().join("input.csv");
+        fs::write(
+            &input,
+            "Name,Location,Type\nfn1,02000100,Function\nSTRAY,03000000,Data Label\n",
+        )
+        .expect("Failed to write file");
+        let mut params = LoadParams {
+            default_block_name: None,
+            default_symbol_type: None,
+            default_version_name: None,
+            version_renames: HashMap::new(),
+        };
+        let results = merge_symbols(
+            &symgen_file,
+            [&input],
+            InFormat::Csv,
+            &params,
+            IntFormat::Hexadecimal,
+        )
+        .expect("Merge failed");
+        let unmerged = &results[0].as_ref().expect("Merge failed").unmerged;
+
+        let report_yml = dir.path().join("unmerged.yml");
+        write_unmerged_symbols(
+            &report_yml,
+            unmerged,
+            InFormat::Yaml,
+            IntFormat::Hexadecimal,
+        )
+        .expect("Write failed");
+        assert_eq!(
+            fs::read_to_string(&report_yml).expect("Failed to read file"),
+            "- name: STRAY\n  address: 0x3000000\n  type: data\n"
+        );
+        assert!(write_unmerged_symbols(
+            dir.path().join("unmerged.txt"),
+            unmerged,
+            InFormat::Flat,
+            IntFormat::Hexadecimal,
+        )
+        .is_err());
+
+        // Unmerged symbols written as CSV can be merged again after choosing a block
+        let report_csv = dir.path().join("unmerged.csv");
+        write_unmerged_symbols(&report_csv, unmerged, InFormat::Csv, IntFormat::Hexadecimal)
+            .expect("Write failed");
+        params.default_block_name = Some("main".to_string());
+        let results = merge_symbols(
+            &symgen_file,
+            [&report_csv],
+            InFormat::Csv,
+            &params,
+            IntFormat::Hexadecimal,
+        )
+        .expect("Merge failed");
+        assert_eq!(results[0].as_ref().expect("Merge failed").added, 1);
+        let merged = SymGen::read(File::open(&symgen_file).expect("Failed to open file"))
+            .expect("Read failed");
+        assert_eq!(
+            merged
+                .data_realized("")
+                .map(|s| (s.name, s.address))
+                .collect::<Vec<_>>(),
+            vec![("STRAY", 0x3000000)]
+        );
+    }
+
+    #[test]
+    fn test_write_file_list() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let out = dir.path().join("out");
+        let list = out.join("manifest.txt");
+        let elsewhere = dir.path().join("other.sym");
+
+        write_file_list(
+            &list,
+            &[
+                out.join("symbols_v2.sym"),
+                out.join("symbols_v1.ghidra"),
+                elsewhere.clone(),
+                out.join("symbols_v2.sym"),
+            ],
+        )
+        .expect("Failed to write file list");
+        assert_eq!(
+            fs::read_to_string(&list).expect("Failed to read file list"),
+            format!(
+                "{}\nsymbols_v1.ghidra\nsymbols_v2.sym\n",
+                elsewhere.display()
+            )
+        );
+    }
+
+    #[cfg(feature = "manifest")]
+    #[test]
+    fn test_write_manifest() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let abc = dir.path().join("abc.sym");
+        let empty = dir.path().join("empty.sym");
+        fs::write(&abc, "abc").expect("Failed to write file");
+        fs::write(&empty, "").expect("Failed to write file");
+        let manifest = dir.path().join("out").join("manifest.sha256");
+
+        write_manifest(&manifest, &[&empty, &abc]).expect("Failed to write manifest");
+        assert_eq!(
+            fs::read_to_string(&manifest).expect("Failed to read manifest"),
+            format!(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  {}\n\
+                e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855  {}\n",
+                abc.display(),
+                empty.display()
+            )
+        );
+    }
 }
@@ -1,17 +1,21 @@
+use std::cell::RefCell;
 use std::error::Error;
 use std::fmt::{self, Display, Formatter};
 use std::fs;
-use std::path::Path;
+use std::io;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use tempfile::{NamedTempFile, PersistError};
 
-use super::data_formats::symgen_yml::{IntFormat, SymGen};
+use super::data_formats::symgen_yml::{self, IntFormat, MergeError, SubregionError, SymGen, Uint};
 
 /// Encapsulates a collection of similar errors for different files.
 #[derive(Debug)]
 pub struct MultiFileError {
     pub base_msg: String,
-    pub errors: Vec<(String, Box<dyn Error>)>,
+    pub errors: Vec<(String, Box<dyn Error + Send + Sync>)>,
 }
 
 impl Error for MultiFileError {}
@@ -26,6 +30,346 @@ impl Display for MultiFileError {
     }
 }
 
+/// An error caused by invalid command line usage.
+#[derive(Debug)]
+pub struct UsageError(pub String);
+
+impl Error for UsageError {}
+
+impl Display for UsageError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// An error that was detached from its original type, keeping only its message and
+/// [`ErrorCategory`]. Errors that can't be sent between threads are converted to
+/// [`ResymgenError`]s this way.
+#[derive(Debug)]
+pub struct DetachedError {
+    msg: String,
+    category: ErrorCategory,
+}
+
+impl DetachedError {
+    pub(crate) fn new(err: &(dyn Error + 'static)) -> Self {
+        Self {
+            msg: err.to_string(),
+            category: ErrorCategory::of(err),
+        }
+    }
+}
+
+impl Error for DetachedError {}
+
+impl Display for DetachedError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+/// The top-level error type returned by the `resymgen` library functions.
+///
+/// Failure modes are split into broad kinds that can be matched on. More variants may be added
+/// in the future, so matches should include a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ResymgenError {
+    /// An I/O operation failed.
+    Io(io::Error),
+    /// A `resymgen` YAML file could not be parsed or written.
+    Yaml(symgen_yml::Error),
+    /// A subregion could not be resolved.
+    Subregion(SubregionError),
+    /// Symbols could not be merged.
+    Merge(MergeError),
+    /// The library was used incorrectly (e.g., with invalid options).
+    Usage(UsageError),
+    /// Operations on multiple files failed.
+    MultiFile(MultiFileError),
+    /// Any other failure, such as malformed data in a non-YAML input format.
+    Other(Box<dyn Error + Send + Sync>),
+}
+
+impl Error for ResymgenError {
+    // Display is transparent, so the source is that of the wrapped error
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(e) => e.source(),
+            Self::Yaml(e) => e.source(),
+            Self::Subregion(e) => e.source(),
+            Self::Merge(e) => e.source(),
+            Self::Usage(e) => e.source(),
+            Self::MultiFile(e) => e.source(),
+            Self::Other(e) => e.source(),
+        }
+    }
+}
+
+impl Display for ResymgenError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{}", e),
+            Self::Yaml(e) => write!(f, "{}", e),
+            Self::Subregion(e) => write!(f, "{}", e),
+            Self::Merge(e) => write!(f, "{}", e),
+            Self::Usage(e) => write!(f, "{}", e),
+            Self::MultiFile(e) => write!(f, "{}", e),
+            Self::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<io::Error> for ResymgenError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<PersistError> for ResymgenError {
+    fn from(e: PersistError) -> Self {
+        Self::Io(e.error)
+    }
+}
+
+impl From<csv::Error> for ResymgenError {
+    fn from(e: csv::Error) -> Self {
+        if e.is_io_error() {
+            if let csv::ErrorKind::Io(e) = e.into_kind() {
+                return Self::Io(e);
+            }
+            unreachable!("csv I/O error without an I/O error kind");
+        }
+        Self::Other(e.into())
+    }
+}
+
+impl From<serde_json::Error> for ResymgenError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Other(e.into())
+    }
+}
+
+impl From<symgen_yml::Error> for ResymgenError {
+    fn from(e: symgen_yml::Error) -> Self {
+        match e {
+            symgen_yml::Error::Io(e) => Self::Io(e),
+            symgen_yml::Error::Subregion(e) => Self::Subregion(e),
+            e => Self::Yaml(e),
+        }
+    }
+}
+
+impl From<SubregionError> for ResymgenError {
+    fn from(e: SubregionError) -> Self {
+        Self::Subregion(e)
+    }
+}
+
+impl From<MergeError> for ResymgenError {
+    fn from(e: MergeError) -> Self {
+        Self::Merge(e)
+    }
+}
+
+impl From<UsageError> for ResymgenError {
+    fn from(e: UsageError) -> Self {
+        Self::Usage(e)
+    }
+}
+
+impl From<MultiFileError> for ResymgenError {
+    fn from(e: MultiFileError) -> Self {
+        Self::MultiFile(e)
+    }
+}
+
+/// Downcasts a boxed error to the specific kinds of errors wrapped by [`ResymgenError`], evaluating
+/// to the [`ResymgenError`] on success, or returning the original box as an `Err` on failure.
+macro_rules! downcast_resymgen_error {
+    ($e:expr) => {{
+        let e = match $e.downcast::<ResymgenError>() {
+            Ok(e) => return Ok(*e),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<io::Error>() {
+            Ok(e) => return Ok(ResymgenError::Io(*e)),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<symgen_yml::Error>() {
+            Ok(e) => return Ok((*e).into()),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<MergeError>() {
+            Ok(e) => return Ok(ResymgenError::Merge(*e)),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<UsageError>() {
+            Ok(e) => return Ok(ResymgenError::Usage(*e)),
+            Err(e) => e,
+        };
+        match e.downcast::<MultiFileError>() {
+            Ok(e) => Ok(ResymgenError::MultiFile(*e)),
+            Err(e) => Err(e),
+        }
+    }};
+}
+
+impl ResymgenError {
+    fn downcast_sync(
+        e: Box<dyn Error + Send + Sync>,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        downcast_resymgen_error!(e)
+    }
+    fn downcast(e: Box<dyn Error>) -> Result<Self, Box<dyn Error>> {
+        downcast_resymgen_error!(e)
+    }
+}
+
+impl From<Box<dyn Error + Send + Sync>> for ResymgenError {
+    /// Recovers the specific kind of error if possible, falling back to [`ResymgenError::Other`].
+    fn from(e: Box<dyn Error + Send + Sync>) -> Self {
+        Self::downcast_sync(e).unwrap_or_else(Self::Other)
+    }
+}
+
+impl From<Box<dyn Error>> for ResymgenError {
+    /// Recovers the specific kind of error if possible. Otherwise, the error can't be kept as is,
+    /// since it might not be thread-safe, so it falls back to a [`ResymgenError::Other`] wrapping
+    /// a [`DetachedError`].
+    fn from(e: Box<dyn Error>) -> Self {
+        Self::downcast(e).unwrap_or_else(|e| Self::Other(Box::new(DetachedError::new(e.as_ref()))))
+    }
+}
+
+impl From<String> for ResymgenError {
+    fn from(msg: String) -> Self {
+        Self::Other(msg.into())
+    }
+}
+
+impl From<&str> for ResymgenError {
+    fn from(msg: &str) -> Self {
+        Self::Other(msg.into())
+    }
+}
+
+/// Broad categories of failures, each with a distinct process exit code.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ErrorCategory {
+    /// The input is invalid, or a check or format check failed. Exit code 1.
+    Failure,
+    /// Invalid command line usage. Exit code 2.
+    Usage,
+    /// An I/O operation failed. Exit code 3.
+    Io,
+    /// Symbols could not be merged. Exit code 4.
+    Merge,
+}
+
+impl ErrorCategory {
+    /// Categorizes an error based on its underlying type.
+    ///
+    /// Errors of unknown types are categorized as [`Failure`]. A [`MultiFileError`] is
+    /// categorized based on the most severe error it contains (see [`severity()`]).
+    ///
+    /// [`Failure`]: ErrorCategory::Failure
+    /// [`severity()`]: ErrorCategory::severity
+    pub fn of(err: &(dyn Error + 'static)) -> Self {
+        if let Some(e) = err.downcast_ref::<ResymgenError>() {
+            return match e {
+                ResymgenError::Io(_) => Self::Io,
+                ResymgenError::Yaml(e) => Self::of_symgen_error(e),
+                ResymgenError::Subregion(SubregionError::SymGen((_, e))) => {
+                    Self::of_symgen_error(e)
+                }
+                ResymgenError::Subregion(_) => Self::Failure,
+                ResymgenError::Merge(_) => Self::Merge,
+                ResymgenError::Usage(_) => Self::Usage,
+                ResymgenError::MultiFile(e) => Self::of(e),
+                ResymgenError::Other(e) => Self::of(e.as_ref()),
+            };
+        }
+        if let Some(e) = err.downcast_ref::<MultiFileError>() {
+            return e
+                .errors
+                .iter()
+                .map(|(_, inner)| Self::of(inner.as_ref()))
+                .max_by_key(Self::severity)
+                .unwrap_or(Self::Failure);
+        }
+        if let Some(e) = err.downcast_ref::<DetachedError>() {
+            return e.category;
+        }
+        if err.is::<UsageError>() {
+            return Self::Usage;
+        }
+        if err.is::<io::Error>() || err.is::<PersistError>() {
+            return Self::Io;
+        }
+        if err.is::<MergeError>() {
+            return Self::Merge;
+        }
+        if let Some(e) = err.downcast_ref::<csv::Error>() {
+            if e.is_io_error() {
+                return Self::Io;
+            }
+        }
+        if let Some(e) = err.downcast_ref::<symgen_yml::Error>() {
+            return Self::of_symgen_error(e);
+        }
+        Self::Failure
+    }
+
+    fn of_symgen_error(err: &symgen_yml::Error) -> Self {
+        match err {
+            symgen_yml::Error::Io(_) => Self::Io,
+            symgen_yml::Error::Subregion(SubregionError::SymGen((_, e))) => {
+                Self::of_symgen_error(e)
+            }
+            _ => Self::Failure,
+        }
+    }
+
+    /// Ranks the category by how severe it is, with higher values being more severe.
+    ///
+    /// Failures where the requested operation couldn't even be attempted (I/O errors and invalid
+    /// usage) are more severe than failures caused by the input itself.
+    pub fn severity(&self) -> u8 {
+        match self {
+            Self::Failure => 0,
+            Self::Merge => 1,
+            Self::Usage => 2,
+            Self::Io => 3,
+        }
+    }
+
+    /// Gets the process exit code associated with the category.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Failure => 1,
+            Self::Usage => 2,
+            Self::Io => 3,
+            Self::Merge => 4,
+        }
+    }
+}
+
+/// Creates a temporary file to write output to before persisting it at `target` with
+/// [`persist_named_temp_file_safe()`].
+///
+/// The temporary file is created in the same directory as `target` where possible, so that
+/// persisting it is an atomic rename that never leaves a partially written file at `target`, and
+/// any existing file at `target` stays intact if writing fails. If the temporary file can't be
+/// created there, it falls back to the system temporary directory.
+pub fn named_temp_file_for<P: AsRef<Path>>(target: P) -> io::Result<NamedTempFile> {
+    let dir = match target.as_ref().parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    NamedTempFile::new_in(dir).or_else(|_| NamedTempFile::new())
+}
+
 /// Persist the temporary file at the target path.
 ///
 /// This wraps `NamedTempFile::persist()` with fallback to manual copying.
@@ -55,18 +399,334 @@ pub fn persist_named_temp_file_safe<P: AsRef<Path>>(
     Ok(())
 }
 
+/// Expand a glob `pattern` into a list of matching file paths, sorted by file path.
+///
+/// Fails if `pattern` is invalid or doesn't match any files.
+pub fn expand_glob(pattern: &str) -> Result<Vec<PathBuf>, ResymgenError> {
+    let paths = glob::glob(pattern)
+        .map_err(|e| UsageError(format!("Invalid glob pattern '{}': {}", pattern, e)))?;
+    let mut files = paths
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| io::Error::new(e.error().kind(), e.to_string()))?;
+    files.retain(|p| p.is_file());
+    if files.is_empty() {
+        return Err(UsageError(format!("No files match glob pattern '{}'", pattern)).into());
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Returns a possibly truncated prefix of `s`, with at most `max_chars` characters, for previews
+/// in messages. If truncated, the "..." suffix will be appended to indicate continuation.
+pub fn truncate(s: &str, max_chars: usize) -> String {
+    match s.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}...", &s[..end]),
+        None => s.to_string(),
+    }
+}
+
+/// Format a byte count as hexadecimal, followed by a human-readable size in binary units,
+/// like `0x1F400 (125.0 KiB)`. Counts under 1 KiB are shown exactly, like `0x40 (64 B)`.
+pub fn format_byte_size(n: Uint) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if n < 1024 {
+        return format!("{:#X} ({} B)", n, n);
+    }
+    let mut size = n as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for &next_unit in UNITS[1..].iter() {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next_unit;
+    }
+    format!("{:#X} ({:.1} {})", n, size, unit)
+}
+
+thread_local! {
+    /// Durations recorded by [`time_phase`], in order of first occurrence, or [`None`] if timing
+    /// is disabled.
+    static PHASE_TIMES: RefCell<Option<Vec<(&'static str, Duration)>>> = RefCell::new(None);
+}
+
+/// Enables recording of per-phase durations with [`time_phase`] on the current thread.
+pub fn enable_timing() {
+    PHASE_TIMES.with(|t| {
+        t.borrow_mut().get_or_insert_with(Vec::new);
+    });
+}
+
+/// Returns the per-phase durations recorded since timing was enabled, in order of first
+/// occurrence. Durations for repeated phases are summed. Returns [`None`] if timing is disabled.
+pub fn phase_times() -> Option<Vec<(&'static str, Duration)>> {
+    PHASE_TIMES.with(|t| t.borrow().clone())
+}
+
+/// Like [`phase_times`], but also clears the durations recorded on the current thread (timing
+/// stays enabled). This is useful for collecting the durations recorded on a worker thread, so
+/// they can be passed to [`record_phase_times`] on the main thread.
+pub fn take_phase_times() -> Option<Vec<(&'static str, Duration)>> {
+    PHASE_TIMES.with(|t| t.borrow_mut().as_mut().map(mem::take))
+}
+
+/// Adds per-phase durations (e.g., from [`take_phase_times`] on another thread) to the durations
+/// recorded on the current thread, if timing is enabled.
+pub fn record_phase_times(times: &[(&'static str, Duration)]) {
+    for &(phase, elapsed) in times {
+        add_phase_time(phase, elapsed);
+    }
+}
+
+fn add_phase_time(phase: &'static str, elapsed: Duration) {
+    PHASE_TIMES.with(|t| {
+        if let Some(times) = t.borrow_mut().as_mut() {
+            match times.iter_mut().find(|(p, _)| *p == phase) {
+                Some((_, total)) => *total += elapsed,
+                None => times.push((phase, elapsed)),
+            }
+        }
+    });
+}
+
+/// A guard that records the time elapsed for a phase when dropped. See [`time_phase`].
+pub struct PhaseTimer {
+    phase: &'static str,
+    start: Option<Instant>,
+}
+
+impl Drop for PhaseTimer {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            add_phase_time(self.phase, start.elapsed());
+        }
+    }
+}
+
+/// Starts timing a `phase` of work (e.g., "read" or "write"). The time elapsed is recorded when
+/// the returned guard is dropped, if timing has been enabled with [`enable_timing`]. If timing is
+/// disabled, the guard does nothing.
+pub fn time_phase(phase: &'static str) -> PhaseTimer {
+    let enabled = PHASE_TIMES.with(|t| t.borrow().is_some());
+    PhaseTimer {
+        phase,
+        start: if enabled { Some(Instant::now()) } else { None },
+    }
+}
+
 /// Recursively write a [`SymGen`] and all its subregions to files, starting with the top-level
-/// file path specified by `top_path`, and using the given `int_format`.
+/// file path specified by `top_path`, and using the given `int_format` and `block_spacing`.
 pub fn symgen_write_recursive<P: AsRef<Path>>(
     symgen: &SymGen,
     top_path: P,
     int_format: IntFormat,
-) -> Result<(), Box<dyn Error>> {
+    block_spacing: usize,
+) -> Result<(), ResymgenError> {
+    let _timer = time_phase("write");
     for cursor in symgen.cursor(top_path.as_ref()).btraverse() {
         // Write to a tempfile first, then replace the old one atomically.
-        let output_file = NamedTempFile::new()?;
-        cursor.symgen().write(&output_file, int_format)?;
+        let output_file = named_temp_file_for(cursor.path())?;
+        cursor
+            .symgen()
+            .write_with_spacing(&output_file, int_format, block_spacing)?;
         persist_named_temp_file_safe(output_file, cursor.path())?;
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_temp_file_for() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        let target = dir.path().join("out.sym");
+        fs::write(&target, "old").expect("Failed to write file");
+
+        let tmp = named_temp_file_for(&target).expect("Failed to create temp file");
+        assert_eq!(tmp.path().parent(), Some(dir.path()));
+        fs::write(tmp.path(), "new").expect("Failed to write file");
+        // The original is untouched until the temp file is persisted
+        assert_eq!(fs::read_to_string(&target).unwrap(), "old");
+        persist_named_temp_file_safe(tmp, &target).expect("Failed to persist");
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+    }
+
+    #[test]
+    fn test_time_phase() {
+        // Disabled by default
+        drop(time_phase("read"));
+        assert_eq!(phase_times(), None);
+
+        enable_timing();
+        drop(time_phase("read"));
+        drop(time_phase("write"));
+        drop(time_phase("read"));
+        assert_eq!(
+            phase_times()
+                .expect("timing is disabled")
+                .into_iter()
+                .map(|(phase, _)| phase)
+                .collect::<Vec<_>>(),
+            vec!["read", "write"]
+        );
+
+        let taken = take_phase_times().expect("timing is disabled");
+        assert_eq!(taken.len(), 2);
+        assert_eq!(phase_times(), Some(Vec::new()));
+        record_phase_times(&[("sort", Duration::from_millis(2))]);
+        record_phase_times(&[("sort", Duration::from_millis(3))]);
+        assert_eq!(
+            phase_times(),
+            Some(vec![("sort", Duration::from_millis(5))])
+        );
+    }
+
+    #[test]
+    fn test_error_category() {
+        let cases: [(Box<dyn Error>, ErrorCategory); 7] = [
+            ("some failure".into(), ErrorCategory::Failure),
+            (
+                UsageError("bad usage".to_string()).into(),
+                ErrorCategory::Usage,
+            ),
+            (
+                io::Error::new(io::ErrorKind::NotFound, "not found").into(),
+                ErrorCategory::Io,
+            ),
+            (
+                symgen_yml::Error::Io(io::Error::new(io::ErrorKind::Other, "io")).into(),
+                ErrorCategory::Io,
+            ),
+            (
+                MultiFileError {
+                    base_msg: "multiple failures".to_string(),
+                    errors: vec![(
+                        "file".to_string(),
+                        io::Error::new(io::ErrorKind::NotFound, "not found").into(),
+                    )],
+                }
+                .into(),
+                ErrorCategory::Io,
+            ),
+            (
+                MultiFileError {
+                    base_msg: "multiple failures".to_string(),
+                    errors: vec![
+                        ("file1".to_string(), "invalid".into()),
+                        (
+                            "file2".to_string(),
+                            io::Error::new(io::ErrorKind::NotFound, "not found").into(),
+                        ),
+                        (
+                            "file3".to_string(),
+                            UsageError("bad usage".to_string()).into(),
+                        ),
+                    ],
+                }
+                .into(),
+                ErrorCategory::Io,
+            ),
+            (
+                DetachedError::new(&UsageError("bad usage".to_string())).into(),
+                ErrorCategory::Usage,
+            ),
+        ];
+        for (err, category) in cases {
+            assert_eq!(ErrorCategory::of(err.as_ref()), category);
+        }
+    }
+
+    #[test]
+    fn test_errors_are_thread_safe() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ResymgenError>();
+        assert_send_sync::<MultiFileError>();
+    }
+
+    #[test]
+    fn test_resymgen_error() {
+        let err: Box<dyn Error> = io::Error::new(io::ErrorKind::NotFound, "not found").into();
+        assert!(matches!(ResymgenError::from(err), ResymgenError::Io(_)));
+        let err: Box<dyn Error> = UsageError("bad usage".to_string()).into();
+        assert!(matches!(ResymgenError::from(err), ResymgenError::Usage(_)));
+        let err: Box<dyn Error> = "some failure".into();
+        assert!(matches!(ResymgenError::from(err), ResymgenError::Other(_)));
+        assert!(matches!(
+            ResymgenError::from(symgen_yml::Error::Io(io::Error::new(
+                io::ErrorKind::Other,
+                "io"
+            ))),
+            ResymgenError::Io(_)
+        ));
+
+        let cases = [
+            (
+                ResymgenError::Other("checks failed".into()),
+                ErrorCategory::Failure,
+            ),
+            (
+                ResymgenError::Usage(UsageError("bad usage".to_string())),
+                ErrorCategory::Usage,
+            ),
+            (
+                ResymgenError::Other(io::Error::new(io::ErrorKind::Other, "io").into()),
+                ErrorCategory::Io,
+            ),
+        ];
+        for (err, category) in cases {
+            assert_eq!(ErrorCategory::of(&err), category);
+            // Categorization survives boxing
+            let err: Box<dyn Error> = err.into();
+            assert_eq!(ErrorCategory::of(err.as_ref()), category);
+        }
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 40), "short");
+        assert_eq!(truncate("exact", 5), "exact");
+        let long = "é".repeat(41);
+        assert_eq!(truncate(&long, 40), format!("{}...", "é".repeat(40)));
+    }
+
+    #[test]
+    fn test_format_byte_size() {
+        let cases = [
+            (0, "0x0 (0 B)"),
+            (0x40, "0x40 (64 B)"),
+            (0x3FF, "0x3FF (1023 B)"),
+            (0x400, "0x400 (1.0 KiB)"),
+            (0x1F400, "0x1F400 (125.0 KiB)"),
+            (0x80000, "0x80000 (512.0 KiB)"),
+            (0x400000, "0x400000 (4.0 MiB)"),
+            (0x1_0000_0000, "0x100000000 (4.0 GiB)"),
+        ];
+        for (n, expected) in cases {
+            assert_eq!(format_byte_size(n), expected);
+        }
+    }
+
+    #[test]
+    fn test_expand_glob() {
+        let dir = tempfile::tempdir().expect("Failed to create tempdir");
+        for name in ["b.csv", "a.csv", "c.txt"] {
+            fs::write(dir.path().join(name), "").expect("Failed to write file");
+        }
+        fs::create_dir(dir.path().join("d.csv")).expect("Failed to create dir");
+
+        let pattern = dir.path().join("*.csv");
+        assert_eq!(
+            expand_glob(pattern.to_str().unwrap()).expect("Failed to expand glob"),
+            vec![dir.path().join("a.csv"), dir.path().join("b.csv")]
+        );
+
+        let pattern = dir.path().join("*.yml");
+        let err = expand_glob(pattern.to_str().unwrap()).expect_err("Expected no matches");
+        assert_eq!(ErrorCategory::of(&err), ErrorCategory::Usage);
+        let err = expand_glob("[").expect_err("Expected invalid pattern");
+        assert_eq!(ErrorCategory::of(&err), ErrorCategory::Usage);
+    }
+}